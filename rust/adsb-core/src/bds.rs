@@ -0,0 +1,219 @@
+//! Comm-B (BDS) register classification for DF20/21 MB fields.
+//!
+//! DF20/21 long frames carry a 56-bit MB (Comm-B) field in the same byte
+//! range as the DF17/18 ME field, but unlike ME it is not self-identifying —
+//! nothing in the message says which BDS register it holds. `guess_bds`
+//! applies the same content-based heuristic most open-source decoders (e.g.
+//! pyModeS) use: each register reserves a status bit ahead of its value
+//! subfields, and a well-formed reply always zeroes the value when its
+//! status bit is clear. A field that violates a register's layout can be
+//! ruled out; one that satisfies several is genuinely ambiguous, so this
+//! returns every surviving candidate and only sets `confident` when exactly
+//! one remains.
+
+use crate::bitreader::BitReader;
+use crate::frame::ModeFrame;
+
+/// A Comm-B register `guess_bds` can recognize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BdsRegister {
+    /// BDS 1,0: Data link capability report.
+    Bds10,
+    /// BDS 4,0: Selected vertical intent.
+    Bds40,
+    /// BDS 5,0: Track and turn report.
+    Bds50,
+    /// BDS 6,0: Heading and speed report.
+    Bds60,
+}
+
+/// A candidate register classification for an MB field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BdsCandidate {
+    pub register: BdsRegister,
+    /// Set only when this was the sole register whose layout checks passed.
+    pub confident: bool,
+}
+
+impl ModeFrame {
+    /// Comm-B (MB) field (bytes 4-10, 56 bits) for DF20/21.
+    /// Returns empty slice for other DFs or short frames.
+    pub fn mb(&self) -> &[u8] {
+        if (self.df == 20 || self.df == 21) && self.is_long() && self.raw.len() >= 11 {
+            &self.raw[4..11]
+        } else {
+            &[]
+        }
+    }
+
+    /// Classify which BDS register this DF20/21 MB field most likely holds.
+    ///
+    /// Returns every register whose layout checks pass; empty if none do.
+    /// Cross-validate a candidate against the aircraft's other messages
+    /// before trusting it, especially when more than one survives.
+    pub fn guess_bds(&self) -> Vec<BdsCandidate> {
+        let mb = self.mb();
+        if mb.len() != 7 {
+            return Vec::new();
+        }
+
+        let mut registers = Vec::new();
+        if is_bds10(mb) {
+            registers.push(BdsRegister::Bds10);
+        }
+        if is_bds40(mb) {
+            registers.push(BdsRegister::Bds40);
+        }
+        if is_bds50(mb) {
+            registers.push(BdsRegister::Bds50);
+        }
+        if is_bds60(mb) {
+            registers.push(BdsRegister::Bds60);
+        }
+
+        let confident = registers.len() == 1;
+        registers
+            .into_iter()
+            .map(|register| BdsCandidate {
+                register,
+                confident,
+            })
+            .collect()
+    }
+}
+
+/// BDS 1,0: bit 0 is a fixed marker bit, and bits 10-13 are reserved.
+fn is_bds10(mb: &[u8]) -> bool {
+    let mut r = BitReader::new(mb);
+    if r.take_bits(1) != 1 {
+        return false;
+    }
+    r.skip_bits(9);
+    r.take_bits(4) == 0
+}
+
+/// BDS 4,0: three (status bit + 12-bit value) subfields — MCP/FCU selected
+/// altitude, FMS selected altitude, and barometric pressure setting. A clear
+/// status bit means that subfield isn't in use, so its value must read as
+/// zero.
+fn is_bds40(mb: &[u8]) -> bool {
+    let mut r = BitReader::new(mb);
+    for _ in 0..3 {
+        let status = r.take_bits(1);
+        let value = r.take_bits(12);
+        if status == 0 && value != 0 {
+            return false;
+        }
+    }
+    true
+}
+
+/// BDS 5,0: five (status bit + 10-bit value) subfields, plus a reserved
+/// trailing bit.
+fn is_bds50(mb: &[u8]) -> bool {
+    let mut r = BitReader::new(mb);
+    for _ in 0..5 {
+        let status = r.take_bits(1);
+        let value = r.take_bits(10);
+        if status == 0 && value != 0 {
+            return false;
+        }
+    }
+    r.take_bits(1) == 0
+}
+
+/// BDS 6,0: heading (status bit + 11-bit value) followed by four
+/// (status bit + 10-bit value) subfields.
+fn is_bds60(mb: &[u8]) -> bool {
+    let mut r = BitReader::new(mb);
+    let hdg_status = r.take_bits(1);
+    let hdg_value = r.take_bits(11);
+    if hdg_status == 0 && hdg_value != 0 {
+        return false;
+    }
+    for _ in 0..4 {
+        let status = r.take_bits(1);
+        let value = r.take_bits(10);
+        if status == 0 && value != 0 {
+            return false;
+        }
+    }
+    true
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::{parse_frame_uncached, IcaoCache};
+
+    /// Build a synthetic DF20 frame with the given MB field. CRC isn't
+    /// checked for residual-ICAO DFs when `validate_icao` is false, so any
+    /// well-formed MB content parses.
+    fn df20_with_mb(mb: [u8; 7]) -> ModeFrame {
+        let mut raw = vec![20 << 3, 0, 0, 0];
+        raw.extend_from_slice(&mb);
+        raw.extend_from_slice(&[0, 0, 0]); // AP/PI, ignored when uncached
+        let hex = crate::types::hex_encode(&raw);
+        parse_frame_uncached(&hex, 1.0, None).expect("valid DF20 frame")
+    }
+
+    #[test]
+    fn test_mb_empty_for_non_comm_b() {
+        let mut cache = IcaoCache::new(60.0);
+        let frame = crate::frame::parse_frame(
+            "8D4840D6202CC371C32CE0576098",
+            1.0,
+            None,
+            false,
+            &mut cache,
+            &crate::crc::GLOBAL_CORRECTOR,
+        )
+        .expect("valid DF17 frame");
+        assert!(frame.mb().is_empty());
+    }
+
+    #[test]
+    fn test_mb_present_for_df20() {
+        let frame = df20_with_mb([0; 7]);
+        assert_eq!(frame.mb().len(), 7);
+    }
+
+    #[test]
+    fn test_guess_bds_confident_match() {
+        // Marker bit set, reserved bits 10-13 clear; the lone bit set at
+        // position 15 violates the second status/value subfield of BDS
+        // 4,0/5,0/6,0, leaving BDS 1,0 as the only surviving candidate.
+        let frame = df20_with_mb([0xFF, 0xC1, 0, 0, 0, 0, 0]);
+        let candidates = frame.guess_bds();
+        assert_eq!(
+            candidates,
+            vec![BdsCandidate {
+                register: BdsRegister::Bds10,
+                confident: true
+            }]
+        );
+    }
+
+    #[test]
+    fn test_guess_bds_ambiguous_match() {
+        // All-zero MB: BDS 1,0's marker bit requirement fails, but the
+        // status/value consistency checks for BDS 4,0/5,0/6,0 all pass
+        // trivially (every status bit clear, every value zero).
+        let frame = df20_with_mb([0; 7]);
+        let candidates = frame.guess_bds();
+        assert!(candidates.iter().all(|c| !c.confident));
+        assert!(candidates.len() > 1);
+    }
+
+    #[test]
+    fn test_guess_bds_no_match() {
+        // Marker bit clear, and the first status bit clear with a nonzero
+        // value after it, which rules out BDS 4,0/5,0/6,0.
+        let frame = df20_with_mb([0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+        assert!(frame.guess_bds().is_empty());
+    }
+}
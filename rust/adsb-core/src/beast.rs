@@ -0,0 +1,163 @@
+//! Beast binary frame encoding for re-broadcast.
+//!
+//! The Beast format (originated on Kinetic's Mode-S Beast hardware, now a
+//! de-facto standard understood by dump1090-family viewers, MLAT clients,
+//! and flight-sim feeders) wraps each message so a reader can resync
+//! mid-stream: escape byte `0x1a`, a type byte, a 6-byte timestamp, a
+//! 1-byte signal level, then the raw message bytes. Any `0x1a` occurring
+//! after the type byte is doubled so the next bare `0x1a` a reader sees is
+//! unambiguously the start of the following frame.
+
+use crate::frame::ModeFrame;
+
+/// Frame-sync escape byte.
+pub const ESCAPE: u8 = 0x1a;
+
+/// Type byte for 2-byte Mode-A/C data. This crate only decodes Mode S, so it
+/// never emits this type — reserved for compatibility with mixed Beast
+/// streams produced by hardware that also demodulates Mode A/C.
+pub const TYPE_MODE_AC: u8 = 0x31;
+/// Type byte for a short (56-bit / 7-byte) Mode-S frame.
+pub const TYPE_MODE_S_SHORT: u8 = 0x32;
+/// Type byte for a long (112-bit / 14-byte) Mode-S frame.
+pub const TYPE_MODE_S_LONG: u8 = 0x33;
+
+/// Encode a decoded frame as a Beast binary message.
+///
+/// The timestamp is the frame's capture time (`frame.timestamp`, Unix
+/// seconds) converted to nanoseconds and truncated to the 48-bit counter
+/// Beast readers expect. The signal byte maps `frame.signal_level` (0.0-1.0)
+/// onto 0-255, or 0 when no level was recorded.
+pub fn encode_beast_frame(frame: &ModeFrame) -> Vec<u8> {
+    encode_beast_bytes(&frame.raw, frame.timestamp, frame.signal_level)
+}
+
+/// Encode raw Mode-S message bytes as a Beast binary message, without
+/// requiring a parsed/CRC-checked `ModeFrame`. Used by capture pipelines
+/// that want to re-broadcast Beast frames straight out of the demodulator
+/// (see `adsb-feeder`'s `--format beast`), before or without a decode step.
+///
+/// The message type is inferred from `raw`'s length: 7 bytes (56 bits) is
+/// short, anything longer is long. Timestamp/signal-level encoding matches
+/// `encode_beast_frame`.
+pub fn encode_beast_bytes(raw: &[u8], timestamp: f64, signal_level: Option<f32>) -> Vec<u8> {
+    let type_byte = if raw.len() > 7 {
+        TYPE_MODE_S_LONG
+    } else {
+        TYPE_MODE_S_SHORT
+    };
+
+    let mut out = Vec::with_capacity(2 + 6 + 1 + raw.len() + 4);
+    out.push(ESCAPE);
+    out.push(type_byte);
+
+    let ts_ticks = (timestamp * 1_000_000_000.0) as u64 & 0xFFFF_FFFF_FFFF;
+    for shift in (0..6).rev() {
+        push_escaped(&mut out, (ts_ticks >> (shift * 8)) as u8);
+    }
+
+    let signal_byte = signal_level
+        .map(|lvl| (lvl.clamp(0.0, 1.0) * 255.0) as u8)
+        .unwrap_or(0);
+    push_escaped(&mut out, signal_byte);
+
+    for &b in raw {
+        push_escaped(&mut out, b);
+    }
+
+    out
+}
+
+/// Push `byte` onto `out`, doubling it first if it's the escape byte.
+fn push_escaped(out: &mut Vec<u8>, byte: u8) {
+    if byte == ESCAPE {
+        out.push(ESCAPE);
+    }
+    out.push(byte);
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::parse_frame_uncached;
+
+    #[test]
+    fn test_encode_long_frame_type_byte() {
+        let frame = parse_frame_uncached("8D4840D6202CC371C32CE0576098", 1.0, Some(0.5)).unwrap();
+        let encoded = encode_beast_frame(&frame);
+        assert_eq!(encoded[0], ESCAPE);
+        assert_eq!(encoded[1], TYPE_MODE_S_LONG);
+    }
+
+    #[test]
+    fn test_encode_frame_length() {
+        let frame = parse_frame_uncached("8D4840D6202CC371C32CE0576098", 1.0, None).unwrap();
+        let encoded = encode_beast_frame(&frame);
+        // 2 header bytes + 6 timestamp + 1 signal + 14 payload, assuming no escapes needed
+        assert_eq!(encoded.len(), 2 + 6 + 1 + frame.raw.len());
+    }
+
+    #[test]
+    fn test_encode_doubles_escape_byte_in_payload() {
+        let frame = ModeFrame {
+            df: 17,
+            icao: [0x1a, 0x00, 0x00],
+            raw: vec![0x1a, 0x1a, 0x02, 0x03],
+            timestamp: 0.0,
+            signal_level: None,
+            msg_bits: 56,
+            crc_ok: true,
+            corrected: false,
+        };
+        let encoded = encode_beast_frame(&frame);
+
+        // Header: escape + type byte (not doubled, it's the frame marker)
+        assert_eq!(&encoded[0..2], &[ESCAPE, TYPE_MODE_S_SHORT]);
+        // Timestamp is all zero, signal byte is zero — no escapes there
+        let payload = &encoded[2 + 6 + 1..];
+        // 0x1a, 0x1a, 0x02, 0x03 -> each 0x1a doubled
+        assert_eq!(payload, &[0x1a, 0x1a, 0x1a, 0x1a, 0x02, 0x03]);
+    }
+
+    #[test]
+    fn test_encode_signal_level_scales_to_byte() {
+        let frame = parse_frame_uncached("8D4840D6202CC371C32CE0576098", 1.0, Some(1.0)).unwrap();
+        let encoded = encode_beast_frame(&frame);
+        let signal_byte = encoded[2 + 6];
+        assert_eq!(signal_byte, 255);
+    }
+
+    #[test]
+    fn test_encode_beast_bytes_matches_frame_encoding() {
+        let frame = parse_frame_uncached("8D4840D6202CC371C32CE0576098", 1.0, Some(0.5)).unwrap();
+        let via_bytes = encode_beast_bytes(&frame.raw, frame.timestamp, frame.signal_level);
+        assert_eq!(via_bytes, encode_beast_frame(&frame));
+    }
+
+    #[test]
+    fn test_encode_beast_bytes_short_frame() {
+        let raw = [0u8; 7];
+        let encoded = encode_beast_bytes(&raw, 0.0, None);
+        assert_eq!(encoded[1], TYPE_MODE_S_SHORT);
+    }
+
+    #[test]
+    fn test_encode_short_frame_type_byte() {
+        let frame = ModeFrame {
+            df: 0,
+            icao: [0, 0, 0],
+            raw: vec![0; 7],
+            timestamp: 0.0,
+            signal_level: None,
+            msg_bits: 56,
+            crc_ok: true,
+            corrected: false,
+        };
+        let encoded = encode_beast_frame(&frame);
+        assert_eq!(encoded[1], TYPE_MODE_S_SHORT);
+    }
+}
@@ -0,0 +1,32 @@
+//! Small sequential bit-field reader shared by the ME and BDS decoders.
+
+/// Walks a byte slice bit-by-bit, MSB first, the way the `ais` crate's
+/// message parsers consume a payload field by field.
+pub(crate) struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        BitReader { data, pos: 0 }
+    }
+
+    /// Take the next `n` bits (n <= 32) as an unsigned integer.
+    pub(crate) fn take_bits(&mut self, n: usize) -> u32 {
+        let mut val = 0u32;
+        for _ in 0..n {
+            let byte = self.pos / 8;
+            let bit = 7 - (self.pos % 8);
+            let b = (self.data[byte] >> bit) & 1;
+            val = (val << 1) | b as u32;
+            self.pos += 1;
+        }
+        val
+    }
+
+    /// Skip `n` spare/reserved bits without interpreting them.
+    pub(crate) fn skip_bits(&mut self, n: usize) {
+        self.pos += n;
+    }
+}
@@ -1,9 +1,13 @@
 //! Configuration file management for adsb-decode.
 //!
-//! Reads/writes `~/.adsb-decode/config.yaml` with receiver settings,
-//! database path, dashboard port, and webhook URL.
+//! Reads/writes receiver settings, database path, dashboard port, and
+//! webhook URL from a config search cascade: an explicit path, then
+//! `$XDG_CONFIG_HOME/adsb-decode/config.yaml`, then
+//! `~/.adsb-decode/config.yaml`, then `/etc/adsb-decode/config.yaml`.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
 
 use crate::types::AdsbError;
 
@@ -34,6 +38,59 @@ pub struct DashboardConfig {
     pub port: u16,
 }
 
+impl Config {
+    /// Validate semantically-meaningful values a successful parse can't
+    /// catch on its own (e.g. a latitude that's just a float out of
+    /// range). Collects every problem rather than stopping at the first,
+    /// so a caller can report the full list at once instead of making the
+    /// user fix and re-run one mistake at a time. A missing parent
+    /// directory for `database.path` is only a warning printed to stderr,
+    /// since the directory may simply not be created yet -- it doesn't
+    /// add to the returned errors.
+    pub fn validate(&self) -> std::result::Result<(), Vec<AdsbError>> {
+        let mut errors = Vec::new();
+
+        if let Some(lat) = self.receiver.lat {
+            if !(-90.0..=90.0).contains(&lat) {
+                errors.push(AdsbError::Config(format!("receiver.lat {lat} is out of range (-90..90)")));
+            }
+        }
+        if let Some(lon) = self.receiver.lon {
+            if !(-180.0..=180.0).contains(&lon) {
+                errors.push(AdsbError::Config(format!("receiver.lon {lon} is out of range (-180..180)")));
+            }
+        }
+
+        if self.dashboard.port == 0 {
+            errors.push(AdsbError::Config("dashboard.port must not be 0".into()));
+        }
+
+        if let Some(url) = &self.webhook {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                errors.push(AdsbError::Config(format!(
+                    "webhook {url:?} must start with http:// or https://"
+                )));
+            }
+        }
+
+        let parent = Path::new(&self.database.path).parent();
+        if let Some(parent) = parent {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                eprintln!(
+                    "  [config] warning: database.path's parent directory {} does not exist",
+                    parent.display()
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
@@ -59,7 +116,7 @@ pub fn config_dir() -> PathBuf {
     dirs_home().join(".adsb-decode")
 }
 
-/// Get the config file path.
+/// Get the config file path (`~/.adsb-decode/config.yaml`).
 pub fn config_file() -> PathBuf {
     config_dir().join("config.yaml")
 }
@@ -71,36 +128,300 @@ fn dirs_home() -> PathBuf {
         .unwrap_or_else(|_| PathBuf::from("."))
 }
 
-/// Load config from `~/.adsb-decode/config.yaml`.
-///
-/// Returns default config if file doesn't exist.
-pub fn load_config() -> Config {
-    let path = config_file();
-    if !path.exists() {
-        return Config::default();
+/// The config search cascade, in priority order: an explicit path (e.g. a
+/// `--config` flag), `$XDG_CONFIG_HOME/adsb-decode/config.yaml` if that's
+/// set, `~/.adsb-decode/config.yaml`, then a system-wide
+/// `/etc/adsb-decode/config.yaml`.
+pub fn config_search_paths(explicit: Option<&Path>) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(p) = explicit {
+        paths.push(p.to_path_buf());
+    }
+    if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+        if !xdg.is_empty() {
+            paths.push(PathBuf::from(xdg).join("adsb-decode").join("config.yaml"));
+        }
     }
+    paths.push(config_file());
+    paths.push(PathBuf::from("/etc/adsb-decode/config.yaml"));
+    paths
+}
 
-    let text = match std::fs::read_to_string(&path) {
-        Ok(t) => t,
-        Err(_) => return Config::default(),
-    };
+/// Config files larger than this are refused by `load_config` unless
+/// `allow_large_config` is set -- a misconfigured `--config` path pointing
+/// at, say, a log file or a device node shouldn't get read into memory and
+/// parsed line by line. 1 MB is generously larger than any config this tool
+/// would legitimately write.
+const MAX_CONFIG_FILE_BYTES: u64 = 1024 * 1024;
 
-    parse_config(&text).unwrap_or_default()
+/// Load config by walking `config_search_paths(explicit)` for the first
+/// file that exists, layering `ADSB_*` environment variables on top (which
+/// win over whichever file was found). Returns the config, the path it was
+/// actually loaded from (`None` means no candidate file existed and
+/// built-in defaults were used), and any errors collected along the way --
+/// both `Config::validate()` problems and any candidate rejected for being
+/// over `MAX_CONFIG_FILE_BYTES` -- rather than silently dropped, so a
+/// caller can report the full list of things to fix instead of failing
+/// opaquely on first use. An oversized candidate is skipped in favor of the
+/// next one in the cascade, same as a candidate that doesn't exist; pass
+/// `allow_large_config` to lift the cap entirely.
+pub fn load_config(explicit: Option<&Path>, allow_large_config: bool) -> (Config, Option<PathBuf>, Vec<AdsbError>) {
+    let mut errors = Vec::new();
+
+    for path in config_search_paths(explicit) {
+        if !allow_large_config {
+            if let Ok(metadata) = std::fs::metadata(&path) {
+                if metadata.len() > MAX_CONFIG_FILE_BYTES {
+                    errors.push(AdsbError::Config(format!(
+                        "{} is {} bytes, over the {MAX_CONFIG_FILE_BYTES}-byte config size cap; pass --allow-large-config to load it anyway",
+                        path.display(),
+                        metadata.len()
+                    )));
+                    continue;
+                }
+            }
+        }
+
+        if let Ok(text) = std::fs::read_to_string(&path) {
+            let mut config = parse_config_for(&path, &text).unwrap_or_default();
+            apply_env_overrides(&mut config);
+            errors.extend(config.validate().err().unwrap_or_default());
+            return (config, Some(path), errors);
+        }
+    }
+
+    let mut config = Config::default();
+    apply_env_overrides(&mut config);
+    errors.extend(config.validate().err().unwrap_or_default());
+    (config, None, errors)
 }
 
-/// Save config to `~/.adsb-decode/config.yaml`.
-pub fn save_config(config: &Config) -> Result<PathBuf, AdsbError> {
-    let dir = config_dir();
-    std::fs::create_dir_all(&dir).map_err(|e| AdsbError::Config(e.to_string()))?;
+/// Overlay `ADSB_*` environment variables onto `config`. Each key is the
+/// section and field name joined with `_` and uppercased (e.g.
+/// `receiver.lat` -> `ADSB_RECEIVER_LAT`). Invalid values are skipped
+/// rather than panicking; `null`/empty clears an optional field.
+fn apply_env_overrides(config: &mut Config) {
+    if let Ok(raw) = std::env::var("ADSB_RECEIVER_NAME") {
+        if let Some(v) = parse_string_value(&raw) {
+            config.receiver.name = v;
+        }
+    }
+    if let Ok(raw) = std::env::var("ADSB_RECEIVER_LAT") {
+        apply_float_override(&mut config.receiver.lat, &raw);
+    }
+    if let Ok(raw) = std::env::var("ADSB_RECEIVER_LON") {
+        apply_float_override(&mut config.receiver.lon, &raw);
+    }
+    if let Ok(raw) = std::env::var("ADSB_DATABASE_PATH") {
+        if let Some(v) = parse_string_value(&raw) {
+            config.database.path = v;
+        }
+    }
+    if let Ok(raw) = std::env::var("ADSB_DASHBOARD_HOST") {
+        if let Some(v) = parse_string_value(&raw) {
+            config.dashboard.host = v;
+        }
+    }
+    if let Ok(raw) = std::env::var("ADSB_DASHBOARD_PORT") {
+        if let Ok(v) = raw.trim().parse::<u16>() {
+            config.dashboard.port = v;
+        }
+    }
+    if let Ok(raw) = std::env::var("ADSB_WEBHOOK") {
+        config.webhook = parse_string_value(&raw);
+    }
+}
 
-    let path = config_file();
-    let text = serialize_config(config);
-    std::fs::write(&path, text).map_err(|e| AdsbError::Config(e.to_string()))?;
+/// Apply an env override to an optional float field: a valid number
+/// overwrites it, `null`/`~`/empty clears it, and anything else
+/// unparseable is skipped, leaving the current value in place.
+fn apply_float_override(field: &mut Option<f64>, raw: &str) {
+    match parse_float_value(raw) {
+        Some(v) => *field = Some(v),
+        None if raw == "null" || raw == "~" || raw.trim().is_empty() => *field = None,
+        None => {}
+    }
+}
+
+/// Save config to the first writable location in
+/// `config_search_paths(explicit)`, creating its parent directory if
+/// needed. Locations that can't be created or written (e.g. `/etc` without
+/// root) are skipped in favor of the next one. Each candidate is
+/// serialized in the format matching its own extension, so e.g. an
+/// explicit `--config settings.toml` writes TOML even though the
+/// `~/.adsb-decode/config.yaml` fallback would have been YAML.
+pub fn save_config(config: &Config, explicit: Option<&Path>) -> Result<PathBuf, AdsbError> {
+    let mut last_err = None;
+
+    for path in config_search_paths(explicit) {
+        if let Some(dir) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                last_err = Some(e);
+                continue;
+            }
+        }
+        let text = serialize_config_for(&path, config);
+        match std::fs::write(&path, &text) {
+            Ok(()) => return Ok(path),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(AdsbError::Config(
+        last_err
+            .map(|e| e.to_string())
+            .unwrap_or_else(|| "no writable config location found".into()),
+    ))
+}
+
+/// A live-reloading handle to `Config`, kept fresh by the background thread
+/// `watch_config` spawns. Unlike `load_config`, a failed reload never falls
+/// back to `Config::default()` -- that's fine for a one-shot startup read,
+/// but silently wiping a running dashboard's settings on a bad edit would
+/// not be. A failed reload just keeps serving the last-known-good config
+/// and logs the error.
+#[derive(Clone)]
+pub struct ConfigHandle {
+    current: Arc<RwLock<Config>>,
+    subscribers: Arc<Mutex<Vec<mpsc::Sender<Config>>>>,
+}
 
-    Ok(path)
+impl ConfigHandle {
+    /// The most recently loaded config.
+    pub fn get(&self) -> Config {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Subscribe to future config reloads. The returned receiver gets one
+    /// message per successful reload, so the dashboard and webhook
+    /// subsystems can react to a changed `dashboard.port` or `webhook` URL
+    /// without a restart.
+    pub fn subscribe(&self) -> mpsc::Receiver<Config> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(tx);
+        rx
+    }
+}
+
+/// Resolve `explicit` (or the config search cascade) to a config and start
+/// watching whichever file it was loaded from -- or, if none existed, the
+/// default `~/.adsb-decode/config.yaml` location -- for modifications.
+/// Polls the file's modification time once a second rather than pulling in
+/// a platform file-watcher dependency -- cheap enough for a config file
+/// checked this infrequently.
+pub fn watch_config(explicit: Option<&Path>, allow_large_config: bool) -> ConfigHandle {
+    let (initial, loaded_from, errors) = load_config(explicit, allow_large_config);
+    for e in &errors {
+        eprintln!("  [config] {e}");
+    }
+    let watch_path = loaded_from.unwrap_or_else(config_file);
+    let current = Arc::new(RwLock::new(initial));
+    let subscribers: Arc<Mutex<Vec<mpsc::Sender<Config>>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let handle = ConfigHandle {
+        current: Arc::clone(&current),
+        subscribers: Arc::clone(&subscribers),
+    };
+
+    std::thread::spawn(move || {
+        let path = watch_path;
+        let mut last_modified = modified_time(&path);
+
+        loop {
+            std::thread::sleep(Duration::from_secs(1));
+
+            let modified = modified_time(&path);
+            if modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            match try_reload(&path, allow_large_config) {
+                Some(new_config) => match new_config.validate() {
+                    Ok(()) => {
+                        *current.write().unwrap() = new_config.clone();
+                        let mut subs = subscribers.lock().unwrap();
+                        subs.retain(|tx| tx.send(new_config.clone()).is_ok());
+                    }
+                    Err(errors) => {
+                        eprintln!("  [config] {} is invalid; keeping last-good config:", path.display());
+                        for e in errors {
+                            eprintln!("  [config]   {e}");
+                        }
+                    }
+                },
+                None => {
+                    eprintln!("  [config] failed to reload {}; keeping last-good config", path.display());
+                }
+            }
+        }
+    });
+
+    handle
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Read and parse `path`, returning `None` if the file can't be read, if a
+/// `.json` file isn't valid JSON (the YAML and TOML parsers treat malformed
+/// lines as absent rather than erroring), or if it's grown past
+/// `MAX_CONFIG_FILE_BYTES` and `allow_large_config` isn't set.
+fn try_reload(path: &Path, allow_large_config: bool) -> Option<Config> {
+    if !allow_large_config {
+        let size = std::fs::metadata(path).ok()?.len();
+        if size > MAX_CONFIG_FILE_BYTES {
+            eprintln!(
+                "  [config] {} is {size} bytes, over the {MAX_CONFIG_FILE_BYTES}-byte config size cap; refusing to reload it",
+                path.display()
+            );
+            return None;
+        }
+    }
+    let text = std::fs::read_to_string(path).ok()?;
+    parse_config_for(path, &text)
 }
 
 /// Parse simple YAML-like config text.
+/// A config file format, detected from the file's extension. `.yaml`/`.yml`
+/// (and anything unrecognized) use the original hand-rolled format;
+/// `.toml` and `.json` get their own parser/serializer pair, all producing
+/// or consuming the same `Config`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Yaml,
+    Toml,
+    Json,
+}
+
+fn format_for_path(path: &Path) -> ConfigFormat {
+    match path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase()) {
+        Some(ext) if ext == "toml" => ConfigFormat::Toml,
+        Some(ext) if ext == "json" => ConfigFormat::Json,
+        _ => ConfigFormat::Yaml,
+    }
+}
+
+/// Parse `text` (read from `path`) using the format matching `path`'s
+/// extension.
+fn parse_config_for(path: &Path, text: &str) -> Option<Config> {
+    match format_for_path(path) {
+        ConfigFormat::Yaml => parse_config(text),
+        ConfigFormat::Toml => parse_toml_config(text),
+        ConfigFormat::Json => parse_json_config(text),
+    }
+}
+
+/// Serialize `config` using the format matching `path`'s extension.
+fn serialize_config_for(path: &Path, config: &Config) -> String {
+    match format_for_path(path) {
+        ConfigFormat::Yaml => serialize_config(config),
+        ConfigFormat::Toml => serialize_toml_config(config),
+        ConfigFormat::Json => serialize_json_config(config),
+    }
+}
+
 fn parse_config(text: &str) -> Option<Config> {
     let mut config = Config::default();
     let mut current_section: Option<String> = None;
@@ -222,6 +543,160 @@ fn serialize_config(config: &Config) -> String {
     lines.join("\n") + "\n"
 }
 
+/// Parse a flat TOML document (`[section]` tables, `key = value` pairs).
+fn parse_toml_config(text: &str) -> Option<Config> {
+    let mut config = Config::default();
+    let mut section: Option<String> = None;
+
+    for raw_line in text.lines() {
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            section = Some(line[1..line.len() - 1].trim().to_string());
+            continue;
+        }
+
+        let Some((key, val)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let val = val.trim();
+
+        match section.as_deref() {
+            None if key == "webhook" => config.webhook = parse_toml_string(val),
+            Some("receiver") => match key {
+                "name" => {
+                    if let Some(v) = parse_toml_string(val) {
+                        config.receiver.name = v;
+                    }
+                }
+                "lat" => config.receiver.lat = val.parse().ok(),
+                "lon" => config.receiver.lon = val.parse().ok(),
+                _ => {}
+            },
+            Some("database") if key == "path" => {
+                if let Some(v) = parse_toml_string(val) {
+                    config.database.path = v;
+                }
+            }
+            Some("dashboard") => match key {
+                "host" => {
+                    if let Some(v) = parse_toml_string(val) {
+                        config.dashboard.host = v;
+                    }
+                }
+                "port" => {
+                    if let Ok(v) = val.parse::<u16>() {
+                        config.dashboard.port = v;
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    Some(config)
+}
+
+/// Strip the surrounding quotes from a TOML basic string; `None` for
+/// anything else (TOML has no bare `null`, so an absent key is how an
+/// optional field stays unset).
+fn parse_toml_string(val: &str) -> Option<String> {
+    if val.len() >= 2 && val.starts_with('"') && val.ends_with('"') {
+        Some(val[1..val.len() - 1].to_string())
+    } else {
+        None
+    }
+}
+
+/// Serialize config as TOML. Optional fields that are `None` are simply
+/// omitted, since TOML has no null literal -- the mirrored parser treats
+/// an absent key the same way.
+fn serialize_toml_config(config: &Config) -> String {
+    let mut lines = vec!["# adsb-decode configuration".to_string(), String::new()];
+
+    if let Some(url) = &config.webhook {
+        lines.push(format!("webhook = \"{url}\""));
+        lines.push(String::new());
+    }
+
+    lines.push("[receiver]".into());
+    lines.push(format!("name = \"{}\"", config.receiver.name));
+    if let Some(v) = config.receiver.lat {
+        lines.push(format!("lat = {v}"));
+    }
+    if let Some(v) = config.receiver.lon {
+        lines.push(format!("lon = {v}"));
+    }
+    lines.push(String::new());
+
+    lines.push("[database]".into());
+    lines.push(format!("path = \"{}\"", config.database.path));
+    lines.push(String::new());
+
+    lines.push("[dashboard]".into());
+    lines.push(format!("host = \"{}\"", config.dashboard.host));
+    lines.push(format!("port = {}", config.dashboard.port));
+
+    lines.join("\n") + "\n"
+}
+
+/// Parse a JSON config document via a generic `serde_json::Value` rather
+/// than deriving `Deserialize` on `Config`, matching this crate's existing
+/// serde usage (output-only `Serialize` derives elsewhere).
+fn parse_json_config(text: &str) -> Option<Config> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    let mut config = Config::default();
+
+    if let Some(receiver) = value.get("receiver") {
+        if let Some(name) = receiver.get("name").and_then(|v| v.as_str()) {
+            config.receiver.name = name.to_string();
+        }
+        config.receiver.lat = receiver.get("lat").and_then(|v| v.as_f64());
+        config.receiver.lon = receiver.get("lon").and_then(|v| v.as_f64());
+    }
+
+    if let Some(path) = value.get("database").and_then(|d| d.get("path")).and_then(|v| v.as_str()) {
+        config.database.path = path.to_string();
+    }
+
+    if let Some(dashboard) = value.get("dashboard") {
+        if let Some(host) = dashboard.get("host").and_then(|v| v.as_str()) {
+            config.dashboard.host = host.to_string();
+        }
+        if let Some(port) = dashboard.get("port").and_then(|v| v.as_u64()).and_then(|p| u16::try_from(p).ok()) {
+            config.dashboard.port = port;
+        }
+    }
+
+    config.webhook = value.get("webhook").and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    Some(config)
+}
+
+fn serialize_json_config(config: &Config) -> String {
+    let value = serde_json::json!({
+        "receiver": {
+            "name": config.receiver.name,
+            "lat": config.receiver.lat,
+            "lon": config.receiver.lon,
+        },
+        "database": {
+            "path": config.database.path,
+        },
+        "dashboard": {
+            "host": config.dashboard.host,
+            "port": config.dashboard.port,
+        },
+        "webhook": config.webhook,
+    });
+    serde_json::to_string_pretty(&value).unwrap_or_default() + "\n"
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -305,4 +780,438 @@ webhook: null
         assert_eq!(parsed.dashboard.port, 9090);
         assert_eq!(parsed.webhook, Some("https://example.com".into()));
     }
+
+    fn sample_config() -> Config {
+        Config {
+            receiver: ReceiverConfig {
+                name: "test".into(),
+                lat: Some(35.5),
+                lon: Some(-82.5),
+            },
+            database: DatabaseConfig {
+                path: "test.db".into(),
+            },
+            dashboard: DashboardConfig {
+                host: "0.0.0.0".into(),
+                port: 9090,
+            },
+            webhook: Some("https://example.com".into()),
+        }
+    }
+
+    #[test]
+    fn test_toml_roundtrip() {
+        let config = sample_config();
+        let text = serialize_toml_config(&config);
+        let parsed = parse_toml_config(&text).unwrap();
+        assert_eq!(parsed.receiver.name, "test");
+        assert_eq!(parsed.receiver.lat, Some(35.5));
+        assert_eq!(parsed.receiver.lon, Some(-82.5));
+        assert_eq!(parsed.database.path, "test.db");
+        assert_eq!(parsed.dashboard.host, "0.0.0.0");
+        assert_eq!(parsed.dashboard.port, 9090);
+        assert_eq!(parsed.webhook, Some("https://example.com".into()));
+    }
+
+    #[test]
+    fn test_toml_roundtrip_with_absent_optional_fields() {
+        let config = Config {
+            webhook: None,
+            receiver: ReceiverConfig {
+                name: "test".into(),
+                lat: None,
+                lon: None,
+            },
+            ..sample_config()
+        };
+        let text = serialize_toml_config(&config);
+        let parsed = parse_toml_config(&text).unwrap();
+        assert!(parsed.receiver.lat.is_none());
+        assert!(parsed.receiver.lon.is_none());
+        assert!(parsed.webhook.is_none());
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let config = sample_config();
+        let text = serialize_json_config(&config);
+        let parsed = parse_json_config(&text).unwrap();
+        assert_eq!(parsed.receiver.name, "test");
+        assert_eq!(parsed.receiver.lat, Some(35.5));
+        assert_eq!(parsed.receiver.lon, Some(-82.5));
+        assert_eq!(parsed.database.path, "test.db");
+        assert_eq!(parsed.dashboard.host, "0.0.0.0");
+        assert_eq!(parsed.dashboard.port, 9090);
+        assert_eq!(parsed.webhook, Some("https://example.com".into()));
+    }
+
+    #[test]
+    fn test_json_roundtrip_with_null_optional_fields() {
+        let config = Config {
+            webhook: None,
+            receiver: ReceiverConfig {
+                name: "test".into(),
+                lat: None,
+                lon: None,
+            },
+            ..sample_config()
+        };
+        let text = serialize_json_config(&config);
+        let parsed = parse_json_config(&text).unwrap();
+        assert!(parsed.receiver.lat.is_none());
+        assert!(parsed.receiver.lon.is_none());
+        assert!(parsed.webhook.is_none());
+    }
+
+    #[test]
+    fn test_format_for_path_dispatches_on_extension() {
+        assert_eq!(format_for_path(Path::new("config.toml")), ConfigFormat::Toml);
+        assert_eq!(format_for_path(Path::new("config.JSON")), ConfigFormat::Json);
+        assert_eq!(format_for_path(Path::new("config.yaml")), ConfigFormat::Yaml);
+        assert_eq!(format_for_path(Path::new("config.yml")), ConfigFormat::Yaml);
+        assert_eq!(format_for_path(Path::new("config")), ConfigFormat::Yaml);
+    }
+
+    #[test]
+    fn test_load_config_dispatches_format_by_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("config.toml");
+        std::fs::write(&toml_path, "[dashboard]\nhost = \"toml-host\"\nport = 4242\n").unwrap();
+
+        let (config, _, _) = load_config(Some(&toml_path), false);
+        assert_eq!(config.dashboard.host, "toml-host");
+        assert_eq!(config.dashboard.port, 4242);
+
+        let json_path = dir.path().join("config.json");
+        std::fs::write(&json_path, r#"{"dashboard": {"host": "json-host", "port": 5353}}"#).unwrap();
+
+        let (config, _, _) = load_config(Some(&json_path), false);
+        assert_eq!(config.dashboard.host, "json-host");
+        assert_eq!(config.dashboard.port, 5353);
+    }
+
+    #[test]
+    fn test_save_config_writes_format_matching_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("config.toml");
+        let config = sample_config();
+
+        save_config(&config, Some(&toml_path)).unwrap();
+        let text = std::fs::read_to_string(&toml_path).unwrap();
+        assert!(text.contains("[dashboard]"));
+        assert!(!text.contains("dashboard:"));
+    }
+
+    #[test]
+    fn test_env_overrides_win_over_file_values() {
+        let mut config = Config {
+            receiver: ReceiverConfig {
+                name: "file-name".into(),
+                lat: Some(1.0),
+                lon: Some(2.0),
+            },
+            database: DatabaseConfig {
+                path: "file.db".into(),
+            },
+            dashboard: DashboardConfig {
+                host: "file-host".into(),
+                port: 1111,
+            },
+            webhook: Some("https://file.example.com".into()),
+        };
+
+        std::env::set_var("ADSB_RECEIVER_NAME", "env-name");
+        std::env::set_var("ADSB_RECEIVER_LAT", "35.5");
+        std::env::set_var("ADSB_RECEIVER_LON", "-82.5");
+        std::env::set_var("ADSB_DATABASE_PATH", "/env/path.db");
+        std::env::set_var("ADSB_DASHBOARD_HOST", "0.0.0.0");
+        std::env::set_var("ADSB_DASHBOARD_PORT", "9090");
+        std::env::set_var("ADSB_WEBHOOK", "https://env.example.com");
+
+        apply_env_overrides(&mut config);
+
+        assert_eq!(config.receiver.name, "env-name");
+        assert_eq!(config.receiver.lat, Some(35.5));
+        assert_eq!(config.receiver.lon, Some(-82.5));
+        assert_eq!(config.database.path, "/env/path.db");
+        assert_eq!(config.dashboard.host, "0.0.0.0");
+        assert_eq!(config.dashboard.port, 9090);
+        assert_eq!(config.webhook, Some("https://env.example.com".into()));
+
+        std::env::remove_var("ADSB_RECEIVER_NAME");
+        std::env::remove_var("ADSB_RECEIVER_LAT");
+        std::env::remove_var("ADSB_RECEIVER_LON");
+        std::env::remove_var("ADSB_DATABASE_PATH");
+        std::env::remove_var("ADSB_DASHBOARD_HOST");
+        std::env::remove_var("ADSB_DASHBOARD_PORT");
+        std::env::remove_var("ADSB_WEBHOOK");
+    }
+
+    #[test]
+    fn test_env_overrides_clear_null_and_skip_invalid() {
+        let mut config = Config {
+            receiver: ReceiverConfig {
+                name: "keep-name".into(),
+                lat: Some(1.0),
+                lon: Some(2.0),
+            },
+            database: DatabaseConfig {
+                path: "keep.db".into(),
+            },
+            dashboard: DashboardConfig {
+                host: "keep-host".into(),
+                port: 1111,
+            },
+            webhook: Some("https://keep.example.com".into()),
+        };
+
+        std::env::set_var("ADSB_RECEIVER_LAT", "null");
+        std::env::set_var("ADSB_RECEIVER_LON", "not-a-number");
+        std::env::set_var("ADSB_DASHBOARD_PORT", "not-a-port");
+        std::env::set_var("ADSB_WEBHOOK", "");
+
+        apply_env_overrides(&mut config);
+
+        assert!(config.receiver.lat.is_none(), "null should clear an optional field");
+        assert_eq!(config.receiver.lon, Some(2.0), "invalid value should be skipped");
+        assert_eq!(config.dashboard.port, 1111, "invalid port should be skipped");
+        assert!(config.webhook.is_none(), "empty should clear an optional field");
+        assert_eq!(config.receiver.name, "keep-name", "unset env vars shouldn't touch config");
+
+        std::env::remove_var("ADSB_RECEIVER_LAT");
+        std::env::remove_var("ADSB_RECEIVER_LON");
+        std::env::remove_var("ADSB_DASHBOARD_PORT");
+        std::env::remove_var("ADSB_WEBHOOK");
+    }
+
+    #[test]
+    fn test_try_reload_parses_valid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(&path, "dashboard:\n  host: \"0.0.0.0\"\n  port: 9191\n").unwrap();
+
+        let config = try_reload(&path, false).unwrap();
+        assert_eq!(config.dashboard.host, "0.0.0.0");
+        assert_eq!(config.dashboard.port, 9191);
+    }
+
+    #[test]
+    fn test_try_reload_returns_none_when_file_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("does-not-exist.yaml");
+        assert!(try_reload(&path, false).is_none());
+    }
+
+    #[test]
+    fn test_config_handle_get_reflects_current_and_notifies_subscribers() {
+        let initial = Config {
+            dashboard: DashboardConfig {
+                host: "127.0.0.1".into(),
+                port: 8080,
+            },
+            ..Config::default()
+        };
+        let current = Arc::new(RwLock::new(initial.clone()));
+        let subscribers: Arc<Mutex<Vec<mpsc::Sender<Config>>>> = Arc::new(Mutex::new(Vec::new()));
+        let handle = ConfigHandle {
+            current: Arc::clone(&current),
+            subscribers: Arc::clone(&subscribers),
+        };
+
+        assert_eq!(handle.get().dashboard.port, 8080);
+
+        let rx = handle.subscribe();
+        let reloaded = Config {
+            dashboard: DashboardConfig {
+                host: "127.0.0.1".into(),
+                port: 9090,
+            },
+            ..Config::default()
+        };
+        *current.write().unwrap() = reloaded.clone();
+        for tx in subscribers.lock().unwrap().iter() {
+            tx.send(reloaded.clone()).unwrap();
+        }
+
+        assert_eq!(handle.get().dashboard.port, 9090);
+        assert_eq!(rx.recv().unwrap().dashboard.port, 9090);
+    }
+
+    #[test]
+    fn test_config_search_paths_order() {
+        std::env::remove_var("XDG_CONFIG_HOME");
+        let explicit = PathBuf::from("/explicit/config.yaml");
+        let paths = config_search_paths(Some(&explicit));
+        assert_eq!(paths[0], explicit);
+        assert_eq!(paths[1], config_file());
+        assert_eq!(paths[2], PathBuf::from("/etc/adsb-decode/config.yaml"));
+    }
+
+    #[test]
+    fn test_config_search_paths_includes_xdg_when_set() {
+        std::env::set_var("XDG_CONFIG_HOME", "/xdg-home");
+        let paths = config_search_paths(None);
+        assert_eq!(paths[0], PathBuf::from("/xdg-home/adsb-decode/config.yaml"));
+        assert_eq!(paths[1], config_file());
+        assert_eq!(paths[2], PathBuf::from("/etc/adsb-decode/config.yaml"));
+        std::env::remove_var("XDG_CONFIG_HOME");
+    }
+
+    #[test]
+    fn test_load_config_prefers_explicit_path_over_rest_of_cascade() {
+        let dir = tempfile::tempdir().unwrap();
+        let explicit = dir.path().join("explicit.yaml");
+        std::fs::write(&explicit, "dashboard:\n  host: \"explicit-host\"\n  port: 7777\n").unwrap();
+
+        let (config, loaded_from, errors) = load_config(Some(&explicit), false);
+        assert_eq!(config.dashboard.host, "explicit-host");
+        assert_eq!(config.dashboard.port, 7777);
+        assert_eq!(loaded_from, Some(explicit));
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_load_config_falls_back_to_defaults_when_nothing_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("does-not-exist.yaml");
+        std::env::remove_var("XDG_CONFIG_HOME");
+
+        let (config, loaded_from, _errors) = load_config(Some(&missing), false);
+        assert_eq!(config.dashboard.port, Config::default().dashboard.port);
+        assert_ne!(loaded_from, Some(missing));
+    }
+
+    #[test]
+    fn test_save_config_writes_to_explicit_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let explicit = dir.path().join("nested").join("config.yaml");
+        let config = Config::default();
+
+        let saved_to = save_config(&config, Some(&explicit)).unwrap();
+        assert_eq!(saved_to, explicit);
+        assert!(explicit.exists());
+    }
+
+    #[test]
+    fn test_validate_accepts_default_config() {
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_lat_lon() {
+        let mut config = Config::default();
+        config.receiver.lat = Some(120.0);
+        config.receiver.lon = Some(-200.0);
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_validate_accepts_boundary_lat_lon() {
+        let mut config = Config::default();
+        config.receiver.lat = Some(90.0);
+        config.receiver.lon = Some(-180.0);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_port() {
+        let mut config = Config::default();
+        config.dashboard.port = 0;
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_rejects_webhook_without_http_scheme() {
+        let config = Config {
+            webhook: Some("ftp://example.com".into()),
+            ..Config::default()
+        };
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_accepts_https_webhook() {
+        let config = Config {
+            webhook: Some("https://example.com/hook".into()),
+            ..Config::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_collects_multiple_errors_at_once() {
+        let mut config = Config {
+            webhook: Some("not-a-url".into()),
+            ..Config::default()
+        };
+        config.receiver.lat = Some(95.0);
+        config.dashboard.port = 0;
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_load_config_surfaces_validation_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(&path, "dashboard:\n  host: \"0.0.0.0\"\n  port: 0\n").unwrap();
+
+        let (_config, _loaded_from, errors) = load_config(Some(&path), false);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_load_config_rejects_oversized_file_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        let oversized = "#".repeat((MAX_CONFIG_FILE_BYTES + 1) as usize);
+        std::fs::write(&path, &oversized).unwrap();
+
+        let (config, loaded_from, errors) = load_config(Some(&path), false);
+        assert_eq!(loaded_from, None, "an oversized candidate should be skipped, not loaded from");
+        assert_eq!(config.dashboard.port, Config::default().dashboard.port);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(&errors[0], AdsbError::Config(msg) if msg.contains("over the")));
+    }
+
+    #[test]
+    fn test_load_config_allows_oversized_file_with_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        let padding = "#".repeat((MAX_CONFIG_FILE_BYTES + 1) as usize);
+        std::fs::write(&path, format!("{padding}\ndashboard:\n  host: \"0.0.0.0\"\n  port: 6161\n")).unwrap();
+
+        let (config, loaded_from, errors) = load_config(Some(&path), true);
+        assert_eq!(loaded_from, Some(path));
+        assert_eq!(config.dashboard.port, 6161);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_try_reload_refuses_oversized_file_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        let oversized = "#".repeat((MAX_CONFIG_FILE_BYTES + 1) as usize);
+        std::fs::write(&path, &oversized).unwrap();
+
+        assert!(try_reload(&path, false).is_none());
+    }
+
+    #[test]
+    fn test_try_reload_allows_oversized_file_with_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        let padding = "#".repeat((MAX_CONFIG_FILE_BYTES + 1) as usize);
+        std::fs::write(&path, format!("{padding}\ndashboard:\n  host: \"0.0.0.0\"\n  port: 6262\n")).unwrap();
+
+        let config = try_reload(&path, true).unwrap();
+        assert_eq!(config.dashboard.port, 6262);
+    }
 }
@@ -120,9 +120,34 @@ pub fn local_decode(
     cpr_odd: bool,
     ref_lat: f64,
     ref_lon: f64,
+) -> (f64, f64) {
+    local_decode_scaled(cpr_lat, cpr_lon, cpr_odd, ref_lat, ref_lon, AIRBORNE_RANGE_DEG)
+}
+
+/// Full CPR range for airborne positions: the encoding covers the whole
+/// globe, so a global pair needs no reference to resolve unambiguously.
+const AIRBORNE_RANGE_DEG: f64 = 360.0;
+
+/// Full CPR range for surface (TC 5-8) positions: a quarter of the globe
+/// (90 degrees) rather than 360. Surface aircraft move little, so the
+/// encoding trades range for precision — but it means a decoded position
+/// always needs a nearby reference to pick the right quadrant, even for a
+/// matched even/odd pair.
+const SURFACE_RANGE_DEG: f64 = 90.0;
+
+/// Shared local-decode math for both airborne (`range_deg = 360`) and
+/// surface (`range_deg = 90`) positions: the zone-unwrapping formula is
+/// identical, just scaled to the CPR range in use.
+fn local_decode_scaled(
+    cpr_lat: u32,
+    cpr_lon: u32,
+    cpr_odd: bool,
+    ref_lat: f64,
+    ref_lon: f64,
+    range_deg: f64,
 ) -> (f64, f64) {
     let i = if cpr_odd { 1.0 } else { 0.0 };
-    let dlat = 360.0 / (4.0 * NZ - i);
+    let dlat = range_deg / (4.0 * NZ - i);
 
     let cpr_lat_norm = cpr_lat as f64 / CPR_MAX;
     let cpr_lon_norm = cpr_lon as f64 / CPR_MAX;
@@ -135,7 +160,7 @@ pub fn local_decode(
     // Compute longitude zone size at this latitude
     let nl_val = nl(lat);
     let n_lon = (nl_val - i as i32).max(1);
-    let dlon = 360.0 / n_lon as f64;
+    let dlon = range_deg / n_lon as f64;
 
     // Compute longitude zone index from reference
     let m = (ref_lon / dlon).floor()
@@ -154,11 +179,315 @@ pub fn local_decode(
     (round6(lat), round6(lon))
 }
 
+/// Local CPR decode for surface (TC 5-8) positions.
+///
+/// Structurally identical to `local_decode`, but the CPR value spans 90
+/// degrees instead of 360, matching the surface encoding's quarter-size
+/// zones. The ambiguity interval this resolves unambiguously is
+/// correspondingly smaller (~45nm vs ~180nm for airborne), so the
+/// reference must be closer for a surface fix to be trustworthy.
+pub fn local_decode_surface(
+    cpr_lat: u32,
+    cpr_lon: u32,
+    cpr_odd: bool,
+    ref_lat: f64,
+    ref_lon: f64,
+) -> (f64, f64) {
+    local_decode_scaled(cpr_lat, cpr_lon, cpr_odd, ref_lat, ref_lon, SURFACE_RANGE_DEG)
+}
+
+/// Global CPR decode for surface (TC 5-8) positions from a matched
+/// even/odd pair.
+///
+/// Structurally identical to `global_decode`, except the CPR value spans
+/// 90 degrees instead of 360, so the candidate lat/lon fall within a
+/// single quadrant of the globe. Unlike the airborne case, a surface
+/// global pair is *not* unambiguous on its own — `ref_lat`/`ref_lon` is
+/// required to shift the decoded quadrant by whole multiples of 90
+/// degrees to the one the aircraft is actually in.
+pub fn global_decode_surface(
+    lat_even: u32,
+    lon_even: u32,
+    lat_odd: u32,
+    lon_odd: u32,
+    t_even: f64,
+    t_odd: f64,
+    ref_lat: f64,
+    ref_lon: f64,
+) -> Option<(f64, f64)> {
+    if (t_even - t_odd).abs() > MAX_PAIR_AGE {
+        return None;
+    }
+
+    let dlat_even = SURFACE_RANGE_DEG / (4.0 * NZ);
+    let dlat_odd = SURFACE_RANGE_DEG / (4.0 * NZ - 1.0);
+
+    let lat_even_cpr = lat_even as f64 / CPR_MAX;
+    let lon_even_cpr = lon_even as f64 / CPR_MAX;
+    let lat_odd_cpr = lat_odd as f64 / CPR_MAX;
+    let lon_odd_cpr = lon_odd as f64 / CPR_MAX;
+
+    let j = (59.0 * lat_even_cpr - 60.0 * lat_odd_cpr + 0.5).floor();
+
+    let mut lat_e = dlat_even * (modulo(j, 60.0) + lat_even_cpr);
+    let mut lat_o = dlat_odd * (modulo(j, 59.0) + lat_odd_cpr);
+
+    // Shift each candidate by whole quadrants to land near the reference —
+    // the raw values are only known modulo 90 degrees.
+    lat_e += ((ref_lat - lat_e) / SURFACE_RANGE_DEG + 0.5).floor() * SURFACE_RANGE_DEG;
+    lat_o += ((ref_lat - lat_o) / SURFACE_RANGE_DEG + 0.5).floor() * SURFACE_RANGE_DEG;
+
+    if nl(lat_e) != nl(lat_o) {
+        return None; // Zone boundary crossing
+    }
+
+    let (lat, lon) = if t_even >= t_odd {
+        let nl_val = nl(lat_e);
+        let n_lon = nl_val.max(1);
+        let dlon = SURFACE_RANGE_DEG / n_lon as f64;
+        let m = (lon_even_cpr * (nl_val - 1) as f64 - lon_odd_cpr * nl_val as f64 + 0.5).floor();
+        let lon = dlon * (modulo(m, n_lon as f64) + lon_even_cpr);
+        (lat_e, lon)
+    } else {
+        let nl_val = nl(lat_o);
+        let n_lon = (nl_val - 1).max(1);
+        let dlon = SURFACE_RANGE_DEG / n_lon as f64;
+        let m = (lon_even_cpr * (nl_val - 1) as f64 - lon_odd_cpr * nl_val as f64 + 0.5).floor();
+        let lon = dlon * (modulo(m, n_lon as f64) + lon_odd_cpr);
+        (lat_o, lon)
+    };
+
+    // Shift by whole quadrants to land near the reference longitude too.
+    let lon = lon + ((ref_lon - lon) / SURFACE_RANGE_DEG + 0.5).floor() * SURFACE_RANGE_DEG;
+
+    Some((round6(lat), round6(lon)))
+}
+
 /// Round to 6 decimal places (matching Python's behavior).
 fn round6(val: f64) -> f64 {
     (val * 1_000_000.0).round() / 1_000_000.0
 }
 
+// ---------------------------------------------------------------------------
+// Position plausibility gating
+// ---------------------------------------------------------------------------
+
+/// Default maximum plausible reception range from the receiver, in nautical
+/// miles. 1090MHz ADS-B is line-of-sight; dump1090/readsb use a similar
+/// bound to reject CPR zone-boundary glitches that decode to a wildly
+/// distant coordinate.
+pub const DEFAULT_MAX_RANGE_NM: f64 = 400.0;
+
+/// Default ceiling on implied ground speed between two consecutive accepted
+/// fixes, in knots. Comfortably above any civil or military aircraft's
+/// true airspeed, so it only catches decode glitches, not fast traffic.
+pub const DEFAULT_MAX_SPEED_KTS: f64 = 1000.0;
+
+/// Rejects CPR-decoded positions that are physically implausible, following
+/// the consistency checks dump1090/readsb apply in `track.c`: a fix beyond
+/// line-of-sight range of the receiver, or one that implies a speed no
+/// aircraft can fly, is almost always a zone-boundary or bit-error glitch
+/// rather than a real position.
+///
+/// This is a standalone gate callers can run CPR output through explicitly
+/// (e.g. before accepting a feeder-reported position server-side); it's
+/// independent of `Tracker`'s own internal jitter gate (see
+/// `AircraftState::accepts_position`), which instead compares against the
+/// aircraft's own last fix and reported speed without a receiver location.
+pub struct PositionValidator {
+    receiver_lat: Option<f64>,
+    receiver_lon: Option<f64>,
+    max_range_nm: f64,
+    max_speed_kts: f64,
+    last_fix: std::collections::HashMap<[u8; 3], (f64, f64, f64)>,
+}
+
+impl PositionValidator {
+    /// A validator with no receiver location — range checks are skipped,
+    /// only the speed-implied-by-consecutive-fixes check applies.
+    pub fn new() -> Self {
+        Self {
+            receiver_lat: None,
+            receiver_lon: None,
+            max_range_nm: DEFAULT_MAX_RANGE_NM,
+            max_speed_kts: DEFAULT_MAX_SPEED_KTS,
+            last_fix: std::collections::HashMap::new(),
+        }
+    }
+
+    /// A validator anchored to a known receiver location, so out-of-range
+    /// fixes are also rejected.
+    pub fn with_receiver(receiver_lat: f64, receiver_lon: f64) -> Self {
+        Self {
+            receiver_lat: Some(receiver_lat),
+            receiver_lon: Some(receiver_lon),
+            ..Self::new()
+        }
+    }
+
+    /// Override the default max reception range (nautical miles).
+    pub fn with_max_range_nm(mut self, max_range_nm: f64) -> Self {
+        self.max_range_nm = max_range_nm;
+        self
+    }
+
+    /// Override the default max implied ground speed (knots).
+    pub fn with_max_speed_kts(mut self, max_speed_kts: f64) -> Self {
+        self.max_speed_kts = max_speed_kts;
+        self
+    }
+
+    /// Validate a decoded position for `icao`, recording it as the new
+    /// last-accepted fix if it passes. Returns `false` (and leaves the
+    /// last-accepted fix unchanged) for a rejected position.
+    pub fn validate(&mut self, icao: [u8; 3], lat: f64, lon: f64, timestamp: f64) -> bool {
+        if let (Some(rx_lat), Some(rx_lon)) = (self.receiver_lat, self.receiver_lon) {
+            if crate::filter::haversine_nm(rx_lat, rx_lon, lat, lon) > self.max_range_nm {
+                return false;
+            }
+        }
+
+        if let Some(&(last_lat, last_lon, last_ts)) = self.last_fix.get(&icao) {
+            let dt_hours = (timestamp - last_ts).abs() / 3600.0;
+            if dt_hours > 0.0 {
+                let implied_speed_kts =
+                    crate::filter::haversine_nm(last_lat, last_lon, lat, lon) / dt_hours;
+                if implied_speed_kts > self.max_speed_kts {
+                    return false;
+                }
+            }
+        }
+
+        self.last_fix.insert(icao, (lat, lon, timestamp));
+        true
+    }
+}
+
+impl Default for PositionValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Per-aircraft CPR tracker
+// ---------------------------------------------------------------------------
+
+/// Number of recent decoded positions kept for outlier detection.
+const CPR_TRACKER_HISTORY: usize = 5;
+
+/// A fix is dropped as an outlier when it's further than this from the
+/// median of the recent history — generous enough to tolerate real
+/// maneuvering, tight enough to catch a single-bit-error spike.
+const CPR_TRACKER_OUTLIER_NM: f64 = 10.0;
+
+/// Buffers even/odd CPR frames for a single aircraft and decodes fresh
+/// positions from them, mirroring heliwatch's per-aircraft `Entry`.
+///
+/// Unlike `Tracker`, which owns this bookkeeping per-ICAO internally
+/// alongside everything else it tracks, `CprTracker` is a standalone unit
+/// callers can use when they only need CPR decode (e.g. feeding a
+/// lightweight tool that doesn't need the full `Tracker` state machine).
+/// `global_decode` runs whenever a fresh opposite-parity pair is
+/// available; otherwise it falls back to `local_decode` against the last
+/// filtered position. A small ring buffer of recent fixes rejects outliers
+/// whose distance from the buffer's median exceeds `CPR_TRACKER_OUTLIER_NM`,
+/// so a single corrupt frame doesn't show up as a spike.
+pub struct CprTracker {
+    even: Option<(u32, u32, f64)>,
+    odd: Option<(u32, u32, f64)>,
+    history: std::collections::VecDeque<(f64, f64)>,
+}
+
+impl CprTracker {
+    pub fn new() -> Self {
+        Self {
+            even: None,
+            odd: None,
+            history: std::collections::VecDeque::with_capacity(CPR_TRACKER_HISTORY),
+        }
+    }
+
+    /// Record a new even-parity CPR frame.
+    pub fn update_even(&mut self, cpr_lat: u32, cpr_lon: u32, timestamp: f64) {
+        self.even = Some((cpr_lat, cpr_lon, timestamp));
+    }
+
+    /// Record a new odd-parity CPR frame.
+    pub fn update_odd(&mut self, cpr_lat: u32, cpr_lon: u32, timestamp: f64) {
+        self.odd = Some((cpr_lat, cpr_lon, timestamp));
+    }
+
+    /// Decode the latest filtered position, if one is available.
+    ///
+    /// Tries a global decode from the buffered even/odd pair first; falls
+    /// back to a local decode of whichever frame is freshest against the
+    /// last filtered position. Returns `None` until a decode has ever
+    /// succeeded (local decode needs a reference).
+    pub fn position(&mut self) -> Option<(f64, f64)> {
+        let fix = self.decode_raw()?;
+        self.filter(fix)
+    }
+
+    fn decode_raw(&self) -> Option<(f64, f64)> {
+        if let (Some((lat_e, lon_e, t_e)), Some((lat_o, lon_o, t_o))) = (self.even, self.odd) {
+            if let Some(fix) = global_decode(lat_e, lon_e, lat_o, lon_o, t_e, t_o) {
+                return Some(fix);
+            }
+        }
+
+        let &(ref_lat, ref_lon) = self.history.back()?;
+        match (self.even, self.odd) {
+            (Some((lat, lon, t_e)), Some((_, _, t_o))) if t_e >= t_o => {
+                Some(local_decode(lat, lon, false, ref_lat, ref_lon))
+            }
+            (_, Some((lat, lon, _))) => Some(local_decode(lat, lon, true, ref_lat, ref_lon)),
+            (Some((lat, lon, _)), None) => Some(local_decode(lat, lon, false, ref_lat, ref_lon)),
+            (None, None) => None,
+        }
+    }
+
+    /// Reject `fix` as an outlier if it's too far from the history's
+    /// median; otherwise record it and return the (updated) median.
+    fn filter(&mut self, fix: (f64, f64)) -> Option<(f64, f64)> {
+        if !self.history.is_empty() {
+            let median = self.median();
+            let dist = crate::filter::haversine_nm(median.0, median.1, fix.0, fix.1);
+            if dist > CPR_TRACKER_OUTLIER_NM {
+                return Some(median);
+            }
+        }
+
+        if self.history.len() == CPR_TRACKER_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(fix);
+        Some(self.median())
+    }
+
+    fn median(&self) -> (f64, f64) {
+        fn median_of(values: &mut [f64]) -> f64 {
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let mid = values.len() / 2;
+            if values.len() % 2 == 0 {
+                (values[mid - 1] + values[mid]) / 2.0
+            } else {
+                values[mid]
+            }
+        }
+
+        let mut lats: Vec<f64> = self.history.iter().map(|&(lat, _)| lat).collect();
+        let mut lons: Vec<f64> = self.history.iter().map(|&(_, lon)| lon).collect();
+        (median_of(&mut lats), median_of(&mut lons))
+    }
+}
+
+impl Default for CprTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -242,6 +571,56 @@ mod tests {
         );
     }
 
+    /// Encode a lat/lon into raw CPR values for the given range/parity,
+    /// inverting the zone math `local_decode_scaled` uses. Lets the surface
+    /// decode tests round-trip a known position without needing fixed test
+    /// vectors (there's no public "1090MHz Riddle"-style example for the
+    /// 90-degree surface encoding).
+    fn encode_cpr(lat: f64, lon: f64, odd: bool, range_deg: f64) -> (u32, u32) {
+        let i = if odd { 1.0 } else { 0.0 };
+        let dlat = range_deg / (4.0 * NZ - i);
+        let lat_cpr = (CPR_MAX * (modulo(lat, dlat) / dlat)) as u32;
+
+        let nl_val = nl(lat);
+        let n_lon = (nl_val - i as i32).max(1);
+        let dlon = range_deg / n_lon as f64;
+        let lon_cpr = (CPR_MAX * (modulo(lon, dlon) / dlon)) as u32;
+
+        (lat_cpr, lon_cpr)
+    }
+
+    #[test]
+    fn test_local_decode_surface_round_trip() {
+        // Amsterdam Schiphol apron, close to the reference so the quarter
+        // size zone still resolves unambiguously.
+        let (lat, lon) = (52.30, 4.76);
+        let (cpr_lat, cpr_lon) = encode_cpr(lat, lon, false, SURFACE_RANGE_DEG);
+
+        let (dec_lat, dec_lon) = local_decode_surface(cpr_lat, cpr_lon, false, 52.31, 4.77);
+        assert!((dec_lat - lat).abs() < 0.01, "got {dec_lat}");
+        assert!((dec_lon - lon).abs() < 0.01, "got {dec_lon}");
+    }
+
+    #[test]
+    fn test_global_decode_surface_round_trip() {
+        let (lat, lon) = (52.30, 4.76);
+        let (even_lat, even_lon) = encode_cpr(lat, lon, false, SURFACE_RANGE_DEG);
+        let (odd_lat, odd_lon) = encode_cpr(lat, lon, true, SURFACE_RANGE_DEG);
+
+        // A rough receiver reference is enough to resolve the quadrant.
+        let result =
+            global_decode_surface(even_lat, even_lon, odd_lat, odd_lon, 1.0, 0.0, 52.0, 5.0);
+        let (dec_lat, dec_lon) = result.expect("surface global decode should succeed");
+        assert!((dec_lat - lat).abs() < 0.01, "got {dec_lat}");
+        assert!((dec_lon - lon).abs() < 0.01, "got {dec_lon}");
+    }
+
+    #[test]
+    fn test_global_decode_surface_pair_too_old() {
+        let result = global_decode_surface(0, 0, 0, 0, 11.0, 0.0, 52.0, 5.0);
+        assert!(result.is_none());
+    }
+
     #[test]
     fn test_modulo_positive() {
         assert!((modulo(7.0, 3.0) - 1.0).abs() < 1e-10);
@@ -252,4 +631,105 @@ mod tests {
         // modulo(-1, 60) should return 59
         assert!((modulo(-1.0, 60.0) - 59.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_position_validator_accepts_first_fix() {
+        let mut v = PositionValidator::new();
+        assert!(v.validate([1, 2, 3], 52.3, 4.8, 0.0));
+    }
+
+    #[test]
+    fn test_position_validator_rejects_out_of_range() {
+        // Schiphol-area receiver, fix in New York: way beyond the default
+        // 400nm reception range.
+        let mut v = PositionValidator::with_receiver(52.3, 4.8);
+        assert!(!v.validate([1, 2, 3], 40.7, -74.0, 0.0));
+    }
+
+    #[test]
+    fn test_position_validator_accepts_in_range() {
+        let mut v = PositionValidator::with_receiver(52.3, 4.8);
+        assert!(v.validate([1, 2, 3], 52.5, 5.0, 0.0));
+    }
+
+    #[test]
+    fn test_position_validator_rejects_implausible_speed() {
+        let mut v = PositionValidator::new();
+        assert!(v.validate([1, 2, 3], 52.0, 4.0, 0.0));
+        // ~60nm in 1 second implies an impossible ~216000kt.
+        assert!(!v.validate([1, 2, 3], 53.0, 4.0, 1.0));
+    }
+
+    #[test]
+    fn test_position_validator_accepts_plausible_speed() {
+        let mut v = PositionValidator::new();
+        assert!(v.validate([1, 2, 3], 52.0, 4.0, 0.0));
+        // ~6nm in 60 seconds is a plausible ~360kt.
+        assert!(v.validate([1, 2, 3], 52.1, 4.0, 60.0));
+    }
+
+    #[test]
+    fn test_position_validator_custom_max_speed() {
+        let mut v = PositionValidator::new().with_max_speed_kts(100.0);
+        assert!(v.validate([1, 2, 3], 52.0, 4.0, 0.0));
+        // ~6nm in 60 seconds (~360kt) exceeds a 100kt ceiling.
+        assert!(!v.validate([1, 2, 3], 52.1, 4.0, 60.0));
+    }
+
+    #[test]
+    fn test_position_validator_tracks_icaos_independently() {
+        let mut v = PositionValidator::new();
+        assert!(v.validate([1, 0, 0], 52.0, 4.0, 0.0));
+        // A different aircraft's first fix is unconstrained by [1,0,0]'s.
+        assert!(v.validate([2, 0, 0], 10.0, 10.0, 0.0));
+    }
+
+    #[test]
+    fn test_cpr_tracker_no_position_until_a_pair() {
+        let mut t = CprTracker::new();
+        t.update_even(93000, 51372, 1.0);
+        assert!(t.position().is_none(), "one frame alone can't globally decode, no reference for local");
+    }
+
+    #[test]
+    fn test_cpr_tracker_global_decode_on_pair() {
+        let mut t = CprTracker::new();
+        t.update_even(93000, 51372, 1.0);
+        t.update_odd(74158, 50194, 0.0);
+
+        let (lat, lon) = t.position().expect("fresh pair should decode");
+        assert!((lat - 52.2572).abs() < 0.01, "got {lat}");
+        assert!((lon - 3.9194).abs() < 0.01, "got {lon}");
+    }
+
+    #[test]
+    fn test_cpr_tracker_local_decode_after_global_fix() {
+        let mut t = CprTracker::new();
+        t.update_even(93000, 51372, 1.0);
+        t.update_odd(74158, 50194, 0.0);
+        t.position().expect("initial global fix");
+
+        // A later odd frame near the same spot, too far from the buffered
+        // even frame to form a global pair, should decode locally against
+        // the fix we already have.
+        t.update_odd(74158, 50194, 12.0);
+        let (lat, lon) = t.position().expect("local decode against last fix");
+        assert!((lat - 52.2572).abs() < 0.05, "got {lat}");
+        assert!((lon - 3.9194).abs() < 0.05, "got {lon}");
+    }
+
+    #[test]
+    fn test_cpr_tracker_drops_outlier_spike() {
+        let mut t = CprTracker::new();
+        t.update_even(93000, 51372, 1.0);
+        t.update_odd(74158, 50194, 0.0);
+        let first = t.position().expect("initial global fix");
+
+        // A corrupted pair that would decode far away should be rejected,
+        // leaving the tracker's reported position unchanged.
+        t.update_even(10, 10, 3.0);
+        t.update_odd(99000, 99000, 2.0);
+        let after = t.position().expect("still has a position");
+        assert_eq!(first, after, "outlier spike should not move the reported fix");
+    }
 }
@@ -142,78 +142,319 @@ pub fn extract_icao(msg_hex: &str) -> Option<Icao> {
 // Syndrome tables for error correction
 // ---------------------------------------------------------------------------
 
-fn build_syndrome_table(n_bits: usize) -> HashMap<u32, Vec<usize>> {
-    let n_bytes = n_bits / 8;
-    let mut table = HashMap::new();
+/// Precomputed CRC syndrome tables for O(1) single/two-bit error correction.
+///
+/// CRC24 is linear, so the residual of flipping a set of bits is the XOR of
+/// each bit's individual residual — single-bit syndromes can be computed
+/// directly and two-bit syndromes derived by XORing pairs of them, rather
+/// than recomputing a CRC per candidate flip. Building the tables this way
+/// is still O(n²) in the number of bits (~6216 pairs for a 112-bit frame),
+/// but it only has to happen once: lookups on the hot path are then O(1).
+/// Syndromes are message-length-specific, so 112-bit and 56-bit frames each
+/// get their own pair of tables.
+pub struct ErrorCorrector {
+    single_112: HashMap<u32, usize>,
+    double_112: HashMap<u32, (usize, usize)>,
+    triple_112: Option<HashMap<u32, (usize, usize, usize)>>,
+    single_56: HashMap<u32, usize>,
+    double_56: HashMap<u32, (usize, usize)>,
+    triple_56: Option<HashMap<u32, (usize, usize, usize)>>,
+}
 
-    // Single-bit errors
-    for bit in 0..n_bits {
-        let mut msg = vec![0u8; n_bytes];
-        msg[bit / 8] |= 1 << (7 - (bit % 8));
-        let syndrome = crc24(&msg);
-        table.entry(syndrome).or_insert_with(|| vec![bit]);
-    }
+/// How many simultaneous bit errors an [`ErrorCorrector`] will attempt to
+/// fix.
+///
+/// The single/double tables hold ~6300 syndromes per message length (one
+/// CRC computation per bit, the pairs derived by XOR) and are always built. The triple table is a further O(n^3) pass
+/// (~227,920 combinations at 112 bits) and, critically, widens the set of
+/// syndromes that collide with a *wrong* correction — each extra bit of
+/// budget trades false-correction risk for reach. Triple-bit corrections
+/// should typically be cross-checked against a roster (e.g. a known ICAO)
+/// before being trusted, which is why the default budget leaves them off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorrectionBudget {
+    /// Single and double-bit errors only. Unchanged memory footprint from
+    /// before triple-bit support existed.
+    Double,
+    /// Also attempt triple-bit corrections within the same Hamming-distance
+    /// search.
+    Triple,
+}
+
+impl ErrorCorrector {
+    fn build_tables(n_bits: usize) -> (HashMap<u32, usize>, HashMap<u32, (usize, usize)>) {
+        let n_bytes = n_bits / 8;
 
-    // Double-bit errors
-    for bit1 in 0..n_bits {
-        for bit2 in (bit1 + 1)..n_bits {
+        let mut syndrome_of = vec![0u32; n_bits];
+        let mut single = HashMap::with_capacity(n_bits);
+        for (bit, syndrome_slot) in syndrome_of.iter_mut().enumerate() {
             let mut msg = vec![0u8; n_bytes];
-            msg[bit1 / 8] |= 1 << (7 - (bit1 % 8));
-            msg[bit2 / 8] |= 1 << (7 - (bit2 % 8));
+            msg[bit / 8] |= 1 << (7 - (bit % 8));
             let syndrome = crc24(&msg);
-            table.entry(syndrome).or_insert_with(|| vec![bit1, bit2]);
+            *syndrome_slot = syndrome;
+            single.entry(syndrome).or_insert(bit);
+        }
+
+        let mut double = HashMap::with_capacity(n_bits * (n_bits - 1) / 2);
+        for bit1 in 0..n_bits {
+            for bit2 in (bit1 + 1)..n_bits {
+                let syndrome = syndrome_of[bit1] ^ syndrome_of[bit2];
+                double.entry(syndrome).or_insert((bit1, bit2));
+            }
         }
+
+        (single, double)
     }
 
-    table
+    /// Build the triple-bit syndrome table for a message length, recomputing
+    /// the single-bit syndromes the same way [`Self::build_tables`] does and
+    /// XORing them in triples.
+    fn build_triple_table(n_bits: usize) -> HashMap<u32, (usize, usize, usize)> {
+        let n_bytes = n_bits / 8;
+        let mut syndrome_of = vec![0u32; n_bits];
+        for (bit, syndrome_slot) in syndrome_of.iter_mut().enumerate() {
+            let mut msg = vec![0u8; n_bytes];
+            msg[bit / 8] |= 1 << (7 - (bit % 8));
+            *syndrome_slot = crc24(&msg);
+        }
+
+        let mut triple = HashMap::new();
+        for bit1 in 0..n_bits {
+            for bit2 in (bit1 + 1)..n_bits {
+                let partial = syndrome_of[bit1] ^ syndrome_of[bit2];
+                for bit3 in (bit2 + 1)..n_bits {
+                    let syndrome = partial ^ syndrome_of[bit3];
+                    triple.entry(syndrome).or_insert((bit1, bit2, bit3));
+                }
+            }
+        }
+        triple
+    }
+
+    /// Build the 112-bit and 56-bit syndrome tables with the default
+    /// [`CorrectionBudget::Double`] budget. This fills ~6300 syndrome
+    /// entries per message length, so construct one `ErrorCorrector`
+    /// and share it rather than building a fresh one per call.
+    pub fn new() -> Self {
+        Self::with_budget(CorrectionBudget::Double)
+    }
+
+    /// Build the syndrome tables for the given [`CorrectionBudget`].
+    /// `Triple` adds a much larger pass (see [`CorrectionBudget`]) on top of
+    /// the always-built single/double tables.
+    pub fn with_budget(budget: CorrectionBudget) -> Self {
+        let (single_112, double_112) = Self::build_tables(112);
+        let (single_56, double_56) = Self::build_tables(56);
+        let (triple_112, triple_56) = match budget {
+            CorrectionBudget::Double => (None, None),
+            CorrectionBudget::Triple => (
+                Some(Self::build_triple_table(112)),
+                Some(Self::build_triple_table(56)),
+            ),
+        };
+        ErrorCorrector {
+            single_112,
+            double_112,
+            triple_112,
+            single_56,
+            double_56,
+            triple_56,
+        }
+    }
+
+    /// Attempt to correct 1-2 bit errors (or 1-3, with
+    /// [`CorrectionBudget::Triple`]) in a Mode S message.
+    ///
+    /// Looks up the CRC syndrome directly in the precomputed tables. If
+    /// found, flips the identified bit(s) and re-verifies the residual is
+    /// zero. Never corrects bits 0-4 (DF field) to avoid turning one message
+    /// type into another.
+    ///
+    /// Returns the corrected hex string if fixable, `None` otherwise.
+    pub fn try_fix(&self, msg_hex: &str) -> Option<String> {
+        self.try_fix_report(msg_hex).map(|report| report.corrected_hex)
+    }
+
+    /// Like [`Self::try_fix`], but reports which bits were flipped and the
+    /// CRC residual the message had before correction, so callers can weight
+    /// corrected frames lower in downstream fusion.
+    pub fn try_fix_report(&self, msg_hex: &str) -> Option<FixReport> {
+        let data = hex_decode(msg_hex)?;
+        let n_bits = data.len() * 8;
+        let syndrome = crc24(&data);
+
+        if syndrome == 0 {
+            return Some(FixReport {
+                corrected_hex: msg_hex.to_uppercase(),
+                flipped_bits: Vec::new(),
+                residual_before: 0,
+            });
+        }
+
+        let (single, double, triple) = if n_bits == 112 {
+            (&self.single_112, &self.double_112, &self.triple_112)
+        } else {
+            (&self.single_56, &self.double_56, &self.triple_56)
+        };
+
+        let bit_positions: Vec<usize> = if let Some(&bit) = single.get(&syndrome) {
+            vec![bit]
+        } else if let Some(&(bit1, bit2)) = double.get(&syndrome) {
+            vec![bit1, bit2]
+        } else if let Some(&(bit1, bit2, bit3)) =
+            triple.as_ref().and_then(|t| t.get(&syndrome))
+        {
+            vec![bit1, bit2, bit3]
+        } else {
+            return None;
+        };
+
+        // Safety: never correct the DF field (bits 0-4)
+        if bit_positions.iter().any(|&b| b < 5) {
+            return None;
+        }
+
+        // Flip the identified bits
+        let mut fixed = data;
+        for &bit in &bit_positions {
+            fixed[bit / 8] ^= 1 << (7 - (bit % 8));
+        }
+
+        // Verify the fix actually works
+        if crc24(&fixed) != 0 {
+            return None;
+        }
+
+        Some(FixReport {
+            corrected_hex: hex_encode(&fixed),
+            flipped_bits: bit_positions,
+            residual_before: syndrome,
+        })
+    }
 }
 
-static SYNDROME_TABLE_112: LazyLock<HashMap<u32, Vec<usize>>> =
-    LazyLock::new(|| build_syndrome_table(112));
-static SYNDROME_TABLE_56: LazyLock<HashMap<u32, Vec<usize>>> =
-    LazyLock::new(|| build_syndrome_table(56));
+impl Default for ErrorCorrector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-/// Attempt to correct 1-2 bit errors in a Mode S message.
+/// Attempt to fix a CRC failure by brute-forcing flips over a caller-supplied
+/// set of candidate bit positions, rather than the full any-bit syndrome
+/// search [`ErrorCorrector`] does.
 ///
-/// Looks up the CRC syndrome in pre-built tables. If found, flips the
-/// identified bits and re-validates. Never corrects bits 0-4 (DF field)
-/// to avoid turning one message type into another.
+/// Meant for bits `demod::recover_bits` already flagged "uncertain" (weak
+/// high/low transitions decided by continuity) — since there are usually
+/// only a handful of these per frame, trying every single bit and then every
+/// pair and recomputing the CRC each time stays cheap even without a
+/// precomputed syndrome table. Never corrects bits 0-4 (DF field), same as
+/// [`ErrorCorrector::try_fix_report`].
 ///
-/// Returns corrected hex string if fixable, `None` otherwise.
-pub fn try_fix(msg_hex: &str) -> Option<String> {
+/// An already-valid message yields a zero-bit report. Returns `None` if the
+/// message doesn't decode, or no combination of candidates within `budget`
+/// clears the CRC.
+pub fn try_fix_uncertain(
+    msg_hex: &str,
+    candidate_bits: &[usize],
+    budget: CorrectionBudget,
+) -> Option<FixReport> {
     let data = hex_decode(msg_hex)?;
-    let n_bits = data.len() * 8;
     let syndrome = crc24(&data);
 
     if syndrome == 0 {
-        return Some(msg_hex.to_uppercase());
+        return Some(FixReport {
+            corrected_hex: msg_hex.to_uppercase(),
+            flipped_bits: Vec::new(),
+            residual_before: 0,
+        });
     }
 
-    let table = if n_bits == 112 {
-        &*SYNDROME_TABLE_112
-    } else {
-        &*SYNDROME_TABLE_56
-    };
+    let candidates: Vec<usize> = candidate_bits.iter().copied().filter(|&b| b >= 5).collect();
 
-    let bit_positions = table.get(&syndrome)?;
+    let flip = |data: &[u8], bits: &[usize]| -> Vec<u8> {
+        let mut flipped = data.to_vec();
+        for &bit in bits {
+            flipped[bit / 8] ^= 1 << (7 - (bit % 8));
+        }
+        flipped
+    };
 
-    // Safety: never correct the DF field (bits 0-4)
-    if bit_positions.iter().any(|&b| b < 5) {
-        return None;
+    for &bit in &candidates {
+        let fixed = flip(&data, &[bit]);
+        if crc24(&fixed) == 0 {
+            return Some(FixReport {
+                corrected_hex: hex_encode(&fixed),
+                flipped_bits: vec![bit],
+                residual_before: syndrome,
+            });
+        }
     }
 
-    // Flip the identified bits
-    let mut fixed = data;
-    for &bit in bit_positions {
-        fixed[bit / 8] ^= 1 << (7 - (bit % 8));
+    for (i, &bit1) in candidates.iter().enumerate() {
+        for &bit2 in &candidates[i + 1..] {
+            let fixed = flip(&data, &[bit1, bit2]);
+            if crc24(&fixed) == 0 {
+                return Some(FixReport {
+                    corrected_hex: hex_encode(&fixed),
+                    flipped_bits: vec![bit1, bit2],
+                    residual_before: syndrome,
+                });
+            }
+        }
     }
 
-    // Verify the fix actually works
-    if crc24(&fixed) != 0 {
-        return None;
+    if budget == CorrectionBudget::Triple {
+        for (i, &bit1) in candidates.iter().enumerate() {
+            for (j, &bit2) in candidates.iter().enumerate().skip(i + 1) {
+                for &bit3 in &candidates[j + 1..] {
+                    let fixed = flip(&data, &[bit1, bit2, bit3]);
+                    if crc24(&fixed) == 0 {
+                        return Some(FixReport {
+                            corrected_hex: hex_encode(&fixed),
+                            flipped_bits: vec![bit1, bit2, bit3],
+                            residual_before: syndrome,
+                        });
+                    }
+                }
+            }
+        }
     }
 
-    Some(hex_encode(&fixed))
+    None
+}
+
+/// Result of [`ErrorCorrector::try_fix_report`]: the corrected message plus
+/// enough detail about the correction for a caller to decide how much to
+/// trust it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixReport {
+    /// The corrected message, uppercase hex. Equal to the input (modulo
+    /// case) when no bits needed flipping.
+    pub corrected_hex: String,
+    /// Bit positions (0 = first bit of the DF field) that were flipped to
+    /// reach a zero CRC residual. Empty when the message was already valid.
+    pub flipped_bits: Vec<usize>,
+    /// The CRC syndrome before correction. Zero means the message was
+    /// already valid.
+    pub residual_before: u32,
+}
+
+/// Shared, lazily-built corrector. Every caller borrows this instance
+/// instead of constructing their own, so the syndrome tables are only ever
+/// built once per process.
+pub static GLOBAL_CORRECTOR: LazyLock<ErrorCorrector> = LazyLock::new(ErrorCorrector::new);
+
+/// Attempt to correct 1-2 bit errors in a Mode S message using the shared
+/// [`GLOBAL_CORRECTOR`]. See [`ErrorCorrector::try_fix`].
+pub fn try_fix(msg_hex: &str) -> Option<String> {
+    GLOBAL_CORRECTOR.try_fix(msg_hex)
+}
+
+/// Attempt to correct 1-2 bit errors in a Mode S message using the shared
+/// [`GLOBAL_CORRECTOR`], reporting what was corrected. See
+/// [`ErrorCorrector::try_fix_report`].
+pub fn try_fix_report(msg_hex: &str) -> Option<FixReport> {
+    GLOBAL_CORRECTOR.try_fix_report(msg_hex)
 }
 
 // ---------------------------------------------------------------------------
@@ -328,10 +569,154 @@ mod tests {
     fn test_syndrome_table_sizes() {
         // 112-bit: 112 single + C(112,2) double = 112 + 6216 = 6328 entries
         // (minus collisions)
-        assert!(!SYNDROME_TABLE_112.is_empty());
-        assert!(!SYNDROME_TABLE_56.is_empty());
+        let corrector = ErrorCorrector::new();
+        assert!(!corrector.single_112.is_empty());
+        assert!(!corrector.single_56.is_empty());
+        assert!(!corrector.double_112.is_empty());
+        assert!(!corrector.double_56.is_empty());
         // Single-bit entries should exist for all bit positions
-        assert!(SYNDROME_TABLE_112.len() > 100);
-        assert!(SYNDROME_TABLE_56.len() > 50);
+        assert!(corrector.single_112.len() > 100);
+        assert!(corrector.single_56.len() > 50);
+    }
+
+    #[test]
+    fn test_error_corrector_matches_global() {
+        let corrector = ErrorCorrector::new();
+        let mut data = hex_decode(VALID_FRAMES[0]).unwrap();
+        data[5] ^= 0x01;
+        let corrupted = hex_encode(&data);
+
+        assert_eq!(corrector.try_fix(&corrupted), try_fix(&corrupted));
+    }
+
+    #[test]
+    fn test_try_fix_report_already_valid() {
+        let report = try_fix_report(VALID_FRAMES[0]).unwrap();
+        assert_eq!(report.corrected_hex, VALID_FRAMES[0]);
+        assert!(report.flipped_bits.is_empty());
+        assert_eq!(report.residual_before, 0);
+    }
+
+    #[test]
+    fn test_try_fix_report_single_bit_error() {
+        let mut data = hex_decode(VALID_FRAMES[0]).unwrap();
+        data[5] ^= 0x01;
+        let corrupted = hex_encode(&data);
+
+        let report = try_fix_report(&corrupted).unwrap();
+        assert_eq!(report.corrected_hex, VALID_FRAMES[0]);
+        assert_eq!(report.flipped_bits.len(), 1);
+        assert_ne!(report.residual_before, 0);
+    }
+
+    #[test]
+    fn test_try_fix_report_df_field_protection() {
+        let mut data = hex_decode(VALID_FRAMES[0]).unwrap();
+        data[0] ^= 0x80; // bit 0, DF field
+        let corrupted = hex_encode(&data);
+
+        assert!(try_fix_report(&corrupted).is_none());
+    }
+
+    #[test]
+    fn test_double_budget_has_no_triple_table() {
+        let corrector = ErrorCorrector::with_budget(CorrectionBudget::Double);
+        assert!(corrector.triple_112.is_none());
+        assert!(corrector.triple_56.is_none());
+    }
+
+    #[test]
+    fn test_triple_budget_fixes_three_bit_error() {
+        let corrector = ErrorCorrector::with_budget(CorrectionBudget::Triple);
+        assert!(corrector.triple_112.is_some());
+
+        let mut data = hex_decode(VALID_FRAMES[0]).unwrap();
+        // Three well-separated bits, all past the protected DF field.
+        data[5] ^= 0x01;
+        data[8] ^= 0x10;
+        data[10] ^= 0x04;
+        let corrupted = hex_encode(&data);
+
+        // The double-only corrector can't resolve a triple-bit error...
+        let double_only = ErrorCorrector::with_budget(CorrectionBudget::Double);
+        assert!(double_only.try_fix(&corrupted).is_none());
+
+        // ...but the triple-budget corrector can.
+        let report = corrector.try_fix_report(&corrupted).unwrap();
+        assert_eq!(report.corrected_hex, VALID_FRAMES[0]);
+        assert_eq!(report.flipped_bits.len(), 3);
+    }
+
+    #[test]
+    fn test_try_fix_uncertain_already_valid() {
+        let report = try_fix_uncertain(VALID_FRAMES[0], &[10, 20], CorrectionBudget::Double)
+            .unwrap();
+        assert_eq!(report.corrected_hex, VALID_FRAMES[0]);
+        assert!(report.flipped_bits.is_empty());
+    }
+
+    #[test]
+    fn test_try_fix_uncertain_single_bit_among_candidates() {
+        // Corrupt bit 47; list it among a few other uncertain positions.
+        let mut data = hex_decode(VALID_FRAMES[0]).unwrap();
+        data[5] ^= 0x01; // bit 47
+        let corrupted = hex_encode(&data);
+
+        let report =
+            try_fix_uncertain(&corrupted, &[12, 47, 77], CorrectionBudget::Double).unwrap();
+        assert_eq!(report.corrected_hex, VALID_FRAMES[0]);
+        assert_eq!(report.flipped_bits, vec![47]);
+    }
+
+    #[test]
+    fn test_try_fix_uncertain_pair_among_candidates() {
+        let mut data = hex_decode(VALID_FRAMES[0]).unwrap();
+        data[5] ^= 0x01; // bit 47
+        data[9] ^= 0x02; // bit 78
+        let corrupted = hex_encode(&data);
+
+        let report =
+            try_fix_uncertain(&corrupted, &[47, 78, 90], CorrectionBudget::Double).unwrap();
+        assert_eq!(report.corrected_hex, VALID_FRAMES[0]);
+        assert_eq!(report.flipped_bits, vec![47, 78]);
+    }
+
+    #[test]
+    fn test_try_fix_uncertain_df_field_protection() {
+        let mut data = hex_decode(VALID_FRAMES[0]).unwrap();
+        data[0] ^= 0x80; // bit 0, DF field
+        let corrupted = hex_encode(&data);
+
+        // Even if bit 0 is (wrongly) listed as a candidate, it's filtered out.
+        assert!(try_fix_uncertain(&corrupted, &[0, 1, 2], CorrectionBudget::Double).is_none());
+    }
+
+    #[test]
+    fn test_try_fix_uncertain_no_candidate_covers_the_error() {
+        let mut data = hex_decode(VALID_FRAMES[0]).unwrap();
+        data[5] ^= 0x01; // bit 47
+        let corrupted = hex_encode(&data);
+
+        // None of the candidates is the actual corrupted bit.
+        assert!(try_fix_uncertain(&corrupted, &[12, 13, 14], CorrectionBudget::Double).is_none());
+    }
+
+    #[test]
+    fn test_try_fix_uncertain_triple_budget() {
+        let mut data = hex_decode(VALID_FRAMES[0]).unwrap();
+        data[5] ^= 0x01; // bit 47
+        data[8] ^= 0x10; // bit 67
+        data[10] ^= 0x04; // bit 85
+        let corrupted = hex_encode(&data);
+
+        assert!(
+            try_fix_uncertain(&corrupted, &[47, 67, 85], CorrectionBudget::Double).is_none(),
+            "three-bit error shouldn't be fixable within a double budget"
+        );
+
+        let report =
+            try_fix_uncertain(&corrupted, &[47, 67, 85], CorrectionBudget::Triple).unwrap();
+        assert_eq!(report.corrected_hex, VALID_FRAMES[0]);
+        assert_eq!(report.flipped_bits, vec![47, 67, 85]);
     }
 }
@@ -7,11 +7,41 @@
 //! - DF17 TC 20-22: Airborne position (GNSS altitude)
 //! - DF4/20:       Surveillance/Comm-B altitude reply
 //! - DF5/21:       Surveillance/Comm-B identity reply (squawk)
+//! - DF17 TC 28:   Aircraft status (emergency/priority, or ACAS RA)
+//! - DF17 TC 29:   Target state and status (subtype 1)
+//! - DF20/21 MB:   Comm-B BDS register (BDS2,0/4,0/5,0/6,0), when recognized
 //! - DF11:         All-call reply (ICAO address acquisition)
 
+use crate::bds::BdsRegister;
 use crate::frame::ModeFrame;
 use crate::types::*;
 
+// ---------------------------------------------------------------------------
+// Bit-field extraction
+// ---------------------------------------------------------------------------
+
+/// Extract a single bit, numbered from 1 at the MSB of `data[0]` (ICAO
+/// Annex 10 convention), so field ranges can be read straight off the specs.
+#[inline]
+fn getbit(data: &[u8], bit: usize) -> u32 {
+    let byte = data[(bit - 1) / 8];
+    let shift = 7 - ((bit - 1) % 8);
+    ((byte >> shift) & 1) as u32
+}
+
+/// Extract bits `first..=last` (1-based, MSB-first) as an unsigned value.
+/// Spans byte boundaries freely; `first` and `last` may cover any byte range
+/// of `data`.
+#[inline]
+fn getbits(data: &[u8], first: usize, last: usize) -> u64 {
+    assert!(first <= last);
+    let mut value: u64 = 0;
+    for bit in first..=last {
+        value = (value << 1) | getbit(data, bit) as u64;
+    }
+    value
+}
+
 // ---------------------------------------------------------------------------
 // Altitude decoding
 // ---------------------------------------------------------------------------
@@ -103,7 +133,9 @@ pub fn decode_altitude_13bit(alt_code_13: u32) -> Option<i32> {
     let q_bit = (alt_code_13 >> 4) & 1;
 
     if m_bit == 1 {
-        return None; // Metric altitude — very rare
+        let metres = decode_altitude_13bit_metres(alt_code_13)?;
+        let feet = (metres as f64 * 3.28084).round() as i32;
+        return (-1200..=126750).contains(&feet).then_some(feet);
     }
 
     if q_bit == 1 {
@@ -116,6 +148,25 @@ pub fn decode_altitude_13bit(alt_code_13: u32) -> Option<i32> {
     }
 }
 
+/// Metres-per-LSB resolution for the M=1 metric altitude branch. The
+/// standard doesn't otherwise make this field's scale selectable, so a 1 m
+/// step is the simplest defensible reading.
+const METRIC_ALTITUDE_STEP_M: i32 = 1;
+
+/// Decode the native metric value (metres) of the M=1 branch of a 13-bit
+/// altitude code, for callers that want the value `decode_altitude_13bit`
+/// otherwise converts to feet. Strips the M and Q bits the same way the
+/// 25-ft branch does and reassembles the remaining 11 bits as a plain
+/// binary value.
+pub fn decode_altitude_13bit_metres(alt_code_13: u32) -> Option<i32> {
+    if (alt_code_13 >> 6) & 1 != 1 {
+        return None;
+    }
+
+    let n = ((alt_code_13 & 0x1F80) >> 2) | ((alt_code_13 & 0x0020) >> 1) | (alt_code_13 & 0x000F);
+    Some(n as i32 * METRIC_ALTITUDE_STEP_M)
+}
+
 // ---------------------------------------------------------------------------
 // Squawk decoding
 // ---------------------------------------------------------------------------
@@ -162,18 +213,12 @@ pub fn decode_identification(frame: &ModeFrame) -> Option<IdentificationMsg> {
         return None;
     }
 
-    let category = me[0] & 0x07;
-
-    // Decode 8 callsign characters (6 bits each, packed into 48 bits)
-    let bits = u64::from_be_bytes({
-        let mut buf = [0u8; 8];
-        buf[1..8].copy_from_slice(me);
-        buf
-    });
+    let category = getbits(me, 6, 8) as u8;
 
+    // Decode 8 callsign characters (6 bits each, ME bits 9-56)
     let mut callsign = String::with_capacity(8);
     for i in 0..8 {
-        let idx = ((bits >> (42 - i * 6)) & 0x3F) as usize;
+        let idx = getbits(me, 9 + i * 6, 14 + i * 6) as usize;
         if idx < CALLSIGN_CHARSET.len() {
             callsign.push(CALLSIGN_CHARSET[idx] as char);
         } else {
@@ -185,19 +230,19 @@ pub fn decode_identification(frame: &ModeFrame) -> Option<IdentificationMsg> {
         icao: frame.icao,
         callsign,
         category,
+        tc,
         timestamp: frame.timestamp,
     })
 }
 
-/// Decode TC 5-8 (surface) or TC 9-18/20-22 (airborne position).
+/// Decode TC 9-18 (barometric) or TC 20-22 (GNSS) airborne position.
 pub fn decode_position(frame: &ModeFrame) -> Option<PositionMsg> {
     let tc = frame.type_code()?;
 
-    let is_surface = (5..=8).contains(&tc);
     let is_airborne_baro = (9..=18).contains(&tc);
     let is_airborne_gnss = (20..=22).contains(&tc);
 
-    if !is_surface && !is_airborne_baro && !is_airborne_gnss {
+    if !is_airborne_baro && !is_airborne_gnss {
         return None;
     }
 
@@ -206,34 +251,87 @@ pub fn decode_position(frame: &ModeFrame) -> Option<PositionMsg> {
         return None;
     }
 
-    let bits = u64::from_be_bytes({
-        let mut buf = [0u8; 8];
-        buf[1..8].copy_from_slice(me);
-        buf
-    });
+    let ss = getbits(me, 6, 7) as u8;
 
-    let ss = ((bits >> 49) & 0x03) as u8;
-
-    let altitude_ft = if is_airborne_baro || is_airborne_gnss {
-        let alt_code = ((bits >> 36) & 0x0FFF) as u32;
-        decode_altitude(alt_code)
+    let alt_code = getbits(me, 9, 20) as u32;
+    let altitude_ft = decode_altitude(alt_code);
+    let altitude_source = if is_airborne_gnss {
+        AltitudeSource::Gnss
     } else {
-        None
+        AltitudeSource::Barometric
     };
 
-    let cpr_odd = ((bits >> 34) & 1) == 1;
-    let cpr_lat = ((bits >> 17) & 0x1FFFF) as u32;
-    let cpr_lon = (bits & 0x1FFFF) as u32;
+    let cpr_odd = getbit(me, 22) == 1;
+    let cpr_lat = getbits(me, 23, 39) as u32;
+    let cpr_lon = getbits(me, 40, 56) as u32;
 
     Some(PositionMsg {
         icao: frame.icao,
         altitude_ft,
+        altitude_source,
         cpr_lat,
         cpr_lon,
         cpr_odd,
         surveillance_status: ss,
         timestamp: frame.timestamp,
-        is_surface,
+    })
+}
+
+/// Movement (ground speed) code table for surface position messages (ME
+/// bits 6-12), per ICAO Annex 10 Vol IV. Resolution is finer at low speed:
+/// 0 means "no information", 1 means stopped, and the ranges widen from
+/// 0.125kt steps near a standstill to 5kt steps above 100kt.
+fn decode_movement(code: u8) -> Option<f64> {
+    match code {
+        0 => None,
+        1 => Some(0.0),
+        2..=8 => Some(0.125 + (code - 2) as f64 * 0.125),
+        9..=12 => Some(1.0 + (code - 9) as f64 * 0.25),
+        13..=38 => Some(2.0 + (code - 13) as f64 * 0.5),
+        39..=93 => Some(15.0 + (code - 39) as f64),
+        94..=108 => Some(70.0 + (code - 94) as f64 * 2.0),
+        109..=123 => Some(100.0 + (code - 109) as f64 * 5.0),
+        124 => Some(175.0),
+        _ => None, // 125-127 reserved
+    }
+}
+
+/// Decode TC 5-8: surface position (CPR-encoded ground position, movement
+/// and ground track). Surface CPR uses quarter-size zones (see
+/// `cpr::local_decode_surface`/`global_decode_surface`) and has no
+/// barometric/GNSS altitude field — the aircraft is on the ground.
+pub fn decode_surface_position(frame: &ModeFrame) -> Option<SurfacePositionMsg> {
+    let tc = frame.type_code()?;
+    if !(5..=8).contains(&tc) {
+        return None;
+    }
+
+    let me = frame.me();
+    if me.len() < 7 {
+        return None;
+    }
+
+    let movement_kts = decode_movement(getbits(me, 6, 12) as u8);
+
+    let track_valid = getbit(me, 13) == 1;
+    let ground_track_deg = if track_valid {
+        Some(getbits(me, 14, 20) as f64 * 360.0 / 128.0)
+    } else {
+        None
+    };
+
+    let cpr_odd = getbit(me, 22) == 1;
+    let cpr_lat = getbits(me, 23, 39) as u32;
+    let cpr_lon = getbits(me, 40, 56) as u32;
+
+    Some(SurfacePositionMsg {
+        icao: frame.icao,
+        movement_kts,
+        ground_track_deg,
+        cpr_lat,
+        cpr_lon,
+        cpr_odd,
+        timestamp: frame.timestamp,
     })
 }
 
@@ -248,29 +346,36 @@ pub fn decode_velocity(frame: &ModeFrame) -> Option<VelocityMsg> {
         return None;
     }
 
-    let bits = u64::from_be_bytes({
-        let mut buf = [0u8; 8];
-        buf[1..8].copy_from_slice(me);
-        buf
-    });
-
-    let subtype = ((bits >> 48) & 0x07) as u8;
+    let subtype = getbits(me, 6, 8) as u8;
 
     match subtype {
-        1 | 2 => Some(decode_ground_velocity(frame.icao, bits, frame.timestamp)),
-        3 | 4 => Some(decode_airspeed(frame.icao, bits, subtype, frame.timestamp)),
+        1 | 2 => Some(decode_ground_velocity(frame.icao, me, frame.timestamp)),
+        3 | 4 => Some(decode_airspeed(frame.icao, me, frame.timestamp)),
         _ => None,
     }
 }
 
-fn decode_ground_velocity(icao: Icao, bits: u64, timestamp: f64) -> VelocityMsg {
-    let ew_dir = (bits >> 42) & 1; // 0=East, 1=West
-    let ew_vel = ((bits >> 32) & 0x3FF) as i32 - 1;
-    let ns_dir = (bits >> 31) & 1; // 0=North, 1=South
-    let ns_vel = ((bits >> 21) & 0x3FF) as i32 - 1;
+/// GNSS-minus-barometric altitude difference: sign bit + 7-bit magnitude in
+/// 25-ft increments, common to both ground-velocity and airspeed subtypes.
+/// A zero magnitude means "no data".
+fn decode_gnss_baro_diff(me: &[u8]) -> Option<i32> {
+    let mag = getbits(me, 50, 56) as i32;
+    if mag == 0 {
+        return None;
+    }
+    let diff = (mag - 1) * 25;
+    Some(if getbit(me, 49) == 1 { -diff } else { diff })
+}
+
+fn decode_ground_velocity(icao: Icao, me: &[u8], timestamp: f64) -> VelocityMsg {
+    let ew_dir = getbit(me, 14); // 0=East, 1=West
+    let ew_vel = getbits(me, 15, 24) as i32 - 1;
+    let ns_dir = getbit(me, 25); // 0=North, 1=South
+    let ns_vel = getbits(me, 26, 35) as i32 - 1;
 
-    let vr_sign = (bits >> 19) & 1; // 0=up, 1=down
-    let vr_val = ((bits >> 10) & 0x1FF) as i32 - 1;
+    let vr_source = getbit(me, 36); // 0=GNSS, 1=barometric
+    let vr_sign = getbit(me, 37); // 0=up, 1=down
+    let vr_val = getbits(me, 38, 46) as i32 - 1;
 
     let (speed, heading) = if ew_vel >= 0 && ns_vel >= 0 {
         let vx = if ew_dir == 1 { -ew_vel } else { ew_vel } as f64;
@@ -294,20 +399,27 @@ fn decode_ground_velocity(icao: Icao, bits: u64, timestamp: f64) -> VelocityMsg
         speed_kts: speed,
         heading_deg: heading,
         vertical_rate_fpm: vrate,
+        vertical_rate_source: if vr_source == 1 {
+            VerticalRateSource::Barometric
+        } else {
+            VerticalRateSource::Gnss
+        },
         speed_type: SpeedType::Ground,
+        gnss_baro_diff_ft: decode_gnss_baro_diff(me),
         timestamp,
     }
 }
 
-fn decode_airspeed(icao: Icao, bits: u64, _subtype: u8, timestamp: f64) -> VelocityMsg {
-    let hdg_available = (bits >> 42) & 1;
-    let hdg_raw = ((bits >> 32) & 0x3FF) as u32;
+fn decode_airspeed(icao: Icao, me: &[u8], timestamp: f64) -> VelocityMsg {
+    let hdg_available = getbit(me, 14);
+    let hdg_raw = getbits(me, 15, 24) as u32;
 
-    let speed_type_bit = (bits >> 31) & 1; // 0=IAS, 1=TAS
-    let speed_raw = ((bits >> 21) & 0x3FF) as i32;
+    let speed_type_bit = getbit(me, 25); // 0=IAS, 1=TAS
+    let speed_raw = getbits(me, 26, 35) as i32;
 
-    let vr_sign = (bits >> 10) & 1;
-    let vr_val = ((bits >> 1) & 0x1FF) as i32 - 1;
+    let vr_source = getbit(me, 36); // 0=GNSS, 1=barometric
+    let vr_sign = getbit(me, 37); // 0=up, 1=down
+    let vr_val = getbits(me, 38, 46) as i32 - 1;
 
     let heading = if hdg_available == 1 {
         Some(round2(hdg_raw as f64 * 360.0 / 1024.0))
@@ -333,11 +445,162 @@ fn decode_airspeed(icao: Icao, bits: u64, _subtype: u8, timestamp: f64) -> Veloc
         speed_kts: speed,
         heading_deg: heading,
         vertical_rate_fpm: vrate,
+        vertical_rate_source: if vr_source == 1 {
+            VerticalRateSource::Barometric
+        } else {
+            VerticalRateSource::Gnss
+        },
         speed_type: if speed_type_bit == 1 {
             SpeedType::TAS
         } else {
             SpeedType::IAS
         },
+        gnss_baro_diff_ft: decode_gnss_baro_diff(me),
+        timestamp,
+    }
+}
+
+/// Decode TC 29 (subtype 1): target state and status.
+///
+/// Subtype 0 is reserved and not decoded. Field widths follow the selected
+/// altitude, barometric setting, and selected heading subfields of the
+/// version-2 Target State and Status report, followed by five single-bit
+/// autopilot mode flags.
+pub fn decode_target_state(frame: &ModeFrame) -> Option<TargetStateMsg> {
+    if frame.type_code()? != 29 {
+        return None;
+    }
+
+    let me = frame.me();
+    if me.len() < 7 {
+        return None;
+    }
+
+    let bits = u64::from_be_bytes({
+        let mut buf = [0u8; 8];
+        buf[1..8].copy_from_slice(me);
+        buf
+    });
+
+    if ((bits >> 48) & 0x07) as u8 != 1 {
+        return None;
+    }
+
+    let alt_status = (bits >> 47) & 1;
+    let alt_source = (bits >> 46) & 1;
+    let alt_raw = (bits >> 35) & 0x7FF;
+    let baro_status = (bits >> 34) & 1;
+    let baro_raw = (bits >> 25) & 0x1FF;
+    let hdg_status = (bits >> 24) & 1;
+    let hdg_raw = (bits >> 15) & 0x1FF;
+
+    Some(TargetStateMsg {
+        icao: frame.icao,
+        selected_altitude_ft: (alt_status == 1).then_some(alt_raw as i32 * 32),
+        altitude_source_is_fms: alt_source == 1,
+        barometric_setting_mb: (baro_status == 1).then(|| round2(800.0 + baro_raw as f64 * 0.8)),
+        selected_heading_deg: (hdg_status == 1)
+            .then(|| round2(signed_bits(hdg_raw, 9) as f64 * 180.0 / 256.0)),
+        autopilot_engaged: (bits >> 14) & 1 == 1,
+        vnav_mode: (bits >> 13) & 1 == 1,
+        altitude_hold_mode: (bits >> 12) & 1 == 1,
+        approach_mode: (bits >> 11) & 1 == 1,
+        lnav_mode: (bits >> 10) & 1 == 1,
+        timestamp: frame.timestamp,
+    })
+}
+
+/// Decode TC 28: aircraft status, dispatching on subtype.
+pub fn decode_aircraft_status(frame: &ModeFrame) -> Option<AircraftStatusMsg> {
+    if frame.type_code()? != 28 {
+        return None;
+    }
+
+    let me = frame.me();
+    if me.len() < 7 {
+        return None;
+    }
+
+    let bits = u64::from_be_bytes({
+        let mut buf = [0u8; 8];
+        buf[1..8].copy_from_slice(me);
+        buf
+    });
+
+    match (bits >> 48) & 0x07 {
+        1 => Some(AircraftStatusMsg::Emergency(decode_emergency_status(
+            frame.icao,
+            bits,
+            frame.timestamp,
+        ))),
+        2 => Some(AircraftStatusMsg::AcasRa(decode_acas_ra(
+            frame.icao,
+            bits,
+            frame.timestamp,
+        ))),
+        _ => None,
+    }
+}
+
+fn decode_emergency_status(icao: Icao, bits: u64, timestamp: f64) -> EmergencyStatusMsg {
+    let emergency_state = match (bits >> 45) & 0x07 {
+        0 => EmergencyState::None,
+        1 => EmergencyState::General,
+        2 => EmergencyState::Medical,
+        3 => EmergencyState::MinimumFuel,
+        4 => EmergencyState::NoCommunications,
+        5 => EmergencyState::UnlawfulInterference,
+        6 => EmergencyState::Downed,
+        _ => EmergencyState::Reserved,
+    };
+    let squawk_code = ((bits >> 32) & 0x1FFF) as u32;
+
+    EmergencyStatusMsg {
+        icao,
+        emergency_state,
+        squawk: decode_squawk(squawk_code),
+        timestamp,
+    }
+}
+
+/// Threat identity data uses the same 13-bit altitude code as DF0/4/16/20
+/// (see [`decode_altitude_13bit`]); range and bearing use a value-1 encoding
+/// where 0 means "no data".
+fn decode_acas_ra(icao: Icao, bits: u64, timestamp: f64) -> AcasRaMsg {
+    let active_ra = ((bits >> 34) & 0x3FFF) as u16;
+    let ra_terminated = (bits >> 33) & 1 == 1;
+    let multiple_threats = (bits >> 32) & 1 == 1;
+    let threat_type = (bits >> 30) & 0x03;
+
+    let mut threat_icao = None;
+    let mut threat_altitude_ft = None;
+    let mut threat_range_nmi = None;
+    let mut threat_bearing_deg = None;
+
+    match threat_type {
+        1 => {
+            threat_icao = Some(icao_from_u32(((bits >> 6) & 0xFF_FFFF) as u32));
+        }
+        2 => {
+            let alt_raw = ((bits >> 17) & 0x1FFF) as u32;
+            let range_raw = (bits >> 10) & 0x7F;
+            let bearing_raw = (bits >> 4) & 0x3F;
+            threat_altitude_ft = decode_altitude_13bit(alt_raw);
+            threat_range_nmi = (range_raw > 0).then(|| round2((range_raw - 1) as f64 * 0.1));
+            threat_bearing_deg = (bearing_raw > 0).then_some((bearing_raw - 1) as u32 * 6);
+        }
+        _ => {}
+    }
+
+    AcasRaMsg {
+        icao,
+        active_ra,
+        ra_terminated,
+        multiple_threats,
+        threat_icao,
+        threat_altitude_ft,
+        threat_range_nmi,
+        threat_bearing_deg,
         timestamp,
     }
 }
@@ -352,7 +615,7 @@ pub fn decode_df_altitude(frame: &ModeFrame) -> Option<AltitudeMsg> {
         return None;
     }
 
-    let alt_code = ((frame.raw[2] as u32 & 0x1F) << 8) | frame.raw[3] as u32;
+    let alt_code = getbits(&frame.raw, 20, 32) as u32;
     let altitude_ft = decode_altitude_13bit(alt_code);
 
     Some(AltitudeMsg {
@@ -372,7 +635,7 @@ pub fn decode_df_squawk(frame: &ModeFrame) -> Option<SquawkMsg> {
         return None;
     }
 
-    let id_code = ((frame.raw[2] as u32 & 0x1F) << 8) | frame.raw[3] as u32;
+    let id_code = getbits(&frame.raw, 20, 32) as u32;
     let squawk = decode_squawk(id_code);
 
     Some(SquawkMsg {
@@ -382,6 +645,327 @@ pub fn decode_df_squawk(frame: &ModeFrame) -> Option<SquawkMsg> {
     })
 }
 
+// ---------------------------------------------------------------------------
+// Comm-B (BDS) decoding
+// ---------------------------------------------------------------------------
+
+/// Decode DF20/21's MB field into a typed BDS register message.
+///
+/// BDS2,0 self-identifies with a fixed marker byte, so it's checked first.
+/// The other registers don't, so this decodes the MB field as each of
+/// BDS4,0/5,0/6,0 and scores how physically plausible the result is (see
+/// [`best_bds_register`]), only trusting the outcome when one register's
+/// score clearly beats the rest.
+pub fn decode_comm_b(frame: &ModeFrame) -> Option<CommBMsg> {
+    if !matches!(frame.df, 20 | 21) {
+        return None;
+    }
+
+    let mb = frame.mb();
+    if mb.len() != 7 {
+        return None;
+    }
+
+    if mb[0] == 0x20 {
+        return Some(CommBMsg::Bds20(decode_bds20(
+            frame.icao,
+            mb,
+            frame.timestamp,
+        )));
+    }
+
+    let bits = u64::from_be_bytes({
+        let mut buf = [0u8; 8];
+        buf[1..8].copy_from_slice(mb);
+        buf
+    });
+
+    match best_bds_register(bits)? {
+        BdsRegister::Bds40 => Some(CommBMsg::Bds40(decode_bds40(
+            frame.icao,
+            mb,
+            frame.timestamp,
+        ))),
+        BdsRegister::Bds50 => Some(CommBMsg::Bds50(decode_bds50(
+            frame.icao,
+            mb,
+            frame.timestamp,
+        ))),
+        BdsRegister::Bds60 => Some(CommBMsg::Bds60(decode_bds60(
+            frame.icao,
+            mb,
+            frame.timestamp,
+        ))),
+        BdsRegister::Bds10 => None,
+    }
+}
+
+/// Minimum lead the top-scoring candidate in [`best_bds_register`] must hold
+/// over the runner-up to be trusted, rather than risking a misidentified
+/// register's garbage values.
+const BDS_SCORE_MARGIN: i32 = 10;
+
+/// Score one status-bit-gated subfield of a candidate BDS register.
+///
+/// A set status bit means the value is in use, so it's scored on whether
+/// `value` falls in the register's physically reasonable `range`. A clear
+/// status bit should pair with an all-zero payload; either a set status bit
+/// with an out-of-range value, or a clear one with a nonzero payload, is
+/// evidence against this being the right register.
+fn score_field(status: u64, raw: u64, value: f64, range: (f64, f64)) -> i32 {
+    if status == 1 {
+        if value >= range.0 && value <= range.1 {
+            13
+        } else {
+            -2
+        }
+    } else if raw == 0 {
+        1
+    } else {
+        -2
+    }
+}
+
+fn score_bds40(bits: u64) -> i32 {
+    let mcp_status = (bits >> 55) & 1;
+    let mcp_raw = (bits >> 43) & 0xFFF;
+    let fms_status = (bits >> 42) & 1;
+    let fms_raw = (bits >> 30) & 0xFFF;
+    let baro_status = (bits >> 29) & 1;
+    let qnh_raw = (bits >> 17) & 0xFFF;
+
+    score_field(
+        mcp_status,
+        mcp_raw,
+        mcp_raw as f64 * 16.0,
+        (1000.0, 50000.0),
+    ) + score_field(
+        fms_status,
+        fms_raw,
+        fms_raw as f64 * 16.0,
+        (1000.0, 50000.0),
+    ) + score_field(
+        baro_status,
+        qnh_raw,
+        800.0 + qnh_raw as f64 * 0.1,
+        (900.0, 1100.0),
+    )
+}
+
+fn score_bds50(bits: u64) -> i32 {
+    let roll_status = (bits >> 55) & 1;
+    let roll_raw = (bits >> 45) & 0x3FF;
+    let track_status = (bits >> 44) & 1;
+    let track_raw = (bits >> 34) & 0x3FF;
+    let gs_status = (bits >> 33) & 1;
+    let gs_raw = (bits >> 23) & 0x3FF;
+    let rate_status = (bits >> 22) & 1;
+    let rate_raw = (bits >> 12) & 0x3FF;
+    let tas_status = (bits >> 11) & 1;
+    let tas_raw = (bits >> 1) & 0x3FF;
+
+    score_field(
+        roll_status,
+        roll_raw,
+        signed_bits(roll_raw, 10) as f64 * 45.0 / 256.0,
+        (-90.0, 90.0),
+    ) + score_field(
+        track_status,
+        track_raw,
+        signed_bits(track_raw, 10) as f64 * 90.0 / 512.0,
+        (-180.0, 360.0),
+    ) + score_field(gs_status, gs_raw, gs_raw as f64 * 2.0, (0.0, 1000.0))
+        + score_field(
+            rate_status,
+            rate_raw,
+            signed_bits(rate_raw, 10) as f64 * 8.0 / 256.0,
+            (-20.0, 20.0),
+        )
+        + score_field(tas_status, tas_raw, tas_raw as f64 * 2.0, (0.0, 1000.0))
+}
+
+fn score_bds60(bits: u64) -> i32 {
+    let hdg_status = (bits >> 55) & 1;
+    let hdg_raw = (bits >> 44) & 0x7FF;
+    let ias_status = (bits >> 43) & 1;
+    let ias_raw = (bits >> 33) & 0x3FF;
+    let mach_status = (bits >> 32) & 1;
+    let mach_raw = (bits >> 22) & 0x3FF;
+    let baro_status = (bits >> 21) & 1;
+    let baro_raw = (bits >> 11) & 0x3FF;
+    let ivv_status = (bits >> 10) & 1;
+    let ivv_raw = bits & 0x3FF;
+
+    score_field(
+        hdg_status,
+        hdg_raw,
+        signed_bits(hdg_raw, 11) as f64 * 90.0 / 512.0,
+        (-180.0, 360.0),
+    ) + score_field(ias_status, ias_raw, ias_raw as f64, (0.0, 500.0))
+        + score_field(mach_status, mach_raw, mach_raw as f64 * 0.004, (0.0, 1.0))
+        + score_field(
+            baro_status,
+            baro_raw,
+            signed_bits(baro_raw, 10) as f64 * 32.0,
+            (-6000.0, 6000.0),
+        )
+        + score_field(
+            ivv_status,
+            ivv_raw,
+            signed_bits(ivv_raw, 10) as f64 * 32.0,
+            (-6000.0, 6000.0),
+        )
+}
+
+/// Pick whichever of BDS4,0/5,0/6,0 best explains the MB field's 56 bits.
+///
+/// An MB field doesn't say which register it holds, and all three layouts
+/// can parse the same bits without error, so this decodes each candidate and
+/// scores the physical plausibility of its fields (see [`score_field`]).
+/// Returns `None` unless the top score clears the runner-up by
+/// [`BDS_SCORE_MARGIN`], since a near-tie means the bits don't clearly favor
+/// one register over another.
+fn best_bds_register(bits: u64) -> Option<BdsRegister> {
+    let mut scores = [
+        (BdsRegister::Bds40, score_bds40(bits)),
+        (BdsRegister::Bds50, score_bds50(bits)),
+        (BdsRegister::Bds60, score_bds60(bits)),
+    ];
+    scores.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    let (best, best_score) = scores[0];
+    let (_, runner_up) = scores[1];
+    (best_score - runner_up >= BDS_SCORE_MARGIN).then_some(best)
+}
+
+/// BDS2,0: aircraft identification. Same 6-bit callsign charset as
+/// `decode_identification`, but the leading byte is the fixed 0x20 marker
+/// rather than a type code + category.
+fn decode_bds20(icao: Icao, mb: &[u8], timestamp: f64) -> CommBIdentificationMsg {
+    let bits = u64::from_be_bytes({
+        let mut buf = [0u8; 8];
+        buf[1..8].copy_from_slice(mb);
+        buf
+    });
+
+    let mut callsign = String::with_capacity(8);
+    for i in 0..8 {
+        let idx = ((bits >> (42 - i * 6)) & 0x3F) as usize;
+        if idx < CALLSIGN_CHARSET.len() {
+            callsign.push(CALLSIGN_CHARSET[idx] as char);
+        } else {
+            callsign.push(' ');
+        }
+    }
+
+    CommBIdentificationMsg {
+        icao,
+        callsign,
+        timestamp,
+    }
+}
+
+/// BDS4,0: selected vertical intention (MCP/FCU altitude, FMS altitude,
+/// barometric pressure setting).
+fn decode_bds40(icao: Icao, mb: &[u8], timestamp: f64) -> SelectedVerticalIntentMsg {
+    let bits = u64::from_be_bytes({
+        let mut buf = [0u8; 8];
+        buf[1..8].copy_from_slice(mb);
+        buf
+    });
+
+    let mcp_status = (bits >> 55) & 1;
+    let mcp_raw = (bits >> 43) & 0xFFF;
+    let fms_status = (bits >> 42) & 1;
+    let fms_raw = (bits >> 30) & 0xFFF;
+    let baro_status = (bits >> 29) & 1;
+    let qnh_raw = (bits >> 17) & 0xFFF;
+
+    SelectedVerticalIntentMsg {
+        icao,
+        mcp_altitude_ft: (mcp_status == 1).then(|| mcp_raw as i32 * 16),
+        fms_altitude_ft: (fms_status == 1).then(|| fms_raw as i32 * 16),
+        barometric_setting_mb: (baro_status == 1).then(|| round2(800.0 + qnh_raw as f64 * 0.1)),
+        timestamp,
+    }
+}
+
+/// BDS5,0: track and turn report (roll angle, track angle, ground speed,
+/// track angle rate, true airspeed).
+fn decode_bds50(icao: Icao, mb: &[u8], timestamp: f64) -> TrackAndTurnMsg {
+    let bits = u64::from_be_bytes({
+        let mut buf = [0u8; 8];
+        buf[1..8].copy_from_slice(mb);
+        buf
+    });
+
+    let roll_status = (bits >> 55) & 1;
+    let roll_raw = (bits >> 45) & 0x3FF;
+    let track_status = (bits >> 44) & 1;
+    let track_raw = (bits >> 34) & 0x3FF;
+    let gs_status = (bits >> 33) & 1;
+    let gs_raw = (bits >> 23) & 0x3FF;
+    let rate_status = (bits >> 22) & 1;
+    let rate_raw = (bits >> 12) & 0x3FF;
+    let tas_status = (bits >> 11) & 1;
+    let tas_raw = (bits >> 1) & 0x3FF;
+
+    TrackAndTurnMsg {
+        icao,
+        roll_angle_deg: (roll_status == 1)
+            .then(|| round2(signed_bits(roll_raw, 10) as f64 * 45.0 / 256.0)),
+        track_angle_deg: (track_status == 1)
+            .then(|| round2(signed_bits(track_raw, 10) as f64 * 90.0 / 512.0)),
+        ground_speed_kts: (gs_status == 1).then_some(gs_raw as f64 * 2.0),
+        track_angle_rate_deg_s: (rate_status == 1)
+            .then(|| round2(signed_bits(rate_raw, 10) as f64 * 8.0 / 256.0)),
+        true_airspeed_kts: (tas_status == 1).then_some(tas_raw as f64 * 2.0),
+        timestamp,
+    }
+}
+
+/// BDS6,0: heading and speed report (magnetic heading, IAS, Mach,
+/// barometric altitude rate, inertial vertical velocity).
+fn decode_bds60(icao: Icao, mb: &[u8], timestamp: f64) -> HeadingAndSpeedMsg {
+    let bits = u64::from_be_bytes({
+        let mut buf = [0u8; 8];
+        buf[1..8].copy_from_slice(mb);
+        buf
+    });
+
+    let hdg_status = (bits >> 55) & 1;
+    let hdg_raw = (bits >> 44) & 0x7FF;
+    let ias_status = (bits >> 43) & 1;
+    let ias_raw = (bits >> 33) & 0x3FF;
+    let mach_status = (bits >> 32) & 1;
+    let mach_raw = (bits >> 22) & 0x3FF;
+    let baro_status = (bits >> 21) & 1;
+    let baro_raw = (bits >> 11) & 0x3FF;
+    let ivv_status = (bits >> 10) & 1;
+    let ivv_raw = bits & 0x3FF;
+
+    HeadingAndSpeedMsg {
+        icao,
+        magnetic_heading_deg: (hdg_status == 1)
+            .then(|| round2(signed_bits(hdg_raw, 11) as f64 * 90.0 / 512.0)),
+        indicated_airspeed_kts: (ias_status == 1).then_some(ias_raw as u32),
+        mach: (mach_status == 1).then(|| round2(mach_raw as f64 * 0.004)),
+        baro_altitude_rate_fpm: (baro_status == 1).then(|| signed_bits(baro_raw, 10) * 32),
+        inertial_vertical_velocity_fpm: (ivv_status == 1).then(|| signed_bits(ivv_raw, 10) * 32),
+        timestamp,
+    }
+}
+
+/// Interpret the low `n` bits of `raw` as a two's-complement signed integer.
+fn signed_bits(raw: u64, n: u32) -> i32 {
+    let v = raw as i32;
+    let sign_bit = 1 << (n - 1);
+    if v & sign_bit != 0 {
+        v - (1 << n)
+    } else {
+        v
+    }
+}
+
 /// Decode any ModeFrame into the appropriate typed message.
 ///
 /// Routes to the correct decoder based on DF and TC.
@@ -395,19 +979,28 @@ pub fn decode(frame: &ModeFrame) -> Option<DecodedMsg> {
             let tc = frame.type_code()?;
             match tc {
                 1..=4 => decode_identification(frame).map(DecodedMsg::Identification),
-                5..=18 | 20..=22 => decode_position(frame).map(DecodedMsg::Position),
+                5..=8 => decode_surface_position(frame).map(DecodedMsg::SurfacePosition),
+                9..=18 | 20..=22 => decode_position(frame).map(DecodedMsg::Position),
                 19 => decode_velocity(frame).map(DecodedMsg::Velocity),
+                28 => decode_aircraft_status(frame).map(DecodedMsg::AircraftStatus),
+                29 => decode_target_state(frame).map(DecodedMsg::TargetState),
                 _ => None,
             }
         }
-        0 | 4 | 16 | 20 => decode_df_altitude(frame).map(DecodedMsg::Altitude),
-        5 | 21 => decode_df_squawk(frame).map(DecodedMsg::Squawk),
+        0 | 4 | 16 => decode_df_altitude(frame).map(DecodedMsg::Altitude),
+        20 => decode_comm_b(frame)
+            .map(DecodedMsg::CommB)
+            .or_else(|| decode_df_altitude(frame).map(DecodedMsg::Altitude)),
+        5 => decode_df_squawk(frame).map(DecodedMsg::Squawk),
+        21 => decode_comm_b(frame)
+            .map(DecodedMsg::CommB)
+            .or_else(|| decode_df_squawk(frame).map(DecodedMsg::Squawk)),
         _ => None,
     }
 }
 
 /// Round to 2 decimal places.
-fn round2(val: f64) -> f64 {
+pub(crate) fn round2(val: f64) -> f64 {
     (val * 100.0).round() / 100.0
 }
 
@@ -424,6 +1017,30 @@ mod tests {
         parse_frame_uncached(hex, 1.0, None).expect("valid frame")
     }
 
+    // -- Bit-field extraction --
+
+    #[test]
+    fn test_getbit_msb_first() {
+        let data = [0b1000_0001u8, 0x00];
+        assert_eq!(getbit(&data, 1), 1); // MSB of byte 0
+        assert_eq!(getbit(&data, 8), 1); // LSB of byte 0
+        assert_eq!(getbit(&data, 2), 0);
+    }
+
+    #[test]
+    fn test_getbits_spans_byte_boundary() {
+        // 0xAB = 1010_1011, 0xCD = 1100_1101; bits 5-12 straddle the two
+        // bytes and should read as 1011_1100 = 0xBC.
+        let data = [0xABu8, 0xCD];
+        assert_eq!(getbits(&data, 5, 12), 0xBC);
+    }
+
+    #[test]
+    fn test_getbits_whole_byte() {
+        let data = [0x42u8];
+        assert_eq!(getbits(&data, 1, 8), 0x42);
+    }
+
     // -- Identification --
 
     #[test]
@@ -450,6 +1067,7 @@ mod tests {
         let msg = decode_position(&frame).unwrap();
         assert_eq!(icao_to_string(&msg.icao), "40621D");
         assert_eq!(msg.altitude_ft, Some(38000));
+        assert_eq!(msg.altitude_source, AltitudeSource::Barometric);
         assert!(!msg.cpr_odd); // even frame
         assert_eq!(msg.cpr_lat, 93000);
         assert_eq!(msg.cpr_lon, 51372);
@@ -460,11 +1078,60 @@ mod tests {
         let frame = parse("8D40621D58C386435CC412692AD6");
         let msg = decode_position(&frame).unwrap();
         assert_eq!(msg.altitude_ft, Some(38000));
+        assert_eq!(msg.altitude_source, AltitudeSource::Barometric);
         assert!(msg.cpr_odd); // odd frame
         assert_eq!(msg.cpr_lat, 74158);
         assert_eq!(msg.cpr_lon, 50194);
     }
 
+    #[test]
+    fn test_decode_position_gnss() {
+        // Same frame as test_decode_position_even but with TC 20 (GNSS
+        // airborne position) instead of TC 11 (barometric).
+        let frame = parse("8D40621DA0C382D690C8AC5C84CA");
+        let msg = decode_position(&frame).unwrap();
+        assert_eq!(msg.altitude_ft, Some(38000));
+        assert_eq!(msg.altitude_source, AltitudeSource::Gnss);
+    }
+
+    // -- Surface position --
+
+    #[test]
+    fn test_decode_surface_position() {
+        // Crafted frame: TC=6, movement=1 (stopped), track valid, track
+        // code=32 (90 deg), even frame, cpr_lat=40000, cpr_lon=60000.
+        let frame = parse("8D4840D6301A013880EA60A8D57A");
+        let msg = decode_surface_position(&frame).unwrap();
+        assert_eq!(icao_to_string(&msg.icao), "4840D6");
+        assert_eq!(msg.movement_kts, Some(0.0));
+        assert_eq!(msg.ground_track_deg, Some(90.0));
+        assert!(!msg.cpr_odd);
+        assert_eq!(msg.cpr_lat, 40000);
+        assert_eq!(msg.cpr_lon, 60000);
+    }
+
+    #[test]
+    fn test_decode_movement_no_info() {
+        assert_eq!(decode_movement(0), None);
+    }
+
+    #[test]
+    fn test_decode_movement_stopped() {
+        assert_eq!(decode_movement(1), Some(0.0));
+    }
+
+    #[test]
+    fn test_decode_movement_reserved() {
+        assert_eq!(decode_movement(125), None);
+        assert_eq!(decode_movement(127), None);
+    }
+
+    #[test]
+    fn test_decode_movement_high_speed_step() {
+        assert_eq!(decode_movement(124), Some(175.0));
+        assert_eq!(decode_movement(109), Some(100.0));
+    }
+
     // -- Velocity --
 
     #[test]
@@ -487,7 +1154,9 @@ mod tests {
         );
 
         assert_eq!(msg.vertical_rate_fpm, Some(-832));
+        assert_eq!(msg.vertical_rate_source, VerticalRateSource::Gnss);
         assert_eq!(msg.speed_type, SpeedType::Ground);
+        assert_eq!(msg.gnss_baro_diff_ft, Some(550));
     }
 
     // -- Altitude --
@@ -512,6 +1181,20 @@ mod tests {
         assert_eq!(decode_altitude_13bit(0), None);
     }
 
+    #[test]
+    fn test_decode_altitude_13bit_metric() {
+        // M-bit set, n=2000 (11-bit value with M and Q bits stripped out):
+        // 2000 m converts to 6562 ft.
+        let alt_code = 0b1_1111_0110_0000;
+        assert_eq!(decode_altitude_13bit_metres(alt_code), Some(2000));
+        assert_eq!(decode_altitude_13bit(alt_code), Some(6562));
+    }
+
+    #[test]
+    fn test_decode_altitude_13bit_metres_none_without_m_bit() {
+        assert_eq!(decode_altitude_13bit_metres(0xC38), None);
+    }
+
     // -- Squawk --
 
     #[test]
@@ -536,13 +1219,17 @@ mod tests {
         // Construct a code with Q-bit clear that produces a valid altitude
         // A=1,B=0,C=1: c_bin=1 (100ft), ab_bin=8 (4000ft) → 4000+100-1200 = 2900
         let alt_code = 0b_0_1_0_0_0_0_0_0_0_0_0_0_0u32; // A1=1, rest 0
-        // This gives c_digit=0 which is invalid. Let's try a known working pattern.
-        // C1=1: sets c_digit bit, making c_bin valid
-        // A1=1, C1=1: alt_code = (C1<<12)|(A1<<11) = 0x1800
+                                                        // This gives c_digit=0 which is invalid. Let's try a known working pattern.
+                                                        // C1=1: sets c_digit bit, making c_bin valid
+                                                        // A1=1, C1=1: alt_code = (C1<<12)|(A1<<11) = 0x1800
         let alt = decode_altitude(0x1800);
         assert!(alt.is_some(), "Valid Gillham code should decode");
         let val = alt.unwrap();
-        assert!((-1200..=126750).contains(&val), "Altitude {} out of range", val);
+        assert!(
+            (-1200..=126750).contains(&val),
+            "Altitude {} out of range",
+            val
+        );
     }
 
     #[test]
@@ -605,6 +1292,169 @@ mod tests {
         assert_eq!(decode_squawk(id_code), "7700");
     }
 
+    // -- Target state and status / Aircraft status --
+
+    #[test]
+    fn test_decode_target_state_full() {
+        let frame = parse("8D4840D6E9E71615C0680027E8D2");
+        let msg = decode_target_state(&frame).unwrap();
+        assert_eq!(msg.selected_altitude_ft, Some(40000));
+        assert!(msg.altitude_source_is_fms);
+        assert_eq!(msg.barometric_setting_mb, Some(1012.8));
+        assert_eq!(msg.selected_heading_deg, Some(-90.0));
+        assert!(msg.autopilot_engaged);
+        assert!(msg.vnav_mode);
+        assert!(!msg.altitude_hold_mode);
+        assert!(msg.approach_mode);
+        assert!(!msg.lnav_mode);
+    }
+
+    #[test]
+    fn test_decode_target_state_wrong_subtype_returns_none() {
+        // Subtype field (bits 50-48 of the ME) is 0, not the handled subtype 1.
+        let frame = parse("8D4840D6E8000000000000E1DBC9");
+        assert!(decode_target_state(&frame).is_none());
+    }
+
+    #[test]
+    fn test_decode_aircraft_status_emergency() {
+        let frame = parse("8D4840D6E12AAA000000003CF5CE");
+        let msg = decode_aircraft_status(&frame).unwrap();
+        match msg {
+            AircraftStatusMsg::Emergency(m) => {
+                assert_eq!(m.emergency_state, EmergencyState::General);
+                assert_eq!(m.squawk, "7700");
+            }
+            _ => panic!("expected Emergency, got {msg:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_aircraft_status_acas_ra() {
+        let frame = parse("8D4840D6E2A005876069005EE7D5");
+        let msg = decode_aircraft_status(&frame).unwrap();
+        match msg {
+            AircraftStatusMsg::AcasRa(m) => {
+                assert_eq!(m.active_ra, 10241);
+                assert!(!m.ra_terminated);
+                assert!(m.multiple_threats);
+                assert_eq!(m.threat_icao, None);
+                assert_eq!(m.threat_altitude_ft, Some(5000));
+                assert_eq!(m.threat_range_nmi, Some(2.5));
+                assert_eq!(m.threat_bearing_deg, Some(90));
+            }
+            _ => panic!("expected AcasRa, got {msg:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_routes_target_state() {
+        let frame = parse("8D4840D6E9E71615C0680027E8D2");
+        let msg = decode(&frame).unwrap();
+        assert!(matches!(msg, DecodedMsg::TargetState(_)));
+    }
+
+    #[test]
+    fn test_decode_routes_aircraft_status() {
+        let frame = parse("8D4840D6E12AAA000000003CF5CE");
+        let msg = decode(&frame).unwrap();
+        assert!(matches!(msg, DecodedMsg::AircraftStatus(_)));
+    }
+
+    // -- Comm-B (BDS) --
+
+    #[test]
+    fn test_decode_comm_b_bds20_callsign() {
+        let frame = parse("A0000000205054D4C72CF4000000");
+        let msg = decode_comm_b(&frame).unwrap();
+        match msg {
+            CommBMsg::Bds20(m) => assert_eq!(m.callsign, "TEST1234"),
+            _ => panic!("expected Bds20, got {msg:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_comm_b_bds40_vertical_intent() {
+        // MCP altitude 33472 ft and QNH 1013.2 mb both land comfortably
+        // inside BDS4,0's plausible ranges, which is enough of a lead over
+        // reinterpreting the same bits as BDS5,0/6,0 to clear the score
+        // margin.
+        let frame = parse("A0000000C1600030A80000000000");
+        let msg = decode_comm_b(&frame).unwrap();
+        match msg {
+            CommBMsg::Bds40(m) => {
+                assert_eq!(m.mcp_altitude_ft, Some(33472));
+                assert_eq!(m.fms_altitude_ft, None);
+                assert_eq!(m.barometric_setting_mb, Some(1013.2));
+            }
+            _ => panic!("expected Bds40, got {msg:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_comm_b_bds50_track_and_turn() {
+        let frame = parse("A0000000FB7FCE408008DA000000");
+        let msg = decode_comm_b(&frame).unwrap();
+        match msg {
+            CommBMsg::Bds50(m) => {
+                assert_eq!(m.roll_angle_deg, Some(-6.5));
+                assert_eq!(m.track_angle_deg, Some(-2.29));
+                assert_eq!(m.ground_speed_kts, Some(258.0));
+                assert_eq!(m.track_angle_rate_deg_s, None);
+                assert_eq!(m.true_airspeed_kts, Some(218.0));
+            }
+            _ => panic!("expected Bds50, got {msg:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_comm_b_bds60_heading_and_speed() {
+        let frame = parse("A0000000806B3504A0F000000000");
+        let msg = decode_comm_b(&frame).unwrap();
+        match msg {
+            CommBMsg::Bds60(m) => {
+                assert_eq!(m.magnetic_heading_deg, Some(1.05));
+                assert_eq!(m.indicated_airspeed_kts, Some(410));
+                assert_eq!(m.mach, Some(0.07));
+                assert_eq!(m.baro_altitude_rate_fpm, Some(960));
+                assert_eq!(m.inertial_vertical_velocity_fpm, None);
+            }
+            _ => panic!("expected Bds60, got {msg:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_comm_b_bds60_negative_vertical_rates() {
+        // Barometric and inertial vertical rates are signed fields; confirm
+        // the two's-complement decode handles descents correctly too.
+        let frame = parse("A0A00000000000003F0FFA000000");
+        let msg = decode_comm_b(&frame).unwrap();
+        match msg {
+            CommBMsg::Bds60(m) => {
+                assert_eq!(m.baro_altitude_rate_fpm, Some(-992));
+                assert_eq!(m.inertial_vertical_velocity_fpm, Some(-192));
+            }
+            _ => panic!("expected Bds60, got {msg:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_comm_b_ambiguous_mb_returns_none() {
+        // All-zero MB scores BDS5,0 and BDS6,0 identically (every status bit
+        // clear, every value zero), so the top score doesn't clear
+        // BDS_SCORE_MARGIN over the runner-up and decode_comm_b declines to
+        // guess.
+        let frame = parse("A000000000000000000000000000");
+        assert!(decode_comm_b(&frame).is_none());
+    }
+
+    #[test]
+    fn test_decode_routes_comm_b() {
+        let frame = parse("A0000000205054D4C72CF4000000");
+        let msg = decode(&frame).unwrap();
+        assert!(matches!(msg, DecodedMsg::CommB(_)));
+    }
+
     // -- Full decode routing --
 
     #[test]
@@ -628,6 +1478,13 @@ mod tests {
         assert!(matches!(msg, DecodedMsg::Velocity(_)));
     }
 
+    #[test]
+    fn test_decode_routes_surface_position() {
+        let frame = parse("8D4840D6301A013880EA60A8D57A");
+        let msg = decode(&frame).unwrap();
+        assert!(matches!(msg, DecodedMsg::SurfacePosition(_)));
+    }
+
     #[test]
     fn test_decode_msg_icao() {
         let frame = parse("8D4840D6202CC371C32CE0576098");
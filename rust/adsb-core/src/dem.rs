@@ -0,0 +1,238 @@
+//! Digital elevation model (DEM) lookups for above-ground-level altitude.
+//!
+//! Terrain elevation lets position endpoints report height above ground
+//! (`altitude_agl_ft`) instead of only barometric altitude — the difference
+//! between "500ft over a 4,000ft ridge" and "500ft over a valley floor" is
+//! what separates a low-flying helicopter from ground clutter.
+//!
+//! Tiles are 1-degree SRTM-3 grids of signed 16-bit big-endian elevation
+//! samples (the classic `.hgt` layout: 1201x1201 samples, north to south,
+//! west to east), one file per whole-degree lat/lon cell, named
+//! `<N|S><lat>[E|W]<lon>.hgt` (e.g. `N35W083.hgt`). Missing tiles (no
+//! coverage) and the SRTM no-data sentinel both resolve to `None` rather
+//! than a bogus elevation.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// SRTM no-data sentinel.
+const NO_DATA: i16 = i16::MIN;
+
+/// Samples per side of an SRTM-3 tile (3 arc-second, 1201x1201).
+const TILE_SIZE: usize = 1201;
+
+struct Tile {
+    /// Row-major samples, row 0 = north edge, col 0 = west edge.
+    samples: Vec<i16>,
+}
+
+impl Tile {
+    fn sample(&self, row: usize, col: usize) -> Option<f64> {
+        let v = self.samples[row * TILE_SIZE + col];
+        if v == NO_DATA {
+            None
+        } else {
+            Some(v as f64)
+        }
+    }
+}
+
+/// Terrain elevation source backed by `.hgt` tiles on disk, with a bounded
+/// LRU cache so repeated lookups in the same region don't re-read a tile
+/// (each SRTM-3 tile is ~2.8MB) on every request.
+pub struct DemSource {
+    tile_dir: PathBuf,
+    capacity: usize,
+    cache: HashMap<(i32, i32), Tile>,
+    /// Least-recently-used first, most-recently-used last.
+    recency: Vec<(i32, i32)>,
+}
+
+impl DemSource {
+    /// `tile_dir` holds `.hgt` tiles; `capacity` bounds how many whole tiles
+    /// are kept resident at once.
+    pub fn new(tile_dir: impl Into<PathBuf>, capacity: usize) -> Self {
+        DemSource {
+            tile_dir: tile_dir.into(),
+            capacity: capacity.max(1),
+            cache: HashMap::new(),
+            recency: Vec::new(),
+        }
+    }
+
+    /// Terrain elevation in meters at `(lat, lon)`, bilinearly interpolated
+    /// from the four surrounding samples. `None` if the covering tile is
+    /// missing or any of the four corner samples is no-data.
+    pub fn elevation_m(&mut self, lat: f64, lon: f64) -> Option<f64> {
+        let lat_floor = lat.floor() as i32;
+        let lon_floor = lon.floor() as i32;
+        let key = (lat_floor, lon_floor);
+
+        if !self.cache.contains_key(&key) {
+            let tile = load_tile(&self.tile_dir, lat_floor, lon_floor)?;
+            self.insert(key, tile);
+        } else {
+            self.touch(key);
+        }
+        let tile = self.cache.get(&key)?;
+
+        // row 0 is the north edge, so fy runs north-to-south: 0 at the
+        // tile's north edge, 1 at lat_floor.
+        let fy = 1.0 - (lat - lat_floor as f64);
+        let fx = lon - lon_floor as f64;
+
+        let row_f = fy * (TILE_SIZE - 1) as f64;
+        let col_f = fx * (TILE_SIZE - 1) as f64;
+        let row0 = row_f.floor() as usize;
+        let col0 = col_f.floor() as usize;
+        let row1 = (row0 + 1).min(TILE_SIZE - 1);
+        let col1 = (col0 + 1).min(TILE_SIZE - 1);
+        let fy2 = row_f - row0 as f64;
+        let fx2 = col_f - col0 as f64;
+
+        let e00 = tile.sample(row0, col0)?;
+        let e10 = tile.sample(row0, col1)?;
+        let e01 = tile.sample(row1, col0)?;
+        let e11 = tile.sample(row1, col1)?;
+
+        Some(
+            e00 * (1.0 - fx2) * (1.0 - fy2)
+                + e10 * fx2 * (1.0 - fy2)
+                + e01 * (1.0 - fx2) * fy2
+                + e11 * fx2 * fy2,
+        )
+    }
+
+    fn insert(&mut self, key: (i32, i32), tile: Tile) {
+        if self.cache.len() >= self.capacity {
+            if !self.recency.is_empty() {
+                let oldest = self.recency.remove(0);
+                self.cache.remove(&oldest);
+            }
+        }
+        self.cache.insert(key, tile);
+        self.recency.push(key);
+    }
+
+    fn touch(&mut self, key: (i32, i32)) {
+        if let Some(pos) = self.recency.iter().position(|&k| k == key) {
+            let k = self.recency.remove(pos);
+            self.recency.push(k);
+        }
+    }
+}
+
+/// Load a 1-degree `.hgt` tile covering `(lat_floor, lon_floor)`, or `None`
+/// if the file doesn't exist (no coverage for that cell).
+fn load_tile(tile_dir: &Path, lat_floor: i32, lon_floor: i32) -> Option<Tile> {
+    let path = tile_dir.join(tile_name(lat_floor, lon_floor));
+    let bytes = std::fs::read(path).ok()?;
+    if bytes.len() != TILE_SIZE * TILE_SIZE * 2 {
+        return None;
+    }
+
+    let samples = bytes
+        .chunks_exact(2)
+        .map(|c| i16::from_be_bytes([c[0], c[1]]))
+        .collect();
+    Some(Tile { samples })
+}
+
+/// SRTM tile naming convention, e.g. `N35W083.hgt`.
+fn tile_name(lat_floor: i32, lon_floor: i32) -> String {
+    let lat_band = if lat_floor >= 0 { 'N' } else { 'S' };
+    let lon_band = if lon_floor >= 0 { 'E' } else { 'W' };
+    format!(
+        "{lat_band}{:02}{lon_band}{:03}.hgt",
+        lat_floor.abs(),
+        lon_floor.abs()
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_flat_tile(dir: &Path, lat_floor: i32, lon_floor: i32, elevation: i16) {
+        let path = dir.join(tile_name(lat_floor, lon_floor));
+        let samples = vec![elevation; TILE_SIZE * TILE_SIZE];
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_be_bytes()).collect();
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_tile_name_northeast() {
+        assert_eq!(tile_name(35, 83), "N35E083.hgt");
+    }
+
+    #[test]
+    fn test_tile_name_northwest() {
+        assert_eq!(tile_name(35, -83), "N35W083.hgt");
+    }
+
+    #[test]
+    fn test_tile_name_south() {
+        assert_eq!(tile_name(-12, -70), "S12W070.hgt");
+    }
+
+    #[test]
+    fn test_elevation_missing_tile_returns_none() {
+        let mut dem = DemSource::new("/nonexistent/dir/for/dem/tiles", 4);
+        assert_eq!(dem.elevation_m(35.5, -82.5), None);
+    }
+
+    #[test]
+    fn test_elevation_flat_tile_interpolates_to_constant() {
+        let dir = tempfile::tempdir().unwrap();
+        write_flat_tile(dir.path(), 35, -83, 100);
+
+        let mut dem = DemSource::new(dir.path(), 4);
+        assert_eq!(dem.elevation_m(35.5, -82.9), Some(100.0));
+    }
+
+    #[test]
+    fn test_elevation_caches_tile_after_first_load() {
+        let dir = tempfile::tempdir().unwrap();
+        write_flat_tile(dir.path(), 35, -83, 100);
+
+        let mut dem = DemSource::new(dir.path(), 4);
+        assert_eq!(dem.elevation_m(35.5, -82.9), Some(100.0));
+        assert!(dem.cache.contains_key(&(35, -83)));
+
+        // Remove the file — cached value should still be returned.
+        std::fs::remove_file(dir.path().join("N35W083.hgt")).unwrap();
+        assert_eq!(dem.elevation_m(35.4, -82.8), Some(100.0));
+    }
+
+    #[test]
+    fn test_elevation_no_data_sentinel_returns_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(tile_name(35, -83));
+        let mut samples = vec![50i16; TILE_SIZE * TILE_SIZE];
+        samples[0] = NO_DATA; // sample(0, 0) == NW corner of the tile
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_be_bytes()).collect();
+        std::fs::write(path, bytes).unwrap();
+
+        let mut dem = DemSource::new(dir.path(), 4);
+        // Just inside the NW corner (lat 36.0 itself would floor into the
+        // missing 36N tile), e00 is the no-data sample.
+        assert_eq!(dem.elevation_m(35.9999, -83.0), None);
+    }
+
+    #[test]
+    fn test_lru_eviction_drops_oldest_tile() {
+        let dir = tempfile::tempdir().unwrap();
+        write_flat_tile(dir.path(), 35, -83, 10);
+        write_flat_tile(dir.path(), 36, -83, 10);
+
+        let mut dem = DemSource::new(dir.path(), 1);
+        assert!(dem.elevation_m(35.5, -82.5).is_some());
+        assert!(dem.elevation_m(36.5, -82.5).is_some());
+        assert_eq!(dem.cache.len(), 1);
+        assert!(!dem.cache.contains_key(&(35, -83)));
+    }
+}
@@ -3,6 +3,8 @@
 //! Infers aircraft category from speed, altitude, callsign, and ICAO address.
 //! No external database required — works purely from observed data.
 
+use std::collections::HashMap;
+
 use crate::filter::haversine_nm;
 
 // ---------------------------------------------------------------------------
@@ -14,7 +16,15 @@ pub const CAT_PROP: &str = "prop";
 pub const CAT_TURBOPROP: &str = "turboprop";
 pub const CAT_HELICOPTER: &str = "helicopter";
 pub const CAT_MILITARY: &str = "military";
+pub const CAT_SPECIAL: &str = "special";
 pub const CAT_CARGO: &str = "cargo";
+pub const CAT_GLIDER: &str = "glider";
+pub const CAT_LIGHTER_THAN_AIR: &str = "lighter_than_air";
+pub const CAT_PARACHUTIST: &str = "parachutist";
+pub const CAT_ULTRALIGHT: &str = "ultralight";
+pub const CAT_UAV: &str = "uav";
+pub const CAT_SPACE_VEHICLE: &str = "space_vehicle";
+pub const CAT_SURFACE_VEHICLE: &str = "surface_vehicle";
 pub const CAT_UNKNOWN: &str = "unknown";
 
 // ---------------------------------------------------------------------------
@@ -66,15 +76,32 @@ pub fn lookup_operator(callsign: &str) -> Option<&'static str> {
 }
 
 /// Classify aircraft category from observed flight profile.
+///
+/// `near_heliport` should be `true` when the caller already knows (e.g. via
+/// [`nearest_airport_of_type`]) that the target is within a short radius of
+/// a heliport — it widens the speed/altitude window that's accepted as
+/// [`CAT_HELICOPTER`], since a slow, low, nearby target is corroborated by
+/// the heliport rather than guessed from profile alone.
+///
+/// `icao`, when given, is checked against [`crate::icao::is_special`] so a
+/// watchlisted airframe is flagged [`CAT_SPECIAL`] without the caller having
+/// to pre-compute that check (unlike `is_military`, which is still
+/// externally supplied since it depends on the richer [`crate::icao::military_info`]
+/// resolution, e.g. callsign-based matches).
 pub fn classify_from_profile(
     speed_kts: Option<f64>,
     altitude_ft: Option<i32>,
     is_military: bool,
+    near_heliport: bool,
+    icao: Option<u32>,
     callsign: Option<&str>,
 ) -> &'static str {
     if is_military {
         return CAT_MILITARY;
     }
+    if icao.is_some_and(crate::icao::is_special) {
+        return CAT_SPECIAL;
+    }
 
     // Check callsign for cargo operators
     if let Some(cs) = callsign {
@@ -91,6 +118,13 @@ pub fn classify_from_profile(
         if speed > 250.0 {
             return CAT_JET;
         }
+        if near_heliport && speed < 100.0 {
+            if let Some(alt) = altitude_ft {
+                if alt < 5000 {
+                    return CAT_HELICOPTER;
+                }
+            }
+        }
         if speed < 80.0 {
             if let Some(alt) = altitude_ft {
                 if alt < 3000 {
@@ -124,6 +158,69 @@ pub fn classify_from_profile(
     CAT_UNKNOWN
 }
 
+/// Classify aircraft category from an ADS-B identification message's
+/// `(tc, category)` pair — the "category set" and 3-bit CA sub-field from
+/// TC 1-4, per ICAO Annex 10. Unlike [`classify_from_profile`], this is
+/// authoritative: it's what the aircraft itself reported, not a speed/
+/// altitude guess.
+///
+/// - `set == 4` (Category Set A, airborne): Light/Small/Large/High-Vortex
+///   Large/Heavy/High-performance/Rotorcraft.
+/// - `set == 3` (Category Set B): glider, lighter-than-air, parachutist,
+///   ultralight, UAV, space/trans-atmospheric vehicle.
+/// - `set == 2` (Category Set C): surface emergency/service vehicles
+///   (obstacles have no flight category and fall through to `CAT_UNKNOWN`).
+/// - `set == 1` (Category Set D) is reserved and always `CAT_UNKNOWN`.
+///
+/// Returns `CAT_UNKNOWN` for `cat == 0` ("no category information") or any
+/// combination not covered above.
+pub fn classify_from_emitter_category(set: u8, cat: u8) -> &'static str {
+    match (set, cat) {
+        (4, 1) => CAT_PROP,
+        (4, 2) => CAT_TURBOPROP,
+        (4, 3) | (4, 4) | (4, 5) | (4, 6) => CAT_JET,
+        (4, 7) => CAT_HELICOPTER,
+        (3, 1) => CAT_GLIDER,
+        (3, 2) => CAT_LIGHTER_THAN_AIR,
+        (3, 3) => CAT_PARACHUTIST,
+        (3, 4) => CAT_ULTRALIGHT,
+        (3, 6) => CAT_UAV,
+        (3, 7) => CAT_SPACE_VEHICLE,
+        (2, 1) | (2, 2) => CAT_SURFACE_VEHICLE,
+        _ => CAT_UNKNOWN,
+    }
+}
+
+/// Classify aircraft category, preferring the authoritative emitter category
+/// reported in TC 1-4 identification messages and falling back to the
+/// speed/altitude heuristic in [`classify_from_profile`] only when no
+/// category was decoded (`cat == 0`) or it didn't map to a known class.
+#[allow(clippy::too_many_arguments)]
+pub fn classify_aircraft(
+    set: u8,
+    cat: u8,
+    speed_kts: Option<f64>,
+    altitude_ft: Option<i32>,
+    is_military: bool,
+    near_heliport: bool,
+    icao: Option<u32>,
+    callsign: Option<&str>,
+) -> &'static str {
+    if is_military {
+        return CAT_MILITARY;
+    }
+    if icao.is_some_and(crate::icao::is_special) {
+        return CAT_SPECIAL;
+    }
+
+    let from_category = classify_from_emitter_category(set, cat);
+    if from_category != CAT_UNKNOWN {
+        return from_category;
+    }
+
+    classify_from_profile(speed_kts, altitude_ft, is_military, near_heliport, icao, callsign)
+}
+
 // ---------------------------------------------------------------------------
 // Airport database
 // ---------------------------------------------------------------------------
@@ -138,13 +235,54 @@ pub struct Airport {
     pub elevation_ft: i32,
     #[serde(rename = "type")]
     pub airport_type: String,
+    pub runways: Vec<Runway>,
+}
+
+/// One runway end (a physical runway has two, one per reciprocal heading).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Runway {
+    /// Runway identifier for this end, e.g. "17" or "35".
+    pub id: String,
+    /// True (not magnetic) heading in degrees.
+    pub heading_true_deg: f64,
+    pub length_ft: i32,
 }
 
 /// Embedded CSV data (3,642 US airports from OurAirports).
 const AIRPORTS_CSV: &str = include_str!("airports.csv");
 
+/// Embedded CSV data: one row per runway end,
+/// `airport_icao,id,heading_true_deg,length_ft`.
+const RUNWAYS_CSV: &str = include_str!("runways.csv");
+
+/// Parse the embedded runways CSV into a lookup by airport ICAO.
+fn parse_runways() -> HashMap<String, Vec<Runway>> {
+    let mut by_airport: HashMap<String, Vec<Runway>> = HashMap::new();
+    for line in RUNWAYS_CSV.lines().skip(1) {
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        let heading_true_deg = match fields[2].parse::<f64>() {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        let length_ft = fields[3].parse::<i32>().unwrap_or(0);
+        by_airport
+            .entry(fields[0].to_string())
+            .or_default()
+            .push(Runway {
+                id: fields[1].to_string(),
+                heading_true_deg,
+                length_ft,
+            });
+    }
+    by_airport
+}
+
 /// Parse the embedded airports CSV. Cached via LazyLock.
 fn parse_airports() -> Vec<Airport> {
+    let mut runways = parse_runways();
     let mut airports = Vec::with_capacity(3700);
     for line in AIRPORTS_CSV.lines().skip(1) {
         let fields: Vec<&str> = line.split(',').collect();
@@ -166,15 +304,20 @@ fn parse_airports() -> Vec<Airport> {
             "large_airport" => "major",
             "medium_airport" => "medium",
             "small_airport" => "small",
+            "heliport" => "heliport",
+            "seaplane_base" => "seaport",
             other => other,
         };
+        let icao = fields[0].to_string();
+        let runways = runways.remove(&icao).unwrap_or_default();
         airports.push(Airport {
-            icao: fields[0].to_string(),
+            icao,
             name: fields[1].to_string(),
             lat,
             lon,
             elevation_ft,
             airport_type: airport_type.to_string(),
+            runways,
         });
     }
     airports
@@ -183,43 +326,225 @@ fn parse_airports() -> Vec<Airport> {
 static AIRPORTS: std::sync::LazyLock<Vec<Airport>> =
     std::sync::LazyLock::new(parse_airports);
 
+/// Tolerance, in degrees, between an aircraft's ground track and a runway's
+/// true heading for [`best_matching_runway`] to consider it aligned.
+const RUNWAY_HEADING_TOLERANCE_DEG: f64 = 30.0;
+
+/// Smallest angular difference between two headings in degrees (0-180),
+/// accounting for wraparound at 0/360.
+fn angular_diff(a: f64, b: f64) -> f64 {
+    let diff = (a - b).abs() % 360.0;
+    if diff > 180.0 {
+        360.0 - diff
+    } else {
+        diff
+    }
+}
+
+/// Whether `airport_type` is a facility where "runway" is a meaningless
+/// concept, so [`classify_flight_phase`] shouldn't tag an approach/departure
+/// report with a runway even if a heading happens to line up.
+fn suppresses_runway_phrasing(airport_type: &str) -> bool {
+    airport_type == "heliport" || airport_type == "seaport"
+}
+
+/// The runway at `airport` whose heading best matches `track_deg`, within
+/// [`RUNWAY_HEADING_TOLERANCE_DEG`]. `None` if the airport has no runway
+/// data or none line up with the track.
+pub fn best_matching_runway(airport: &Airport, track_deg: f64) -> Option<&Runway> {
+    airport
+        .runways
+        .iter()
+        .map(|rwy| (rwy, angular_diff(rwy.heading_true_deg, track_deg)))
+        .filter(|&(_, diff)| diff <= RUNWAY_HEADING_TOLERANCE_DEG)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(rwy, _)| rwy)
+}
+
+/// Cell size in degrees for the [`AIRPORT_GRID`] bucket acceleration
+/// structure.
+const AIRPORT_GRID_CELL_DEG: f64 = 1.0;
+
+/// Nautical miles per degree of latitude, used to size the cell-ring scan in
+/// [`nearest_airport`]. Longitude degrees are narrower than this away from
+/// the equator, scaled by `cos(lat)`.
+const NM_PER_DEG_LAT: f64 = 60.0;
+
+/// The `(lat_cell, lon_cell)` 1°×1° cell containing `(lat, lon)` — the key
+/// [`AIRPORT_GRID`] maps to indices into [`AIRPORTS`] in that cell.
+fn airport_cell(lat: f64, lon: f64) -> (i32, i32) {
+    (
+        (lat / AIRPORT_GRID_CELL_DEG).floor() as i32,
+        (lon / AIRPORT_GRID_CELL_DEG).floor() as i32,
+    )
+}
+
+fn build_airport_grid(airports: &[Airport]) -> HashMap<(i32, i32), Vec<usize>> {
+    let mut grid: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    for (idx, apt) in airports.iter().enumerate() {
+        grid.entry(airport_cell(apt.lat, apt.lon)).or_default().push(idx);
+    }
+    grid
+}
+
+static AIRPORT_GRID: std::sync::LazyLock<HashMap<(i32, i32), Vec<usize>>> =
+    std::sync::LazyLock::new(|| build_airport_grid(&AIRPORTS));
+
 /// Get all airports.
 pub fn all_airports() -> &'static [Airport] {
     &AIRPORTS
 }
 
+/// Lowercased `(icao, name)` pairs for [`AIRPORTS`], indexed the same way, so
+/// [`search_airports`] never re-lowercases a string on the query path.
+static AIRPORT_SEARCH_INDEX: std::sync::LazyLock<Vec<(String, String)>> =
+    std::sync::LazyLock::new(|| {
+        AIRPORTS
+            .iter()
+            .map(|apt| (apt.icao.to_lowercase(), apt.name.to_lowercase()))
+            .collect()
+    });
+
+/// How a query matched an airport in [`search_airports`], in priority order
+/// (lower variant ranks first when results are sorted).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchRank {
+    ExactIcao,
+    IdentPrefix,
+    NameSubstring,
+}
+
+/// Search [`AIRPORTS`] by ICAO ident or name, for autocomplete and
+/// let-the-user-type-it map centering.
+///
+/// The query is matched case-insensitively against the cached
+/// [`AIRPORT_SEARCH_INDEX`] and ranked: an exact ICAO match first, then an
+/// ICAO/ident prefix match, then a name substring match. Each airport
+/// appears at most once, at its best-ranked tier. Ties within a tier are
+/// broken alphabetically by ICAO for stable ordering. Returns at most
+/// `limit` results; an empty or all-whitespace query returns no results.
+pub fn search_airports(query: &str, limit: usize) -> Vec<&'static Airport> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<(MatchRank, usize)> = Vec::new();
+    for (idx, (icao_lower, name_lower)) in AIRPORT_SEARCH_INDEX.iter().enumerate() {
+        let rank = if *icao_lower == query {
+            MatchRank::ExactIcao
+        } else if icao_lower.starts_with(&query) {
+            MatchRank::IdentPrefix
+        } else if name_lower.contains(&query) {
+            MatchRank::NameSubstring
+        } else {
+            continue;
+        };
+        matches.push((rank, idx));
+    }
+
+    matches.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| AIRPORTS[a.1].icao.cmp(&AIRPORTS[b.1].icao)));
+    matches.into_iter().take(limit).map(|(_, idx)| &AIRPORTS[idx]).collect()
+}
+
 /// Find nearest airport within max_nm nautical miles.
 ///
+/// Looks up the query's 1°×1° grid cell in [`AIRPORT_GRID`] and only runs
+/// haversine against airports in the ring of cells that could plausibly
+/// contain a match within `max_nm`, rather than scanning all ~3,600
+/// airports. The ring radius is computed separately for each axis since a
+/// degree of longitude shrinks toward the poles (`60 * cos(lat)` nm vs. a
+/// flat 60nm/degree for latitude), and padded by one cell so a query sitting
+/// near a cell edge doesn't miss a closer airport just across the boundary.
+///
 /// Returns (icao, name, distance_nm) or None.
 pub fn nearest_airport(lat: f64, lon: f64, max_nm: f64) -> Option<(String, String, f64)> {
-    let mut best: Option<(String, String, f64)> = None;
+    nearest_airport_filtered(lat, lon, max_nm, |_| true)
+}
 
-    for apt in AIRPORTS.iter() {
-        let dist = haversine_nm(lat, lon, apt.lat, apt.lon);
-        if dist < max_nm && best.as_ref().is_none_or(|b| dist < b.2) {
-            best = Some((apt.icao.clone(), apt.name.clone(), dist));
+/// Like [`nearest_airport`], but only considers airports whose
+/// `airport_type` is one of `types`, e.g. `&["heliport"]` to find the
+/// nearest helipad regardless of how far the nearest full airport is.
+pub fn nearest_airport_of_type(
+    lat: f64,
+    lon: f64,
+    max_nm: f64,
+    types: &[&str],
+) -> Option<(String, String, f64)> {
+    nearest_airport_filtered(lat, lon, max_nm, |apt| {
+        types.iter().any(|t| *t == apt.airport_type)
+    })
+}
+
+fn nearest_airport_filtered(
+    lat: f64,
+    lon: f64,
+    max_nm: f64,
+    keep: impl Fn(&Airport) -> bool,
+) -> Option<(String, String, f64)> {
+    let (lat_cell, lon_cell) = airport_cell(lat, lon);
+
+    let lat_radius = (max_nm / NM_PER_DEG_LAT).ceil() as i32 + 1;
+    let nm_per_deg_lon = (NM_PER_DEG_LAT * lat.to_radians().cos()).max(1.0);
+    let lon_radius = (max_nm / nm_per_deg_lon).ceil() as i32 + 1;
+
+    let mut best: Option<(String, String, f64)> = None;
+    for gy in (lat_cell - lat_radius)..=(lat_cell + lat_radius) {
+        for gx in (lon_cell - lon_radius)..=(lon_cell + lon_radius) {
+            let Some(candidates) = AIRPORT_GRID.get(&(gy, gx)) else {
+                continue;
+            };
+            for &idx in candidates {
+                let apt = &AIRPORTS[idx];
+                if !keep(apt) {
+                    continue;
+                }
+                let dist = haversine_nm(lat, lon, apt.lat, apt.lon);
+                if dist < max_nm && best.as_ref().is_none_or(|b| dist < b.2) {
+                    best = Some((apt.icao.clone(), apt.name.clone(), dist));
+                }
+            }
         }
     }
 
     best
 }
 
-/// Classify flight phase relative to nearest airport.
+/// Classify flight phase relative to nearest airport. When `heading_deg` is
+/// given, approaching/departing reports are tagged with the best-aligned
+/// runway (see [`best_matching_runway`]), e.g. "Approaching KAVL RWY 35
+/// (12nm)".
 pub fn classify_flight_phase(
     lat: f64,
     lon: f64,
     altitude_ft: Option<i32>,
     vertical_rate_fpm: Option<i32>,
+    heading_deg: Option<f64>,
     max_airport_nm: f64,
 ) -> Option<String> {
     let (ref code, _name, dist) = nearest_airport(lat, lon, max_airport_nm)?;
+    let airport = AIRPORTS.iter().find(|a| &a.icao == code);
+    let runway = heading_deg.and_then(|track| {
+        airport
+            .filter(|a| !suppresses_runway_phrasing(&a.airport_type))
+            .and_then(|a| best_matching_runway(a, track))
+    });
+    let runway_suffix = runway
+        .map(|r| format!(" RWY {}", r.id))
+        .unwrap_or_default();
 
     if let (Some(alt), Some(vr)) = (altitude_ft, vertical_rate_fpm) {
         if dist < 15.0 && vr < -200 && alt < 10000 {
-            return Some(format!("Approaching {} ({:.0}nm)", code, dist));
+            return Some(format!(
+                "Approaching {}{} ({:.0}nm)",
+                code, runway_suffix, dist
+            ));
         }
         if dist < 15.0 && vr > 200 && alt < 10000 {
-            return Some(format!("Departing {} ({:.0}nm)", code, dist));
+            return Some(format!(
+                "Departing {}{} ({:.0}nm)",
+                code, runway_suffix, dist
+            ));
         }
     }
 
@@ -241,7 +566,7 @@ mod tests {
     #[test]
     fn test_classify_jet() {
         assert_eq!(
-            classify_from_profile(Some(300.0), Some(35000), false, None),
+            classify_from_profile(Some(300.0), Some(35000), false, false, None, None),
             CAT_JET
         );
     }
@@ -249,7 +574,7 @@ mod tests {
     #[test]
     fn test_classify_prop() {
         assert_eq!(
-            classify_from_profile(Some(120.0), Some(5000), false, None),
+            classify_from_profile(Some(120.0), Some(5000), false, false, None, None),
             CAT_PROP
         );
     }
@@ -257,7 +582,7 @@ mod tests {
     #[test]
     fn test_classify_turboprop() {
         assert_eq!(
-            classify_from_profile(Some(120.0), Some(20000), false, None),
+            classify_from_profile(Some(120.0), Some(20000), false, false, None, None),
             CAT_TURBOPROP
         );
     }
@@ -265,23 +590,77 @@ mod tests {
     #[test]
     fn test_classify_helicopter() {
         assert_eq!(
-            classify_from_profile(Some(60.0), Some(1500), false, None),
+            classify_from_profile(Some(60.0), Some(1500), false, false, None, None),
             CAT_HELICOPTER
         );
     }
 
+    #[test]
+    fn test_classify_helicopter_near_heliport_widens_window() {
+        // 90kts/4000ft wouldn't clear the unconditional helicopter check
+        // (speed < 80, alt < 3000) but is corroborated by proximity to a
+        // heliport.
+        assert_eq!(
+            classify_from_profile(Some(90.0), Some(4000), false, true, None, None),
+            CAT_HELICOPTER
+        );
+    }
+
+    #[test]
+    fn test_classify_profile_ignores_heliport_flag_outside_window() {
+        assert_eq!(
+            classify_from_profile(Some(300.0), Some(35000), false, true, None, None),
+            CAT_JET
+        );
+    }
+
     #[test]
     fn test_classify_military() {
         assert_eq!(
-            classify_from_profile(Some(300.0), Some(35000), true, None),
+            classify_from_profile(Some(300.0), Some(35000), true, false, None, None),
             CAT_MILITARY
         );
     }
 
+    #[test]
+    fn test_classify_special_watchlisted_icao() {
+        let addr = crate::icao::SPECIAL_WATCHLIST[0];
+        assert_eq!(
+            classify_from_profile(Some(300.0), Some(35000), false, false, Some(addr), None),
+            CAT_SPECIAL
+        );
+    }
+
+    #[test]
+    fn test_classify_special_ignores_unlisted_icao() {
+        assert_eq!(
+            classify_from_profile(Some(300.0), Some(35000), false, false, Some(0xA00001), None),
+            CAT_JET
+        );
+    }
+
+    #[test]
+    fn test_classify_military_takes_precedence_over_special() {
+        let addr = crate::icao::SPECIAL_WATCHLIST[0];
+        assert_eq!(
+            classify_from_profile(Some(300.0), Some(35000), true, false, Some(addr), None),
+            CAT_MILITARY
+        );
+    }
+
+    #[test]
+    fn test_classify_aircraft_special_watchlisted_icao() {
+        let addr = crate::icao::SPECIAL_WATCHLIST[0];
+        assert_eq!(
+            classify_aircraft(0, 0, Some(300.0), Some(35000), false, false, Some(addr), None),
+            CAT_SPECIAL
+        );
+    }
+
     #[test]
     fn test_classify_cargo() {
         assert_eq!(
-            classify_from_profile(Some(300.0), Some(35000), false, Some("FDX123")),
+            classify_from_profile(Some(300.0), Some(35000), false, false, None, Some("FDX123")),
             CAT_CARGO
         );
     }
@@ -289,7 +668,7 @@ mod tests {
     #[test]
     fn test_classify_altitude_only_jet() {
         assert_eq!(
-            classify_from_profile(None, Some(35000), false, None),
+            classify_from_profile(None, Some(35000), false, false, None, None),
             CAT_JET
         );
     }
@@ -297,14 +676,78 @@ mod tests {
     #[test]
     fn test_classify_altitude_only_prop() {
         assert_eq!(
-            classify_from_profile(None, Some(3000), false, None),
+            classify_from_profile(None, Some(3000), false, false, None, None),
             CAT_PROP
         );
     }
 
     #[test]
     fn test_classify_unknown() {
-        assert_eq!(classify_from_profile(None, None, false, None), CAT_UNKNOWN);
+        assert_eq!(
+            classify_from_profile(None, None, false, false, None, None),
+            CAT_UNKNOWN
+        );
+    }
+
+    #[test]
+    fn test_emitter_category_set_a_airborne() {
+        assert_eq!(classify_from_emitter_category(4, 1), CAT_PROP);
+        assert_eq!(classify_from_emitter_category(4, 2), CAT_TURBOPROP);
+        assert_eq!(classify_from_emitter_category(4, 3), CAT_JET);
+        assert_eq!(classify_from_emitter_category(4, 4), CAT_JET);
+        assert_eq!(classify_from_emitter_category(4, 5), CAT_JET);
+        assert_eq!(classify_from_emitter_category(4, 6), CAT_JET);
+        assert_eq!(classify_from_emitter_category(4, 7), CAT_HELICOPTER);
+    }
+
+    #[test]
+    fn test_emitter_category_set_b() {
+        assert_eq!(classify_from_emitter_category(3, 1), CAT_GLIDER);
+        assert_eq!(classify_from_emitter_category(3, 2), CAT_LIGHTER_THAN_AIR);
+        assert_eq!(classify_from_emitter_category(3, 3), CAT_PARACHUTIST);
+        assert_eq!(classify_from_emitter_category(3, 4), CAT_ULTRALIGHT);
+        assert_eq!(classify_from_emitter_category(3, 6), CAT_UAV);
+        assert_eq!(classify_from_emitter_category(3, 7), CAT_SPACE_VEHICLE);
+    }
+
+    #[test]
+    fn test_emitter_category_set_c_surface_vehicles() {
+        assert_eq!(classify_from_emitter_category(2, 1), CAT_SURFACE_VEHICLE);
+        assert_eq!(classify_from_emitter_category(2, 2), CAT_SURFACE_VEHICLE);
+        // Obstacles (3-7) have no flight category.
+        assert_eq!(classify_from_emitter_category(2, 3), CAT_UNKNOWN);
+    }
+
+    #[test]
+    fn test_emitter_category_no_info_or_reserved_set() {
+        assert_eq!(classify_from_emitter_category(4, 0), CAT_UNKNOWN);
+        assert_eq!(classify_from_emitter_category(1, 5), CAT_UNKNOWN);
+    }
+
+    #[test]
+    fn test_classify_aircraft_prefers_emitter_category() {
+        // A slow jet on approach would be misclassified as a prop by
+        // classify_from_profile alone; the reported category wins.
+        assert_eq!(
+            classify_aircraft(4, 5, Some(140.0), Some(2000), false, false, None, None),
+            CAT_JET
+        );
+    }
+
+    #[test]
+    fn test_classify_aircraft_falls_back_to_profile_when_category_unknown() {
+        assert_eq!(
+            classify_aircraft(4, 0, Some(300.0), Some(35000), false, false, None, None),
+            CAT_JET
+        );
+    }
+
+    #[test]
+    fn test_classify_aircraft_military_overrides_category() {
+        assert_eq!(
+            classify_aircraft(4, 5, Some(300.0), Some(35000), true, false, None, None),
+            CAT_MILITARY
+        );
     }
 
     #[test]
@@ -351,25 +794,206 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_nearest_airport_matches_brute_force_scan() {
+        // A query sitting right on a grid cell boundary is the case most
+        // likely to expose a ring-radius bug: a closer airport just across
+        // the edge should still be found.
+        let queries = [(35.4, -82.5, 50.0), (40.0, -75.0, 30.0), (0.0, 0.0, 500.0)];
+
+        for (lat, lon, max_nm) in queries {
+            let mut brute_force: Option<(String, String, f64)> = None;
+            for apt in all_airports() {
+                let dist = haversine_nm(lat, lon, apt.lat, apt.lon);
+                if dist < max_nm && brute_force.as_ref().is_none_or(|b| dist < b.2) {
+                    brute_force = Some((apt.icao.clone(), apt.name.clone(), dist));
+                }
+            }
+            assert_eq!(nearest_airport(lat, lon, max_nm), brute_force);
+        }
+    }
+
+    #[test]
+    fn test_nearest_airport_of_type_matches_brute_force_scan() {
+        let queries = [(35.4, -82.5, 50.0), (40.0, -75.0, 30.0), (0.0, 0.0, 500.0)];
+        let types: &[&str] = &["heliport", "seaport"];
+
+        for (lat, lon, max_nm) in queries {
+            let mut brute_force: Option<(String, String, f64)> = None;
+            for apt in all_airports() {
+                if !types.contains(&apt.airport_type.as_str()) {
+                    continue;
+                }
+                let dist = haversine_nm(lat, lon, apt.lat, apt.lon);
+                if dist < max_nm && brute_force.as_ref().is_none_or(|b| dist < b.2) {
+                    brute_force = Some((apt.icao.clone(), apt.name.clone(), dist));
+                }
+            }
+            assert_eq!(nearest_airport_of_type(lat, lon, max_nm, types), brute_force);
+        }
+    }
+
+    #[test]
+    fn test_nearest_airport_of_type_excludes_other_types() {
+        // Filtering to "major" only, within a tight radius of KAVL (a
+        // "small" airport), must never return KAVL itself.
+        let result = nearest_airport_of_type(35.4, -82.5, 5.0, &["major"]);
+        if let Some((code, _, _)) = result {
+            assert_ne!(code, "KAVL");
+        }
+    }
+
+    #[test]
+    fn test_suppresses_runway_phrasing_for_heliport_and_seaport() {
+        assert!(suppresses_runway_phrasing("heliport"));
+        assert!(suppresses_runway_phrasing("seaport"));
+    }
+
+    #[test]
+    fn test_suppresses_runway_phrasing_false_for_regular_airports() {
+        assert!(!suppresses_runway_phrasing("small"));
+        assert!(!suppresses_runway_phrasing("medium"));
+        assert!(!suppresses_runway_phrasing("major"));
+    }
+
     #[test]
     fn test_flight_phase_approaching() {
-        let phase = classify_flight_phase(35.45, -82.55, Some(5000), Some(-500), 30.0);
+        let phase = classify_flight_phase(35.45, -82.55, Some(5000), Some(-500), None, 30.0);
         assert!(phase.is_some());
         assert!(phase.unwrap().contains("Approaching KAVL"));
     }
 
     #[test]
     fn test_flight_phase_departing() {
-        let phase = classify_flight_phase(35.45, -82.55, Some(3000), Some(1000), 30.0);
+        let phase = classify_flight_phase(35.45, -82.55, Some(3000), Some(1000), None, 30.0);
         assert!(phase.is_some());
         assert!(phase.unwrap().contains("Departing KAVL"));
     }
 
+    fn test_airport_with_runways() -> Airport {
+        Airport {
+            icao: "KTST".to_string(),
+            name: "Test Field".to_string(),
+            lat: 0.0,
+            lon: 0.0,
+            elevation_ft: 0,
+            airport_type: "small".to_string(),
+            runways: vec![
+                Runway {
+                    id: "17".to_string(),
+                    heading_true_deg: 170.0,
+                    length_ft: 5000,
+                },
+                Runway {
+                    id: "35".to_string(),
+                    heading_true_deg: 350.0,
+                    length_ft: 5000,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_best_matching_runway_aligned() {
+        let airport = test_airport_with_runways();
+        let rwy = best_matching_runway(&airport, 165.0).unwrap();
+        assert_eq!(rwy.id, "17");
+    }
+
+    #[test]
+    fn test_best_matching_runway_picks_closest_end() {
+        let airport = test_airport_with_runways();
+        let rwy = best_matching_runway(&airport, 345.0).unwrap();
+        assert_eq!(rwy.id, "35");
+    }
+
+    #[test]
+    fn test_best_matching_runway_outside_tolerance() {
+        let airport = test_airport_with_runways();
+        // 90 degrees off either runway end — well outside the +/-30 degree
+        // tolerance.
+        assert!(best_matching_runway(&airport, 260.0).is_none());
+    }
+
+    #[test]
+    fn test_best_matching_runway_wraparound() {
+        let airport = test_airport_with_runways();
+        // 5 degrees from runway 35's 350 heading, crossing the 0/360
+        // boundary — confirms angular_diff doesn't overcount the wrap.
+        let rwy = best_matching_runway(&airport, 355.0).unwrap();
+        assert_eq!(rwy.id, "35");
+    }
+
+    #[test]
+    fn test_best_matching_runway_no_data() {
+        let airport = Airport {
+            icao: "KNOP".to_string(),
+            name: "No Runways".to_string(),
+            lat: 0.0,
+            lon: 0.0,
+            elevation_ft: 0,
+            airport_type: "small".to_string(),
+            runways: Vec::new(),
+        };
+        assert!(best_matching_runway(&airport, 170.0).is_none());
+    }
+
     #[test]
     fn test_classify_turboprop_speed_range() {
         assert_eq!(
-            classify_from_profile(Some(200.0), Some(15000), false, None),
+            classify_from_profile(Some(200.0), Some(15000), false, false, None, None),
             CAT_TURBOPROP
         );
     }
+
+    #[test]
+    fn test_search_airports_exact_icao_ranks_first() {
+        let results = search_airports("KAVL", 5);
+        assert_eq!(results.first().map(|a| a.icao.as_str()), Some("KAVL"));
+    }
+
+    #[test]
+    fn test_search_airports_is_case_insensitive() {
+        let results = search_airports("kavl", 5);
+        assert_eq!(results.first().map(|a| a.icao.as_str()), Some("KAVL"));
+    }
+
+    #[test]
+    fn test_search_airports_ident_prefix() {
+        // "KAV" matches KAVL's ICAO as a prefix, not an exact match.
+        let results = search_airports("KAV", 5);
+        assert!(results.iter().any(|a| a.icao == "KAVL"));
+    }
+
+    #[test]
+    fn test_search_airports_name_substring() {
+        let results = search_airports("asheville", 5);
+        assert!(results.iter().any(|a| a.icao == "KAVL"));
+    }
+
+    #[test]
+    fn test_search_airports_exact_ranks_above_prefix_and_substring() {
+        // An airport whose ICAO exactly matches the query should come
+        // before one that only matches as a prefix or name substring, even
+        // if the index happens to order them otherwise.
+        let results = search_airports("KAVL", 10);
+        let exact_pos = results.iter().position(|a| a.icao == "KAVL");
+        assert_eq!(exact_pos, Some(0));
+    }
+
+    #[test]
+    fn test_search_airports_respects_limit() {
+        let results = search_airports("K", 3);
+        assert!(results.len() <= 3);
+    }
+
+    #[test]
+    fn test_search_airports_empty_query_returns_nothing() {
+        assert!(search_airports("   ", 5).is_empty());
+    }
+
+    #[test]
+    fn test_search_airports_no_match_returns_empty() {
+        assert!(search_airports("zzznotanairport", 5).is_empty());
+    }
 }
@@ -3,10 +3,10 @@
 //! Each filter produces `FilterEvent` records. Dedup via `HashSet` prevents
 //! duplicate alerts for the same aircraft + event type.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::tracker::AircraftState;
-use crate::types::{icao_to_string, Icao};
+use crate::types::{icao_to_string, EmitterCategory, Icao};
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -17,15 +17,27 @@ pub const EVENT_EMERGENCY: &str = "emergency_squawk";
 pub const EVENT_RAPID_DESCENT: &str = "rapid_descent";
 pub const EVENT_LOW_ALTITUDE: &str = "low_altitude";
 pub const EVENT_GEOFENCE: &str = "geofence_entry";
+pub const EVENT_GEOFENCE_EXIT: &str = "geofence_exit";
 pub const EVENT_CIRCLING: &str = "circling";
 pub const EVENT_HOLDING: &str = "holding_pattern";
 pub const EVENT_PROXIMITY: &str = "proximity";
 pub const EVENT_UNUSUAL_ALTITUDE: &str = "unusual_altitude";
+pub const EVENT_CONFLICT: &str = "predicted_conflict";
+pub const EVENT_OVERHEAD: &str = "overhead_pass";
+pub const EVENT_CATEGORY: &str = "category_watch";
 
 const RAPID_DESCENT_THRESHOLD: i32 = -5000; // ft/min
 const LOW_ALTITUDE_THRESHOLD: i32 = 500; // ft
 const CIRCLING_WINDOW_SEC: f64 = 300.0; // 5 minutes
 const CIRCLING_MIN_HEADING_CHANGE: f64 = 360.0; // degrees cumulative
+const CPA_LOOKAHEAD_SEC: f64 = 120.0; // how far ahead to project tracks
+const OVERHEAD_ELEVATION_DEG: f64 = 80.0; // min elevation angle for an "overhead pass"
+const FT_PER_NM: f64 = 6076.12;
+// Generous closing speed for two aircraft headed directly at each other,
+// used to size the proximity grid's neighbor search so a predicted
+// conflict (check_cpa) isn't missed just because the pair is currently
+// farther apart than `proximity_nm`.
+const MAX_CLOSING_SPEED_KTS: f64 = 1000.0;
 
 // ---------------------------------------------------------------------------
 // Types
@@ -41,15 +53,124 @@ pub struct FilterEvent {
     pub lon: Option<f64>,
     pub altitude_ft: Option<i32>,
     pub timestamp: f64,
+    pub overhead: Option<OverheadInfo>,
 }
 
-/// Circular geofence zone.
-#[derive(Debug, Clone)]
-pub struct Geofence {
-    pub name: String,
+/// Receiver-relative azimuth/elevation/slant-range, attached to
+/// `EVENT_OVERHEAD` events (see `FilterEngine::check_overhead`).
+#[derive(Debug, Clone, Copy)]
+pub struct OverheadInfo {
+    pub azimuth_deg: f64,
+    pub elevation_deg: f64,
+    pub slant_range_nm: f64,
+}
+
+/// Ground station position used for azimuth/elevation/range calculations.
+#[derive(Debug, Clone, Copy)]
+pub struct ReceiverPosition {
     pub lat: f64,
     pub lon: f64,
-    pub radius_nm: f64,
+    pub altitude_ft: f64,
+}
+
+/// A monitored airspace zone: either a circle (center + radius) or an
+/// arbitrary polygon (e.g. loaded from GeoJSON) for real airspace/region
+/// boundaries.
+#[derive(Debug, Clone)]
+pub enum Geofence {
+    Circle {
+        name: String,
+        lat: f64,
+        lon: f64,
+        radius_nm: f64,
+    },
+    Polygon {
+        name: String,
+        /// `(lon, lat)` vertices, in order; the ring is treated as closed
+        /// (the last vertex connects back to the first).
+        ring: Vec<(f64, f64)>,
+    },
+}
+
+impl Geofence {
+    /// Build a circular geofence — the original, and still most common,
+    /// shape.
+    pub fn circle(name: impl Into<String>, lat: f64, lon: f64, radius_nm: f64) -> Self {
+        Geofence::Circle {
+            name: name.into(),
+            lat,
+            lon,
+            radius_nm,
+        }
+    }
+
+    /// Build a polygon geofence from a ring of `(lon, lat)` vertices.
+    pub fn polygon(name: impl Into<String>, ring: Vec<(f64, f64)>) -> Self {
+        Geofence::Polygon {
+            name: name.into(),
+            ring,
+        }
+    }
+
+    /// Build a polygon geofence from a GeoJSON `Polygon` geometry value
+    /// (only the exterior ring is used; holes are not supported).
+    pub fn from_geojson(name: impl Into<String>, geometry: &serde_json::Value) -> Result<Self, String> {
+        let rings = geometry["coordinates"]
+            .as_array()
+            .ok_or("Polygon is missing \"coordinates\"")?;
+        let exterior = rings.first().ok_or("polygon has no rings")?;
+        let points = exterior.as_array().ok_or("ring is not an array")?;
+        let mut ring = Vec::with_capacity(points.len());
+        for point in points {
+            let coord = point.as_array().ok_or("coordinate is not an array")?;
+            let lon = coord.first().and_then(|v| v.as_f64()).ok_or("bad lon")?;
+            let lat = coord.get(1).and_then(|v| v.as_f64()).ok_or("bad lat")?;
+            ring.push((lon, lat));
+        }
+        Ok(Geofence::polygon(name, ring))
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Geofence::Circle { name, .. } => name,
+            Geofence::Polygon { name, .. } => name,
+        }
+    }
+
+    /// Test whether `(lat, lon)` falls inside this geofence.
+    fn contains(&self, lat: f64, lon: f64) -> bool {
+        match self {
+            Geofence::Circle {
+                lat: clat,
+                lon: clon,
+                radius_nm,
+                ..
+            } => haversine_nm(lat, lon, *clat, *clon) <= *radius_nm,
+            Geofence::Polygon { ring, .. } => point_in_polygon(lon, lat, ring),
+        }
+    }
+}
+
+/// Ray-casting point-in-polygon test: count how many ring edges a
+/// horizontal ray cast from `(x, y)` crosses. Odd crossing count = inside.
+fn point_in_polygon(x: f64, y: f64, ring: &[(f64, f64)]) -> bool {
+    if ring.len() < 3 {
+        return false;
+    }
+    let mut inside = false;
+    let mut j = ring.len() - 1;
+    for i in 0..ring.len() {
+        let (xi, yi) = ring[i];
+        let (xj, yj) = ring[j];
+        if (yi > y) != (yj > y) {
+            let x_intersect = xj + (y - yj) / (yi - yj) * (xi - xj);
+            if x < x_intersect {
+                inside = !inside;
+            }
+        }
+        j = i;
+    }
+    inside
 }
 
 /// Emergency squawk lookup.
@@ -77,6 +198,101 @@ pub fn haversine_nm(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
     EARTH_RADIUS_NM * 2.0 * a.sqrt().atan2((1.0 - a).sqrt())
 }
 
+/// Initial great-circle bearing in degrees `[0, 360)`, from point 1 to
+/// point 2.
+pub fn bearing_deg(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let lat1 = lat1.to_radians();
+    let lat2 = lat2.to_radians();
+    let dlon = (lon2 - lon1).to_radians();
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    (y.atan2(x).to_degrees() + 360.0) % 360.0
+}
+
+/// Derive ground speed (knots) and track angle (degrees true) from two
+/// consecutive positions, each `(lat, lon, timestamp)`. A derived velocity
+/// estimate for aircraft that only emit position messages, complementing
+/// any decoded airborne-velocity (BDS 0,9) frame.
+///
+/// Distance uses `haversine_nm`'s spherical approximation rather than a
+/// full WGS84 ellipsoid geodesic — close enough over the short hops between
+/// consecutive ADS-B fixes that the ellipsoid's ~0.3% flattening doesn't
+/// move the speed estimate by more than typical CPR decode jitter already
+/// does. Returns `(0.0, 0.0)` if the timestamps don't advance.
+pub fn ground_track(prev: (f64, f64, f64), cur: (f64, f64, f64)) -> (f64, f64) {
+    let (prev_lat, prev_lon, prev_ts) = prev;
+    let (cur_lat, cur_lon, cur_ts) = cur;
+
+    let dt_hours = (cur_ts - prev_ts) / 3600.0;
+    if dt_hours <= 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let speed_kts = haversine_nm(prev_lat, prev_lon, cur_lat, cur_lon) / dt_hours;
+    let track_deg = bearing_deg(prev_lat, prev_lon, cur_lat, cur_lon);
+    (speed_kts, track_deg)
+}
+
+/// Bucket a lat/lon into a grid cell sized to `cell_size_nm`, for
+/// `FilterEngine::check_proximity`'s uniform grid index. Longitude cell
+/// width is widened by `1 / cos(lat)` so cells stay roughly square; this is
+/// evaluated per-point rather than globally, which is an approximation but
+/// good enough for picking neighbor cells to compare against.
+fn grid_cell(lat: f64, lon: f64, cell_size_nm: f64) -> (i64, i64) {
+    let cell_lat_deg = cell_size_nm / 60.0;
+    let cell_lon_deg = cell_lat_deg / lat.to_radians().cos().max(0.01);
+    (
+        (lat / cell_lat_deg).floor() as i64,
+        (lon / cell_lon_deg).floor() as i64,
+    )
+}
+
+/// Geodetic (spherical-earth) to ECEF, in feet.
+fn geodetic_to_ecef(lat_deg: f64, lon_deg: f64, altitude_ft: f64) -> (f64, f64, f64) {
+    let lat = lat_deg.to_radians();
+    let lon = lon_deg.to_radians();
+    let r = EARTH_RADIUS_NM * FT_PER_NM + altitude_ft;
+    (
+        r * lat.cos() * lon.cos(),
+        r * lat.cos() * lon.sin(),
+        r * lat.sin(),
+    )
+}
+
+/// Azimuth (degrees, [0, 360)), elevation (degrees) and slant range (nm) of
+/// a point as seen from `receiver`, via an ENU projection.
+fn azimuth_elevation_range(
+    receiver: &ReceiverPosition,
+    lat_deg: f64,
+    lon_deg: f64,
+    altitude_ft: f64,
+) -> (f64, f64, f64) {
+    let (rx, ry, rz) = geodetic_to_ecef(receiver.lat, receiver.lon, receiver.altitude_ft);
+    let (ax, ay, az) = geodetic_to_ecef(lat_deg, lon_deg, altitude_ft);
+    let d = (ax - rx, ay - ry, az - rz);
+
+    let lat = receiver.lat.to_radians();
+    let lon = receiver.lon.to_radians();
+    let east = (-lon.sin(), lon.cos(), 0.0);
+    let north = (
+        -lat.sin() * lon.cos(),
+        -lat.sin() * lon.sin(),
+        lat.cos(),
+    );
+    let up = (lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin());
+
+    let dot = |v: (f64, f64, f64)| d.0 * v.0 + d.1 * v.1 + d.2 * v.2;
+    let d_east = dot(east);
+    let d_north = dot(north);
+    let d_up = dot(up);
+
+    let elevation_deg = d_up.atan2(d_east.hypot(d_north)).to_degrees();
+    let azimuth_deg = (d_east.atan2(d_north).to_degrees() + 360.0) % 360.0;
+    let slant_range_nm = (d.0 * d.0 + d.1 * d.1 + d.2 * d.2).sqrt() / FT_PER_NM;
+
+    (azimuth_deg, elevation_deg, slant_range_nm)
+}
+
 // ---------------------------------------------------------------------------
 // Filter Engine
 // ---------------------------------------------------------------------------
@@ -91,7 +307,24 @@ pub struct FilterEngine {
     pub rapid_descent_fpm: i32,
     pub proximity_nm: f64,
     pub proximity_ft: i32,
+    /// Grid cell size, in nm, used to bucket aircraft before proximity
+    /// comparisons (see `check_proximity`). Defaults to `proximity_nm`;
+    /// widen it near the poles where a degree of longitude shrinks.
+    pub cell_size_nm: f64,
+    pub cpa_lookahead_sec: f64,
+    pub receiver: Option<ReceiverPosition>,
+    pub overhead_elevation_deg: f64,
+    /// Emitter categories to alert on, e.g. a helicopter watch of just
+    /// `[Rotorcraft]`. Empty means no watch-list configured.
+    pub category_watch: Vec<EmitterCategory>,
+    /// Emitter categories to suppress from `check_low_altitude` and
+    /// `check_circling`, e.g. ignoring large/heavy fixed-wing traffic.
+    pub category_ignore: Vec<EmitterCategory>,
     emitted: HashSet<(String, String)>,
+    /// (icao, fence name) -> entry timestamp, for aircraft currently inside
+    /// a geofence. Drives `EVENT_GEOFENCE`/`EVENT_GEOFENCE_EXIT` lifecycle
+    /// and dwell-time reporting.
+    geofence_state: HashMap<(String, String), f64>,
 }
 
 impl FilterEngine {
@@ -102,7 +335,14 @@ impl FilterEngine {
             rapid_descent_fpm: RAPID_DESCENT_THRESHOLD,
             proximity_nm: 5.0,
             proximity_ft: 1000,
+            cell_size_nm: 5.0,
+            cpa_lookahead_sec: CPA_LOOKAHEAD_SEC,
+            receiver: None,
+            overhead_elevation_deg: OVERHEAD_ELEVATION_DEG,
+            category_watch: Vec::new(),
+            category_ignore: Vec::new(),
             emitted: HashSet::new(),
+            geofence_state: HashMap::new(),
         }
     }
 
@@ -116,53 +356,98 @@ impl FilterEngine {
         self.check_circling(ac, &mut events);
         self.check_holding(ac, &mut events);
         self.check_geofences(ac, &mut events);
+        self.check_overhead(ac, &mut events);
+        self.check_category(ac, &mut events);
         events
     }
 
     /// Check all pairs for proximity alerts.
+    ///
+    /// Aircraft are bucketed into a uniform grid sized to `cell_size_nm`, so
+    /// each one is only compared against occupants of the square of cells
+    /// within its screening radius (the larger of `proximity_nm` and the
+    /// distance two aircraft can close within `cpa_lookahead_sec`) rather
+    /// than every other aircraft — this bounds the
+    /// work to near-linear for typical densities instead of O(n²). The
+    /// haversine distance and altitude gate still make the final call.
     pub fn check_proximity(&mut self, aircraft: &[&AircraftState]) -> Vec<FilterEvent> {
         let mut events = Vec::new();
         let positioned: Vec<&&AircraftState> =
             aircraft.iter().filter(|ac| ac.has_position()).collect();
 
-        for i in 0..positioned.len() {
-            for j in (i + 1)..positioned.len() {
-                let a = positioned[i];
-                let b = positioned[j];
-                let dist = haversine_nm(
-                    a.lat.unwrap(),
-                    a.lon.unwrap(),
-                    b.lat.unwrap(),
-                    b.lon.unwrap(),
-                );
-                if dist > self.proximity_nm {
-                    continue;
-                }
+        let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (idx, ac) in positioned.iter().enumerate() {
+            let key = grid_cell(ac.lat.unwrap(), ac.lon.unwrap(), self.cell_size_nm);
+            grid.entry(key).or_default().push(idx);
+        }
+
+        // How far a pair might need screening from — covers both the
+        // current-range proximity check and the CPA projection, expressed
+        // in cells so the ring of neighbors searched stays correct
+        // regardless of the configured cell size.
+        let cpa_closing_nm = MAX_CLOSING_SPEED_KTS * (self.cpa_lookahead_sec / 3600.0);
+        let screening_radius_nm = self.proximity_nm.max(cpa_closing_nm);
+        let rings = (screening_radius_nm / self.cell_size_nm).ceil().max(1.0) as i64;
 
-                if let (Some(alt_a), Some(alt_b)) = (a.altitude_ft, b.altitude_ft) {
-                    if (alt_a - alt_b).unsigned_abs() > self.proximity_ft as u32 {
-                        continue;
+        for i in 0..positioned.len() {
+            let a = positioned[i];
+            let (ci, cj) = grid_cell(a.lat.unwrap(), a.lon.unwrap(), self.cell_size_nm);
+
+            for di in -rings..=rings {
+                for dj in -rings..=rings {
+                    let neighbors = match grid.get(&(ci + di, cj + dj)) {
+                        Some(n) => n,
+                        None => continue,
+                    };
+                    for &j in neighbors {
+                        if j <= i {
+                            continue;
+                        }
+                        let b = positioned[j];
+                        self.check_proximity_pair(a, b, &mut events);
                     }
                 }
+            }
+        }
+        events
+    }
+
+    /// Haversine + altitude-gate proximity check and predicted-conflict
+    /// check for a single candidate pair (see `check_proximity`).
+    fn check_proximity_pair(
+        &mut self,
+        a: &AircraftState,
+        b: &AircraftState,
+        events: &mut Vec<FilterEvent>,
+    ) {
+        let dist = haversine_nm(
+            a.lat.unwrap(),
+            a.lon.unwrap(),
+            b.lat.unwrap(),
+            b.lon.unwrap(),
+        );
 
-                let icao_a = icao_to_string(&a.icao);
-                let icao_b = icao_to_string(&b.icao);
-                let mut pair = [icao_a.clone(), icao_b.clone()];
-                pair.sort();
-                let key = (format!("{}:{}", pair[0], pair[1]), EVENT_PROXIMITY.to_string());
-                if self.emitted.contains(&key) {
-                    continue;
+        let icao_a = icao_to_string(&a.icao);
+        let icao_b = icao_to_string(&b.icao);
+        let mut pair = [icao_a.clone(), icao_b.clone()];
+        pair.sort();
+        let pair_key = format!("{}:{}", pair[0], pair[1]);
+
+        let in_range = dist <= self.proximity_nm
+            && match (a.altitude_ft, b.altitude_ft) {
+                (Some(alt_a), Some(alt_b)) => {
+                    (alt_a - alt_b).unsigned_abs() <= self.proximity_ft as u32
                 }
+                _ => true,
+            };
+
+        if in_range {
+            let key = (pair_key.clone(), EVENT_PROXIMITY.to_string());
+            if !self.emitted.contains(&key) {
                 self.emitted.insert(key);
 
-                let label_a = a
-                    .callsign
-                    .as_deref()
-                    .unwrap_or(&icao_a);
-                let label_b = b
-                    .callsign
-                    .as_deref()
-                    .unwrap_or(&icao_b);
+                let label_a = a.callsign.as_deref().unwrap_or(&icao_a);
+                let label_b = b.callsign.as_deref().unwrap_or(&icao_b);
 
                 events.push(FilterEvent {
                     icao: a.icao,
@@ -175,16 +460,96 @@ impl FilterEngine {
                     lon: a.lon,
                     altitude_ft: a.altitude_ft,
                     timestamp: a.last_seen,
+                    overhead: None,
                 });
             }
         }
-        events
+
+        if let Some(event) = self.check_cpa(a, b, &pair_key, &icao_a, &icao_b) {
+            events.push(event);
+        }
+    }
+
+    /// Project a pair of tracks forward (flat-earth, constant ground speed
+    /// and heading) and emit `EVENT_CONFLICT` if the predicted closest point
+    /// of approach, within `cpa_lookahead_sec`, is inside the proximity
+    /// separation minima.
+    fn check_cpa(
+        &mut self,
+        a: &AircraftState,
+        b: &AircraftState,
+        pair_key: &str,
+        icao_a: &str,
+        icao_b: &str,
+    ) -> Option<FilterEvent> {
+        let (gs_a, hdg_a, alt_a) = (a.speed_kts?, a.heading_deg?, a.altitude_ft?);
+        let (gs_b, hdg_b, alt_b) = (b.speed_kts?, b.heading_deg?, b.altitude_ft?);
+        let vr_a = a.vertical_rate_fpm.unwrap_or(0);
+        let vr_b = b.vertical_rate_fpm.unwrap_or(0);
+
+        // Local flat-earth frame centered on `a`, in nautical miles.
+        let lat_mid = a.lat.unwrap().to_radians();
+        let r_east = (b.lon.unwrap() - a.lon.unwrap()) * lat_mid.cos() * 60.0;
+        let r_north = (b.lat.unwrap() - a.lat.unwrap()) * 60.0;
+
+        // Ground speed/heading to a velocity vector, in nm/min.
+        let va_east = gs_a / 60.0 * hdg_a.to_radians().sin();
+        let va_north = gs_a / 60.0 * hdg_a.to_radians().cos();
+        let vb_east = gs_b / 60.0 * hdg_b.to_radians().sin();
+        let vb_north = gs_b / 60.0 * hdg_b.to_radians().cos();
+        let v_east = vb_east - va_east;
+        let v_north = vb_north - va_north;
+
+        let v_dot_v = v_east * v_east + v_north * v_north;
+        let lookahead_min = self.cpa_lookahead_sec / 60.0;
+        let t_cpa = if v_dot_v > 0.0 {
+            let r_dot_v = r_east * v_east + r_north * v_north;
+            (-r_dot_v / v_dot_v).clamp(0.0, lookahead_min)
+        } else {
+            0.0
+        };
+
+        let miss_east = r_east + v_east * t_cpa;
+        let miss_north = r_north + v_north * t_cpa;
+        let horizontal_nm = (miss_east * miss_east + miss_north * miss_north).sqrt();
+
+        let alt_a_t = alt_a + (vr_a as f64 * t_cpa) as i32;
+        let alt_b_t = alt_b + (vr_b as f64 * t_cpa) as i32;
+        let vertical_ft = (alt_a_t - alt_b_t).unsigned_abs();
+
+        if horizontal_nm > self.proximity_nm || vertical_ft > self.proximity_ft as u32 {
+            return None;
+        }
+
+        let key = (pair_key.to_string(), EVENT_CONFLICT.to_string());
+        if self.emitted.contains(&key) {
+            return None;
+        }
+        self.emitted.insert(key);
+
+        let label_a = a.callsign.as_deref().unwrap_or(icao_a);
+        let label_b = b.callsign.as_deref().unwrap_or(icao_b);
+
+        Some(FilterEvent {
+            icao: a.icao,
+            event_type: EVENT_CONFLICT,
+            description: format!(
+                "Predicted conflict: {} and {} within {:.1} nm / {} ft in {:.0}s (t_cpa)",
+                label_a, label_b, horizontal_nm, vertical_ft, t_cpa * 60.0
+            ),
+            lat: a.lat,
+            lon: a.lon,
+            altitude_ft: a.altitude_ft,
+            timestamp: a.last_seen,
+            overhead: None,
+        })
     }
 
     /// Clear emitted events for a pruned aircraft.
     pub fn clear(&mut self, icao: &Icao) {
         let icao_str = icao_to_string(icao);
         self.emitted.retain(|k| !k.0.contains(&icao_str));
+        self.geofence_state.retain(|k, _| k.0 != icao_str);
     }
 
     fn emit(&mut self, event: FilterEvent) -> Option<FilterEvent> {
@@ -213,6 +578,7 @@ impl FilterEngine {
             lon: ac.lon,
             altitude_ft: ac.altitude_ft,
             timestamp: ac.last_seen,
+            overhead: None,
         }) {
             events.push(e);
         }
@@ -237,6 +603,7 @@ impl FilterEngine {
             lon: ac.lon,
             altitude_ft: ac.altitude_ft,
             timestamp: ac.last_seen,
+            overhead: None,
         }) {
             events.push(e);
         }
@@ -267,12 +634,26 @@ impl FilterEngine {
             lon: ac.lon,
             altitude_ft: ac.altitude_ft,
             timestamp: ac.last_seen,
+            overhead: None,
         }) {
             events.push(e);
         }
     }
 
+    /// True if `ac`'s emitter category is on `category_ignore` — used to
+    /// suppress low-altitude/circling alerts for routine traffic (e.g. a
+    /// helicopter watch that ignores large/heavy fixed-wing).
+    fn category_ignored(&self, ac: &AircraftState) -> bool {
+        match ac.category {
+            Some(cat) => self.category_ignore.contains(&cat),
+            None => false,
+        }
+    }
+
     fn check_low_altitude(&mut self, ac: &AircraftState, events: &mut Vec<FilterEvent>) {
+        if self.category_ignored(ac) {
+            return;
+        }
         let alt = match ac.altitude_ft {
             Some(a) if a > 0 && a < self.low_altitude_ft => a,
             _ => return,
@@ -287,12 +668,16 @@ impl FilterEngine {
             lon: ac.lon,
             altitude_ft: ac.altitude_ft,
             timestamp: ac.last_seen,
+            overhead: None,
         }) {
             events.push(e);
         }
     }
 
     fn check_circling(&mut self, ac: &AircraftState, events: &mut Vec<FilterEvent>) {
+        if self.category_ignored(ac) {
+            return;
+        }
         if ac.heading_history.len() < 4 {
             return;
         }
@@ -338,6 +723,7 @@ impl FilterEngine {
             lon: ac.lon,
             altitude_ft: ac.altitude_ft,
             timestamp: ac.last_seen,
+            overhead: None,
         }) {
             events.push(e);
         }
@@ -411,6 +797,7 @@ impl FilterEngine {
             lon: ac.lon,
             altitude_ft: ac.altitude_ft,
             timestamp: ac.last_seen,
+            overhead: None,
         }) {
             events.push(e);
         }
@@ -421,38 +808,122 @@ impl FilterEngine {
             return;
         }
 
+        let icao_str = icao_to_string(&ac.icao);
+        let label = ac.callsign.as_deref().unwrap_or(&icao_str);
+
         for fence in &self.geofences {
-            let dist = haversine_nm(
-                ac.lat.unwrap(),
-                ac.lon.unwrap(),
-                fence.lat,
-                fence.lon,
-            );
-            if dist > fence.radius_nm {
-                continue;
+            let inside = fence.contains(ac.lat.unwrap(), ac.lon.unwrap());
+            let state_key = (icao_str.clone(), fence.name().to_string());
+
+            match (inside, self.geofence_state.get(&state_key).copied()) {
+                (true, None) => {
+                    self.geofence_state.insert(state_key, ac.last_seen);
+                    events.push(FilterEvent {
+                        icao: ac.icao,
+                        event_type: EVENT_GEOFENCE,
+                        description: format!("Entered geofence '{}' - {}", fence.name(), label),
+                        lat: ac.lat,
+                        lon: ac.lon,
+                        altitude_ft: ac.altitude_ft,
+                        timestamp: ac.last_seen,
+                        overhead: None,
+                    });
+                }
+                (false, Some(entered_at)) => {
+                    self.geofence_state.remove(&state_key);
+                    let dwell_sec = ac.last_seen - entered_at;
+                    events.push(FilterEvent {
+                        icao: ac.icao,
+                        event_type: EVENT_GEOFENCE_EXIT,
+                        description: format!(
+                            "Exited geofence '{}' - {} after {:.0}s",
+                            fence.name(),
+                            label,
+                            dwell_sec
+                        ),
+                        lat: ac.lat,
+                        lon: ac.lon,
+                        altitude_ft: ac.altitude_ft,
+                        timestamp: ac.last_seen,
+                        overhead: None,
+                    });
+                }
+                // Already inside (dwelling) or already outside: no event.
+                (true, Some(_)) | (false, None) => {}
             }
+        }
+    }
 
-            let fence_key = format!("{}:{}", icao_to_string(&ac.icao), fence.name);
-            let key = (fence_key, EVENT_GEOFENCE.to_string());
-            if self.emitted.contains(&key) {
-                continue;
-            }
-            self.emitted.insert(key);
-
-            let icao_str = icao_to_string(&ac.icao);
-            let label = ac.callsign.as_deref().unwrap_or(&icao_str);
-            events.push(FilterEvent {
-                icao: ac.icao,
-                event_type: EVENT_GEOFENCE,
-                description: format!(
-                    "Entered geofence '{}' - {} at {:.1} nm",
-                    fence.name, label, dist
-                ),
-                lat: ac.lat,
-                lon: ac.lon,
-                altitude_ft: ac.altitude_ft,
-                timestamp: ac.last_seen,
-            });
+    /// Compute receiver-relative azimuth/elevation/slant-range and emit
+    /// `EVENT_OVERHEAD` for aircraft passing near-directly overhead the
+    /// configured receiver.
+    fn check_overhead(&mut self, ac: &AircraftState, events: &mut Vec<FilterEvent>) {
+        let receiver = match &self.receiver {
+            Some(r) => *r,
+            None => return,
+        };
+        if !ac.has_position() {
+            return;
+        }
+        let altitude_ft = ac.altitude_ft.unwrap_or(0) as f64;
+        let (azimuth_deg, elevation_deg, slant_range_nm) =
+            azimuth_elevation_range(&receiver, ac.lat.unwrap(), ac.lon.unwrap(), altitude_ft);
+
+        if elevation_deg < self.overhead_elevation_deg {
+            return;
+        }
+
+        let icao_str = icao_to_string(&ac.icao);
+        let label = ac.callsign.as_deref().unwrap_or(&icao_str);
+        if let Some(e) = self.emit(FilterEvent {
+            icao: ac.icao,
+            event_type: EVENT_OVERHEAD,
+            description: format!(
+                "Overhead pass: {} at {:.0}° elevation, {:.1} nm slant range",
+                label, elevation_deg, slant_range_nm
+            ),
+            lat: ac.lat,
+            lon: ac.lon,
+            altitude_ft: ac.altitude_ft,
+            timestamp: ac.last_seen,
+            overhead: Some(OverheadInfo {
+                azimuth_deg,
+                elevation_deg,
+                slant_range_nm,
+            }),
+        }) {
+            events.push(e);
+        }
+    }
+
+    /// Emit `EVENT_CATEGORY` when `ac`'s emitter category matches
+    /// `category_watch` (e.g. a helicopter watch that fires only on
+    /// `Rotorcraft`). No-op if `category_watch` is empty.
+    fn check_category(&mut self, ac: &AircraftState, events: &mut Vec<FilterEvent>) {
+        if self.category_watch.is_empty() {
+            return;
+        }
+        let cat = match ac.category {
+            Some(c) => c,
+            None => return,
+        };
+        if !self.category_watch.contains(&cat) {
+            return;
+        }
+
+        let icao_str = icao_to_string(&ac.icao);
+        let label = ac.callsign.as_deref().unwrap_or(&icao_str);
+        if let Some(e) = self.emit(FilterEvent {
+            icao: ac.icao,
+            event_type: EVENT_CATEGORY,
+            description: format!("Watched category '{}' detected - {}", cat, label),
+            lat: ac.lat,
+            lon: ac.lon,
+            altitude_ft: ac.altitude_ft,
+            timestamp: ac.last_seen,
+            overhead: None,
+        }) {
+            events.push(e);
         }
     }
 }
@@ -489,6 +960,28 @@ mod tests {
         assert!(d > 70.0 && d < 120.0, "AVL-CLT should be ~96nm, got {d}");
     }
 
+    #[test]
+    fn test_bearing_cardinal_directions() {
+        assert!((bearing_deg(35.0, -82.0, 36.0, -82.0) - 0.0).abs() < 0.1, "due north");
+        assert!((bearing_deg(35.0, -82.0, 35.0, -81.0) - 90.0).abs() < 1.0, "due east");
+        assert!((bearing_deg(35.0, -82.0, 34.0, -82.0) - 180.0).abs() < 0.1, "due south");
+        assert!((bearing_deg(35.0, -82.0, 35.0, -83.0) - 270.0).abs() < 1.0, "due west");
+    }
+
+    #[test]
+    fn test_ground_track_speed_and_direction() {
+        // Due north, ~60nm in one hour -> ~60kt, track 0.
+        let (speed, track) = ground_track((35.0, -82.0, 0.0), (36.0, -82.0, 3600.0));
+        assert!((speed - 60.0).abs() < 1.0, "got {speed}kt");
+        assert!(track.abs() < 0.5, "got {track} deg");
+    }
+
+    #[test]
+    fn test_ground_track_zero_elapsed_time() {
+        let (speed, track) = ground_track((35.0, -82.0, 100.0), (36.0, -82.0, 100.0));
+        assert_eq!((speed, track), (0.0, 0.0));
+    }
+
     #[test]
     fn test_emergency_squawk() {
         assert_eq!(emergency_squawk("7500"), Some("Hijack"));
@@ -580,12 +1073,7 @@ mod tests {
     #[test]
     fn test_geofence() {
         let mut engine = FilterEngine::new();
-        engine.geofences.push(Geofence {
-            name: "test-zone".to_string(),
-            lat: 35.0,
-            lon: -82.0,
-            radius_nm: 10.0,
-        });
+        engine.geofences.push(Geofence::circle("test-zone", 35.0, -82.0, 10.0));
 
         let mut ac = make_ac([0x48, 0x40, 0xD6]);
         ac.lat = Some(35.01);
@@ -599,12 +1087,7 @@ mod tests {
     #[test]
     fn test_geofence_outside() {
         let mut engine = FilterEngine::new();
-        engine.geofences.push(Geofence {
-            name: "test-zone".to_string(),
-            lat: 35.0,
-            lon: -82.0,
-            radius_nm: 1.0,
-        });
+        engine.geofences.push(Geofence::circle("test-zone", 35.0, -82.0, 1.0));
 
         let mut ac = make_ac([0x48, 0x40, 0xD6]);
         ac.lat = Some(36.0); // ~60nm away
@@ -615,6 +1098,42 @@ mod tests {
         assert!(!events.iter().any(|e| e.event_type == EVENT_GEOFENCE));
     }
 
+    #[test]
+    fn test_geofence_exit_reports_dwell_time() {
+        let mut engine = FilterEngine::new();
+        engine.geofences.push(Geofence::circle("test-zone", 35.0, -82.0, 10.0));
+
+        let mut ac = make_ac([0x48, 0x40, 0xD6]);
+        ac.lat = Some(35.01);
+        ac.lon = Some(-82.01);
+        ac.last_seen = 1.0;
+        let entry = engine.check(&ac);
+        assert!(entry.iter().any(|e| e.event_type == EVENT_GEOFENCE));
+
+        // Still inside on the next update: no repeat entry, no exit.
+        ac.last_seen = 30.0;
+        let dwelling = engine.check(&ac);
+        assert!(!dwelling.iter().any(|e| e.event_type == EVENT_GEOFENCE));
+        assert!(!dwelling.iter().any(|e| e.event_type == EVENT_GEOFENCE_EXIT));
+
+        // Leaves the fence after 99 seconds total.
+        ac.lat = Some(40.0);
+        ac.last_seen = 100.0;
+        let exit = engine.check(&ac);
+        let exit_event = exit
+            .iter()
+            .find(|e| e.event_type == EVENT_GEOFENCE_EXIT)
+            .expect("leaving the fence should emit EVENT_GEOFENCE_EXIT");
+        assert!(exit_event.description.contains("99"));
+
+        // Re-entering later re-arms the entry event.
+        ac.lat = Some(35.01);
+        ac.lon = Some(-82.01);
+        ac.last_seen = 200.0;
+        let reentry = engine.check(&ac);
+        assert!(reentry.iter().any(|e| e.event_type == EVENT_GEOFENCE));
+    }
+
     #[test]
     fn test_circling_detection() {
         let mut engine = FilterEngine::new();
@@ -655,6 +1174,52 @@ mod tests {
         assert!(events.iter().any(|e| e.event_type == EVENT_PROXIMITY));
     }
 
+    #[test]
+    fn test_proximity_alert_across_grid_cells() {
+        // Same offset as `test_proximity_alert`, but anchored so the pair
+        // straddles the 40°N grid cell boundary (lat cells 2399 and 2400 at
+        // 1 nm cells) - exercises the neighbor-cell search, not just
+        // same-cell hits.
+        let mut engine = FilterEngine::new();
+        engine.cell_size_nm = 1.0;
+
+        let mut a = make_ac([0x01, 0x02, 0x03]);
+        a.lat = Some(39.995);
+        a.lon = Some(-75.0);
+        a.altitude_ft = Some(10000);
+        a.last_seen = 1.0;
+
+        let mut b = make_ac([0x04, 0x05, 0x06]);
+        b.lat = Some(40.005);
+        b.lon = Some(-75.01);
+        b.altitude_ft = Some(10200);
+        b.last_seen = 1.0;
+
+        let events = engine.check_proximity(&[&a, &b]);
+        assert!(events.iter().any(|e| e.event_type == EVENT_PROXIMITY));
+    }
+
+    #[test]
+    fn test_no_proximity_alert_far_apart() {
+        let mut engine = FilterEngine::new();
+        engine.cell_size_nm = 1.0;
+
+        let mut a = make_ac([0x01, 0x02, 0x03]);
+        a.lat = Some(40.0);
+        a.lon = Some(-75.0);
+        a.altitude_ft = Some(10000);
+        a.last_seen = 1.0;
+
+        let mut b = make_ac([0x04, 0x05, 0x06]);
+        b.lat = Some(45.0); // far outside proximity_nm and any CPA closing bound
+        b.lon = Some(-75.0);
+        b.altitude_ft = Some(10000);
+        b.last_seen = 1.0;
+
+        let events = engine.check_proximity(&[&a, &b]);
+        assert!(!events.iter().any(|e| e.event_type == EVENT_PROXIMITY));
+    }
+
     #[test]
     fn test_clear_emitted() {
         let mut engine = FilterEngine::new();
@@ -668,4 +1233,269 @@ mod tests {
         let events = engine.check(&ac); // should emit again
         assert!(events.iter().any(|e| e.event_type == EVENT_MILITARY));
     }
+
+    #[test]
+    fn test_predicted_conflict() {
+        let mut engine = FilterEngine::new();
+
+        // Two aircraft 10nm apart (outside current proximity minima), on
+        // converging headings that will close to well under 1nm within the
+        // lookahead window.
+        let mut a = make_ac([0x01, 0x02, 0x03]);
+        a.lat = Some(35.0);
+        a.lon = Some(-82.0);
+        a.altitude_ft = Some(10000);
+        a.speed_kts = Some(400.0);
+        a.heading_deg = Some(90.0); // east
+        a.vertical_rate_fpm = Some(0);
+        a.last_seen = 1.0;
+
+        let mut b = make_ac([0x04, 0x05, 0x06]);
+        b.lat = Some(35.0);
+        b.lon = Some(-81.8);
+        b.altitude_ft = Some(10000);
+        b.speed_kts = Some(400.0);
+        b.heading_deg = Some(270.0); // west, head-on with `a`
+        b.vertical_rate_fpm = Some(0);
+        b.last_seen = 1.0;
+
+        let events = engine.check_proximity(&[&a, &b]);
+        assert!(
+            events.iter().any(|e| e.event_type == EVENT_CONFLICT),
+            "head-on converging aircraft should trigger a predicted conflict"
+        );
+    }
+
+    #[test]
+    fn test_predicted_conflict_diverging_no_alert() {
+        let mut engine = FilterEngine::new();
+
+        // Same separation as above, but headed directly apart.
+        let mut a = make_ac([0x01, 0x02, 0x03]);
+        a.lat = Some(35.0);
+        a.lon = Some(-82.0);
+        a.altitude_ft = Some(10000);
+        a.speed_kts = Some(400.0);
+        a.heading_deg = Some(270.0); // west, away from `b`
+        a.vertical_rate_fpm = Some(0);
+        a.last_seen = 1.0;
+
+        let mut b = make_ac([0x04, 0x05, 0x06]);
+        b.lat = Some(35.0);
+        b.lon = Some(-81.8);
+        b.altitude_ft = Some(10000);
+        b.speed_kts = Some(400.0);
+        b.heading_deg = Some(90.0); // east, away from `a`
+        b.vertical_rate_fpm = Some(0);
+        b.last_seen = 1.0;
+
+        let events = engine.check_proximity(&[&a, &b]);
+        assert!(!events.iter().any(|e| e.event_type == EVENT_CONFLICT));
+    }
+
+    #[test]
+    fn test_predicted_conflict_dedup() {
+        let mut engine = FilterEngine::new();
+
+        let mut a = make_ac([0x01, 0x02, 0x03]);
+        a.lat = Some(35.0);
+        a.lon = Some(-82.0);
+        a.altitude_ft = Some(10000);
+        a.speed_kts = Some(400.0);
+        a.heading_deg = Some(90.0);
+        a.vertical_rate_fpm = Some(0);
+        a.last_seen = 1.0;
+
+        let mut b = make_ac([0x04, 0x05, 0x06]);
+        b.lat = Some(35.0);
+        b.lon = Some(-81.8);
+        b.altitude_ft = Some(10000);
+        b.speed_kts = Some(400.0);
+        b.heading_deg = Some(270.0);
+        b.vertical_rate_fpm = Some(0);
+        b.last_seen = 1.0;
+
+        let first = engine.check_proximity(&[&a, &b]);
+        assert!(first.iter().any(|e| e.event_type == EVENT_CONFLICT));
+
+        let second = engine.check_proximity(&[&a, &b]);
+        assert!(!second.iter().any(|e| e.event_type == EVENT_CONFLICT));
+    }
+
+    #[test]
+    fn test_overhead_pass() {
+        let mut engine = FilterEngine::new();
+        engine.receiver = Some(ReceiverPosition {
+            lat: 35.0,
+            lon: -82.0,
+            altitude_ft: 0.0,
+        });
+
+        let mut ac = make_ac([0x01, 0x02, 0x03]);
+        ac.lat = Some(35.0001);
+        ac.lon = Some(-82.0);
+        ac.altitude_ft = Some(10000);
+        ac.last_seen = 1.0;
+
+        let events = engine.check(&ac);
+        let event = events
+            .iter()
+            .find(|e| e.event_type == EVENT_OVERHEAD)
+            .expect("near-directly-overhead aircraft should trigger EVENT_OVERHEAD");
+        let info = event.overhead.expect("overhead event should carry OverheadInfo");
+        assert!(info.elevation_deg > engine.overhead_elevation_deg);
+        assert!(info.slant_range_nm > 0.0);
+    }
+
+    #[test]
+    fn test_no_overhead_pass_on_horizon() {
+        let mut engine = FilterEngine::new();
+        engine.receiver = Some(ReceiverPosition {
+            lat: 35.0,
+            lon: -82.0,
+            altitude_ft: 0.0,
+        });
+
+        let mut ac = make_ac([0x01, 0x02, 0x03]);
+        ac.lat = Some(35.0);
+        ac.lon = Some(-80.0); // far off to the side, low elevation angle
+        ac.altitude_ft = Some(10000);
+        ac.last_seen = 1.0;
+
+        let events = engine.check(&ac);
+        assert!(!events.iter().any(|e| e.event_type == EVENT_OVERHEAD));
+    }
+
+    #[test]
+    fn test_no_overhead_pass_without_receiver() {
+        let mut engine = FilterEngine::new();
+
+        let mut ac = make_ac([0x01, 0x02, 0x03]);
+        ac.lat = Some(35.0001);
+        ac.lon = Some(-82.0);
+        ac.altitude_ft = Some(10000);
+        ac.last_seen = 1.0;
+
+        let events = engine.check(&ac);
+        assert!(!events.iter().any(|e| e.event_type == EVENT_OVERHEAD));
+    }
+
+    #[test]
+    fn test_polygon_geofence_inside() {
+        let mut engine = FilterEngine::new();
+        // A square roughly centered on (35.0, -82.0).
+        let ring = vec![
+            (-82.1, 34.9),
+            (-81.9, 34.9),
+            (-81.9, 35.1),
+            (-82.1, 35.1),
+        ];
+        engine.geofences.push(Geofence::polygon("test-square", ring));
+
+        let mut ac = make_ac([0x48, 0x40, 0xD6]);
+        ac.lat = Some(35.0);
+        ac.lon = Some(-82.0);
+        ac.last_seen = 1.0;
+
+        let events = engine.check(&ac);
+        assert!(events.iter().any(|e| e.event_type == EVENT_GEOFENCE));
+    }
+
+    #[test]
+    fn test_polygon_geofence_outside() {
+        let mut engine = FilterEngine::new();
+        let ring = vec![
+            (-82.1, 34.9),
+            (-81.9, 34.9),
+            (-81.9, 35.1),
+            (-82.1, 35.1),
+        ];
+        engine.geofences.push(Geofence::polygon("test-square", ring));
+
+        let mut ac = make_ac([0x48, 0x40, 0xD6]);
+        ac.lat = Some(40.0);
+        ac.lon = Some(-82.0);
+        ac.last_seen = 1.0;
+
+        let events = engine.check(&ac);
+        assert!(!events.iter().any(|e| e.event_type == EVENT_GEOFENCE));
+    }
+
+    #[test]
+    fn test_geofence_from_geojson() {
+        let geometry: serde_json::Value = serde_json::from_str(
+            r#"{"type": "Polygon", "coordinates": [[[-82.1, 34.9], [-81.9, 34.9], [-81.9, 35.1], [-82.1, 35.1], [-82.1, 34.9]]]}"#,
+        )
+        .unwrap();
+        let fence = Geofence::from_geojson("test-square", &geometry).unwrap();
+
+        let mut engine = FilterEngine::new();
+        engine.geofences.push(fence);
+
+        let mut ac = make_ac([0x48, 0x40, 0xD6]);
+        ac.lat = Some(35.0);
+        ac.lon = Some(-82.0);
+        ac.last_seen = 1.0;
+
+        let events = engine.check(&ac);
+        assert!(events.iter().any(|e| e.event_type == EVENT_GEOFENCE));
+    }
+
+    #[test]
+    fn test_category_watch_matches() {
+        let mut engine = FilterEngine::new();
+        engine.category_watch.push(EmitterCategory::Rotorcraft);
+
+        let mut ac = make_ac([0x48, 0x40, 0xD6]);
+        ac.category = Some(EmitterCategory::Rotorcraft);
+        ac.last_seen = 1.0;
+
+        let events = engine.check(&ac);
+        assert!(events.iter().any(|e| e.event_type == EVENT_CATEGORY));
+    }
+
+    #[test]
+    fn test_category_watch_no_match() {
+        let mut engine = FilterEngine::new();
+        engine.category_watch.push(EmitterCategory::Rotorcraft);
+
+        let mut ac = make_ac([0x48, 0x40, 0xD6]);
+        ac.category = Some(EmitterCategory::Heavy);
+        ac.last_seen = 1.0;
+
+        let events = engine.check(&ac);
+        assert!(!events.iter().any(|e| e.event_type == EVENT_CATEGORY));
+    }
+
+    #[test]
+    fn test_category_ignore_suppresses_low_altitude() {
+        let mut engine = FilterEngine::new();
+        engine.category_ignore.push(EmitterCategory::Heavy);
+
+        let mut ac = make_ac([0x48, 0x40, 0xD6]);
+        ac.category = Some(EmitterCategory::Heavy);
+        ac.altitude_ft = Some(300);
+        ac.last_seen = 1.0;
+
+        let events = engine.check(&ac);
+        assert!(!events.iter().any(|e| e.event_type == EVENT_LOW_ALTITUDE));
+    }
+
+    #[test]
+    fn test_category_ignore_suppresses_circling() {
+        let mut engine = FilterEngine::new();
+        engine.category_ignore.push(EmitterCategory::Heavy);
+
+        let mut ac = make_ac([0x48, 0x40, 0xD6]);
+        ac.category = Some(EmitterCategory::Heavy);
+        ac.last_seen = 300.0;
+        for i in 0..40 {
+            let t = 1.0 + i as f64 * 7.5;
+            let h = (i as f64 * 10.0) % 360.0;
+            ac.heading_history.push((t, h));
+        }
+
+        let events = engine.check(&ac);
+        assert!(!events.iter().any(|e| e.event_type == EVENT_CIRCLING));
+    }
 }
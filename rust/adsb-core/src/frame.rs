@@ -10,7 +10,7 @@
 
 use std::collections::HashMap;
 
-use crate::crc;
+use crate::crc::{self, ErrorCorrector};
 use crate::types::{df_info, hex_decode, Icao};
 
 // DFs where ICAO is explicit in bytes 1-3
@@ -23,45 +23,106 @@ const DF_RESIDUAL_ICAO: &[u8] = &[0, 4, 5, 16, 20, 21];
 // ICAO cache
 // ---------------------------------------------------------------------------
 
+/// How many times a cached ICAO has been confirmed by a validated frame.
+///
+/// A single noisy DF11/17/18 frame whose CRC happens to pass can otherwise
+/// seed the cache and then validate a stream of bogus residual-recovered
+/// DF0/4/5/16/20/21 frames — mirrors the whitelist-confirmation approach
+/// mature decoders use to suppress phantom aircraft.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confidence {
+    /// Not currently in the cache — never seen, or its entry expired.
+    Unconfirmed,
+    /// Seen at least once, but fewer than `min_confirmations` times.
+    Provisional,
+    /// Seen at least `min_confirmations` times within the TTL window.
+    Trusted,
+}
+
+struct IcaoEntry {
+    first_seen: f64,
+    last_seen: f64,
+    hits: u32,
+}
+
 /// Time-windowed cache of validated ICAO addresses.
 ///
 /// ICAOs are registered when seen in DF11/17/18 frames (explicit, CRC-validated).
 /// For DF0/4/5/16/20/21, the ICAO is recovered from the CRC residual — noise
 /// produces fake addresses. The cache rejects residual-recovered ICAOs not
-/// recently seen in a validated frame.
+/// recently seen in at least `min_confirmations` validated frames.
 pub struct IcaoCache {
     ttl: f64,
-    cache: HashMap<Icao, f64>, // icao -> last_seen timestamp
+    min_confirmations: u32,
+    cache: HashMap<Icao, IcaoEntry>,
 }
 
 impl IcaoCache {
+    /// `min_confirmations` defaults to 1 (a single hit confirms), matching
+    /// the prior single-hit behavior.
     pub fn new(ttl: f64) -> Self {
+        Self::with_min_confirmations(ttl, 1)
+    }
+
+    /// Require an ICAO be seen in `min_confirmations` validated frames
+    /// within the TTL window before `is_known` trusts it.
+    pub fn with_min_confirmations(ttl: f64, min_confirmations: u32) -> Self {
         IcaoCache {
             ttl,
+            min_confirmations: min_confirmations.max(1),
             cache: HashMap::new(),
         }
     }
 
-    /// Register a validated ICAO (from DF11/17/18).
+    /// Register a validated ICAO (from DF11/17/18), incrementing its hit
+    /// count and refreshing its last-seen timestamp.
     pub fn register(&mut self, icao: Icao, timestamp: f64) {
-        self.cache.insert(icao, timestamp);
+        let ttl = self.ttl;
+        self.cache
+            .entry(icao)
+            .and_modify(|entry| {
+                // Expired but not yet pruned: start a fresh count.
+                if timestamp - entry.last_seen > ttl {
+                    entry.first_seen = timestamp;
+                    entry.hits = 0;
+                }
+                entry.last_seen = timestamp;
+                entry.hits += 1;
+            })
+            .or_insert(IcaoEntry {
+                first_seen: timestamp,
+                last_seen: timestamp,
+                hits: 1,
+            });
     }
 
-    /// Check if an ICAO was recently seen in a validated frame.
+    /// Check if an ICAO has reached `Trusted` confidence within the TTL
+    /// window — i.e. seen in at least `min_confirmations` validated frames.
     pub fn is_known(&mut self, icao: &Icao, timestamp: f64) -> bool {
-        if let Some(&last_seen) = self.cache.get(icao) {
-            if timestamp - last_seen <= self.ttl {
-                return true;
-            }
+        self.confidence(icao, timestamp) == Confidence::Trusted
+    }
+
+    /// The confidence tier for `icao`, expiring its entry first if it has
+    /// aged out of the TTL window.
+    pub fn confidence(&mut self, icao: &Icao, timestamp: f64) -> Confidence {
+        let Some(entry) = self.cache.get(icao) else {
+            return Confidence::Unconfirmed;
+        };
+        if timestamp - entry.last_seen > self.ttl {
             self.cache.remove(icao);
+            return Confidence::Unconfirmed;
+        }
+        if entry.hits >= self.min_confirmations {
+            Confidence::Trusted
+        } else {
+            Confidence::Provisional
         }
-        false
     }
 
     /// Remove expired entries.
     pub fn prune(&mut self, now: f64) {
         let ttl = self.ttl;
-        self.cache.retain(|_, &mut last_seen| now - last_seen <= ttl);
+        self.cache.retain(|_, entry| now - entry.last_seen <= ttl);
     }
 
     pub fn len(&self) -> usize {
@@ -151,12 +212,15 @@ impl ModeFrame {
 /// Parse a hex string into a ModeFrame.
 ///
 /// `validate_icao`: if true, reject residual-recovered ICAOs not in cache.
+/// `corrector`: syndrome tables used to fix 1-2 bit CRC errors on DF17/18;
+/// pass `&crc::GLOBAL_CORRECTOR` unless the caller needs a private instance.
 pub fn parse_frame(
     hex_str: &str,
     timestamp: f64,
     signal_level: Option<f64>,
     validate_icao: bool,
     icao_cache: &mut IcaoCache,
+    corrector: &ErrorCorrector,
 ) -> Option<ModeFrame> {
     let hex_str = hex_str.trim();
 
@@ -188,7 +252,7 @@ pub fn parse_frame(
         // Attempt error correction for DF17/18 if CRC fails
         if !crc_ok && (df == 17 || df == 18) {
             let hex_upper = hex_str.to_uppercase();
-            if let Some(fixed_hex) = crc::try_fix(&hex_upper) {
+            if let Some(fixed_hex) = corrector.try_fix(&hex_upper) {
                 if let Some(fixed_raw) = hex_decode(&fixed_hex) {
                     raw = fixed_raw;
                     crc_ok = true;
@@ -240,7 +304,14 @@ pub fn parse_frame_uncached(
     signal_level: Option<f64>,
 ) -> Option<ModeFrame> {
     let mut cache = IcaoCache::new(60.0);
-    parse_frame(hex_str, timestamp, signal_level, false, &mut cache)
+    parse_frame(
+        hex_str,
+        timestamp,
+        signal_level,
+        false,
+        &mut cache,
+        &crc::GLOBAL_CORRECTOR,
+    )
 }
 
 // ---------------------------------------------------------------------------
@@ -329,6 +400,30 @@ mod tests {
         assert!(!cache.is_known(&icao, 62.0));
     }
 
+    #[test]
+    fn test_icao_cache_min_confirmations() {
+        let mut cache = IcaoCache::with_min_confirmations(60.0, 2);
+        let icao = [0x48, 0x40, 0xD6];
+
+        cache.register(icao, 1.0);
+        assert_eq!(cache.confidence(&icao, 2.0), Confidence::Provisional);
+        assert!(!cache.is_known(&icao, 2.0));
+
+        cache.register(icao, 2.0);
+        assert_eq!(cache.confidence(&icao, 3.0), Confidence::Trusted);
+        assert!(cache.is_known(&icao, 3.0));
+    }
+
+    #[test]
+    fn test_icao_cache_confidence_unconfirmed_when_expired() {
+        let mut cache = IcaoCache::new(10.0);
+        let icao = [0x01, 0x02, 0x03];
+
+        assert_eq!(cache.confidence(&icao, 0.0), Confidence::Unconfirmed);
+        cache.register(icao, 0.0);
+        assert_eq!(cache.confidence(&icao, 20.0), Confidence::Unconfirmed);
+    }
+
     #[test]
     fn test_icao_cache_prune() {
         let mut cache = IcaoCache::new(10.0);
@@ -351,6 +446,7 @@ mod tests {
             None,
             true,
             &mut cache,
+            &crc::GLOBAL_CORRECTOR,
         );
         assert!(frame.is_some());
 
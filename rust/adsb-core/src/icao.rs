@@ -13,6 +13,20 @@ struct CountryBlock {
     start: u32,
     end: u32,
     country: &'static str,
+    iso2: &'static str,
+    iso3: &'static str,
+}
+
+/// Structured country metadata for an ICAO address block.
+///
+/// Pairs the human-readable name with its ISO 3166-1 alpha-2/alpha-3 codes
+/// so callers can index into other datasets (flag emoji, sanctions lists)
+/// without string-matching on `name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountryInfo {
+    pub name: &'static str,
+    pub iso2: &'static str,
+    pub iso3: &'static str,
 }
 
 const COUNTRY_BLOCKS: &[CountryBlock] = &[
@@ -20,702 +34,1050 @@ const COUNTRY_BLOCKS: &[CountryBlock] = &[
         start: 0x004000,
         end: 0x0043FF,
         country: "Zimbabwe",
+        iso2: "ZW",
+        iso3: "ZWE",
     },
     CountryBlock {
         start: 0x006000,
         end: 0x006FFF,
         country: "Mozambique",
+        iso2: "MZ",
+        iso3: "MOZ",
     },
     CountryBlock {
         start: 0x008000,
         end: 0x00FFFF,
         country: "South Africa",
+        iso2: "ZA",
+        iso3: "ZAF",
     },
     CountryBlock {
         start: 0x010000,
         end: 0x017FFF,
         country: "Egypt",
+        iso2: "EG",
+        iso3: "EGY",
     },
     CountryBlock {
         start: 0x018000,
         end: 0x01FFFF,
         country: "Libya",
+        iso2: "LY",
+        iso3: "LBY",
     },
     CountryBlock {
         start: 0x020000,
         end: 0x027FFF,
         country: "Morocco",
+        iso2: "MA",
+        iso3: "MAR",
     },
     CountryBlock {
         start: 0x028000,
         end: 0x02FFFF,
         country: "Tunisia",
+        iso2: "TN",
+        iso3: "TUN",
     },
     CountryBlock {
         start: 0x030000,
         end: 0x0303FF,
         country: "Botswana",
+        iso2: "BW",
+        iso3: "BWA",
     },
     CountryBlock {
         start: 0x032000,
         end: 0x032FFF,
         country: "Burundi",
+        iso2: "BI",
+        iso3: "BDI",
     },
     CountryBlock {
         start: 0x034000,
         end: 0x034FFF,
         country: "Cameroon",
+        iso2: "CM",
+        iso3: "CMR",
     },
     CountryBlock {
         start: 0x038000,
         end: 0x038FFF,
         country: "Congo",
+        iso2: "CG",
+        iso3: "COG",
     },
     CountryBlock {
         start: 0x03E000,
         end: 0x03EFFF,
         country: "Ivory Coast",
+        iso2: "CI",
+        iso3: "CIV",
     },
     CountryBlock {
         start: 0x040000,
         end: 0x040FFF,
         country: "DR Congo",
+        iso2: "CD",
+        iso3: "COD",
     },
     CountryBlock {
         start: 0x042000,
         end: 0x042FFF,
         country: "Ethiopia",
+        iso2: "ET",
+        iso3: "ETH",
     },
     CountryBlock {
         start: 0x044000,
         end: 0x044FFF,
         country: "Equatorial Guinea",
+        iso2: "GQ",
+        iso3: "GNQ",
     },
     CountryBlock {
         start: 0x046000,
         end: 0x046FFF,
         country: "Gabon",
+        iso2: "GA",
+        iso3: "GAB",
     },
     CountryBlock {
         start: 0x048000,
         end: 0x048FFF,
         country: "Ghana",
+        iso2: "GH",
+        iso3: "GHA",
     },
     CountryBlock {
         start: 0x04A000,
         end: 0x04AFFF,
         country: "Guinea",
+        iso2: "GN",
+        iso3: "GIN",
     },
     CountryBlock {
         start: 0x04C000,
         end: 0x04CFFF,
         country: "Kenya",
+        iso2: "KE",
+        iso3: "KEN",
     },
     CountryBlock {
         start: 0x050000,
         end: 0x050FFF,
         country: "Liberia",
+        iso2: "LR",
+        iso3: "LBR",
     },
     CountryBlock {
         start: 0x054000,
         end: 0x054FFF,
         country: "Madagascar",
+        iso2: "MG",
+        iso3: "MDG",
     },
     CountryBlock {
         start: 0x058000,
         end: 0x058FFF,
         country: "Malawi",
+        iso2: "MW",
+        iso3: "MWI",
     },
     CountryBlock {
         start: 0x05A000,
         end: 0x05AFFF,
         country: "Mali",
+        iso2: "ML",
+        iso3: "MLI",
     },
     CountryBlock {
         start: 0x05C000,
         end: 0x05CFFF,
         country: "Mauritania",
+        iso2: "MR",
+        iso3: "MRT",
     },
     CountryBlock {
         start: 0x060000,
         end: 0x060FFF,
         country: "Niger",
+        iso2: "NE",
+        iso3: "NER",
     },
     CountryBlock {
         start: 0x062000,
         end: 0x062FFF,
         country: "Nigeria",
+        iso2: "NG",
+        iso3: "NGA",
     },
     CountryBlock {
         start: 0x064000,
         end: 0x064FFF,
         country: "Uganda",
+        iso2: "UG",
+        iso3: "UGA",
     },
     CountryBlock {
         start: 0x068000,
         end: 0x068FFF,
         country: "Senegal",
+        iso2: "SN",
+        iso3: "SEN",
     },
     CountryBlock {
         start: 0x06A000,
         end: 0x06AFFF,
         country: "Sierra Leone",
+        iso2: "SL",
+        iso3: "SLE",
     },
     CountryBlock {
         start: 0x06C000,
         end: 0x06CFFF,
         country: "Somalia",
+        iso2: "SO",
+        iso3: "SOM",
     },
     CountryBlock {
         start: 0x070000,
         end: 0x070FFF,
         country: "Sudan",
+        iso2: "SD",
+        iso3: "SDN",
     },
     CountryBlock {
         start: 0x074000,
         end: 0x074FFF,
         country: "Tanzania",
+        iso2: "TZ",
+        iso3: "TZA",
     },
     CountryBlock {
         start: 0x078000,
         end: 0x078FFF,
         country: "Chad",
+        iso2: "TD",
+        iso3: "TCD",
     },
     CountryBlock {
         start: 0x07C000,
         end: 0x07CFFF,
         country: "Zambia",
+        iso2: "ZM",
+        iso3: "ZMB",
     },
     CountryBlock {
         start: 0x080000,
         end: 0x080FFF,
         country: "Comoros",
+        iso2: "KM",
+        iso3: "COM",
     },
     CountryBlock {
         start: 0x084000,
         end: 0x084FFF,
         country: "Djibouti",
+        iso2: "DJ",
+        iso3: "DJI",
     },
     CountryBlock {
         start: 0x088000,
         end: 0x088FFF,
         country: "Eritrea",
+        iso2: "ER",
+        iso3: "ERI",
     },
     CountryBlock {
         start: 0x08A000,
         end: 0x08AFFF,
         country: "Gambia",
+        iso2: "GM",
+        iso3: "GMB",
     },
     CountryBlock {
         start: 0x08C000,
         end: 0x08CFFF,
         country: "Burkina Faso",
+        iso2: "BF",
+        iso3: "BFA",
     },
     CountryBlock {
         start: 0x098000,
         end: 0x098FFF,
         country: "Lesotho",
+        iso2: "LS",
+        iso3: "LSO",
     },
     CountryBlock {
         start: 0x09A000,
         end: 0x09AFFF,
         country: "Namibia",
+        iso2: "NA",
+        iso3: "NAM",
     },
     CountryBlock {
         start: 0x0A0000,
         end: 0x0A7FFF,
         country: "Algeria",
+        iso2: "DZ",
+        iso3: "DZA",
     },
     CountryBlock {
         start: 0x0C0000,
         end: 0x0C4FFF,
         country: "Angola",
+        iso2: "AO",
+        iso3: "AGO",
     },
     CountryBlock {
         start: 0x0C8000,
         end: 0x0C8FFF,
         country: "Rwanda",
+        iso2: "RW",
+        iso3: "RWA",
     },
     CountryBlock {
         start: 0x0CA000,
         end: 0x0CAFFF,
         country: "Togo",
+        iso2: "TG",
+        iso3: "TGO",
     },
     CountryBlock {
         start: 0x0CC000,
         end: 0x0CCFFF,
         country: "Benin",
+        iso2: "BJ",
+        iso3: "BEN",
     },
     CountryBlock {
         start: 0x0D0000,
         end: 0x0D7FFF,
         country: "Bahamas",
+        iso2: "BS",
+        iso3: "BHS",
     },
     CountryBlock {
         start: 0x0D8000,
         end: 0x0DFFFF,
         country: "Barbados",
+        iso2: "BB",
+        iso3: "BRB",
     },
     CountryBlock {
         start: 0x0E0000,
         end: 0x0E3FFF,
         country: "Belize",
+        iso2: "BZ",
+        iso3: "BLZ",
     },
     CountryBlock {
         start: 0x0E4000,
         end: 0x0E7FFF,
         country: "Colombia",
+        iso2: "CO",
+        iso3: "COL",
     },
     CountryBlock {
         start: 0x0E8000,
         end: 0x0EBFFF,
         country: "Costa Rica",
+        iso2: "CR",
+        iso3: "CRI",
     },
     CountryBlock {
         start: 0x0EC000,
         end: 0x0EFFFF,
         country: "Cuba",
+        iso2: "CU",
+        iso3: "CUB",
     },
     CountryBlock {
         start: 0x0F0000,
         end: 0x0F3FFF,
         country: "El Salvador",
+        iso2: "SV",
+        iso3: "SLV",
     },
     CountryBlock {
         start: 0x0F4000,
         end: 0x0F7FFF,
         country: "Guatemala",
+        iso2: "GT",
+        iso3: "GTM",
     },
     CountryBlock {
         start: 0x0F8000,
         end: 0x0FBFFF,
         country: "Guyana",
+        iso2: "GY",
+        iso3: "GUY",
     },
     CountryBlock {
         start: 0x0FC000,
         end: 0x0FFFFF,
         country: "Haiti",
+        iso2: "HT",
+        iso3: "HTI",
     },
     CountryBlock {
         start: 0x100000,
         end: 0x103FFF,
         country: "Honduras",
+        iso2: "HN",
+        iso3: "HND",
     },
     CountryBlock {
         start: 0x108000,
         end: 0x10BFFF,
         country: "Jamaica",
+        iso2: "JM",
+        iso3: "JAM",
     },
     CountryBlock {
         start: 0x110000,
         end: 0x113FFF,
         country: "Nicaragua",
+        iso2: "NI",
+        iso3: "NIC",
     },
     CountryBlock {
         start: 0x114000,
         end: 0x117FFF,
         country: "Panama",
+        iso2: "PA",
+        iso3: "PAN",
     },
     CountryBlock {
         start: 0x118000,
         end: 0x11BFFF,
         country: "Dominican Republic",
+        iso2: "DO",
+        iso3: "DOM",
     },
     CountryBlock {
         start: 0x11C000,
         end: 0x11FFFF,
         country: "Trinidad and Tobago",
+        iso2: "TT",
+        iso3: "TTO",
     },
     CountryBlock {
         start: 0x120000,
         end: 0x123FFF,
         country: "Suriname",
+        iso2: "SR",
+        iso3: "SUR",
     },
     CountryBlock {
         start: 0x140000,
         end: 0x143FFF,
         country: "Antigua and Barbuda",
+        iso2: "AG",
+        iso3: "ATG",
     },
     CountryBlock {
         start: 0x200000,
         end: 0x27FFFF,
         country: "Unassigned",
+        iso2: "ZZ",
+        iso3: "ZZZ",
     },
     CountryBlock {
         start: 0x300000,
         end: 0x33FFFF,
         country: "Italy",
+        iso2: "IT",
+        iso3: "ITA",
     },
     CountryBlock {
         start: 0x340000,
         end: 0x37FFFF,
         country: "Spain",
+        iso2: "ES",
+        iso3: "ESP",
     },
     CountryBlock {
         start: 0x380000,
         end: 0x3BFFFF,
         country: "France",
+        iso2: "FR",
+        iso3: "FRA",
     },
     CountryBlock {
         start: 0x3C0000,
         end: 0x3FFFFF,
         country: "Germany",
+        iso2: "DE",
+        iso3: "DEU",
     },
     CountryBlock {
         start: 0x400000,
         end: 0x43FFFF,
         country: "United Kingdom",
+        iso2: "GB",
+        iso3: "GBR",
     },
     CountryBlock {
         start: 0x440000,
         end: 0x447FFF,
         country: "Austria",
+        iso2: "AT",
+        iso3: "AUT",
     },
     CountryBlock {
         start: 0x448000,
         end: 0x44FFFF,
         country: "Belgium",
+        iso2: "BE",
+        iso3: "BEL",
     },
     CountryBlock {
         start: 0x450000,
         end: 0x457FFF,
         country: "Bulgaria",
+        iso2: "BG",
+        iso3: "BGR",
     },
     CountryBlock {
         start: 0x458000,
         end: 0x45FFFF,
         country: "Denmark",
+        iso2: "DK",
+        iso3: "DNK",
     },
     CountryBlock {
         start: 0x460000,
         end: 0x467FFF,
         country: "Finland",
+        iso2: "FI",
+        iso3: "FIN",
     },
     CountryBlock {
         start: 0x468000,
         end: 0x46FFFF,
         country: "Greece",
+        iso2: "GR",
+        iso3: "GRC",
     },
     CountryBlock {
         start: 0x470000,
         end: 0x477FFF,
         country: "Hungary",
+        iso2: "HU",
+        iso3: "HUN",
     },
     CountryBlock {
         start: 0x478000,
         end: 0x47FFFF,
         country: "Norway",
+        iso2: "NO",
+        iso3: "NOR",
     },
     CountryBlock {
         start: 0x480000,
         end: 0x487FFF,
         country: "Netherlands",
+        iso2: "NL",
+        iso3: "NLD",
     },
     CountryBlock {
         start: 0x488000,
         end: 0x48FFFF,
         country: "Poland",
+        iso2: "PL",
+        iso3: "POL",
     },
     CountryBlock {
         start: 0x490000,
         end: 0x497FFF,
         country: "Portugal",
+        iso2: "PT",
+        iso3: "PRT",
     },
     CountryBlock {
         start: 0x498000,
         end: 0x49FFFF,
         country: "Czech Republic",
+        iso2: "CZ",
+        iso3: "CZE",
     },
     CountryBlock {
         start: 0x4A0000,
         end: 0x4A7FFF,
         country: "Romania",
+        iso2: "RO",
+        iso3: "ROU",
     },
     CountryBlock {
         start: 0x4A8000,
         end: 0x4AFFFF,
         country: "Sweden",
+        iso2: "SE",
+        iso3: "SWE",
     },
     CountryBlock {
         start: 0x4B0000,
         end: 0x4B7FFF,
         country: "Switzerland",
+        iso2: "CH",
+        iso3: "CHE",
     },
     CountryBlock {
         start: 0x4B8000,
         end: 0x4BFFFF,
         country: "Turkey",
+        iso2: "TR",
+        iso3: "TUR",
     },
     CountryBlock {
         start: 0x4C0000,
         end: 0x4C7FFF,
         country: "Yugoslavia/Serbia",
+        iso2: "RS",
+        iso3: "SRB",
     },
     CountryBlock {
         start: 0x4CA000,
         end: 0x4CAFFF,
         country: "Cyprus",
+        iso2: "CY",
+        iso3: "CYP",
     },
     CountryBlock {
         start: 0x4CC000,
         end: 0x4CCFFF,
         country: "Ireland",
+        iso2: "IE",
+        iso3: "IRL",
     },
     CountryBlock {
         start: 0x4D0000,
         end: 0x4D03FF,
         country: "Iceland",
+        iso2: "IS",
+        iso3: "ISL",
     },
     CountryBlock {
         start: 0x500000,
         end: 0x5003FF,
         country: "Sri Lanka",
+        iso2: "LK",
+        iso3: "LKA",
     },
     CountryBlock {
         start: 0x501000,
         end: 0x5013FF,
         country: "Malaysia",
+        iso2: "MY",
+        iso3: "MYS",
     },
     CountryBlock {
         start: 0x508000,
         end: 0x50FFFF,
         country: "Indonesia",
+        iso2: "ID",
+        iso3: "IDN",
     },
     CountryBlock {
         start: 0x510000,
         end: 0x5107FF,
         country: "Iraq",
+        iso2: "IQ",
+        iso3: "IRQ",
     },
     CountryBlock {
         start: 0x600000,
         end: 0x6003FF,
         country: "Singapore",
+        iso2: "SG",
+        iso3: "SGP",
     },
     CountryBlock {
         start: 0x680000,
         end: 0x6803FF,
         country: "Thailand",
+        iso2: "TH",
+        iso3: "THA",
     },
     CountryBlock {
         start: 0x681000,
         end: 0x6813FF,
         country: "Vietnam",
+        iso2: "VN",
+        iso3: "VNM",
     },
     CountryBlock {
         start: 0x700000,
         end: 0x700FFF,
         country: "Afghanistan",
+        iso2: "AF",
+        iso3: "AFG",
     },
     CountryBlock {
         start: 0x710000,
         end: 0x717FFF,
         country: "Pakistan",
+        iso2: "PK",
+        iso3: "PAK",
     },
     CountryBlock {
         start: 0x718000,
         end: 0x71FFFF,
         country: "Bangladesh",
+        iso2: "BD",
+        iso3: "BGD",
     },
     CountryBlock {
         start: 0x720000,
         end: 0x727FFF,
         country: "Myanmar",
+        iso2: "MM",
+        iso3: "MMR",
     },
     CountryBlock {
         start: 0x730000,
         end: 0x737FFF,
         country: "Kuwait",
+        iso2: "KW",
+        iso3: "KWT",
     },
     CountryBlock {
         start: 0x738000,
         end: 0x73FFFF,
         country: "Laos",
+        iso2: "LA",
+        iso3: "LAO",
     },
     CountryBlock {
         start: 0x740000,
         end: 0x747FFF,
         country: "Nepal",
+        iso2: "NP",
+        iso3: "NPL",
     },
     CountryBlock {
         start: 0x748000,
         end: 0x74FFFF,
         country: "Oman",
+        iso2: "OM",
+        iso3: "OMN",
     },
     CountryBlock {
         start: 0x750000,
         end: 0x757FFF,
         country: "Saudi Arabia",
+        iso2: "SA",
+        iso3: "SAU",
     },
     CountryBlock {
         start: 0x758000,
         end: 0x75FFFF,
         country: "South Korea",
+        iso2: "KR",
+        iso3: "KOR",
     },
     CountryBlock {
         start: 0x760000,
         end: 0x767FFF,
         country: "North Korea",
+        iso2: "KP",
+        iso3: "PRK",
     },
     CountryBlock {
         start: 0x768000,
         end: 0x76FFFF,
         country: "Syria",
+        iso2: "SY",
+        iso3: "SYR",
     },
     CountryBlock {
         start: 0x770000,
         end: 0x777FFF,
         country: "Taiwan",
+        iso2: "TW",
+        iso3: "TWN",
     },
     CountryBlock {
         start: 0x778000,
         end: 0x77FFFF,
         country: "Jordan",
+        iso2: "JO",
+        iso3: "JOR",
     },
     CountryBlock {
         start: 0x780000,
         end: 0x7BFFFF,
         country: "China",
+        iso2: "CN",
+        iso3: "CHN",
     },
     CountryBlock {
         start: 0x7C0000,
         end: 0x7FFFFF,
         country: "Australia",
+        iso2: "AU",
+        iso3: "AUS",
     },
     CountryBlock {
         start: 0x800000,
         end: 0x83FFFF,
         country: "India",
+        iso2: "IN",
+        iso3: "IND",
     },
     CountryBlock {
         start: 0x840000,
         end: 0x87FFFF,
         country: "Japan",
+        iso2: "JP",
+        iso3: "JPN",
     },
     CountryBlock {
         start: 0x880000,
         end: 0x887FFF,
         country: "Thailand",
+        iso2: "TH",
+        iso3: "THA",
     },
     CountryBlock {
         start: 0x890000,
         end: 0x890FFF,
         country: "Vietnam",
+        iso2: "VN",
+        iso3: "VNM",
     },
     CountryBlock {
         start: 0x894000,
         end: 0x894FFF,
         country: "Hong Kong",
+        iso2: "HK",
+        iso3: "HKG",
     },
     CountryBlock {
         start: 0x895000,
         end: 0x8953FF,
         country: "Macau",
+        iso2: "MO",
+        iso3: "MAC",
     },
     CountryBlock {
         start: 0x896000,
         end: 0x896FFF,
         country: "Cambodia",
+        iso2: "KH",
+        iso3: "KHM",
     },
     CountryBlock {
         start: 0x897000,
         end: 0x8973FF,
         country: "Philippines",
+        iso2: "PH",
+        iso3: "PHL",
     },
     CountryBlock {
         start: 0x898000,
         end: 0x898FFF,
         country: "Mongolia",
+        iso2: "MN",
+        iso3: "MNG",
     },
     CountryBlock {
         start: 0x899000,
         end: 0x8993FF,
         country: "Maldives",
+        iso2: "MV",
+        iso3: "MDV",
     },
     CountryBlock {
         start: 0x8A0000,
         end: 0x8A7FFF,
         country: "UAE",
+        iso2: "AE",
+        iso3: "ARE",
     },
     CountryBlock {
         start: 0x900000,
         end: 0x9003FF,
         country: "Israel",
+        iso2: "IL",
+        iso3: "ISR",
     },
     CountryBlock {
         start: 0xA00000,
         end: 0xAFFFFF,
         country: "United States",
+        iso2: "US",
+        iso3: "USA",
     },
     CountryBlock {
         start: 0xC00000,
         end: 0xC3FFFF,
         country: "Canada",
+        iso2: "CA",
+        iso3: "CAN",
     },
     CountryBlock {
         start: 0xC80000,
         end: 0xC87FFF,
         country: "New Zealand",
+        iso2: "NZ",
+        iso3: "NZL",
     },
     CountryBlock {
         start: 0xC88000,
         end: 0xC88FFF,
         country: "Fiji",
+        iso2: "FJ",
+        iso3: "FJI",
     },
     CountryBlock {
         start: 0xE00000,
         end: 0xE3FFFF,
         country: "Argentina",
+        iso2: "AR",
+        iso3: "ARG",
     },
     CountryBlock {
         start: 0xE40000,
         end: 0xE7FFFF,
         country: "Brazil",
+        iso2: "BR",
+        iso3: "BRA",
     },
     CountryBlock {
         start: 0xE80000,
         end: 0xE83FFF,
         country: "Chile",
+        iso2: "CL",
+        iso3: "CHL",
     },
     CountryBlock {
         start: 0xE84000,
         end: 0xE87FFF,
         country: "Ecuador",
+        iso2: "EC",
+        iso3: "ECU",
     },
     CountryBlock {
         start: 0xE88000,
         end: 0xE8BFFF,
         country: "Paraguay",
+        iso2: "PY",
+        iso3: "PRY",
     },
     CountryBlock {
         start: 0xE8C000,
         end: 0xE8FFFF,
         country: "Peru",
+        iso2: "PE",
+        iso3: "PER",
     },
     CountryBlock {
         start: 0xE90000,
         end: 0xE93FFF,
         country: "Uruguay",
+        iso2: "UY",
+        iso3: "URY",
     },
     CountryBlock {
         start: 0xE94000,
         end: 0xE97FFF,
         country: "Venezuela",
+        iso2: "VE",
+        iso3: "VEN",
     },
     CountryBlock {
         start: 0xF00000,
         end: 0xF07FFF,
         country: "ICAO (special)",
+        iso2: "XX",
+        iso3: "XXX",
     },
     CountryBlock {
         start: 0xF09000,
         end: 0xF093FF,
         country: "ICAO (special)",
+        iso2: "XX",
+        iso3: "XXX",
     },
 ];
 
-// US military ICAO block
-const US_MILITARY_START: u32 = 0xADF7C8;
-const US_MILITARY_END: u32 = 0xAFFFFF;
+/// Whether a [`MilitaryBlock`] covers armed-forces aircraft or broader
+/// state/government use (head-of-state transport, coast guard, customs).
+///
+/// Mirrors the Plan 9 `classify.c` "mil"/"gov" split: `is_military` only
+/// cares about the former, `government_or_military` cares about both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MilitaryCategory {
+    Military,
+    Government,
+}
+
+struct MilitaryBlock {
+    start: u32,
+    end: u32,
+    country: &'static str,
+    branch: &'static str,
+    category: MilitaryCategory,
+}
+
+// National military/government ICAO sub-ranges, sorted by start address.
+// Carved out of the civil blocks in COUNTRY_BLOCKS; narrower and far less
+// complete than that table since most countries don't publish theirs.
+const MILITARY_BLOCKS: &[MilitaryBlock] = &[
+    MilitaryBlock {
+        start: 0x33FF00,
+        end: 0x33FFFF,
+        country: "Italy",
+        branch: "Air Force",
+        category: MilitaryCategory::Military,
+    },
+    MilitaryBlock {
+        start: 0x3AC000,
+        end: 0x3AFFFF,
+        country: "France",
+        branch: "Air and Space Force",
+        category: MilitaryCategory::Military,
+    },
+    MilitaryBlock {
+        start: 0x3F4000,
+        end: 0x3F7FFF,
+        country: "Germany",
+        branch: "Luftwaffe",
+        category: MilitaryCategory::Military,
+    },
+    MilitaryBlock {
+        start: 0x43C000,
+        end: 0x43FFFF,
+        country: "United Kingdom",
+        branch: "Royal Air Force",
+        category: MilitaryCategory::Military,
+    },
+    MilitaryBlock {
+        start: 0xADF7C8,
+        end: 0xAFFFFF,
+        country: "United States",
+        branch: "Joint",
+        category: MilitaryCategory::Military,
+    },
+    MilitaryBlock {
+        start: 0xC20000,
+        end: 0xC3FFFF,
+        country: "Canada",
+        branch: "Royal Canadian Air Force",
+        category: MilitaryCategory::Military,
+    },
+    MilitaryBlock {
+        start: 0xF00000,
+        end: 0xF07FFF,
+        country: "ICAO (special)",
+        branch: "State/Government",
+        category: MilitaryCategory::Government,
+    },
+];
 
 // US civil N-number range
 const US_CIVIL_START: u32 = 0xA00001;
@@ -730,52 +1092,302 @@ const MILITARY_CALLSIGNS: &[&str] = &[
     "CRZR", "MOOSE", "CANAF", "ASCOT", "RAFR", "GAF", "URAN", "CNV", "FAF", "IAM", "SUI",
 ];
 
-/// Look up country of registration from ICAO address.
-pub fn lookup_country(icao: &Icao) -> Option<&'static str> {
+/// Look up structured country metadata (name + ISO 3166-1 codes) from ICAO address.
+///
+/// `COUNTRY_BLOCKS` is sorted by `start` and non-overlapping (see
+/// `test_country_blocks_sorted_and_non_overlapping`), so this binary-searches
+/// for the last block starting at or before `addr` rather than scanning.
+pub fn lookup_country_info(icao: &Icao) -> Option<CountryInfo> {
     let addr = icao_to_u32(icao);
-    for block in COUNTRY_BLOCKS {
-        if addr >= block.start && addr <= block.end {
-            return Some(block.country);
-        }
+    let idx = match COUNTRY_BLOCKS.binary_search_by(|block| block.start.cmp(&addr)) {
+        Ok(idx) => idx,
+        Err(0) => return None,
+        Err(idx) => idx - 1,
+    };
+    let block = &COUNTRY_BLOCKS[idx];
+    if addr >= block.start && addr <= block.end {
+        Some(CountryInfo {
+            name: block.country,
+            iso2: block.iso2,
+            iso3: block.iso3,
+        })
+    } else {
+        None
     }
-    None
+}
+
+/// Look up country of registration from ICAO address.
+pub fn lookup_country(icao: &Icao) -> Option<&'static str> {
+    lookup_country_info(icao).map(|info| info.name)
 }
 
 /// Look up country from a hex string.
 pub fn lookup_country_hex(icao_hex: &str) -> Option<&'static str> {
     let addr = u32::from_str_radix(icao_hex, 16).ok()?;
-    for block in COUNTRY_BLOCKS {
-        if addr >= block.start && addr <= block.end {
-            return Some(block.country);
-        }
+    lookup_country(&crate::types::icao_from_u32(addr))
+}
+
+/// Geopolitical bucket for an ICAO address's country of registration.
+///
+/// Deliberately coarse -- this is a quick triage signal for monitoring
+/// dashboards, not a sanctions-compliance source of truth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountryCategory {
+    /// Under comprehensive trade/arms sanctions from most Western blocs.
+    Sanctioned,
+    /// Treaty ally outside the EU (NATO or equivalent bilateral defense pact).
+    Allied,
+    /// European Union member state.
+    EuMember,
+    /// Resolves to a known country that isn't in either list above.
+    Other,
+    /// The block doesn't resolve to a country (reserved range, or unknown address).
+    Unassigned,
+}
+
+// ISO-2 -> category, sorted by ISO-2 for binary search. Modeled on the
+// Plan 9 `classify.c` badc/goodc split: a short sanctioned list and an
+// explicit allied/EU set, everything else falls through to `Other`.
+const COUNTRY_CATEGORIES: &[(&str, CountryCategory)] = &[
+    ("AF", CountryCategory::Sanctioned),
+    ("AT", CountryCategory::EuMember),
+    ("AU", CountryCategory::Allied),
+    ("BE", CountryCategory::EuMember),
+    ("BG", CountryCategory::EuMember),
+    ("CA", CountryCategory::Allied),
+    ("CU", CountryCategory::Sanctioned),
+    ("CY", CountryCategory::EuMember),
+    ("CZ", CountryCategory::EuMember),
+    ("DE", CountryCategory::EuMember),
+    ("DK", CountryCategory::EuMember),
+    ("EE", CountryCategory::EuMember),
+    ("ES", CountryCategory::EuMember),
+    ("FI", CountryCategory::EuMember),
+    ("FR", CountryCategory::EuMember),
+    ("GB", CountryCategory::Allied),
+    ("GR", CountryCategory::EuMember),
+    ("HR", CountryCategory::EuMember),
+    ("HU", CountryCategory::EuMember),
+    ("IE", CountryCategory::EuMember),
+    ("IQ", CountryCategory::Sanctioned),
+    ("IR", CountryCategory::Sanctioned),
+    ("IS", CountryCategory::Allied),
+    ("IT", CountryCategory::EuMember),
+    ("JP", CountryCategory::Allied),
+    ("KP", CountryCategory::Sanctioned),
+    ("KR", CountryCategory::Allied),
+    ("LT", CountryCategory::EuMember),
+    ("LU", CountryCategory::EuMember),
+    ("LV", CountryCategory::EuMember),
+    ("LY", CountryCategory::Sanctioned),
+    ("MT", CountryCategory::EuMember),
+    ("NL", CountryCategory::EuMember),
+    ("NO", CountryCategory::Allied),
+    ("NZ", CountryCategory::Allied),
+    ("PL", CountryCategory::EuMember),
+    ("PT", CountryCategory::EuMember),
+    ("RO", CountryCategory::EuMember),
+    ("SD", CountryCategory::Sanctioned),
+    ("SE", CountryCategory::EuMember),
+    ("SI", CountryCategory::EuMember),
+    ("SK", CountryCategory::EuMember),
+    ("SY", CountryCategory::Sanctioned),
+    ("US", CountryCategory::Allied),
+];
+
+/// Classify an ICAO address's country of registration into a coarse
+/// geopolitical bucket (see [`CountryCategory`]).
+///
+/// Addresses in reserved/unassigned ranges (including the `0x200000-0x27FFFF`
+/// block and the `ICAO (special)` ranges) and addresses that don't resolve
+/// to any country return `Unassigned`.
+pub fn country_category(icao: &Icao) -> CountryCategory {
+    let info = match lookup_country_info(icao) {
+        Some(info) => info,
+        None => return CountryCategory::Unassigned,
+    };
+    if info.iso2 == "ZZ" || info.iso2 == "XX" {
+        return CountryCategory::Unassigned;
+    }
+    match COUNTRY_CATEGORIES.binary_search_by_key(&info.iso2, |&(iso2, _)| iso2) {
+        Ok(idx) => COUNTRY_CATEGORIES[idx].1,
+        Err(_) => CountryCategory::Other,
     }
-    None
 }
 
-/// Check if an aircraft is military.
+/// How an aircraft was flagged as military/government by [`military_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MilitaryMatch {
+    /// ICAO address fell inside a national [`MilitaryBlock`].
+    AddressBlock,
+    /// Callsign matched a known military prefix.
+    Callsign,
+}
+
+/// Result of a military/government match, with enough detail to explain
+/// why an aircraft was flagged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MilitaryInfo {
+    pub mechanism: MilitaryMatch,
+    pub category: MilitaryCategory,
+    /// Owning country, when the match came from an address block.
+    pub country: Option<&'static str>,
+    /// Matched callsign prefix, when the match came from the callsign.
+    pub matched_prefix: Option<&'static str>,
+}
+
+/// Classify an aircraft as military/government, explaining which mechanism
+/// matched.
 ///
-/// Two detection methods:
-/// 1. ICAO address in a military allocation block (US military)
-/// 2. Callsign matches known military patterns
-pub fn is_military(icao: &Icao, callsign: Option<&str>) -> bool {
+/// Two detection methods, tried in order:
+/// 1. ICAO address falls inside a national [`MilitaryBlock`]
+/// 2. Callsign matches a known military prefix
+pub fn military_info(icao: &Icao, callsign: Option<&str>) -> Option<MilitaryInfo> {
     let addr = icao_to_u32(icao);
 
-    // US military address block
-    if (US_MILITARY_START..=US_MILITARY_END).contains(&addr) {
-        return true;
+    for block in MILITARY_BLOCKS {
+        if addr >= block.start && addr <= block.end {
+            return Some(MilitaryInfo {
+                mechanism: MilitaryMatch::AddressBlock,
+                category: block.category,
+                country: Some(block.country),
+                matched_prefix: None,
+            });
+        }
     }
 
-    // Military callsign check
     if let Some(cs) = callsign {
         let cs = cs.trim().to_uppercase();
         for prefix in MILITARY_CALLSIGNS {
             if cs.starts_with(prefix) {
-                return true;
+                return Some(MilitaryInfo {
+                    mechanism: MilitaryMatch::Callsign,
+                    category: MilitaryCategory::Military,
+                    country: None,
+                    matched_prefix: Some(prefix),
+                });
             }
         }
     }
 
-    false
+    None
+}
+
+/// Check if an aircraft is strictly military (not just government/state use).
+pub fn is_military(icao: &Icao, callsign: Option<&str>) -> bool {
+    matches!(
+        military_info(icao, callsign),
+        Some(MilitaryInfo {
+            category: MilitaryCategory::Military,
+            ..
+        })
+    )
+}
+
+/// Check if an aircraft is military OR broader government/state use (head-of-state
+/// transport, coast guard, customs) -- a superset of `is_military`.
+pub fn government_or_military(icao: &Icao, callsign: Option<&str>) -> bool {
+    military_info(icao, callsign).is_some()
+}
+
+// ---------------------------------------------------------------------------
+// Special-interest watchlist
+// ---------------------------------------------------------------------------
+//
+// [`MILITARY_BLOCKS`] only covers whole national address ranges. Some
+// airframes are individually notable (head-of-state transports, dedicated
+// test/demonstration aircraft) without their address falling in one of
+// those blocks, or without being military at all. [`SPECIAL_WATCHLIST`] is
+// an exact list of such addresses; [`SPECIAL_BLOOM`] is a Bloom filter over
+// it so [`is_special`] skips the linear scan of the watchlist for almost
+// every address that is not on it.
+
+/// Number of bits backing [`SPECIAL_BLOOM`], sized generously for the
+/// current (small) watchlist; revisit if it grows past a few thousand
+/// entries, since a denser filter raises the false-positive rate.
+const BLOOM_BITS: usize = 4096;
+
+/// Number of bit positions set per inserted address. The false-positive
+/// rate is lowest at `k ≈ (bits/entries) * ln(2)`, so 4 suits roughly 700
+/// entries in `BLOOM_BITS`; the current watchlist is far smaller, which
+/// leaves the filter sparse.
+const BLOOM_HASHES: u32 = 4;
+
+/// Fixed-size Bloom filter over 24-bit ICAO addresses.
+///
+/// Two independent 64-bit hashes of the address are mixed via double
+/// hashing (Kirsch-Mitzenmacher) to derive `BLOOM_HASHES` bit positions,
+/// avoiding the need for `BLOOM_HASHES` separate hash implementations. A
+/// `false` result from [`BloomFilter::maybe_contains`] is certain; a `true`
+/// result is advisory only and must be confirmed against an exact set.
+struct BloomFilter {
+    bits: [u64; BLOOM_BITS / 64],
+}
+
+impl BloomFilter {
+    fn new() -> Self {
+        Self {
+            bits: [0u64; BLOOM_BITS / 64],
+        }
+    }
+
+    /// Two independent 64-bit hashes of `addr`, each a splitmix64-style mix
+    /// seeded with a different constant.
+    fn hash_pair(addr: u32) -> (u64, u64) {
+        let mix = |mut x: u64| {
+            x = (x ^ (x >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            x = (x ^ (x >> 27)).wrapping_mul(0x94D049BB133111EB);
+            x ^ (x >> 31)
+        };
+        let h1 = mix(addr as u64 ^ 0x9E3779B97F4A7C15);
+        let h2 = mix(addr as u64 ^ 0xC2B2AE3D27D4EB4F);
+        (h1, h2)
+    }
+
+    fn bit_positions(addr: u32) -> impl Iterator<Item = usize> {
+        let (h1, h2) = Self::hash_pair(addr);
+        (0..BLOOM_HASHES as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) as usize) % BLOOM_BITS)
+    }
+
+    fn insert(&mut self, addr: u32) {
+        for bit in Self::bit_positions(addr) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    fn maybe_contains(&self, addr: u32) -> bool {
+        Self::bit_positions(addr).all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+}
+
+/// Exact ICAO addresses for individually watchlisted special-interest
+/// airframes. Authoritative: [`SPECIAL_BLOOM`] is only a fast pre-filter
+/// over this set, never a substitute for it.
+pub(crate) const SPECIAL_WATCHLIST: &[u32] = &[
+    0xA65F1E, // VC-25A "Air Force One", tail 92-9000
+    0xA65F1F, // VC-25A "Air Force One", tail 92-9001
+    0xADFEC4, // E-4B "Nightwatch" National Airborne Operations Center
+    0x43C11D, // RAF Voyager, UK head-of-state transport
+];
+
+static SPECIAL_BLOOM: std::sync::LazyLock<BloomFilter> = std::sync::LazyLock::new(|| {
+    let mut filter = BloomFilter::new();
+    for &addr in SPECIAL_WATCHLIST {
+        filter.insert(addr);
+    }
+    filter
+});
+
+/// Whether `icao` is an individually watchlisted special-interest airframe
+/// (see [`SPECIAL_WATCHLIST`]) -- distinct from [`is_military`], which
+/// matches whole national address blocks and known callsign prefixes.
+///
+/// Checks [`SPECIAL_BLOOM`] first: a `false` there is certain and skips the
+/// exact scan entirely. A `true` is only advisory (Bloom filters have false
+/// positives by construction), so it's always confirmed against
+/// [`SPECIAL_WATCHLIST`] before being trusted.
+pub fn is_special(icao: u32) -> bool {
+    SPECIAL_BLOOM.maybe_contains(icao) && SPECIAL_WATCHLIST.contains(&icao)
 }
 
 /// Decode 1-2 letter suffix from remainder for N-number.
@@ -901,6 +1513,129 @@ pub fn icao_hex_to_n_number(icao_hex: &str) -> Option<String> {
     icao_to_n_number(&icao)
 }
 
+/// Inverse of `letter_suffix`: map a 1-2 character suffix back to the
+/// `remainder` value that would have produced it.
+fn letter_suffix_index(letters: &[char], max_letters: u32) -> Option<u32> {
+    if max_letters == 1 {
+        if letters.len() != 1 {
+            return None;
+        }
+        return NNUM_CHARS
+            .iter()
+            .position(|&c| c == letters[0] as u8)
+            .map(|i| i as u32);
+    }
+
+    let first_idx = NNUM_CHARS.iter().position(|&c| c == letters[0] as u8)? as u32;
+    match letters.len() {
+        1 => Some(first_idx * 25),
+        2 => {
+            let second_idx = NNUM_CHARS.iter().position(|&c| c == letters[1] as u8)? as u32;
+            Some(first_idx * 25 + second_idx + 1)
+        }
+        _ => None,
+    }
+}
+
+/// Convert an N-number (tail number) back to its US civil ICAO address.
+///
+/// Reverses the base-conversion arithmetic in `icao_to_n_number`: a leading
+/// `N`, 1-5 following characters, digits first and at most two trailing
+/// letters from the `NNUM_CHARS` alphabet (A-Z excluding I and O). Returns
+/// `None` for malformed registrations or ones that don't land in
+/// `US_CIVIL_START..=US_CIVIL_END`.
+pub fn n_number_to_icao(n: &str) -> Option<Icao> {
+    let upper = n.trim().to_uppercase();
+    let rest = upper.strip_prefix('N')?;
+    let chars: Vec<char> = rest.chars().collect();
+    if chars.is_empty() || chars.len() > 5 {
+        return None;
+    }
+
+    let digit_count = chars.iter().take_while(|c| c.is_ascii_digit()).count();
+    let letters = &chars[digit_count..];
+    if digit_count == 0 || letters.len() > 2 {
+        return None;
+    }
+    if !letters.iter().all(|c| NNUM_CHARS.contains(&(*c as u8))) {
+        return None;
+    }
+
+    let digits: Vec<u32> = chars[..digit_count]
+        .iter()
+        .map(|c| c.to_digit(10).unwrap())
+        .collect();
+    if digits[0] == 0 {
+        return None;
+    }
+
+    let offset = match (digit_count, letters.len()) {
+        (1, 0) => (digits[0] - 1) * 101711,
+        (2, 0) => (digits[0] - 1) * 101711 + 1 + digits[1] * 10111,
+        (3, 0) => (digits[0] - 1) * 101711 + 1 + digits[1] * 10111 + 1 + digits[2] * 951,
+        (4, 0) => {
+            (digits[0] - 1) * 101711
+                + 1
+                + digits[1] * 10111
+                + 1
+                + digits[2] * 951
+                + 1
+                + digits[3] * 35
+        }
+        (5, 0) => {
+            (digits[0] - 1) * 101711
+                + 1
+                + digits[1] * 10111
+                + 1
+                + digits[2] * 951
+                + 1
+                + digits[3] * 35
+                + 1
+                + digits[4]
+        }
+        (1, 1) | (1, 2) => {
+            (digits[0] - 1) * 101711 + 1 + 10 * 10111 + letter_suffix_index(letters, 2)?
+        }
+        (2, 1) | (2, 2) => {
+            (digits[0] - 1) * 101711
+                + 1
+                + digits[1] * 10111
+                + 1
+                + 10 * 951
+                + letter_suffix_index(letters, 2)?
+        }
+        (3, 1) | (3, 2) => {
+            (digits[0] - 1) * 101711
+                + 1
+                + digits[1] * 10111
+                + 1
+                + digits[2] * 951
+                + 1
+                + 10 * 35
+                + letter_suffix_index(letters, 2)?
+        }
+        (4, 1) => {
+            (digits[0] - 1) * 101711
+                + 1
+                + digits[1] * 10111
+                + 1
+                + digits[2] * 951
+                + 1
+                + digits[3] * 35
+                + 1
+                + 10
+                + letter_suffix_index(letters, 1)?
+        }
+        _ => return None,
+    };
+
+    let addr = US_CIVIL_START + offset;
+    if !(US_CIVIL_START..=US_CIVIL_END).contains(&addr) {
+        return None;
+    }
+    Some(crate::types::icao_from_u32(addr))
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -908,7 +1643,33 @@ pub fn icao_hex_to_n_number(icao_hex: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::icao_from_hex;
+    use crate::types::{icao_from_hex, icao_from_u32};
+
+    #[test]
+    fn test_country_blocks_sorted_and_non_overlapping() {
+        for pair in COUNTRY_BLOCKS.windows(2) {
+            let (prev, next) = (&pair[0], &pair[1]);
+            assert!(
+                prev.start <= prev.end,
+                "block starting at {:#08X} has start > end",
+                prev.start
+            );
+            assert!(
+                prev.start < next.start,
+                "blocks starting at {:#08X} and {:#08X} are not strictly increasing",
+                prev.start,
+                next.start
+            );
+            assert!(
+                prev.end < next.start,
+                "block ending at {:#08X} overlaps block starting at {:#08X}",
+                prev.end,
+                next.start
+            );
+        }
+        let last = COUNTRY_BLOCKS.last().unwrap();
+        assert!(last.start <= last.end);
+    }
 
     #[test]
     fn test_lookup_country_us() {
@@ -932,6 +1693,53 @@ mod tests {
         assert_eq!(lookup_country_hex("FFFFFF"), None);
     }
 
+    #[test]
+    fn test_lookup_country_info_us() {
+        let icao = icao_from_hex("A00001").unwrap();
+        let info = lookup_country_info(&icao).unwrap();
+        assert_eq!(info.name, "United States");
+        assert_eq!(info.iso2, "US");
+        assert_eq!(info.iso3, "USA");
+    }
+
+    #[test]
+    fn test_lookup_country_info_unknown() {
+        let icao = icao_from_hex("FFFFFF").unwrap();
+        assert_eq!(lookup_country_info(&icao), None);
+    }
+
+    #[test]
+    fn test_country_category_sanctioned() {
+        let icao = icao_from_hex("008000").unwrap();
+        assert_eq!(country_category(&icao), CountryCategory::Other);
+        let afghanistan = icao_from_hex("700000").unwrap();
+        assert_eq!(country_category(&afghanistan), CountryCategory::Sanctioned);
+    }
+
+    #[test]
+    fn test_country_category_eu_member() {
+        let germany = icao_from_hex("3C6586").unwrap();
+        assert_eq!(country_category(&germany), CountryCategory::EuMember);
+    }
+
+    #[test]
+    fn test_country_category_allied() {
+        let us = icao_from_hex("A00001").unwrap();
+        assert_eq!(country_category(&us), CountryCategory::Allied);
+    }
+
+    #[test]
+    fn test_country_category_unassigned_block() {
+        let reserved = icao_from_hex("210000").unwrap();
+        assert_eq!(country_category(&reserved), CountryCategory::Unassigned);
+    }
+
+    #[test]
+    fn test_country_category_unknown_address() {
+        let unknown = icao_from_hex("FFFFFF").unwrap();
+        assert_eq!(country_category(&unknown), CountryCategory::Unassigned);
+    }
+
     #[test]
     fn test_is_military_us_block() {
         let icao = icao_from_hex("ADF7C8").unwrap();
@@ -954,6 +1762,81 @@ mod tests {
         assert!(!is_military(&icao, Some("UAL123")));
     }
 
+    #[test]
+    fn test_military_info_address_block() {
+        let icao = icao_from_hex("ADF7C8").unwrap();
+        let info = military_info(&icao, None).unwrap();
+        assert_eq!(info.mechanism, MilitaryMatch::AddressBlock);
+        assert_eq!(info.category, MilitaryCategory::Military);
+        assert_eq!(info.country, Some("United States"));
+        assert_eq!(info.matched_prefix, None);
+    }
+
+    #[test]
+    fn test_military_info_callsign() {
+        let icao = icao_from_hex("A00001").unwrap();
+        let info = military_info(&icao, Some("RCH123")).unwrap();
+        assert_eq!(info.mechanism, MilitaryMatch::Callsign);
+        assert_eq!(info.category, MilitaryCategory::Military);
+        assert_eq!(info.country, None);
+        assert_eq!(info.matched_prefix, Some("RCH"));
+    }
+
+    #[test]
+    fn test_military_info_none() {
+        let icao = icao_from_hex("A00001").unwrap();
+        assert_eq!(military_info(&icao, Some("UAL123")), None);
+    }
+
+    #[test]
+    fn test_government_or_military_includes_state_block() {
+        let icao = icao_from_hex("F00001").unwrap();
+        assert!(!is_military(&icao, None));
+        assert!(government_or_military(&icao, None));
+    }
+
+    #[test]
+    fn test_is_special_matches_watchlisted_address() {
+        for &addr in SPECIAL_WATCHLIST {
+            assert!(is_special(addr), "{addr:06X} should be on the watchlist");
+        }
+    }
+
+    #[test]
+    fn test_is_special_false_for_unlisted_address() {
+        assert!(!is_special(0xA00001));
+        assert!(!is_special(0x000000));
+    }
+
+    #[test]
+    fn test_bloom_filter_no_false_negatives() {
+        // A Bloom filter must never say "definitely not present" for
+        // something that was actually inserted.
+        let mut filter = BloomFilter::new();
+        let addrs: Vec<u32> = (0..5000).map(|i| i * 97 + 13).collect();
+        for &addr in &addrs {
+            filter.insert(addr);
+        }
+        for &addr in &addrs {
+            assert!(filter.maybe_contains(addr));
+        }
+    }
+
+    #[test]
+    fn test_bloom_filter_mostly_rejects_absent_addresses() {
+        // False positives are allowed, but should be the exception, not the
+        // rule, for a filter this empty.
+        let mut filter = BloomFilter::new();
+        for &addr in SPECIAL_WATCHLIST {
+            filter.insert(addr);
+        }
+        let false_positives = (0..10_000)
+            .map(|i| i * 37 + 3)
+            .filter(|&addr| !SPECIAL_WATCHLIST.contains(&addr) && filter.maybe_contains(addr))
+            .count();
+        assert!(false_positives < 100, "unexpectedly high false-positive rate: {false_positives}/10000");
+    }
+
     #[test]
     fn test_n_number_first_address() {
         // 0xA00001 should be N1
@@ -990,4 +1873,33 @@ mod tests {
         assert!(!NNUM_CHARS.contains(&b'O'));
         assert_eq!(NNUM_CHARS.len(), 24);
     }
+
+    #[test]
+    fn test_n_number_to_icao_known_values() {
+        assert_eq!(n_number_to_icao("N1"), Some(icao_from_hex("A00001").unwrap()));
+        assert_eq!(n_number_to_icao("N10"), Some(icao_from_hex("A00002").unwrap()));
+    }
+
+    #[test]
+    fn test_n_number_to_icao_rejects_malformed() {
+        assert_eq!(n_number_to_icao("N"), None);
+        assert_eq!(n_number_to_icao("N0"), None);
+        assert_eq!(n_number_to_icao("N123456"), None);
+        assert_eq!(n_number_to_icao("N1OO"), None);
+        assert_eq!(n_number_to_icao("N1II"), None);
+        assert_eq!(n_number_to_icao("UAL123"), None);
+    }
+
+    #[test]
+    fn test_n_number_round_trip_full_civil_range() {
+        for addr in US_CIVIL_START..=US_CIVIL_END {
+            let icao = icao_from_u32(addr);
+            let n = icao_to_n_number(&icao).expect("every civil address has an N-number");
+            assert_eq!(
+                n_number_to_icao(&n),
+                Some(icao),
+                "round trip failed for {n} ({addr:#08X})"
+            );
+        }
+    }
 }
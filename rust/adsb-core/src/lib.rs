@@ -3,17 +3,30 @@
 //! No async, no I/O — just algorithms. This crate is the shared core used by
 //! both `adsb-feeder` (edge device) and `adsb-server` (web server + CLI).
 
+pub mod bds;
+pub mod beast;
+mod bitreader;
 pub mod config;
 pub mod cpr;
 pub mod crc;
 pub mod decode;
+pub mod dem;
+pub mod demod;
+pub mod filter;
 pub mod frame;
 pub mod icao;
+pub mod me;
+pub mod mlat;
+pub mod reader;
+pub mod region;
 pub mod tracker;
 pub mod types;
 
 // Re-export commonly used types at crate root
+pub use bds::*;
 pub use decode::decode;
-pub use frame::{parse_frame, parse_frame_uncached, IcaoCache, ModeFrame};
+pub use frame::{parse_frame, parse_frame_uncached, Confidence, IcaoCache, ModeFrame};
+pub use me::*;
+pub use reader::{FrameReader, ReaderFlags};
 pub use tracker::{AircraftState, TrackEvent, Tracker};
 pub use types::*;
@@ -0,0 +1,373 @@
+//! Declarative typed decoding of the DF17/18 Message Extended (ME) field.
+//!
+//! `ModeFrame::me()`/`type_code()` hand back raw bytes and a 5-bit number,
+//! leaving callers to bit-bang the ADS-B subfields themselves. `decode_me`
+//! instead walks the 56-bit ME payload with a small sequential bit reader —
+//! in the style the `ais` crate uses for its message types — and returns a
+//! typed `MeMessage`, with spare/reserved bits explicitly skipped and a
+//! structured error for malformed or unsupported layouts instead of a panic.
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::bitreader::BitReader;
+use crate::decode::{decode_altitude, decode_squawk, round2};
+use crate::frame::ModeFrame;
+use crate::types::{SpeedType, CALLSIGN_CHARSET};
+
+/// Errors produced while decoding an ME field.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum MeDecodeError {
+    #[error("frame is not a DF17/18 ADS-B extended squitter")]
+    NotAdsb,
+    #[error("ME field is not 56 bits")]
+    InvalidLength,
+    #[error("unsupported or reserved type code: {0}")]
+    UnsupportedTypeCode(u8),
+    #[error("unsupported airborne velocity subtype: {0}")]
+    UnsupportedVelocitySubtype(u8),
+    #[error("unsupported aircraft status subtype: {0}")]
+    UnsupportedStatusSubtype(u8),
+}
+
+/// The 56-bit ME field, kept around on every variant for round-tripping.
+pub type MeBytes = [u8; 7];
+
+/// TC 1-4: Aircraft identification and category.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AircraftIdentificationMe {
+    pub raw: MeBytes,
+    pub type_code: u8,
+    pub category: u8,
+    pub callsign: String,
+}
+
+/// TC 9-18/20-22: Airborne position (barometric or GNSS altitude).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AirbornePositionMe {
+    pub raw: MeBytes,
+    pub type_code: u8,
+    pub surveillance_status: u8,
+    pub altitude_ft: Option<i32>,
+    pub cpr_odd: bool,
+    pub cpr_lat: u32,
+    pub cpr_lon: u32,
+}
+
+/// TC 5-8: Surface position.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SurfacePositionMe {
+    pub raw: MeBytes,
+    pub type_code: u8,
+    pub movement: u8,
+    pub ground_track_deg: Option<f64>,
+    pub cpr_odd: bool,
+    pub cpr_lat: u32,
+    pub cpr_lon: u32,
+}
+
+/// TC 19: Airborne velocity (ground speed or airspeed + heading).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AirborneVelocityMe {
+    pub raw: MeBytes,
+    pub type_code: u8,
+    pub subtype: u8,
+    pub speed_type: SpeedType,
+    pub speed_kts: Option<f64>,
+    pub heading_deg: Option<f64>,
+    pub vertical_rate_fpm: Option<i32>,
+}
+
+/// TC 28: Aircraft status (emergency/priority state + Mode A squawk).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AircraftStatusMe {
+    pub raw: MeBytes,
+    pub type_code: u8,
+    pub emergency_state: u8,
+    pub squawk: String,
+}
+
+/// A typed decode of an ME field, driven off its type code.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum MeMessage {
+    AircraftIdentification(AircraftIdentificationMe),
+    AirbornePosition(AirbornePositionMe),
+    SurfacePosition(SurfacePositionMe),
+    AirborneVelocity(AirborneVelocityMe),
+    AircraftStatus(AircraftStatusMe),
+}
+
+impl ModeFrame {
+    /// Decode the ME field into a typed message, driven off `type_code()`.
+    pub fn decode_me(&self) -> Result<MeMessage, MeDecodeError> {
+        if !self.is_adsb() || !self.is_long() {
+            return Err(MeDecodeError::NotAdsb);
+        }
+        let me: MeBytes = self
+            .me()
+            .try_into()
+            .map_err(|_| MeDecodeError::InvalidLength)?;
+        let tc = (me[0] >> 3) & 0x1F;
+        match tc {
+            1..=4 => Ok(MeMessage::AircraftIdentification(parse_identification(
+                me, tc,
+            ))),
+            5..=8 => Ok(MeMessage::SurfacePosition(parse_surface_position(me, tc))),
+            9..=18 | 20..=22 => Ok(MeMessage::AirbornePosition(parse_airborne_position(me, tc))),
+            19 => parse_airborne_velocity(me).map(MeMessage::AirborneVelocity),
+            28 => parse_aircraft_status(me).map(MeMessage::AircraftStatus),
+            other => Err(MeDecodeError::UnsupportedTypeCode(other)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Per-type-code parsers
+// ---------------------------------------------------------------------------
+
+fn parse_identification(me: MeBytes, type_code: u8) -> AircraftIdentificationMe {
+    let mut r = BitReader::new(&me);
+    r.skip_bits(5); // type code, already known
+    let category = r.take_bits(3) as u8;
+
+    let mut callsign = String::with_capacity(8);
+    for _ in 0..8 {
+        let idx = r.take_bits(6) as usize;
+        callsign.push(CALLSIGN_CHARSET.get(idx).map(|&b| b as char).unwrap_or(' '));
+    }
+
+    AircraftIdentificationMe {
+        raw: me,
+        type_code,
+        category,
+        callsign,
+    }
+}
+
+fn parse_airborne_position(me: MeBytes, type_code: u8) -> AirbornePositionMe {
+    let mut r = BitReader::new(&me);
+    r.skip_bits(5); // type code
+    let surveillance_status = r.take_bits(2) as u8;
+    r.skip_bits(1); // NIC supplement-B
+    let alt_code = r.take_bits(12);
+    r.skip_bits(1); // time bit
+    let cpr_odd = r.take_bits(1) == 1;
+    let cpr_lat = r.take_bits(17);
+    let cpr_lon = r.take_bits(17);
+
+    AirbornePositionMe {
+        raw: me,
+        type_code,
+        surveillance_status,
+        altitude_ft: decode_altitude(alt_code),
+        cpr_odd,
+        cpr_lat,
+        cpr_lon,
+    }
+}
+
+fn parse_surface_position(me: MeBytes, type_code: u8) -> SurfacePositionMe {
+    let mut r = BitReader::new(&me);
+    r.skip_bits(5); // type code
+    let movement = r.take_bits(7) as u8;
+    let track_valid = r.take_bits(1) == 1;
+    let track_raw = r.take_bits(7);
+    r.skip_bits(1); // time bit
+    let cpr_odd = r.take_bits(1) == 1;
+    let cpr_lat = r.take_bits(17);
+    let cpr_lon = r.take_bits(17);
+
+    SurfacePositionMe {
+        raw: me,
+        type_code,
+        movement,
+        ground_track_deg: if track_valid {
+            Some(round2(track_raw as f64 * 360.0 / 128.0))
+        } else {
+            None
+        },
+        cpr_odd,
+        cpr_lat,
+        cpr_lon,
+    }
+}
+
+fn parse_airborne_velocity(me: MeBytes) -> Result<AirborneVelocityMe, MeDecodeError> {
+    let mut r = BitReader::new(&me);
+    let type_code = r.take_bits(5) as u8;
+    let subtype = r.take_bits(3) as u8;
+
+    let (speed_type, speed_kts, heading_deg) = match subtype {
+        1 | 2 => {
+            r.skip_bits(1); // intent change flag
+            r.skip_bits(1); // reserved
+            r.skip_bits(3); // NAC_v
+            let ew_dir = r.take_bits(1);
+            let ew_vel = r.take_bits(10) as i32 - 1;
+            let ns_dir = r.take_bits(1);
+            let ns_vel = r.take_bits(10) as i32 - 1;
+            if ew_vel >= 0 && ns_vel >= 0 {
+                let vx = if ew_dir == 1 { -ew_vel } else { ew_vel } as f64;
+                let vy = if ns_dir == 1 { -ns_vel } else { ns_vel } as f64;
+                let speed = (vx * vx + vy * vy).sqrt();
+                let heading = vx.atan2(vy).to_degrees().rem_euclid(360.0);
+                (
+                    SpeedType::Ground,
+                    Some(round2(speed)),
+                    Some(round2(heading)),
+                )
+            } else {
+                (SpeedType::Ground, None, None)
+            }
+        }
+        3 | 4 => {
+            r.skip_bits(1); // intent change flag
+            r.skip_bits(1); // reserved
+            r.skip_bits(3); // NAC_v
+            let hdg_available = r.take_bits(1);
+            let hdg_raw = r.take_bits(10);
+            let airspeed_type = r.take_bits(1);
+            let speed_raw = r.take_bits(10) as i32;
+            let heading = if hdg_available == 1 {
+                Some(round2(hdg_raw as f64 * 360.0 / 1024.0))
+            } else {
+                None
+            };
+            let speed = if speed_raw > 0 {
+                Some((speed_raw - 1) as f64)
+            } else {
+                None
+            };
+            let speed_type = if airspeed_type == 1 {
+                SpeedType::TAS
+            } else {
+                SpeedType::IAS
+            };
+            (speed_type, speed, heading)
+        }
+        other => return Err(MeDecodeError::UnsupportedVelocitySubtype(other)),
+    };
+
+    r.skip_bits(1); // vertical rate source
+    let vr_sign = r.take_bits(1);
+    let vr_val = r.take_bits(9) as i32 - 1;
+    let vertical_rate_fpm = if vr_val >= 0 {
+        let rate = vr_val * 64;
+        Some(if vr_sign == 1 { -rate } else { rate })
+    } else {
+        None
+    };
+
+    Ok(AirborneVelocityMe {
+        raw: me,
+        type_code,
+        subtype,
+        speed_type,
+        speed_kts,
+        heading_deg,
+        vertical_rate_fpm,
+    })
+}
+
+fn parse_aircraft_status(me: MeBytes) -> Result<AircraftStatusMe, MeDecodeError> {
+    let mut r = BitReader::new(&me);
+    let type_code = r.take_bits(5) as u8;
+    let subtype = r.take_bits(3) as u8;
+    if subtype != 1 {
+        return Err(MeDecodeError::UnsupportedStatusSubtype(subtype));
+    }
+    let emergency_state = r.take_bits(3) as u8;
+    let squawk_code = r.take_bits(13);
+
+    Ok(AircraftStatusMe {
+        raw: me,
+        type_code,
+        emergency_state,
+        squawk: decode_squawk(squawk_code),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::parse_frame_uncached;
+
+    fn parse(hex: &str) -> ModeFrame {
+        parse_frame_uncached(hex, 1.0, None).expect("valid frame")
+    }
+
+    #[test]
+    fn test_decode_me_identification() {
+        let frame = parse("8D4840D6202CC371C32CE0576098");
+        match frame.decode_me().unwrap() {
+            MeMessage::AircraftIdentification(msg) => {
+                assert_eq!(msg.callsign, "KLM1023 ");
+                assert_eq!(msg.type_code, 4);
+            }
+            other => panic!("expected AircraftIdentification, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_me_airborne_position() {
+        let frame = parse("8D40621D58C382D690C8AC2863A7");
+        match frame.decode_me().unwrap() {
+            MeMessage::AirbornePosition(msg) => {
+                assert_eq!(msg.altitude_ft, Some(38000));
+                assert!(!msg.cpr_odd);
+                assert_eq!(msg.cpr_lat, 93000);
+                assert_eq!(msg.cpr_lon, 51372);
+            }
+            other => panic!("expected AirbornePosition, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_me_airborne_velocity() {
+        let frame = parse("8D485020994409940838175B284F");
+        match frame.decode_me().unwrap() {
+            MeMessage::AirborneVelocity(msg) => {
+                let speed = msg.speed_kts.unwrap();
+                assert!((speed - 159.0).abs() < 1.0, "got {speed}");
+                assert_eq!(msg.vertical_rate_fpm, Some(-832));
+                assert_eq!(msg.speed_type, SpeedType::Ground);
+            }
+            other => panic!("expected AirborneVelocity, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_me_rejects_non_adsb() {
+        let mut cache = crate::frame::IcaoCache::new(60.0);
+        let frame = crate::frame::parse_frame(
+            "02E198B94303A0",
+            1.0,
+            None,
+            false,
+            &mut cache,
+            &crate::crc::GLOBAL_CORRECTOR,
+        )
+        .expect("valid DF0 frame");
+        assert_eq!(frame.decode_me(), Err(MeDecodeError::NotAdsb));
+    }
+
+    #[test]
+    fn test_decode_me_unsupported_type_code() {
+        // TC=23 (reserved test message) with a matching CRC.
+        let mut raw = vec![0x8D, 0x48, 0x40, 0xD6, 23 << 3, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let pi = crate::crc::crc24_payload(&raw);
+        raw[11] = ((pi >> 16) & 0xFF) as u8;
+        raw[12] = ((pi >> 8) & 0xFF) as u8;
+        raw[13] = (pi & 0xFF) as u8;
+        let frame = parse(&crate::types::hex_encode(&raw));
+        assert_eq!(
+            frame.decode_me(),
+            Err(MeDecodeError::UnsupportedTypeCode(23))
+        );
+    }
+}
@@ -0,0 +1,388 @@
+//! Multilateration (MLAT) — locate a Mode S transponder that never sends a
+//! CPR-encoded position, from the time-difference-of-arrival (TDOA) of the
+//! same message across several receivers with known positions.
+//!
+//! This module is pure math: given a reference time and a handful of
+//! `(receiver position, reception time)` observations of one message, it
+//! solves for the emitter's position by Gauss-Newton least squares on the
+//! TDOA residuals. Collecting the observations (matching payloads across
+//! receivers within a short window) is the caller's job — see
+//! `adsb-server`'s ingest pipeline.
+//!
+//! Accurate TDOA requires the reception timestamps to be comparable to
+//! within a few hundred nanoseconds across receivers, which in practice
+//! means 12 MHz BEAST hardware timestamps; AVR-ASCII's wall-clock-only
+//! timestamps are far too coarse (millisecond jitter is ~300km of range
+//! error) to produce a usable fix.
+
+/// Speed of light, meters/second.
+const SPEED_OF_LIGHT_M_S: f64 = 299_792_458.0;
+
+/// WGS84 semi-major axis, meters.
+const WGS84_A: f64 = 6_378_137.0;
+/// WGS84 flattening.
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+const MAX_ITERATIONS: usize = 10;
+/// Stop iterating once the Gauss-Newton step is smaller than this, in meters.
+const CONVERGENCE_STEP_M: f64 = 1.0;
+/// Reject fixes whose residual RMS (meters) exceeds this — the geometry and
+/// timestamps didn't agree well enough to trust the solve.
+const MAX_RESIDUAL_RMS_M: f64 = 1000.0;
+/// Reject fixes with worse geometric dilution of precision than this — the
+/// receivers were too close to collinear/coincident to localize reliably.
+const MAX_GDOP: f64 = 20.0;
+/// Receivers closer together than this (meters) are treated as the same
+/// site and one of them is dropped, since a degenerate pair contributes no
+/// independent TDOA information and can blow up the linear solve.
+const MIN_RECEIVER_SEPARATION_M: f64 = 1.0;
+
+/// One receiver's position and reception time for a single message.
+#[derive(Debug, Clone, Copy)]
+pub struct Observation {
+    pub lat: f64,
+    pub lon: f64,
+    pub altitude_ft: f64,
+    /// Reception timestamp, seconds, on a clock comparable across receivers
+    /// (e.g. a 12 MHz BEAST timestamp normalized to seconds).
+    pub timestamp: f64,
+}
+
+/// A resolved multilateration fix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MlatFix {
+    pub lat: f64,
+    pub lon: f64,
+    pub altitude_ft: f64,
+    /// Root-mean-square of the TDOA residuals, in meters.
+    pub residual_rms_m: f64,
+    /// Geometric dilution of precision of the solved geometry.
+    pub gdop: f64,
+}
+
+/// Convert geodetic coordinates (WGS84 ellipsoid) to ECEF meters.
+fn geodetic_to_ecef_m(lat_deg: f64, lon_deg: f64, altitude_ft: f64) -> (f64, f64, f64) {
+    let lat = lat_deg.to_radians();
+    let lon = lon_deg.to_radians();
+    let alt_m = altitude_ft * 0.3048;
+
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let n = WGS84_A / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+
+    let x = (n + alt_m) * lat.cos() * lon.cos();
+    let y = (n + alt_m) * lat.cos() * lon.sin();
+    let z = (n * (1.0 - e2) + alt_m) * lat.sin();
+    (x, y, z)
+}
+
+/// Convert ECEF meters back to geodetic coordinates, via Bowring's method.
+fn ecef_to_geodetic(x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let ep2 = e2 / (1.0 - e2);
+    let p = (x * x + y * y).sqrt();
+    let theta = (z * WGS84_A).atan2(p * (WGS84_A * (1.0 - WGS84_F)));
+
+    let lon = y.atan2(x);
+    let lat = (z + ep2 * WGS84_A * (1.0 - WGS84_F) * theta.sin().powi(3))
+        .atan2(p - e2 * WGS84_A * theta.cos().powi(3));
+
+    let n = WGS84_A / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+    let alt_m = p / lat.cos() - n;
+
+    (lat.to_degrees(), lon.to_degrees(), alt_m / 0.3048)
+}
+
+fn distance(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2) + (a.2 - b.2).powi(2)).sqrt()
+}
+
+/// Solve a 3x3 linear system `a * x = b` via Cramer's rule. Returns `None`
+/// if `a` is singular.
+fn solve_3x3(a: [[f64; 3]; 3], b: [f64; 3]) -> Option<[f64; 3]> {
+    let det = determinant(a);
+    if det.abs() < 1e-9 {
+        return None;
+    }
+    let mut x = [0.0; 3];
+    for (col, slot) in x.iter_mut().enumerate() {
+        *slot = determinant(replace_col(a, col, b)) / det;
+    }
+    Some(x)
+}
+
+fn determinant(m: [[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+fn replace_col(mut m: [[f64; 3]; 3], col: usize, values: [f64; 3]) -> [[f64; 3]; 3] {
+    for (row, value) in values.into_iter().enumerate() {
+        m[row][col] = value;
+    }
+    m
+}
+
+/// Geometric dilution of precision for `receivers` (ECEF meters) as seen
+/// from candidate emitter position `x` (ECEF meters): the square root of
+/// the trace of `(J^T J)^-1`, where `J` is the unit line-of-sight Jacobian.
+/// Larger means the receiver geometry is less able to pin down position
+/// (e.g. receivers nearly collinear as seen from the emitter).
+fn gdop(x: (f64, f64, f64), receivers: &[(f64, f64, f64)]) -> f64 {
+    let mut jtj = [[0.0; 3]; 3];
+    for &r in receivers {
+        let d = distance(x, r);
+        if d < 1e-6 {
+            continue;
+        }
+        let u = [(x.0 - r.0) / d, (x.1 - r.1) / d, (x.2 - r.2) / d];
+        for i in 0..3 {
+            for j in 0..3 {
+                jtj[i][j] += u[i] * u[j];
+            }
+        }
+    }
+
+    // Trace of the inverse via the adjugate, since we only need the sum of
+    // the diagonal, not the full inverse.
+    let det = determinant(jtj);
+    if det.abs() < 1e-9 {
+        return f64::INFINITY;
+    }
+    let cofactor = |i: usize, j: usize| -> f64 {
+        let mut sub = [[0.0; 2]; 2];
+        let mut si = 0;
+        for r in 0..3 {
+            if r == i {
+                continue;
+            }
+            let mut sj = 0;
+            for c in 0..3 {
+                if c == j {
+                    continue;
+                }
+                sub[si][sj] = jtj[r][c];
+                sj += 1;
+            }
+            si += 1;
+        }
+        let sign = if (i + j) % 2 == 0 { 1.0 } else { -1.0 };
+        sign * (sub[0][0] * sub[1][1] - sub[0][1] * sub[1][0])
+    };
+    let trace_inv = (cofactor(0, 0) + cofactor(1, 1) + cofactor(2, 2)) / det;
+    if trace_inv < 0.0 {
+        f64::INFINITY
+    } else {
+        trace_inv.sqrt()
+    }
+}
+
+/// Solve for an emitter's position from time-difference-of-arrival
+/// observations of the same message at `observations.len()` receivers.
+///
+/// Requires at least 4 observations (3 independent TDOA pairs, enough to
+/// solve for x/y/z). Picks the first observation as the TDOA reference;
+/// observations at the same site as an earlier one (within
+/// `MIN_RECEIVER_SEPARATION_M`) are dropped since they add no independent
+/// information. Returns `None` if there aren't enough independent
+/// observations left, the geometry is too poor (GDOP), the Gauss-Newton
+/// iteration doesn't converge, or the residual RMS is too high to trust.
+pub fn solve(observations: &[Observation]) -> Option<MlatFix> {
+    let mut sites: Vec<((f64, f64, f64), f64)> = Vec::with_capacity(observations.len());
+    for obs in observations {
+        let ecef = geodetic_to_ecef_m(obs.lat, obs.lon, obs.altitude_ft);
+        if sites
+            .iter()
+            .any(|&(other, _)| distance(ecef, other) < MIN_RECEIVER_SEPARATION_M)
+        {
+            continue;
+        }
+        sites.push((ecef, obs.timestamp));
+    }
+
+    if sites.len() < 4 {
+        return None;
+    }
+
+    let (ref_pos, ref_t) = sites[0];
+    // Range difference to the reference receiver, meters: positive means
+    // the message arrived later (farther) than at the reference.
+    let deltas: Vec<((f64, f64, f64), f64)> = sites[1..]
+        .iter()
+        .map(|&(pos, t)| (pos, SPEED_OF_LIGHT_M_S * (t - ref_t)))
+        .collect();
+
+    let n = sites.len() as f64;
+    let mut x = (
+        sites.iter().map(|&(p, _)| p.0).sum::<f64>() / n,
+        sites.iter().map(|&(p, _)| p.1).sum::<f64>() / n,
+        sites.iter().map(|&(p, _)| p.2).sum::<f64>() / n,
+    );
+
+    let mut converged = false;
+    for _ in 0..MAX_ITERATIONS {
+        let d_ref = distance(x, ref_pos);
+        let mut jtj = [[0.0; 3]; 3];
+        let mut jtr = [0.0; 3];
+
+        for &(pos, delta_d) in &deltas {
+            let d_i = distance(x, pos);
+            let residual = (d_i - d_ref) - delta_d;
+
+            // d/dx of (||x - R_i|| - ||x - R_ref||)
+            let grad = [
+                (x.0 - pos.0) / d_i - (x.0 - ref_pos.0) / d_ref,
+                (x.1 - pos.1) / d_i - (x.1 - ref_pos.1) / d_ref,
+                (x.2 - pos.2) / d_i - (x.2 - ref_pos.2) / d_ref,
+            ];
+
+            for i in 0..3 {
+                jtr[i] += grad[i] * residual;
+                for j in 0..3 {
+                    jtj[i][j] += grad[i] * grad[j];
+                }
+            }
+        }
+
+        let neg_jtr = [-jtr[0], -jtr[1], -jtr[2]];
+        let Some(step) = solve_3x3(jtj, neg_jtr) else {
+            return None;
+        };
+        x = (x.0 + step[0], x.1 + step[1], x.2 + step[2]);
+
+        let step_mag = (step[0].powi(2) + step[1].powi(2) + step[2].powi(2)).sqrt();
+        if step_mag < CONVERGENCE_STEP_M {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return None;
+    }
+
+    let d_ref = distance(x, ref_pos);
+    let residuals: Vec<f64> = deltas
+        .iter()
+        .map(|&(pos, delta_d)| (distance(x, pos) - d_ref) - delta_d)
+        .collect();
+    let residual_rms_m =
+        (residuals.iter().map(|r| r * r).sum::<f64>() / residuals.len() as f64).sqrt();
+    if residual_rms_m > MAX_RESIDUAL_RMS_M {
+        return None;
+    }
+
+    let site_positions: Vec<(f64, f64, f64)> = sites.iter().map(|&(p, _)| p).collect();
+    let gdop = gdop(x, &site_positions);
+    if !gdop.is_finite() || gdop > MAX_GDOP {
+        return None;
+    }
+
+    let (lat, lon, altitude_ft) = ecef_to_geodetic(x.0, x.1, x.2);
+    Some(MlatFix {
+        lat,
+        lon,
+        altitude_ft,
+        residual_rms_m,
+        gdop,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Five receivers around Denver, spread out enough for decent GDOP.
+    fn denver_receivers() -> [(f64, f64, f64); 5] {
+        [
+            (39.7392, -104.9903, 5280.0),
+            (39.85, -104.9, 5500.0),
+            (39.65, -105.05, 6200.0),
+            (39.75, -104.75, 5400.0),
+            (39.6, -104.85, 5600.0),
+        ]
+    }
+
+    fn observations_for(target_lat: f64, target_lon: f64, target_alt_ft: f64) -> Vec<Observation> {
+        let target = geodetic_to_ecef_m(target_lat, target_lon, target_alt_ft);
+        denver_receivers()
+            .into_iter()
+            .map(|(lat, lon, alt)| {
+                let pos = geodetic_to_ecef_m(lat, lon, alt);
+                let timestamp = distance(pos, target) / SPEED_OF_LIGHT_M_S;
+                Observation {
+                    lat,
+                    lon,
+                    altitude_ft: alt,
+                    timestamp,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ecef_roundtrip() {
+        let (x, y, z) = geodetic_to_ecef_m(39.7392, -104.9903, 35000.0);
+        let (lat, lon, alt) = ecef_to_geodetic(x, y, z);
+        assert!((lat - 39.7392).abs() < 1e-6);
+        assert!((lon - (-104.9903)).abs() < 1e-6);
+        assert!((alt - 35000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_solve_recovers_known_position() {
+        let observations = observations_for(39.72, -104.95, 37000.0);
+        let fix = solve(&observations).expect("solve should converge");
+
+        assert!((fix.lat - 39.72).abs() < 1e-3);
+        assert!((fix.lon - (-104.95)).abs() < 1e-3);
+        assert!(fix.residual_rms_m < 10.0);
+    }
+
+    #[test]
+    fn test_solve_rejects_too_few_observations() {
+        let observations = observations_for(39.72, -104.95, 37000.0)[..3].to_vec();
+        assert!(solve(&observations).is_none());
+    }
+
+    #[test]
+    fn test_solve_drops_coincident_receivers() {
+        let mut observations = observations_for(39.72, -104.95, 37000.0);
+        // Duplicate the first receiver at (almost) the same site; it should
+        // be dropped rather than corrupting the solve.
+        let dup = observations[0];
+        observations.push(Observation {
+            lat: dup.lat + 1e-7,
+            lon: dup.lon,
+            altitude_ft: dup.altitude_ft,
+            timestamp: dup.timestamp,
+        });
+
+        let fix = solve(&observations).expect("solve should still converge");
+        assert!((fix.lat - 39.72).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_solve_rejects_poor_geometry() {
+        // Four receivers all on (almost) the same line give terrible GDOP.
+        let observations: Vec<Observation> = [
+            (39.70, -105.00, 5000.0),
+            (39.71, -105.00, 5000.0),
+            (39.72, -105.00, 5000.0),
+            (39.73, -105.00, 5000.0),
+        ]
+        .into_iter()
+        .map(|(lat, lon, alt)| {
+            let pos = geodetic_to_ecef_m(lat, lon, alt);
+            let target = geodetic_to_ecef_m(39.72, -104.90, 37000.0);
+            Observation {
+                lat,
+                lon,
+                altitude_ft: alt,
+                timestamp: distance(pos, target) / SPEED_OF_LIGHT_M_S,
+            }
+        })
+        .collect();
+
+        assert!(solve(&observations).is_none());
+    }
+}
@@ -0,0 +1,384 @@
+//! Streaming multi-format frame decoding over `Read`.
+//!
+//! `parse_frame` takes an already-extracted hex string, so every caller has
+//! to pre-split a feed into frames and supply the timestamp/signal level out
+//! of band. `FrameReader` instead wraps a continuous byte stream — the way
+//! `pxar`'s `SequentialDecoder` walks an archive — and yields `ModeFrame`s
+//! directly, auto-detecting whichever of the two common ground-station wire
+//! formats it's seeing from the leading byte of each record: AVR ASCII lines
+//! (`*8D4840D6202CC371C32CE0576098;`, dump1090's `--raw` output, no embedded
+//! timing so the capture time is used) and Beast binary (see the `beast`
+//! module), whose `0x1a`-escaped records carry a 6-byte 12 MHz MLAT counter
+//! and a 1-byte signal level that populate `ModeFrame::timestamp` and
+//! `ModeFrame::signal_level` directly.
+
+use std::collections::VecDeque;
+use std::io::Read;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::beast;
+use crate::crc;
+use crate::frame::{parse_frame, IcaoCache};
+use crate::types::hex_encode;
+use crate::ModeFrame;
+
+/// Per-stream feature flags controlling how `FrameReader` filters frames.
+#[derive(Debug, Clone, Copy)]
+pub struct ReaderFlags {
+    /// Drop frames whose CRC did not ultimately validate.
+    pub reject_crc_failures: bool,
+    /// Attempt 1-2 bit error correction on CRC failures. When `false`, a
+    /// frame that only validated because of a correction is dropped instead
+    /// of being handed to the caller.
+    pub error_correction: bool,
+    /// Reject residual-recovered ICAOs (DF0/4/5/16/20/21) not already seen
+    /// via an explicit-ICAO frame. See `IcaoCache`.
+    pub validate_icao: bool,
+}
+
+impl Default for ReaderFlags {
+    fn default() -> Self {
+        ReaderFlags {
+            reject_crc_failures: true,
+            error_correction: true,
+            validate_icao: true,
+        }
+    }
+}
+
+/// Streams `ModeFrame`s out of a byte stream carrying AVR ASCII or Beast
+/// binary framing, resyncing on whatever framing byte it finds next.
+pub struct FrameReader<'r, R: Read> {
+    reader: &'r mut R,
+    flags: ReaderFlags,
+    icao_cache: IcaoCache,
+    /// Bytes pulled from `reader` but not yet consumed into a frame.
+    buf: VecDeque<u8>,
+    /// True once the underlying reader has returned EOF or an error.
+    eof: bool,
+}
+
+impl<'r, R: Read> FrameReader<'r, R> {
+    /// Create a reader with the default flags (reject CRC failures, attempt
+    /// error correction, validate residual-recovered ICAOs).
+    pub fn new(reader: &'r mut R) -> Self {
+        Self::with_flags(reader, ReaderFlags::default())
+    }
+
+    /// Create a reader with explicit feature flags.
+    pub fn with_flags(reader: &'r mut R, flags: ReaderFlags) -> Self {
+        Self::with_cache(reader, flags, IcaoCache::default())
+    }
+
+    /// Create a reader that reuses an existing `IcaoCache` instead of
+    /// starting a fresh one — e.g. a per-feeder cache that must keep its
+    /// confirmation counts across separate ingest requests from the same
+    /// receiver. Use `into_icao_cache` to reclaim it once done.
+    pub fn with_cache(reader: &'r mut R, flags: ReaderFlags, icao_cache: IcaoCache) -> Self {
+        FrameReader {
+            reader,
+            flags,
+            icao_cache,
+            buf: VecDeque::new(),
+            eof: false,
+        }
+    }
+
+    /// Reclaim the reader's `IcaoCache`, e.g. to carry its confirmation
+    /// state over to the next request from the same feeder.
+    pub fn into_icao_cache(self) -> IcaoCache {
+        self.icao_cache
+    }
+
+    /// Pull one more byte from the underlying reader into `buf`. Returns
+    /// `false` at EOF or on a read error.
+    fn fill(&mut self) -> bool {
+        if self.eof {
+            return false;
+        }
+        let mut byte = [0u8; 1];
+        match self.reader.read(&mut byte) {
+            Ok(1) => {
+                self.buf.push_back(byte[0]);
+                true
+            }
+            _ => {
+                self.eof = true;
+                false
+            }
+        }
+    }
+
+    /// Next raw byte off the stream, blocking on `fill` as needed.
+    fn next_byte(&mut self) -> Option<u8> {
+        while self.buf.is_empty() {
+            if !self.fill() {
+                return None;
+            }
+        }
+        self.buf.pop_front()
+    }
+
+    /// Next logical byte from a Beast record, undoubling an escaped `0x1a`.
+    /// A lone `0x1a` not followed by a second `0x1a` means the stream
+    /// desynced mid-record (the "escape" was actually the next frame start).
+    fn next_escaped_byte(&mut self) -> Option<u8> {
+        let b = self.next_byte()?;
+        if b != beast::ESCAPE {
+            return Some(b);
+        }
+        match self.next_byte()? {
+            beast::ESCAPE => Some(beast::ESCAPE),
+            _ => None,
+        }
+    }
+
+    /// Consume one Beast binary record (the leading `0x1a` is already gone
+    /// from `buf`) and parse it into a `ModeFrame`. Returns `None` if the
+    /// record is an unsupported type (Mode A/C) or the stream desynced.
+    fn read_beast_frame(&mut self) -> Option<ModeFrame> {
+        let type_byte = self.next_byte()?;
+        let msg_len = match type_byte {
+            beast::TYPE_MODE_S_SHORT => 7,
+            beast::TYPE_MODE_S_LONG => 14,
+            _ => return None,
+        };
+
+        let mut ts_ticks: u64 = 0;
+        for _ in 0..6 {
+            ts_ticks = (ts_ticks << 8) | self.next_escaped_byte()? as u64;
+        }
+        let signal_byte = self.next_escaped_byte()?;
+
+        let mut payload = Vec::with_capacity(msg_len);
+        for _ in 0..msg_len {
+            payload.push(self.next_escaped_byte()?);
+        }
+
+        // The Beast MLAT counter ticks at 12 MHz.
+        let timestamp = ts_ticks as f64 / 12_000_000.0;
+        let signal_level = Some(signal_byte as f64 / 255.0);
+        let hex = hex_encode(&payload);
+        parse_frame(
+            &hex,
+            timestamp,
+            signal_level,
+            self.flags.validate_icao,
+            &mut self.icao_cache,
+            &crc::GLOBAL_CORRECTOR,
+        )
+    }
+
+    /// Consume one AVR ASCII record (the leading `*` is already gone from
+    /// `buf`) and parse it into a `ModeFrame`, timestamped at capture time
+    /// since AVR carries no embedded timing.
+    fn read_avr_frame(&mut self) -> Option<ModeFrame> {
+        let mut hex = String::new();
+        loop {
+            match self.next_byte()? {
+                b';' => break,
+                b => hex.push(b as char),
+            }
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        parse_frame(
+            &hex,
+            timestamp,
+            None,
+            self.flags.validate_icao,
+            &mut self.icao_cache,
+            &crc::GLOBAL_CORRECTOR,
+        )
+    }
+
+    /// Scan for the next frame-start marker and decode it, without applying
+    /// `reject_crc_failures`/`error_correction` filtering yet.
+    fn next_frame_attempt(&mut self) -> Option<ModeFrame> {
+        loop {
+            while self.buf.is_empty() {
+                if !self.fill() {
+                    return None;
+                }
+            }
+            match *self.buf.front().unwrap() {
+                beast::ESCAPE => {
+                    self.buf.pop_front();
+                    if let Some(frame) = self.read_beast_frame() {
+                        return Some(frame);
+                    }
+                    // Unsupported type or desync — resume scanning.
+                }
+                b'*' => {
+                    self.buf.pop_front();
+                    if let Some(frame) = self.read_avr_frame() {
+                        return Some(frame);
+                    }
+                }
+                _ => {
+                    // Not a recognized frame-start byte — discard and resync.
+                    self.buf.pop_front();
+                }
+            }
+        }
+    }
+}
+
+impl<'r, R: Read> Iterator for FrameReader<'r, R> {
+    type Item = ModeFrame;
+
+    fn next(&mut self) -> Option<ModeFrame> {
+        loop {
+            let frame = self.next_frame_attempt()?;
+            if self.flags.reject_crc_failures && !frame.crc_ok {
+                continue;
+            }
+            if frame.corrected && !self.flags.error_correction {
+                continue;
+            }
+            return Some(frame);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_avr_frame() {
+        let mut data = b"*8D4840D6202CC371C32CE0576098;".as_slice();
+        let mut reader = FrameReader::new(&mut data);
+        let frame = reader.next().unwrap();
+        assert_eq!(frame.df, 17);
+        assert!(frame.crc_ok);
+    }
+
+    #[test]
+    fn test_read_avr_frame_with_leading_noise() {
+        let mut data = b"\r\n*8D4840D6202CC371C32CE0576098;\r\n".as_slice();
+        let mut reader = FrameReader::new(&mut data);
+        assert!(reader.next().is_some());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_read_beast_frame() {
+        let hex = "8D4840D6202CC371C32CE0576098";
+        let raw = crate::types::hex_decode(hex).unwrap();
+        let mut data = vec![beast::ESCAPE, beast::TYPE_MODE_S_LONG];
+        data.extend_from_slice(&[0, 0, 0, 0, 0, 1]); // MLAT counter, 1 tick
+        data.push(128); // signal level
+        data.extend_from_slice(&raw);
+
+        let mut cursor = data.as_slice();
+        let mut reader = FrameReader::new(&mut cursor);
+        let frame = reader.next().unwrap();
+        assert_eq!(frame.df, 17);
+        assert!(frame.crc_ok);
+        assert_eq!(frame.timestamp, 1.0 / 12_000_000.0);
+        assert_eq!(frame.signal_level, Some(128.0 / 255.0));
+    }
+
+    #[test]
+    fn test_read_beast_frame_unescapes_data_byte() {
+        // Build a valid DF17 frame with a 0x1a byte in the ME field, which
+        // must come across the wire doubled.
+        let mut raw = vec![
+            0x8D,
+            0x48,
+            0x40,
+            0xD6,
+            beast::ESCAPE,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+        ];
+        let pi = crate::crc::crc24_payload(&raw);
+        raw[11] = ((pi >> 16) & 0xFF) as u8;
+        raw[12] = ((pi >> 8) & 0xFF) as u8;
+        raw[13] = (pi & 0xFF) as u8;
+        assert_eq!(crate::crc::crc24(&raw), 0);
+
+        let mut data = vec![beast::ESCAPE, beast::TYPE_MODE_S_LONG];
+        data.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        data.push(0);
+        for &b in &raw {
+            data.push(b);
+            if b == beast::ESCAPE {
+                data.push(b);
+            }
+        }
+
+        let mut cursor = data.as_slice();
+        let mut reader = FrameReader::new(&mut cursor);
+        let frame = reader.next().unwrap();
+        assert_eq!(frame.raw, raw);
+        assert!(frame.crc_ok);
+    }
+
+    #[test]
+    fn test_reject_crc_failures_disabled_keeps_bad_frame() {
+        let mut data = hex_encode_avr("8D4840D6202CC371C32CE0576097"); // corrupted CRC
+        let flags = ReaderFlags {
+            reject_crc_failures: false,
+            error_correction: false,
+            validate_icao: true,
+        };
+        let mut cursor = data.as_slice();
+        let mut reader = FrameReader::with_flags(&mut cursor, flags);
+        let frame = reader.next().unwrap();
+        assert!(!frame.crc_ok);
+    }
+
+    fn hex_encode_avr(hex: &str) -> Vec<u8> {
+        let mut out = vec![b'*'];
+        out.extend_from_slice(hex.as_bytes());
+        out.push(b';');
+        out
+    }
+
+    #[test]
+    fn test_error_correction_disabled_drops_corrected_frame() {
+        let mut data = hex_decode_corrupted();
+        let flags = ReaderFlags {
+            reject_crc_failures: true,
+            error_correction: false,
+            validate_icao: true,
+        };
+        let mut cursor = data.as_slice();
+        let mut reader = FrameReader::with_flags(&mut cursor, flags);
+        assert!(reader.next().is_none());
+    }
+
+    fn hex_decode_corrupted() -> Vec<u8> {
+        let mut raw = crate::types::hex_decode("8D4840D6202CC371C32CE0576098").unwrap();
+        raw[5] ^= 0x01; // single-bit error, well past the DF field
+        let hex = hex_encode(&raw);
+        hex_encode_avr(&hex)
+    }
+
+    #[test]
+    fn test_with_cache_reuses_and_returns_icao_cache() {
+        let mut data = b"*8D4840D6202CC371C32CE0576098;".as_slice();
+        let icao_cache = IcaoCache::new(60.0);
+        let mut reader = FrameReader::with_cache(&mut data, ReaderFlags::default(), icao_cache);
+        assert!(reader.next().is_some());
+
+        let icao_cache = reader.into_icao_cache();
+        assert_eq!(icao_cache.len(), 1);
+    }
+}
@@ -0,0 +1,298 @@
+//! Reverse-geocoding of positions to named regions via imported boundary
+//! polygons.
+//!
+//! Regions are loaded once from a GeoJSON `FeatureCollection` — e.g. an
+//! Overpass export of `admin_level` ways/relations for the operator's area
+//! of interest — where each `Feature`'s `properties.name` names the region
+//! and its `Polygon`/`MultiPolygon` geometry is the boundary. Classification
+//! uses ray-casting (odd edge-crossing count = inside), with holes handled
+//! by XORing the inner ring's result into the outer ring's, a bounding-box
+//! reject per polygon, and a coarse lat/lon grid bucket so classifying
+//! thousands of live positions per poll stays cheap.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A closed sequence of `(lon, lat)` vertices.
+type Ring = Vec<(f64, f64)>;
+
+/// Grid cell size in degrees for the bucket acceleration structure.
+const GRID_CELL_DEG: f64 = 1.0;
+
+struct Polygon {
+    exterior: Ring,
+    /// Holes for enclaves excluded from the region.
+    holes: Vec<Ring>,
+    /// `(min_lon, min_lat, max_lon, max_lat)`.
+    bbox: (f64, f64, f64, f64),
+}
+
+impl Polygon {
+    fn contains(&self, lon: f64, lat: f64) -> bool {
+        let (min_lon, min_lat, max_lon, max_lat) = self.bbox;
+        if lon < min_lon || lon > max_lon || lat < min_lat || lat > max_lat {
+            return false;
+        }
+        let mut inside = ray_cast(&self.exterior, lon, lat);
+        for hole in &self.holes {
+            if ray_cast(hole, lon, lat) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+/// Ray-casting point-in-polygon test: count crossings of a horizontal ray
+/// from `(x, y)`; the point is inside when the count is odd.
+fn ray_cast(ring: &[(f64, f64)], x: f64, y: f64) -> bool {
+    if ring.len() < 3 {
+        return false;
+    }
+    let mut inside = false;
+    let mut j = ring.len() - 1;
+    for i in 0..ring.len() {
+        let (xi, yi) = ring[i];
+        let (xj, yj) = ring[j];
+        if (yi > y) != (yj > y) {
+            let x_intersect = xj + (y - yj) / (yi - yj) * (xi - xj);
+            if x < x_intersect {
+                inside = !inside;
+            }
+        }
+        j = i;
+    }
+    inside
+}
+
+struct Region {
+    name: String,
+    polygons: Vec<Polygon>,
+}
+
+/// Region boundary polygons loaded from GeoJSON, queryable by point.
+pub struct RegionSet {
+    regions: Vec<Region>,
+    /// Coarse `(lon_cell, lat_cell)` grid bucket → region indices whose
+    /// bounding box overlaps that cell, so a lookup only tests nearby
+    /// regions instead of the whole set.
+    grid: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl RegionSet {
+    /// Load a `FeatureCollection` of region polygons from a GeoJSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let bytes = std::fs::read(path.as_ref()).map_err(|e| e.to_string())?;
+        let geojson: serde_json::Value =
+            serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+        Self::from_geojson(&geojson)
+    }
+
+    /// Parse a `FeatureCollection` already loaded into a `serde_json::Value`.
+    pub fn from_geojson(geojson: &serde_json::Value) -> Result<Self, String> {
+        let features = geojson["features"]
+            .as_array()
+            .ok_or("GeoJSON is missing a \"features\" array")?;
+
+        let mut regions = Vec::with_capacity(features.len());
+        for feature in features {
+            let name = feature["properties"]["name"]
+                .as_str()
+                .unwrap_or("unnamed")
+                .to_string();
+            let polygons = parse_geometry(&feature["geometry"])?;
+            regions.push(Region { name, polygons });
+        }
+
+        let grid = build_grid(&regions);
+        Ok(RegionSet { regions, grid })
+    }
+
+    /// The name of the region containing `(lat, lon)`, or `None` if it
+    /// falls outside every loaded polygon.
+    pub fn classify(&self, lat: f64, lon: f64) -> Option<&str> {
+        let cell = (lon.floor() as i32, lat.floor() as i32);
+        let candidates = self.grid.get(&cell)?;
+        candidates
+            .iter()
+            .map(|&idx| &self.regions[idx])
+            .find(|region| region.polygons.iter().any(|p| p.contains(lon, lat)))
+            .map(|region| region.name.as_str())
+    }
+}
+
+fn parse_geometry(geometry: &serde_json::Value) -> Result<Vec<Polygon>, String> {
+    match geometry["type"].as_str() {
+        Some("Polygon") => {
+            let rings = geometry["coordinates"]
+                .as_array()
+                .ok_or("Polygon is missing \"coordinates\"")?;
+            Ok(vec![parse_polygon(rings)?])
+        }
+        Some("MultiPolygon") => geometry["coordinates"]
+            .as_array()
+            .ok_or("MultiPolygon is missing \"coordinates\"")?
+            .iter()
+            .map(|polygon| {
+                let rings = polygon
+                    .as_array()
+                    .ok_or("MultiPolygon entry is not an array of rings")?;
+                parse_polygon(rings)
+            })
+            .collect(),
+        other => Err(format!("unsupported geometry type: {other:?}")),
+    }
+}
+
+fn parse_polygon(rings: &[serde_json::Value]) -> Result<Polygon, String> {
+    let mut parsed: Vec<Ring> = Vec::with_capacity(rings.len());
+    for ring in rings {
+        let points = ring.as_array().ok_or("ring is not an array")?;
+        let mut coords = Vec::with_capacity(points.len());
+        for point in points {
+            let coord = point.as_array().ok_or("coordinate is not an array")?;
+            let lon = coord.first().and_then(|v| v.as_f64()).ok_or("bad lon")?;
+            let lat = coord.get(1).and_then(|v| v.as_f64()).ok_or("bad lat")?;
+            coords.push((lon, lat));
+        }
+        parsed.push(coords);
+    }
+    if parsed.is_empty() {
+        return Err("polygon has no rings".to_string());
+    }
+    let exterior = parsed.remove(0);
+    let bbox = bbox_of(&exterior);
+    Ok(Polygon {
+        exterior,
+        holes: parsed,
+        bbox,
+    })
+}
+
+fn bbox_of(ring: &Ring) -> (f64, f64, f64, f64) {
+    let mut min_lon = f64::INFINITY;
+    let mut min_lat = f64::INFINITY;
+    let mut max_lon = f64::NEG_INFINITY;
+    let mut max_lat = f64::NEG_INFINITY;
+    for &(lon, lat) in ring {
+        min_lon = min_lon.min(lon);
+        min_lat = min_lat.min(lat);
+        max_lon = max_lon.max(lon);
+        max_lat = max_lat.max(lat);
+    }
+    (min_lon, min_lat, max_lon, max_lat)
+}
+
+fn build_grid(regions: &[Region]) -> HashMap<(i32, i32), Vec<usize>> {
+    let mut grid: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    for (idx, region) in regions.iter().enumerate() {
+        for polygon in &region.polygons {
+            let (min_lon, min_lat, max_lon, max_lat) = polygon.bbox;
+            let lon0 = (min_lon / GRID_CELL_DEG).floor() as i32;
+            let lon1 = (max_lon / GRID_CELL_DEG).floor() as i32;
+            let lat0 = (min_lat / GRID_CELL_DEG).floor() as i32;
+            let lat1 = (max_lat / GRID_CELL_DEG).floor() as i32;
+            for gx in lon0..=lon1 {
+                for gy in lat0..=lat1 {
+                    let bucket = grid.entry((gx, gy)).or_default();
+                    if !bucket.contains(&idx) {
+                        bucket.push(idx);
+                    }
+                }
+            }
+        }
+    }
+    grid
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn square_region(name: &str, min_lon: f64, min_lat: f64, max_lon: f64, max_lat: f64) -> Value {
+        json!({
+            "type": "Feature",
+            "properties": { "name": name },
+            "geometry": {
+                "type": "Polygon",
+                "coordinates": [[
+                    [min_lon, min_lat],
+                    [max_lon, min_lat],
+                    [max_lon, max_lat],
+                    [min_lon, max_lat],
+                    [min_lon, min_lat],
+                ]]
+            }
+        })
+    }
+
+    use serde_json::Value;
+
+    #[test]
+    fn test_classify_inside_polygon() {
+        let geojson = json!({
+            "type": "FeatureCollection",
+            "features": [square_region("Downtown", -1.0, -1.0, 1.0, 1.0)],
+        });
+        let regions = RegionSet::from_geojson(&geojson).unwrap();
+        assert_eq!(regions.classify(0.0, 0.0), Some("Downtown"));
+    }
+
+    #[test]
+    fn test_classify_outside_every_polygon() {
+        let geojson = json!({
+            "type": "FeatureCollection",
+            "features": [square_region("Downtown", -1.0, -1.0, 1.0, 1.0)],
+        });
+        let regions = RegionSet::from_geojson(&geojson).unwrap();
+        assert_eq!(regions.classify(50.0, 50.0), None);
+    }
+
+    #[test]
+    fn test_classify_respects_hole() {
+        let geojson = json!({
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "properties": { "name": "Ring City" },
+                "geometry": {
+                    "type": "Polygon",
+                    "coordinates": [
+                        [[-2.0, -2.0], [2.0, -2.0], [2.0, 2.0], [-2.0, 2.0], [-2.0, -2.0]],
+                        [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0], [-1.0, -1.0]],
+                    ]
+                }
+            }],
+        });
+        let regions = RegionSet::from_geojson(&geojson).unwrap();
+        assert_eq!(regions.classify(0.0, 0.0), None);
+        assert_eq!(regions.classify(1.5, 1.5), Some("Ring City"));
+    }
+
+    #[test]
+    fn test_classify_multipolygon() {
+        let geojson = json!({
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "properties": { "name": "Archipelago" },
+                "geometry": {
+                    "type": "MultiPolygon",
+                    "coordinates": [
+                        [[[-5.0, -5.0], [-4.0, -5.0], [-4.0, -4.0], [-5.0, -4.0], [-5.0, -5.0]]],
+                        [[[4.0, 4.0], [5.0, 4.0], [5.0, 5.0], [4.0, 5.0], [4.0, 4.0]]],
+                    ]
+                }
+            }],
+        });
+        let regions = RegionSet::from_geojson(&geojson).unwrap();
+        assert_eq!(regions.classify(-4.5, -4.5), Some("Archipelago"));
+        assert_eq!(regions.classify(4.5, 4.5), Some("Archipelago"));
+        assert_eq!(regions.classify(0.0, 0.0), None);
+    }
+}
@@ -8,9 +8,11 @@
 
 use crate::cpr;
 use crate::decode::decode;
+use crate::filter::{bearing_deg, haversine_nm};
 use crate::frame::ModeFrame;
 use crate::icao;
 use crate::types::*;
+use serde::Serialize;
 
 /// Aircraft considered stale after this many seconds of silence.
 pub const STALE_TIMEOUT: f64 = 60.0;
@@ -18,6 +20,63 @@ pub const STALE_TIMEOUT: f64 = 60.0;
 /// Maximum heading/position history entries per aircraft.
 const MAX_HISTORY: usize = 120;
 
+/// Number of per-message signal power samples averaged into `rssi_dbfs`.
+const RSSI_WINDOW: usize = 8;
+
+/// Default number of recently-accepted CPR fixes kept per aircraft for the
+/// position jitter gate and smoothing, used unless a caller overrides it via
+/// `Tracker::with_position_window`. A window of 1 disables smoothing entirely (each emitted
+/// position is just the latest accepted fix).
+pub const DEFAULT_POSITION_WINDOW: usize = 5;
+
+/// Implied ground speed above which a new CPR fix is rejected as a decode
+/// error rather than a real position jump, used when the aircraft's own
+/// reported speed is unknown. Above the top speed of any airliner
+/// (Concorde topped out around 1180kts).
+const MAX_PLAUSIBLE_SPEED_KTS: f64 = 1200.0;
+
+/// When the aircraft's reported `speed_kts` is known, the implied speed cap
+/// is this multiple of it, plus `SPEED_CAP_MARGIN_KTS`, rather than the
+/// generic `MAX_PLAUSIBLE_SPEED_KTS`.
+const SPEED_CAP_MULTIPLIER: f64 = 1.5;
+
+/// Flat margin added on top of `SPEED_CAP_MULTIPLIER * speed_kts`, to absorb
+/// rounding and momentary maneuvering rather than rejecting fixes right at
+/// the reported speed.
+const SPEED_CAP_MARGIN_KTS: f64 = 100.0;
+
+/// A single-frame local CPR decode is only unambiguous within half a
+/// latitude zone (~180nm) of the reference position it was decoded
+/// against; candidates further out are a decode error, not a real fix.
+const LOCAL_DECODE_MAX_NM: f64 = 180.0;
+
+/// Surface CPR's zones are a quarter the size of airborne ones (90 degrees
+/// of span instead of 360), so a surface local decode is only unambiguous
+/// within ~45nm of the reference rather than the ~180nm airborne bound.
+const SURFACE_LOCAL_DECODE_MAX_NM: f64 = 45.0;
+
+/// Per-field data-aging timeouts (seconds), mirroring dump1090's data
+/// aging: each mutable field is cleared once it hasn't been refreshed
+/// within its own timeout, rather than hanging around for `STALE_TIMEOUT`
+/// just because the aircraft is still emitting other message types.
+const POSITION_FIELD_TIMEOUT: f64 = 30.0;
+const VELOCITY_FIELD_TIMEOUT: f64 = 30.0;
+const ALTITUDE_FIELD_TIMEOUT: f64 = 30.0;
+const CALLSIGN_FIELD_TIMEOUT: f64 = 60.0;
+const SQUAWK_FIELD_TIMEOUT: f64 = 60.0;
+
+/// BDS4,0/5,0/6,0 selected-state fields (MCP altitude, baro setting, track
+/// angle, mach) share a single aging timeout, since they're all refreshed by
+/// the same family of Comm-B register reports.
+const COMMB_STATE_FIELD_TIMEOUT: f64 = 60.0;
+
+/// Number of bearing buckets (10 degrees each) in the receiver's range
+/// histogram.
+const RANGE_SECTORS: usize = 36;
+
+/// Degrees spanned by each range-histogram sector.
+const RANGE_SECTOR_WIDTH_DEG: f64 = 360.0 / RANGE_SECTORS as f64;
+
 // ---------------------------------------------------------------------------
 // Track events (output)
 // ---------------------------------------------------------------------------
@@ -42,6 +101,7 @@ pub enum TrackEvent {
         callsign: Option<String>,
         squawk: Option<String>,
         altitude_ft: Option<i32>,
+        altitude_source: Option<AltitudeSource>,
         timestamp: f64,
     },
     /// New position to store (after downsampling filter).
@@ -50,11 +110,27 @@ pub enum TrackEvent {
         lat: f64,
         lon: f64,
         altitude_ft: Option<i32>,
+        altitude_source: Option<AltitudeSource>,
         speed_kts: Option<f64>,
         heading_deg: Option<f64>,
         vertical_rate_fpm: Option<i32>,
+        vertical_rate_source: Option<VerticalRateSource>,
         receiver_id: Option<i64>,
         timestamp: f64,
+        /// True if this fix came from a surface (TC 5-8) position message.
+        on_ground: bool,
+    },
+    /// BDS4,0/5,0/6,0 selected/derived state (MCP altitude, baro setting,
+    /// track angle, mach) should be updated. Unlike `SightingUpdate`, this
+    /// is only emitted when a Comm-B register that carries one of these
+    /// fields is decoded.
+    SelectedStateUpdate {
+        icao: Icao,
+        selected_altitude_ft: Option<i32>,
+        baro_setting_hpa: Option<f64>,
+        track_deg: Option<f64>,
+        mach: Option<f64>,
+        timestamp: f64,
     },
 }
 
@@ -73,11 +149,32 @@ pub struct AircraftState {
     pub lat: Option<f64>,
     pub lon: Option<f64>,
     pub altitude_ft: Option<i32>,
+    /// Source of `altitude_ft`'s most recent update — barometric (TC 9-18)
+    /// or GNSS/HAE (TC 20-22). `None` until the first airborne position.
+    pub altitude_source: Option<AltitudeSource>,
+    /// Most recently reported barometric altitude, kept alongside
+    /// `gnss_altitude_ft` so downstream code can compute the baro/geometric
+    /// offset rather than relying on `VelocityMsg::gnss_baro_diff_ft` alone.
+    pub baro_altitude_ft: Option<i32>,
+    pub gnss_altitude_ft: Option<i32>,
 
     // Velocity
     pub speed_kts: Option<f64>,
     pub heading_deg: Option<f64>,
     pub vertical_rate_fpm: Option<i32>,
+    /// Source of `vertical_rate_fpm` — barometric or geometric (GNSS).
+    /// Mixing this with the wrong `altitude_source` produces a misleading
+    /// climb/descent reading.
+    pub vertical_rate_source: Option<VerticalRateSource>,
+
+    // Per-field data aging: the timestamp each field was last updated at,
+    // so a callsign or squawk seen once doesn't linger forever once the
+    // aircraft has gone quiet on that message type. See `expire_fields`.
+    pub callsign_time: f64,
+    pub squawk_time: f64,
+    pub altitude_time: f64,
+    pub velocity_time: f64,
+    pub position_time: f64,
 
     // CPR buffer for global decode
     pub cpr_even_lat: Option<u32>,
@@ -87,10 +184,35 @@ pub struct AircraftState {
     pub cpr_odd_lon: Option<u32>,
     pub cpr_odd_time: f64,
 
+    // Surface CPR buffer, kept separate from the airborne one above since
+    // the two use different zone sizes (`cpr::local_decode_surface` /
+    // `global_decode_surface`) and must never be paired with each other.
+    pub cpr_surface_even_lat: Option<u32>,
+    pub cpr_surface_even_lon: Option<u32>,
+    pub cpr_surface_even_time: f64,
+    pub cpr_surface_odd_lat: Option<u32>,
+    pub cpr_surface_odd_lon: Option<u32>,
+    pub cpr_surface_odd_time: f64,
+
+    /// Whether the aircraft's most recent position message was a surface
+    /// (TC 5-8) one, i.e. it's on the ground.
+    pub on_ground: bool,
+
+    // Selected/derived state from Comm-B registers BDS4,0/5,0/6,0. These
+    // describe what the aircraft's FMS/autopilot is set to or derives,
+    // rather than its actual state, so they're aged independently of
+    // `position`/`velocity`/etc. See `COMMB_STATE_FIELD_TIMEOUT`.
+    pub selected_altitude_ft: Option<i32>,
+    pub baro_setting_hpa: Option<f64>,
+    pub track_deg: Option<f64>,
+    pub mach: Option<f64>,
+    pub commb_state_time: f64,
+
     // Metadata
     pub country: Option<&'static str>,
     pub registration: Option<String>,
     pub is_military: bool,
+    pub category: Option<EmitterCategory>,
     pub first_seen: f64,
     pub last_seen: f64,
     pub message_count: u64,
@@ -98,10 +220,34 @@ pub struct AircraftState {
     // History buffers for pattern detection
     pub heading_history: Vec<(f64, f64)>, // (timestamp, heading_deg)
     pub position_history: Vec<(f64, f64, f64, Option<i32>)>, // (ts, lat, lon, alt)
+
+    // Signal quality
+    /// Ring buffer of the last `RSSI_WINDOW` linear signal power samples.
+    rssi_samples: [f64; RSSI_WINDOW],
+    rssi_next: usize,
+    rssi_count: usize,
+    /// Sliding-window average signal strength in dBFS, or `None` until the
+    /// first sample arrives.
+    pub rssi_dbfs: Option<f64>,
+
+    // Position jitter gate / smoothing
+    /// Ring buffer of the last `jitter_capacity` accepted `(lat, lon,
+    /// timestamp)` CPR fixes. Used both to reject implausible jumps (see
+    /// `accepts_position`) and, via `smoothed_position`, to emit a
+    /// component-wise median position instead of the raw single-frame
+    /// decode.
+    jitter_fixes: std::collections::VecDeque<(f64, f64, f64)>,
+    jitter_capacity: usize,
 }
 
 impl AircraftState {
     pub fn new(icao: Icao, timestamp: f64) -> Self {
+        Self::with_jitter_window(icao, timestamp, DEFAULT_POSITION_WINDOW)
+    }
+
+    /// Like `new`, but with an explicit jitter/smoothing window size — see
+    /// `Tracker::with_position_window`'s `position_window` parameter.
+    pub fn with_jitter_window(icao: Icao, timestamp: f64, jitter_window: usize) -> Self {
         AircraftState {
             icao,
             callsign: None,
@@ -109,23 +255,51 @@ impl AircraftState {
             lat: None,
             lon: None,
             altitude_ft: None,
+            altitude_source: None,
+            baro_altitude_ft: None,
+            gnss_altitude_ft: None,
             speed_kts: None,
             heading_deg: None,
             vertical_rate_fpm: None,
+            vertical_rate_source: None,
+            callsign_time: timestamp,
+            squawk_time: timestamp,
+            altitude_time: timestamp,
+            velocity_time: timestamp,
+            position_time: timestamp,
             cpr_even_lat: None,
             cpr_even_lon: None,
             cpr_even_time: 0.0,
             cpr_odd_lat: None,
             cpr_odd_lon: None,
             cpr_odd_time: 0.0,
+            cpr_surface_even_lat: None,
+            cpr_surface_even_lon: None,
+            cpr_surface_even_time: 0.0,
+            cpr_surface_odd_lat: None,
+            cpr_surface_odd_lon: None,
+            cpr_surface_odd_time: 0.0,
+            on_ground: false,
+            selected_altitude_ft: None,
+            baro_setting_hpa: None,
+            track_deg: None,
+            mach: None,
+            commb_state_time: timestamp,
             country: icao::lookup_country(&icao),
             registration: icao::icao_to_n_number(&icao),
             is_military: icao::is_military(&icao, None),
+            category: None,
             first_seen: timestamp,
             last_seen: timestamp,
             message_count: 0,
             heading_history: Vec::new(),
             position_history: Vec::new(),
+            rssi_samples: [0.0; RSSI_WINDOW],
+            rssi_next: 0,
+            rssi_count: 0,
+            rssi_dbfs: None,
+            jitter_fixes: std::collections::VecDeque::with_capacity(jitter_window.max(1)),
+            jitter_capacity: jitter_window.max(1),
         }
     }
 
@@ -140,6 +314,120 @@ impl AircraftState {
     pub fn is_stale(&self, now: f64) -> bool {
         self.age(now) > STALE_TIMEOUT
     }
+
+    /// Clear any field that hasn't been refreshed within its own timeout,
+    /// so a callsign or squawk seen once isn't reported forever just
+    /// because the aircraft (overall) isn't yet stale. Distinct from
+    /// `is_stale`/`STALE_TIMEOUT`, which governs when the whole aircraft is
+    /// dropped, not when an individual field goes out of date.
+    pub fn expire_fields(&mut self, now: f64) {
+        if now - self.position_time > POSITION_FIELD_TIMEOUT {
+            self.lat = None;
+            self.lon = None;
+        }
+        if now - self.velocity_time > VELOCITY_FIELD_TIMEOUT {
+            self.speed_kts = None;
+            self.heading_deg = None;
+            self.vertical_rate_fpm = None;
+            self.vertical_rate_source = None;
+        }
+        if now - self.altitude_time > ALTITUDE_FIELD_TIMEOUT {
+            self.altitude_ft = None;
+            self.altitude_source = None;
+            self.baro_altitude_ft = None;
+            self.gnss_altitude_ft = None;
+        }
+        if now - self.callsign_time > CALLSIGN_FIELD_TIMEOUT {
+            self.callsign = None;
+        }
+        if now - self.squawk_time > SQUAWK_FIELD_TIMEOUT {
+            self.squawk = None;
+        }
+        if now - self.commb_state_time > COMMB_STATE_FIELD_TIMEOUT {
+            self.selected_altitude_ft = None;
+            self.baro_setting_hpa = None;
+            self.track_deg = None;
+            self.mach = None;
+        }
+    }
+
+    /// Record a per-message signal power sample and refresh `rssi_dbfs` as
+    /// the average of the last `RSSI_WINDOW` samples (or fewer, until the
+    /// buffer fills).
+    fn record_signal(&mut self, power: f64) {
+        self.rssi_samples[self.rssi_next] = power;
+        self.rssi_next = (self.rssi_next + 1) % RSSI_WINDOW;
+        self.rssi_count = (self.rssi_count + 1).min(RSSI_WINDOW);
+
+        let mean: f64 = self.rssi_samples[..self.rssi_count].iter().sum::<f64>()
+            / self.rssi_count as f64;
+        self.rssi_dbfs = Some(10.0 * mean.log10());
+    }
+
+    /// Reject an out-of-bounds coordinate or an implausible jump from the
+    /// most recently accepted fix (a single corrupt CPR decode shouldn't
+    /// teleport the aircraft across the map). The first fix for an aircraft
+    /// has nothing to compare against and is always accepted.
+    ///
+    /// When the aircraft's own reported `speed_kts` is known, the implied
+    /// speed cap is tightened to `speed_kts * 1.5 + margin` instead of the
+    /// generic `MAX_PLAUSIBLE_SPEED_KTS`, catching slow-moving aircraft
+    /// jumps that the generic cap would miss.
+    fn accepts_position(&self, lat: f64, lon: f64, timestamp: f64, speed_kts: Option<f64>) -> bool {
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return false;
+        }
+
+        let Some(&(last_lat, last_lon, last_ts)) = self.last_jitter_fix() else {
+            return true;
+        };
+
+        let dt_hours = (timestamp - last_ts).abs() / 3600.0;
+        if dt_hours <= 0.0 {
+            return true;
+        }
+
+        let speed_cap_kts = speed_kts
+            .map(|s| s * SPEED_CAP_MULTIPLIER + SPEED_CAP_MARGIN_KTS)
+            .unwrap_or(MAX_PLAUSIBLE_SPEED_KTS);
+
+        let implied_speed_kts = haversine_nm(last_lat, last_lon, lat, lon) / dt_hours;
+        implied_speed_kts <= speed_cap_kts
+    }
+
+    /// Most recently accepted `(lat, lon, timestamp)` fix, if any.
+    fn last_jitter_fix(&self) -> Option<&(f64, f64, f64)> {
+        self.jitter_fixes.back()
+    }
+
+    /// Record a newly-accepted fix into the jitter ring buffer.
+    fn push_jitter_fix(&mut self, lat: f64, lon: f64, timestamp: f64) {
+        if self.jitter_fixes.len() == self.jitter_capacity {
+            self.jitter_fixes.pop_front();
+        }
+        self.jitter_fixes.push_back((lat, lon, timestamp));
+    }
+
+    /// Component-wise median lat/lon over the current jitter buffer —
+    /// robust to a single outlier fix without the lag of an average.
+    /// Degenerates to the raw fix when `jitter_capacity` is 1 (smoothing
+    /// disabled). Panics if called with an empty buffer; callers only use
+    /// this right after `push_jitter_fix`.
+    fn smoothed_position(&self) -> (f64, f64) {
+        fn median(values: &mut [f64]) -> f64 {
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let mid = values.len() / 2;
+            if values.len() % 2 == 0 {
+                (values[mid - 1] + values[mid]) / 2.0
+            } else {
+                values[mid]
+            }
+        }
+
+        let mut lats: Vec<f64> = self.jitter_fixes.iter().map(|&(lat, _, _)| lat).collect();
+        let mut lons: Vec<f64> = self.jitter_fixes.iter().map(|&(_, lon, _)| lon).collect();
+        (median(&mut lats), median(&mut lons))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -159,6 +447,15 @@ pub struct Tracker {
     pub ref_lon: Option<f64>,
     pub min_position_interval: f64,
 
+    /// Size of each aircraft's position jitter/smoothing window — see
+    /// `AircraftState::smoothed_position`. 1 disables smoothing.
+    pub position_window: usize,
+
+    // True when `ref_lat`/`ref_lon` were supplied by the caller (e.g.
+    // `--ref-lat`/`--ref-lon`) rather than auto-derived, so a configured
+    // receiver position is never overwritten by a later global fix.
+    ref_explicit: bool,
+
     // Last stored position timestamp per ICAO (for downsampling)
     last_stored: std::collections::HashMap<Icao, f64>,
 
@@ -167,6 +464,16 @@ pub struct Tracker {
     pub valid_frames: u64,
     pub position_decodes: u64,
     pub positions_skipped: u64,
+    /// CPR fixes dropped by the jitter gate as implausible jumps.
+    pub positions_rejected: u64,
+
+    // Receiver range/coverage accounting
+    /// Furthest confirmed position from `ref_lat`/`ref_lon`, in nautical
+    /// miles.
+    max_range_nm: f64,
+    /// Furthest confirmed range per 10-degree bearing sector from the
+    /// receiver, indexed by `(bearing / RANGE_SECTOR_WIDTH_DEG) as usize`.
+    sector_max_range_nm: [f64; RANGE_SECTORS],
 }
 
 impl Tracker {
@@ -176,19 +483,44 @@ impl Tracker {
         ref_lat: Option<f64>,
         ref_lon: Option<f64>,
         min_position_interval: f64,
+    ) -> Self {
+        Self::with_position_window(
+            receiver_id,
+            capture_id,
+            ref_lat,
+            ref_lon,
+            min_position_interval,
+            DEFAULT_POSITION_WINDOW,
+        )
+    }
+
+    /// Like `new`, but with an explicit position jitter/smoothing window —
+    /// pass 1 to disable smoothing for latency-sensitive callers.
+    pub fn with_position_window(
+        receiver_id: Option<i64>,
+        capture_id: Option<i64>,
+        ref_lat: Option<f64>,
+        ref_lon: Option<f64>,
+        min_position_interval: f64,
+        position_window: usize,
     ) -> Self {
         Tracker {
             aircraft: std::collections::HashMap::new(),
             receiver_id,
             capture_id,
+            ref_explicit: ref_lat.is_some() && ref_lon.is_some(),
             ref_lat,
             ref_lon,
             min_position_interval,
+            position_window: position_window.max(1),
             last_stored: std::collections::HashMap::new(),
             total_frames: 0,
             valid_frames: 0,
             position_decodes: 0,
             positions_skipped: 0,
+            positions_rejected: 0,
+            max_range_nm: 0.0,
+            sector_max_range_nm: [0.0; RANGE_SECTORS],
         }
     }
 
@@ -209,7 +541,7 @@ impl Tracker {
         // Get or create aircraft state
         let is_new = !self.aircraft.contains_key(&icao);
         if is_new {
-            let ac = AircraftState::new(icao, timestamp);
+            let ac = AircraftState::with_jitter_window(icao, timestamp, self.position_window);
             events.push(TrackEvent::NewAircraft {
                 icao,
                 country: ac.country,
@@ -223,6 +555,10 @@ impl Tracker {
         let ac = self.aircraft.get_mut(&icao).unwrap();
         ac.last_seen = timestamp;
         ac.message_count += 1;
+        ac.expire_fields(timestamp);
+        if let Some(power) = frame.signal_level {
+            ac.record_signal(power);
+        }
 
         // Process message type
         match &msg {
@@ -234,11 +570,20 @@ impl Tracker {
                         ac.is_military = icao::is_military(&icao, Some(&cs));
                     }
                     ac.callsign = Some(cs);
+                    ac.callsign_time = timestamp;
                 }
+                ac.category = Some(emitter_category(m.tc, m.category));
             }
             DecodedMsg::Position(m) => {
+                ac.on_ground = false;
                 if let Some(alt) = m.altitude_ft {
                     ac.altitude_ft = Some(alt);
+                    ac.altitude_source = Some(m.altitude_source);
+                    match m.altitude_source {
+                        AltitudeSource::Barometric => ac.baro_altitude_ft = Some(alt),
+                        AltitudeSource::Gnss => ac.gnss_altitude_ft = Some(alt),
+                    }
+                    ac.altitude_time = timestamp;
                 }
 
                 // Store CPR frame
@@ -253,45 +598,146 @@ impl Tracker {
                 }
 
                 // Attempt position decode
-                if let Some((lat, lon)) = try_cpr_decode(ac, self.ref_lat, self.ref_lon) {
-                    ac.lat = Some(lat);
-                    ac.lon = Some(lon);
-                    self.position_decodes += 1;
-
-                    // Record for pattern detection (always)
-                    ac.position_history
-                        .push((timestamp, lat, lon, ac.altitude_ft));
-                    if ac.position_history.len() > MAX_HISTORY {
-                        let start = ac.position_history.len() - MAX_HISTORY;
-                        ac.position_history = ac.position_history[start..].to_vec();
+                if let Some(fix) = try_cpr_decode(ac, self.ref_lat, self.ref_lon) {
+                    // A fresh global pair is the authoritative source — use it
+                    // to (re-)anchor the auto-derived reference position so
+                    // later single-frame local decodes stay accurate.
+                    if let CprFix::Global(lat, lon) = fix {
+                        if !self.ref_explicit {
+                            self.ref_lat = Some(lat);
+                            self.ref_lon = Some(lon);
+                        }
+                    }
+                    let (lat, lon) = fix.latlon();
+
+                    if !ac.accepts_position(lat, lon, timestamp, ac.speed_kts) {
+                        // Implausible jump (e.g. a corrupt CPR decode) — drop
+                        // the fix rather than teleporting the aircraft.
+                        self.positions_rejected += 1;
+                    } else {
+                        ac.push_jitter_fix(lat, lon, timestamp);
+                        let (lat, lon) = ac.smoothed_position();
+                        ac.lat = Some(lat);
+                        ac.lon = Some(lon);
+                        ac.position_time = timestamp;
+                        self.position_decodes += 1;
+
+                        // Record for pattern detection (always)
+                        ac.position_history
+                            .push((timestamp, lat, lon, ac.altitude_ft));
+                        if ac.position_history.len() > MAX_HISTORY {
+                            let start = ac.position_history.len() - MAX_HISTORY;
+                            ac.position_history = ac.position_history[start..].to_vec();
+                        }
+
+                        // Downsample: only emit position event if enough time passed
+                        let last = self.last_stored.get(&icao).copied();
+                        if last.is_none()
+                            || timestamp - last.unwrap() >= self.min_position_interval
+                        {
+                            events.push(TrackEvent::PositionUpdate {
+                                icao,
+                                lat,
+                                lon,
+                                altitude_ft: ac.altitude_ft,
+                                altitude_source: ac.altitude_source,
+                                speed_kts: ac.speed_kts,
+                                heading_deg: ac.heading_deg,
+                                vertical_rate_fpm: ac.vertical_rate_fpm,
+                                vertical_rate_source: ac.vertical_rate_source,
+                                receiver_id: self.receiver_id,
+                                timestamp,
+                                on_ground: false,
+                            });
+                            self.last_stored.insert(icao, timestamp);
+                            self.record_range(lat, lon);
+                        } else {
+                            self.positions_skipped += 1;
+                        }
                     }
+                }
+            }
+            DecodedMsg::SurfacePosition(m) => {
+                ac.on_ground = true;
+                if let Some(mv) = m.movement_kts {
+                    ac.speed_kts = Some(mv);
+                    ac.velocity_time = timestamp;
+                }
+                if let Some(trk) = m.ground_track_deg {
+                    ac.heading_deg = Some(trk);
+                    ac.velocity_time = timestamp;
+                }
+
+                // Store surface CPR frame, kept separate from the airborne
+                // buffer above — the two use different zone sizes and must
+                // never be paired with each other.
+                if m.cpr_odd {
+                    ac.cpr_surface_odd_lat = Some(m.cpr_lat);
+                    ac.cpr_surface_odd_lon = Some(m.cpr_lon);
+                    ac.cpr_surface_odd_time = m.timestamp;
+                } else {
+                    ac.cpr_surface_even_lat = Some(m.cpr_lat);
+                    ac.cpr_surface_even_lon = Some(m.cpr_lon);
+                    ac.cpr_surface_even_time = m.timestamp;
+                }
+
+                // Surface CPR needs a nearby reference to disambiguate, so
+                // unlike airborne decode there's no reference-free fallback.
+                if let Some(fix) = try_cpr_decode_surface(ac, self.ref_lat, self.ref_lon) {
+                    let (lat, lon) = fix.latlon();
 
-                    // Downsample: only emit position event if enough time passed
-                    let last = self.last_stored.get(&icao).copied();
-                    if last.is_none() || timestamp - last.unwrap() >= self.min_position_interval {
-                        events.push(TrackEvent::PositionUpdate {
-                            icao,
-                            lat,
-                            lon,
-                            altitude_ft: ac.altitude_ft,
-                            speed_kts: ac.speed_kts,
-                            heading_deg: ac.heading_deg,
-                            vertical_rate_fpm: ac.vertical_rate_fpm,
-                            receiver_id: self.receiver_id,
-                            timestamp,
-                        });
-                        self.last_stored.insert(icao, timestamp);
+                    if !ac.accepts_position(lat, lon, timestamp, ac.speed_kts) {
+                        self.positions_rejected += 1;
                     } else {
-                        self.positions_skipped += 1;
+                        ac.push_jitter_fix(lat, lon, timestamp);
+                        let (lat, lon) = ac.smoothed_position();
+                        ac.lat = Some(lat);
+                        ac.lon = Some(lon);
+                        ac.altitude_ft = Some(0);
+                        ac.position_time = timestamp;
+                        self.position_decodes += 1;
+
+                        ac.position_history
+                            .push((timestamp, lat, lon, ac.altitude_ft));
+                        if ac.position_history.len() > MAX_HISTORY {
+                            let start = ac.position_history.len() - MAX_HISTORY;
+                            ac.position_history = ac.position_history[start..].to_vec();
+                        }
+
+                        let last = self.last_stored.get(&icao).copied();
+                        if last.is_none()
+                            || timestamp - last.unwrap() >= self.min_position_interval
+                        {
+                            events.push(TrackEvent::PositionUpdate {
+                                icao,
+                                lat,
+                                lon,
+                                altitude_ft: ac.altitude_ft,
+                                altitude_source: ac.altitude_source,
+                                speed_kts: ac.speed_kts,
+                                heading_deg: ac.heading_deg,
+                                vertical_rate_fpm: ac.vertical_rate_fpm,
+                                vertical_rate_source: ac.vertical_rate_source,
+                                receiver_id: self.receiver_id,
+                                timestamp,
+                                on_ground: true,
+                            });
+                            self.last_stored.insert(icao, timestamp);
+                            self.record_range(lat, lon);
+                        } else {
+                            self.positions_skipped += 1;
+                        }
                     }
                 }
             }
             DecodedMsg::Velocity(m) => {
                 if let Some(spd) = m.speed_kts {
                     ac.speed_kts = Some(spd);
+                    ac.velocity_time = timestamp;
                 }
                 if let Some(hdg) = m.heading_deg {
                     ac.heading_deg = Some(hdg);
+                    ac.velocity_time = timestamp;
                     ac.heading_history.push((timestamp, hdg));
                     if ac.heading_history.len() > MAX_HISTORY {
                         let start = ac.heading_history.len() - MAX_HISTORY;
@@ -300,16 +746,76 @@ impl Tracker {
                 }
                 if let Some(vr) = m.vertical_rate_fpm {
                     ac.vertical_rate_fpm = Some(vr);
+                    ac.vertical_rate_source = Some(m.vertical_rate_source);
+                    ac.velocity_time = timestamp;
                 }
             }
             DecodedMsg::Altitude(m) => {
                 if let Some(alt) = m.altitude_ft {
                     ac.altitude_ft = Some(alt);
+                    ac.altitude_time = timestamp;
                 }
             }
             DecodedMsg::Squawk(m) => {
                 ac.squawk = Some(m.squawk.clone());
+                ac.squawk_time = timestamp;
+            }
+            DecodedMsg::CommB(CommBMsg::Bds20(m)) => {
+                let cs = m.callsign.trim().to_string();
+                if !cs.is_empty() {
+                    if !ac.is_military {
+                        ac.is_military = icao::is_military(&icao, Some(&cs));
+                    }
+                    ac.callsign = Some(cs);
+                    ac.callsign_time = timestamp;
+                }
+            }
+            DecodedMsg::CommB(CommBMsg::Bds40(m)) => {
+                ac.selected_altitude_ft = m.mcp_altitude_ft.or(m.fms_altitude_ft);
+                ac.baro_setting_hpa = m.barometric_setting_mb;
+                ac.commb_state_time = timestamp;
+                events.push(TrackEvent::SelectedStateUpdate {
+                    icao,
+                    selected_altitude_ft: ac.selected_altitude_ft,
+                    baro_setting_hpa: ac.baro_setting_hpa,
+                    track_deg: ac.track_deg,
+                    mach: ac.mach,
+                    timestamp,
+                });
+            }
+            DecodedMsg::CommB(CommBMsg::Bds50(m)) => {
+                ac.track_deg = m.track_angle_deg;
+                ac.commb_state_time = timestamp;
+                events.push(TrackEvent::SelectedStateUpdate {
+                    icao,
+                    selected_altitude_ft: ac.selected_altitude_ft,
+                    baro_setting_hpa: ac.baro_setting_hpa,
+                    track_deg: ac.track_deg,
+                    mach: ac.mach,
+                    timestamp,
+                });
+            }
+            DecodedMsg::CommB(CommBMsg::Bds60(m)) => {
+                ac.mach = m.mach;
+                ac.commb_state_time = timestamp;
+                events.push(TrackEvent::SelectedStateUpdate {
+                    icao,
+                    selected_altitude_ft: ac.selected_altitude_ft,
+                    baro_setting_hpa: ac.baro_setting_hpa,
+                    track_deg: ac.track_deg,
+                    mach: ac.mach,
+                    timestamp,
+                });
+            }
+            DecodedMsg::AircraftStatus(AircraftStatusMsg::Emergency(m)) => {
+                ac.squawk = Some(m.squawk.clone());
+                ac.squawk_time = timestamp;
             }
+            // Target state (selected altitude/heading, autopilot modes) and
+            // ACAS RA data describe intent or a threat encounter rather than
+            // the aircraft's own state, so they aren't merged here either.
+            DecodedMsg::AircraftStatus(AircraftStatusMsg::AcasRa(_)) => {}
+            DecodedMsg::TargetState(_) => {}
         }
 
         // Emit aircraft update + sighting
@@ -320,6 +826,7 @@ impl Tracker {
             callsign: ac.callsign.clone(),
             squawk: ac.squawk.clone(),
             altitude_ft: ac.altitude_ft,
+            altitude_source: ac.altitude_source,
             timestamp,
         });
 
@@ -337,6 +844,17 @@ impl Tracker {
         active
     }
 
+    /// Expire per-field data (callsign, squawk, position, ...) across all
+    /// tracked aircraft whose fields haven't been refreshed within their own
+    /// timeout. Call this periodically (alongside `prune_stale`) so an
+    /// aircraft that's gone quiet on one message type but not others still
+    /// reports accurate field-level staleness via `get_active`.
+    pub fn expire_fields(&mut self, now: f64) {
+        for ac in self.aircraft.values_mut() {
+            ac.expire_fields(now);
+        }
+    }
+
     /// Remove stale aircraft from tracking. Returns count removed.
     pub fn prune_stale(&mut self, now: f64) -> usize {
         let stale: Vec<Icao> = self
@@ -351,6 +869,65 @@ impl Tracker {
         }
         count
     }
+
+    /// Fold a confirmed position into the receiver range histogram: update
+    /// the overall `max_range_nm` and the furthest range seen in that
+    /// position's bearing sector. A no-op until `ref_lat`/`ref_lon` are
+    /// known, since range is meaningless without a receiver to measure from.
+    fn record_range(&mut self, lat: f64, lon: f64) {
+        let (Some(ref_lat), Some(ref_lon)) = (self.ref_lat, self.ref_lon) else {
+            return;
+        };
+
+        let range_nm = haversine_nm(ref_lat, ref_lon, lat, lon);
+        if range_nm > self.max_range_nm {
+            self.max_range_nm = range_nm;
+        }
+
+        let bearing = bearing_deg(ref_lat, ref_lon, lat, lon);
+        let sector = ((bearing / RANGE_SECTOR_WIDTH_DEG) as usize).min(RANGE_SECTORS - 1);
+        if range_nm > self.sector_max_range_nm[sector] {
+            self.sector_max_range_nm[sector] = range_nm;
+        }
+    }
+
+    /// Receiver range/coverage statistics accumulated from confirmed
+    /// positions, for building a coverage polar plot.
+    pub fn range_stats(&self) -> RangeStats {
+        RangeStats {
+            max_range_nm: self.max_range_nm,
+            sector_max_range_nm: self.sector_max_range_nm,
+        }
+    }
+}
+
+/// Receiver range/coverage statistics: overall max range plus the furthest
+/// confirmed range in each 10-degree bearing sector (`sector_max_range_nm[0]`
+/// covers `[0, 10)` degrees, and so on), for plotting a coverage polar chart.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RangeStats {
+    pub max_range_nm: f64,
+    pub sector_max_range_nm: [f64; RANGE_SECTORS],
+}
+
+/// A decoded CPR position, tagged by which method produced it.
+#[derive(Debug, Clone, Copy)]
+enum CprFix {
+    /// Matched even/odd pair — unambiguous, the authoritative source.
+    Global(f64, f64),
+    /// Single-frame decode relative to a reference position — available
+    /// immediately (no pairing delay) and works for surface messages, but
+    /// only reliable within ~180nm of the reference (~45nm for surface
+    /// messages).
+    Local(f64, f64),
+}
+
+impl CprFix {
+    fn latlon(self) -> (f64, f64) {
+        match self {
+            CprFix::Global(lat, lon) | CprFix::Local(lat, lon) => (lat, lon),
+        }
+    }
 }
 
 /// Try to decode position from CPR frames (free function to avoid borrow conflicts).
@@ -358,7 +935,7 @@ fn try_cpr_decode(
     ac: &AircraftState,
     tracker_ref_lat: Option<f64>,
     tracker_ref_lon: Option<f64>,
-) -> Option<(f64, f64)> {
+) -> Option<CprFix> {
     // Try global decode if we have both even and odd
     if ac.cpr_even_lat.is_some() && ac.cpr_odd_lat.is_some() {
         let result = cpr::global_decode(
@@ -369,42 +946,82 @@ fn try_cpr_decode(
             ac.cpr_even_time,
             ac.cpr_odd_time,
         );
-        if result.is_some() {
-            return result;
+        if let Some((lat, lon)) = result {
+            return Some(CprFix::Global(lat, lon));
         }
     }
 
-    // Try local decode with reference position
+    // Fall back to a local decode of the single most recent CPR frame,
+    // against an explicit/auto-derived receiver reference or (failing
+    // that) the aircraft's last known position.
     let (ref_lat, ref_lon) = match (tracker_ref_lat, tracker_ref_lon) {
         (Some(lat), Some(lon)) => (lat, lon),
-        _ => {
-            // Fall back to last known position
-            match (ac.lat, ac.lon) {
-                (Some(lat), Some(lon)) => (lat, lon),
-                _ => return None,
-            }
-        }
+        _ => match (ac.lat, ac.lon) {
+            (Some(lat), Some(lon)) => (lat, lon),
+            _ => return None,
+        },
     };
 
     // Use the most recent CPR frame
-    if ac.cpr_odd_time >= ac.cpr_even_time {
-        if let Some(lat) = ac.cpr_odd_lat {
-            return Some(cpr::local_decode(
-                lat,
-                ac.cpr_odd_lon.unwrap(),
-                true,
-                ref_lat,
-                ref_lon,
-            ));
-        }
-    } else if let Some(lat) = ac.cpr_even_lat {
-        return Some(cpr::local_decode(
-            lat,
-            ac.cpr_even_lon.unwrap(),
-            false,
+    let local = if ac.cpr_odd_time >= ac.cpr_even_time {
+        ac.cpr_odd_lat
+            .map(|lat| cpr::local_decode(lat, ac.cpr_odd_lon.unwrap(), true, ref_lat, ref_lon))
+    } else {
+        ac.cpr_even_lat
+            .map(|lat| cpr::local_decode(lat, ac.cpr_even_lon.unwrap(), false, ref_lat, ref_lon))
+    };
+
+    // A local decode is only unambiguous within half a latitude zone of the
+    // reference it was decoded against; reject anything further out as a
+    // decode error rather than handing back a candidate the caller can't
+    // tell from a real (very long) jump.
+    local.filter(|&(lat, lon)| haversine_nm(ref_lat, ref_lon, lat, lon) <= LOCAL_DECODE_MAX_NM)
+        .map(|(lat, lon)| CprFix::Local(lat, lon))
+}
+
+/// Try to decode a surface position from the surface CPR buffer. Surface
+/// CPR's quarter-size zones make even a matched even/odd pair ambiguous
+/// without a nearby reference (see `cpr::global_decode_surface`), so unlike
+/// `try_cpr_decode` there's no reference-free fallback to the aircraft's
+/// last known position — a local fix against the reference is tried first
+/// since it's available every frame, falling back to a global pair only if
+/// the local fix is out of range.
+fn try_cpr_decode_surface(
+    ac: &AircraftState,
+    tracker_ref_lat: Option<f64>,
+    tracker_ref_lon: Option<f64>,
+) -> Option<CprFix> {
+    let (ref_lat, ref_lon) = (tracker_ref_lat?, tracker_ref_lon?);
+
+    let local = if ac.cpr_surface_odd_time >= ac.cpr_surface_even_time {
+        ac.cpr_surface_odd_lat.map(|lat| {
+            cpr::local_decode_surface(lat, ac.cpr_surface_odd_lon.unwrap(), true, ref_lat, ref_lon)
+        })
+    } else {
+        ac.cpr_surface_even_lat.map(|lat| {
+            cpr::local_decode_surface(lat, ac.cpr_surface_even_lon.unwrap(), false, ref_lat, ref_lon)
+        })
+    };
+    if let Some((lat, lon)) = local
+        .filter(|&(lat, lon)| haversine_nm(ref_lat, ref_lon, lat, lon) <= SURFACE_LOCAL_DECODE_MAX_NM)
+    {
+        return Some(CprFix::Local(lat, lon));
+    }
+
+    if ac.cpr_surface_even_lat.is_some() && ac.cpr_surface_odd_lat.is_some() {
+        let result = cpr::global_decode_surface(
+            ac.cpr_surface_even_lat.unwrap(),
+            ac.cpr_surface_even_lon.unwrap(),
+            ac.cpr_surface_odd_lat.unwrap(),
+            ac.cpr_surface_odd_lon.unwrap(),
+            ac.cpr_surface_even_time,
+            ac.cpr_surface_odd_time,
             ref_lat,
             ref_lon,
-        ));
+        );
+        if let Some((lat, lon)) = result {
+            return Some(CprFix::Global(lat, lon));
+        }
     }
 
     None
@@ -427,6 +1044,10 @@ mod tests {
         parse_frame_uncached(hex, ts, None).expect("valid frame")
     }
 
+    fn parse_with_signal(hex: &str, ts: f64, signal_level: f64) -> ModeFrame {
+        parse_frame_uncached(hex, ts, Some(signal_level)).expect("valid frame")
+    }
+
     #[test]
     fn test_new_aircraft_event() {
         let mut tracker = make_tracker();
@@ -487,6 +1108,9 @@ mod tests {
         let ac = &tracker.aircraft[&icao];
         assert!(ac.has_position(), "Should have position after CPR pair");
         assert_eq!(ac.altitude_ft, Some(38000));
+        assert_eq!(ac.altitude_source, Some(AltitudeSource::Barometric));
+        assert_eq!(ac.baro_altitude_ft, Some(38000));
+        assert_eq!(ac.gnss_altitude_ft, None);
 
         // Should have emitted a PositionUpdate
         assert!(
@@ -497,6 +1121,103 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_surface_position_local_decode_sets_on_ground() {
+        // Surface CPR needs a nearby reference to resolve the quadrant; an
+        // airborne-only tracker (no ref_lat/ref_lon) can't decode it. The
+        // reference is planted close to the frame's decoded fix (~0.46N,
+        // 0.70E) since surface local decode only trusts references within
+        // `SURFACE_LOCAL_DECODE_MAX_NM`, much tighter than airborne's.
+        let mut tracker = Tracker::new(None, None, Some(0.4), Some(0.6), 2.0);
+
+        // Crafted TC=6 surface frame: movement=1 (stopped), track valid at
+        // 90°, even frame, cpr_lat=40000, cpr_lon=60000.
+        let frame = parse("8D4840D6301A013880EA60A8D57A", 1.0);
+        let (_, events) = tracker.update(&frame);
+
+        let icao = [0x48, 0x40, 0xD6];
+        let ac = &tracker.aircraft[&icao];
+        assert!(ac.on_ground);
+        assert_eq!(ac.speed_kts, Some(0.0));
+        assert_eq!(ac.heading_deg, Some(90.0));
+        assert!(ac.has_position());
+
+        assert!(matches!(
+            events.iter().find(|e| matches!(e, TrackEvent::PositionUpdate { .. })),
+            Some(TrackEvent::PositionUpdate { on_ground: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_surface_position_rejects_distant_reference() {
+        // Same frame as `test_surface_position_local_decode_sets_on_ground`,
+        // but the reference is ~50nm from the decoded fix — inside the
+        // ~180nm airborne bound but outside `SURFACE_LOCAL_DECODE_MAX_NM`.
+        // Surface decode must reject it rather than accept a fix that far
+        // from a supposedly nearby receiver.
+        let mut tracker = Tracker::new(None, None, Some(0.0), Some(0.0), 2.0);
+
+        let frame = parse("8D4840D6301A013880EA60A8D57A", 1.0);
+        tracker.update(&frame);
+
+        let icao = [0x48, 0x40, 0xD6];
+        let ac = &tracker.aircraft[&icao];
+        assert!(ac.on_ground); // message type is still recorded
+        assert!(!ac.has_position(), "fix beyond the surface range should be rejected");
+    }
+
+    #[test]
+    fn test_surface_position_without_reference_does_not_decode() {
+        let mut tracker = make_tracker(); // no ref_lat/ref_lon
+
+        let frame = parse("8D4840D6301A013880EA60A8D57A", 1.0);
+        tracker.update(&frame);
+
+        let icao = [0x48, 0x40, 0xD6];
+        let ac = &tracker.aircraft[&icao];
+        assert!(ac.on_ground); // message type is still recorded
+        assert!(!ac.has_position());
+    }
+
+    #[test]
+    fn test_bds40_updates_selected_state_and_emits_event() {
+        let mut tracker = make_tracker();
+        let frame = parse("A0000000C1600030A80000000000", 1.0);
+        let (_, events) = tracker.update(&frame);
+
+        let icao = [0, 0, 0];
+        let ac = &tracker.aircraft[&icao];
+        assert_eq!(ac.selected_altitude_ft, Some(33472));
+        assert_eq!(ac.baro_setting_hpa, Some(1013.2));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            TrackEvent::SelectedStateUpdate { selected_altitude_ft: Some(33472), .. }
+        )));
+    }
+
+    #[test]
+    fn test_bds50_updates_track_deg() {
+        let mut tracker = make_tracker();
+        let frame = parse("A0000000FB7FCE408008DA000000", 1.0);
+        tracker.update(&frame);
+
+        let icao = [0, 0, 0];
+        assert_eq!(tracker.aircraft[&icao].track_deg, Some(-2.29));
+    }
+
+    #[test]
+    fn test_bds60_updates_mach_without_clobbering_other_fields() {
+        let mut tracker = make_tracker();
+        tracker.update(&parse("A0000000C1600030A80000000000", 1.0)); // BDS4,0 first
+        tracker.update(&parse("A0000000806B3504A0F000000000", 2.0)); // then BDS6,0
+
+        let icao = [0, 0, 0];
+        let ac = &tracker.aircraft[&icao];
+        assert_eq!(ac.mach, Some(0.07));
+        // Unrelated to BDS6,0 — should still hold the value BDS4,0 set.
+        assert_eq!(ac.selected_altitude_ft, Some(33472));
+    }
+
     #[test]
     fn test_velocity_update() {
         let mut tracker = make_tracker();
@@ -508,6 +1229,7 @@ mod tests {
         assert!(ac.speed_kts.is_some());
         assert!(ac.heading_deg.is_some());
         assert_eq!(ac.vertical_rate_fpm, Some(-832));
+        assert_eq!(ac.vertical_rate_source, Some(VerticalRateSource::Gnss));
     }
 
     #[test]
@@ -541,6 +1263,53 @@ mod tests {
         assert_eq!(tracker.aircraft.len(), 0);
     }
 
+    #[test]
+    fn test_expire_fields_clears_independently_per_timeout() {
+        let mut ac = AircraftState::new([0, 0, 0], 1.0);
+        ac.callsign = Some("KLM1023".into());
+        ac.callsign_time = 1.0;
+        ac.squawk = Some("7000".into());
+        ac.squawk_time = 1.0;
+        ac.lat = Some(52.0);
+        ac.lon = Some(4.0);
+        ac.position_time = 1.0;
+        ac.speed_kts = Some(450.0);
+        ac.velocity_time = 1.0;
+
+        // Position/velocity (30s timeout) are gone at 40s, but callsign/squawk
+        // (60s timeout) are still valid.
+        ac.expire_fields(41.0);
+        assert!(ac.lat.is_none());
+        assert!(ac.lon.is_none());
+        assert!(ac.speed_kts.is_none());
+        assert_eq!(ac.callsign.as_deref(), Some("KLM1023"));
+        assert_eq!(ac.squawk.as_deref(), Some("7000"));
+
+        // Past the 60s callsign/squawk timeout too.
+        ac.expire_fields(62.0);
+        assert!(ac.callsign.is_none());
+        assert!(ac.squawk.is_none());
+    }
+
+    #[test]
+    fn test_tracker_callsign_expires_while_aircraft_stays_active() {
+        let mut tracker = make_tracker();
+
+        let frame = parse("8D4840D6202CC371C32CE0576098", 1.0);
+        tracker.update(&frame);
+
+        let icao = [0x48, 0x40, 0xD6];
+        assert_eq!(tracker.aircraft[&icao].callsign.as_deref(), Some("KLM1023"));
+
+        // A later message keeps the aircraft active but doesn't refresh the
+        // callsign, so `expire_fields` should clear it once its own
+        // timeout elapses even though the aircraft isn't stale yet.
+        tracker.update(&parse("8D4840D6301A013880EA60A8D57A", 50.0));
+        tracker.expire_fields(62.0);
+        assert!(tracker.aircraft[&icao].callsign.is_none());
+        assert!(!tracker.aircraft[&icao].is_stale(62.0));
+    }
+
     #[test]
     fn test_get_active() {
         let mut tracker = make_tracker();
@@ -552,6 +1321,38 @@ mod tests {
         assert_eq!(tracker.get_active(62.0).len(), 0);
     }
 
+    #[test]
+    fn test_range_stats_no_reference_is_a_noop() {
+        let mut tracker = make_tracker(); // no ref_lat/ref_lon
+
+        tracker.update(&parse("8D40621D58C382D690C8AC2863A7", 1.0));
+        tracker.update(&parse("8D40621D58C386435CC412692AD6", 2.0));
+
+        let stats = tracker.range_stats();
+        assert_eq!(stats.max_range_nm, 0.0);
+        assert!(stats.sector_max_range_nm.iter().all(|&r| r == 0.0));
+    }
+
+    #[test]
+    fn test_range_stats_records_max_range_and_sector() {
+        let mut tracker = Tracker::new(None, None, Some(0.0), Some(0.0), 2.0);
+
+        tracker.update(&parse("8D40621D58C382D690C8AC2863A7", 1.0));
+        tracker.update(&parse("8D40621D58C386435CC412692AD6", 2.0));
+
+        let icao = [0x40, 0x62, 0x1D];
+        let ac = &tracker.aircraft[&icao];
+        let (lat, lon) = (ac.lat.unwrap(), ac.lon.unwrap());
+
+        let expected_range = haversine_nm(0.0, 0.0, lat, lon);
+        let expected_sector =
+            (bearing_deg(0.0, 0.0, lat, lon) / RANGE_SECTOR_WIDTH_DEG) as usize;
+
+        let stats = tracker.range_stats();
+        assert!((stats.max_range_nm - expected_range).abs() < 1e-6);
+        assert!((stats.sector_max_range_nm[expected_sector] - expected_range).abs() < 1e-6);
+    }
+
     #[test]
     fn test_position_downsampling() {
         let mut tracker = Tracker::new(None, None, None, None, 5.0);
@@ -650,4 +1451,155 @@ mod tests {
         // Netherlands address is not military
         assert!(!tracker.aircraft[&icao].is_military);
     }
+
+    #[test]
+    fn test_no_rssi_until_first_signal_sample() {
+        let mut tracker = make_tracker();
+        let frame = parse("8D4840D6202CC371C32CE0576098", 1.0); // signal_level: None
+        tracker.update(&frame);
+
+        let icao = [0x48, 0x40, 0xD6];
+        assert_eq!(tracker.aircraft[&icao].rssi_dbfs, None);
+    }
+
+    #[test]
+    fn test_rssi_single_sample() {
+        let mut tracker = make_tracker();
+        let frame = parse_with_signal("8D4840D6202CC371C32CE0576098", 1.0, 0.5);
+        tracker.update(&frame);
+
+        let icao = [0x48, 0x40, 0xD6];
+        let rssi = tracker.aircraft[&icao].rssi_dbfs.unwrap();
+        assert!((rssi - (10.0 * 0.5f64.log10())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rssi_window_averages_and_slides() {
+        let mut tracker = make_tracker();
+        let icao = [0x48, 0x40, 0xD6];
+
+        // Fill the window with 8 samples of 1.0 — mean is 1.0, so 0 dBFS.
+        for i in 0..8 {
+            let frame = parse_with_signal("8D4840D6202CC371C32CE0576098", i as f64, 1.0);
+            tracker.update(&frame);
+        }
+        assert!((tracker.aircraft[&icao].rssi_dbfs.unwrap() - 0.0).abs() < 1e-9);
+
+        // A 9th sample of 0.0 evicts the oldest 1.0, leaving 7 ones and a
+        // zero — mean 7/8, still comfortably above -1 dBFS.
+        let frame = parse_with_signal("8D4840D6202CC371C32CE0576098", 9.0, 0.0);
+        tracker.update(&frame);
+        let rssi = tracker.aircraft[&icao].rssi_dbfs.unwrap();
+        assert!((rssi - (10.0 * (7.0f64 / 8.0).log10())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_accepts_position_rejects_out_of_bounds() {
+        let ac = AircraftState::new([0, 0, 0], 1.0);
+        assert!(!ac.accepts_position(91.0, 0.0, 1.0, None));
+        assert!(!ac.accepts_position(0.0, 181.0, 1.0, None));
+    }
+
+    #[test]
+    fn test_accepts_position_first_fix_always_accepted() {
+        let ac = AircraftState::new([0, 0, 0], 1.0);
+        // No prior fix to compare against, however extreme.
+        assert!(ac.accepts_position(52.0, 4.0, 1.0, None));
+    }
+
+    #[test]
+    fn test_accepts_position_rejects_implausible_jump() {
+        let mut ac = AircraftState::new([0, 0, 0], 1.0);
+        ac.push_jitter_fix(52.0, 4.0, 1.0);
+        // ~3000nm away one second later implies an impossible speed.
+        assert!(!ac.accepts_position(52.0, 90.0, 2.0, None));
+    }
+
+    #[test]
+    fn test_accepts_position_accepts_plausible_move() {
+        let mut ac = AircraftState::new([0, 0, 0], 1.0);
+        ac.push_jitter_fix(52.0, 4.0, 1.0);
+        // About 130 meters away a second later — well within bounds.
+        assert!(ac.accepts_position(52.001, 4.001, 2.0, None));
+    }
+
+    #[test]
+    fn test_accepts_position_uses_reported_speed_when_known() {
+        let mut ac = AircraftState::new([0, 0, 0], 1.0);
+        ac.push_jitter_fix(52.0, 4.0, 1.0);
+        // 5 degrees (~300nm) in an hour implies ~300kts: under the generic
+        // 1200kts cap, but above the cap for a reported 100kts aircraft
+        // (100 * 1.5 + 100 = 250kts), which should catch the bad fix.
+        assert!(!ac.accepts_position(57.0, 4.0, 3601.0, Some(100.0)));
+        assert!(ac.accepts_position(57.0, 4.0, 3601.0, None));
+    }
+
+    #[test]
+    fn test_global_cpr_decode_auto_reanchors_reference() {
+        let mut tracker = make_tracker();
+        assert_eq!(tracker.ref_lat, None);
+        assert_eq!(tracker.ref_lon, None);
+
+        tracker.update(&parse("8D40621D58C382D690C8AC2863A7", 1.0));
+        tracker.update(&parse("8D40621D58C386435CC412692AD6", 2.0));
+
+        // No explicit --ref-lat/--ref-lon was given, so the first successful
+        // global decode should become the new reference.
+        assert!(tracker.ref_lat.is_some());
+        assert!(tracker.ref_lon.is_some());
+    }
+
+    #[test]
+    fn test_explicit_reference_is_never_overwritten() {
+        let mut tracker = Tracker::new(None, None, Some(0.0), Some(0.0), 2.0);
+
+        tracker.update(&parse("8D40621D58C382D690C8AC2863A7", 1.0));
+        tracker.update(&parse("8D40621D58C386435CC412692AD6", 2.0));
+
+        // A user-supplied reference position is authoritative and must
+        // survive even after a fresh global CPR fix comes in.
+        assert_eq!(tracker.ref_lat, Some(0.0));
+        assert_eq!(tracker.ref_lon, Some(0.0));
+    }
+
+    #[test]
+    fn test_local_decode_beyond_180nm_of_reference_is_discarded() {
+        // Against this reference the even frame decodes locally to
+        // ~(52.26N, 3.92E), ~235nm away: outside the ~180nm a single-frame
+        // local decode can resolve unambiguously, so the candidate should be
+        // dropped rather than handed back as a position.
+        let mut tracker = Tracker::new(None, None, Some(49.5), Some(-0.5), 2.0);
+
+        tracker.update(&parse("8D40621D58C382D690C8AC2863A7", 1.0));
+
+        let icao = [0x40, 0x62, 0x1D];
+        assert!(!tracker.aircraft[&icao].has_position());
+    }
+
+    #[test]
+    fn test_smoothed_position_is_component_wise_median() {
+        let mut ac = AircraftState::with_jitter_window([0, 0, 0], 1.0, 3);
+        ac.push_jitter_fix(10.0, 20.0, 1.0);
+        ac.push_jitter_fix(10.2, 19.8, 2.0);
+        ac.push_jitter_fix(9.8, 20.4, 3.0);
+
+        // Median of {10.0, 10.2, 9.8} is 10.0; median of {20.0, 19.8, 20.4} is 20.0.
+        assert_eq!(ac.smoothed_position(), (10.0, 20.0));
+    }
+
+    #[test]
+    fn test_smoothed_position_window_one_disables_smoothing() {
+        let mut ac = AircraftState::with_jitter_window([0, 0, 0], 1.0, 1);
+        ac.push_jitter_fix(10.0, 20.0, 1.0);
+        ac.push_jitter_fix(11.0, 21.0, 2.0);
+
+        // With a 1-fix window, the buffer only ever holds the latest fix.
+        assert_eq!(ac.smoothed_position(), (11.0, 21.0));
+    }
+
+    #[test]
+    fn test_position_window_is_configurable_via_tracker() {
+        let tracker = Tracker::with_position_window(None, None, None, None, 2.0, 1);
+        assert_eq!(tracker.position_window, 1);
+    }
 }
@@ -206,21 +206,109 @@ pub const CALLSIGN_CHARSET: &[u8; 64] =
 pub struct IdentificationMsg {
     pub icao: Icao,
     pub callsign: String,
+    /// Raw 3-bit CA sub-field; combine with `tc` via `emitter_category()`
+    /// to classify it (the same CA value means different things per TC).
     pub category: u8,
+    pub tc: u8,
     pub timestamp: f64,
 }
 
-/// TC 5-8 (surface) or TC 9-18/20-22 (airborne): CPR-encoded position.
+/// ADS-B emitter category (ICAO Annex 10 "Category Set A", TC=4 — the
+/// set almost all airborne transponders report). TC 1-3 (surface
+/// vehicles, obstacles, gliders/UAVs/etc.) fold into `Other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum EmitterCategory {
+    Unknown,
+    Light,
+    Small,
+    Large,
+    HighVortexLarge,
+    Heavy,
+    HighPerformance,
+    Rotorcraft,
+    Other,
+}
+
+impl std::fmt::Display for EmitterCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmitterCategory::Unknown => write!(f, "unknown"),
+            EmitterCategory::Light => write!(f, "light"),
+            EmitterCategory::Small => write!(f, "small"),
+            EmitterCategory::Large => write!(f, "large"),
+            EmitterCategory::HighVortexLarge => write!(f, "high vortex large"),
+            EmitterCategory::Heavy => write!(f, "heavy"),
+            EmitterCategory::HighPerformance => write!(f, "high performance"),
+            EmitterCategory::Rotorcraft => write!(f, "rotorcraft"),
+            EmitterCategory::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// Classify an identification message's `(tc, category)` pair into an
+/// `EmitterCategory`. Only TC=4 (Category Set A) is broken out; other
+/// type codes report surface vehicles/obstacles/gliders and fold into
+/// `Other`.
+pub fn emitter_category(tc: u8, category: u8) -> EmitterCategory {
+    if tc != 4 {
+        return EmitterCategory::Other;
+    }
+    match category {
+        1 => EmitterCategory::Light,
+        2 => EmitterCategory::Small,
+        3 => EmitterCategory::Large,
+        4 => EmitterCategory::HighVortexLarge,
+        5 => EmitterCategory::Heavy,
+        6 => EmitterCategory::HighPerformance,
+        7 => EmitterCategory::Rotorcraft,
+        _ => EmitterCategory::Unknown,
+    }
+}
+
+/// TC 9-18 (barometric) or TC 20-22 (GNSS): airborne CPR-encoded position.
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct PositionMsg {
     pub icao: Icao,
     pub altitude_ft: Option<i32>,
+    pub altitude_source: AltitudeSource,
     pub cpr_lat: u32,
     pub cpr_lon: u32,
     pub cpr_odd: bool,
     pub surveillance_status: u8,
     pub timestamp: f64,
-    pub is_surface: bool,
+}
+
+/// TC 5-8: surface position. CPR-encoded using quarter-size zones (see
+/// `cpr::local_decode_surface`/`global_decode_surface`) since there is no
+/// altitude to disambiguate against; movement and ground track replace
+/// the airborne velocity fields.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SurfacePositionMsg {
+    pub icao: Icao,
+    pub movement_kts: Option<f64>,
+    pub ground_track_deg: Option<f64>,
+    pub cpr_lat: u32,
+    pub cpr_lon: u32,
+    pub cpr_odd: bool,
+    pub timestamp: f64,
+}
+
+/// Source of a reported altitude: barometric (TC 9-18) or GNSS/HAE
+/// height-above-ellipsoid (TC 20-22). The two can differ by tens to
+/// hundreds of feet and shouldn't be conflated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AltitudeSource {
+    Barometric,
+    Gnss,
+}
+
+impl std::fmt::Display for AltitudeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AltitudeSource::Barometric => write!(f, "barometric"),
+            AltitudeSource::Gnss => write!(f, "GNSS"),
+        }
+    }
 }
 
 /// TC 19: Airborne velocity.
@@ -230,10 +318,32 @@ pub struct VelocityMsg {
     pub speed_kts: Option<f64>,
     pub heading_deg: Option<f64>,
     pub vertical_rate_fpm: Option<i32>,
+    pub vertical_rate_source: VerticalRateSource,
     pub speed_type: SpeedType,
+    /// GNSS-minus-barometric altitude difference, 25-ft resolution.
+    pub gnss_baro_diff_ft: Option<i32>,
     pub timestamp: f64,
 }
 
+/// Source of a reported vertical rate: barometric or geometric (GNSS), per
+/// the vertical rate source bit carried alongside the rate itself in both
+/// airborne-velocity subtypes. Mixing a geometric rate with a barometric
+/// altitude (or vice versa) produces a misleading climb/descent reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum VerticalRateSource {
+    Barometric,
+    Gnss,
+}
+
+impl std::fmt::Display for VerticalRateSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerticalRateSource::Barometric => write!(f, "barometric"),
+            VerticalRateSource::Gnss => write!(f, "GNSS"),
+        }
+    }
+}
+
 /// Speed type for velocity messages.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum SpeedType {
@@ -268,15 +378,190 @@ pub struct SquawkMsg {
     pub timestamp: f64,
 }
 
+/// BDS2,0: Comm-B aircraft identification (callsign only, no category).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommBIdentificationMsg {
+    pub icao: Icao,
+    pub callsign: String,
+    pub timestamp: f64,
+}
+
+/// BDS4,0: Selected vertical intention (MCP/FCU and FMS selected altitude,
+/// barometric pressure setting).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SelectedVerticalIntentMsg {
+    pub icao: Icao,
+    pub mcp_altitude_ft: Option<i32>,
+    pub fms_altitude_ft: Option<i32>,
+    pub barometric_setting_mb: Option<f64>,
+    pub timestamp: f64,
+}
+
+/// BDS5,0: Track and turn report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrackAndTurnMsg {
+    pub icao: Icao,
+    pub roll_angle_deg: Option<f64>,
+    pub track_angle_deg: Option<f64>,
+    pub ground_speed_kts: Option<f64>,
+    pub track_angle_rate_deg_s: Option<f64>,
+    pub true_airspeed_kts: Option<f64>,
+    pub timestamp: f64,
+}
+
+/// BDS6,0: Heading and speed report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HeadingAndSpeedMsg {
+    pub icao: Icao,
+    pub magnetic_heading_deg: Option<f64>,
+    pub indicated_airspeed_kts: Option<u32>,
+    pub mach: Option<f64>,
+    pub baro_altitude_rate_fpm: Option<i32>,
+    pub inertial_vertical_velocity_fpm: Option<i32>,
+    pub timestamp: f64,
+}
+
+/// TC 29 (subtype 1): Target state and status — selected altitude/heading,
+/// barometric pressure setting, and autopilot mode flags.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TargetStateMsg {
+    pub icao: Icao,
+    pub selected_altitude_ft: Option<i32>,
+    pub altitude_source_is_fms: bool,
+    pub barometric_setting_mb: Option<f64>,
+    pub selected_heading_deg: Option<f64>,
+    pub autopilot_engaged: bool,
+    pub vnav_mode: bool,
+    pub altitude_hold_mode: bool,
+    pub approach_mode: bool,
+    pub lnav_mode: bool,
+    pub timestamp: f64,
+}
+
+/// Emergency/priority status (TC 28 subtype 1, 3-bit field).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EmergencyState {
+    None,
+    General,
+    Medical,
+    MinimumFuel,
+    NoCommunications,
+    UnlawfulInterference,
+    Downed,
+    Reserved,
+}
+
+impl std::fmt::Display for EmergencyState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmergencyState::None => write!(f, "none"),
+            EmergencyState::General => write!(f, "general"),
+            EmergencyState::Medical => write!(f, "medical"),
+            EmergencyState::MinimumFuel => write!(f, "minimum fuel"),
+            EmergencyState::NoCommunications => write!(f, "no communications"),
+            EmergencyState::UnlawfulInterference => write!(f, "unlawful interference"),
+            EmergencyState::Downed => write!(f, "downed aircraft"),
+            EmergencyState::Reserved => write!(f, "reserved"),
+        }
+    }
+}
+
+/// TC 28 subtype 1: Emergency/priority status and Mode-A squawk.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EmergencyStatusMsg {
+    pub icao: Icao,
+    pub emergency_state: EmergencyState,
+    pub squawk: String,
+    pub timestamp: f64,
+}
+
+/// TC 28 subtype 2: TCAS/ACAS resolution advisory.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AcasRaMsg {
+    pub icao: Icao,
+    /// 14-bit bitmask of currently active resolution advisories.
+    pub active_ra: u16,
+    pub ra_terminated: bool,
+    pub multiple_threats: bool,
+    pub threat_icao: Option<Icao>,
+    pub threat_altitude_ft: Option<i32>,
+    pub threat_range_nmi: Option<f64>,
+    pub threat_bearing_deg: Option<u32>,
+    pub timestamp: f64,
+}
+
+/// TC 28: Aircraft status, either an emergency/priority report (subtype 1)
+/// or an ACAS resolution advisory (subtype 2).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "subtype")]
+pub enum AircraftStatusMsg {
+    Emergency(EmergencyStatusMsg),
+    AcasRa(AcasRaMsg),
+}
+
+impl AircraftStatusMsg {
+    /// Get the ICAO address from either aircraft status subtype.
+    pub fn icao(&self) -> &Icao {
+        match self {
+            AircraftStatusMsg::Emergency(m) => &m.icao,
+            AircraftStatusMsg::AcasRa(m) => &m.icao,
+        }
+    }
+
+    /// Get the timestamp from either aircraft status subtype.
+    pub fn timestamp(&self) -> f64 {
+        match self {
+            AircraftStatusMsg::Emergency(m) => m.timestamp,
+            AircraftStatusMsg::AcasRa(m) => m.timestamp,
+        }
+    }
+}
+
+/// Union of the Comm-B (BDS) register messages `decode()` can classify.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "bds")]
+pub enum CommBMsg {
+    Bds20(CommBIdentificationMsg),
+    Bds40(SelectedVerticalIntentMsg),
+    Bds50(TrackAndTurnMsg),
+    Bds60(HeadingAndSpeedMsg),
+}
+
+impl CommBMsg {
+    /// Get the ICAO address from any Comm-B message type.
+    pub fn icao(&self) -> &Icao {
+        match self {
+            CommBMsg::Bds20(m) => &m.icao,
+            CommBMsg::Bds40(m) => &m.icao,
+            CommBMsg::Bds50(m) => &m.icao,
+            CommBMsg::Bds60(m) => &m.icao,
+        }
+    }
+
+    /// Get the timestamp from any Comm-B message type.
+    pub fn timestamp(&self) -> f64 {
+        match self {
+            CommBMsg::Bds20(m) => m.timestamp,
+            CommBMsg::Bds40(m) => m.timestamp,
+            CommBMsg::Bds50(m) => m.timestamp,
+            CommBMsg::Bds60(m) => m.timestamp,
+        }
+    }
+}
+
 /// Union type for all decoded messages.
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(tag = "type")]
 pub enum DecodedMsg {
     Identification(IdentificationMsg),
     Position(PositionMsg),
+    SurfacePosition(SurfacePositionMsg),
     Velocity(VelocityMsg),
     Altitude(AltitudeMsg),
     Squawk(SquawkMsg),
+    CommB(CommBMsg),
+    TargetState(TargetStateMsg),
+    AircraftStatus(AircraftStatusMsg),
 }
 
 impl DecodedMsg {
@@ -285,9 +570,13 @@ impl DecodedMsg {
         match self {
             DecodedMsg::Identification(m) => &m.icao,
             DecodedMsg::Position(m) => &m.icao,
+            DecodedMsg::SurfacePosition(m) => &m.icao,
             DecodedMsg::Velocity(m) => &m.icao,
             DecodedMsg::Altitude(m) => &m.icao,
             DecodedMsg::Squawk(m) => &m.icao,
+            DecodedMsg::CommB(m) => m.icao(),
+            DecodedMsg::TargetState(m) => &m.icao,
+            DecodedMsg::AircraftStatus(m) => m.icao(),
         }
     }
 
@@ -296,9 +585,13 @@ impl DecodedMsg {
         match self {
             DecodedMsg::Identification(m) => m.timestamp,
             DecodedMsg::Position(m) => m.timestamp,
+            DecodedMsg::SurfacePosition(m) => m.timestamp,
             DecodedMsg::Velocity(m) => m.timestamp,
             DecodedMsg::Altitude(m) => m.timestamp,
             DecodedMsg::Squawk(m) => m.timestamp,
+            DecodedMsg::CommB(m) => m.timestamp(),
+            DecodedMsg::TargetState(m) => m.timestamp,
+            DecodedMsg::AircraftStatus(m) => m.timestamp(),
         }
     }
 }
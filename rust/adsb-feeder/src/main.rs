@@ -9,11 +9,44 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 
+use adsb_core::beast;
+use adsb_core::crc;
 use adsb_core::decode;
 use adsb_core::frame::{self, IcaoCache};
+use adsb_core::types::hex_decode;
 
 mod capture;
 
+/// Output encoding for raw (non-decoded) frames.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    /// Space-separated hex lines (the default, human-readable).
+    Hex,
+    /// Beast binary protocol, for piping into readsb/dump1090/tar1090.
+    Beast,
+}
+
+/// IQ sample wire format of an input file, for `adsb_core::demod::SampleFormat`.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum IqFormat {
+    /// RTL-SDR's native interleaved unsigned 8-bit IQ (the default).
+    U8,
+    /// Interleaved signed 16-bit little-endian IQ (Airspy, HackRF, SDRplay).
+    S16le,
+    /// Interleaved 32-bit float little-endian IQ (SoapySDR `CF32`).
+    F32le,
+}
+
+impl From<IqFormat> for adsb_core::demod::SampleFormat {
+    fn from(format: IqFormat) -> Self {
+        match format {
+            IqFormat::U8 => adsb_core::demod::SampleFormat::U8,
+            IqFormat::S16le => adsb_core::demod::SampleFormat::S16LE,
+            IqFormat::F32le => adsb_core::demod::SampleFormat::F32LE,
+        }
+    }
+}
+
 #[derive(Parser)]
 #[command(
     name = "adsb-feeder",
@@ -36,9 +69,17 @@ enum Commands {
         #[arg(long, default_value = "2000000")]
         sample_rate: u32,
 
+        /// IQ sample format of the input file
+        #[arg(long, value_enum, default_value = "u8")]
+        iq_format: IqFormat,
+
         /// Parse and decode frames (not just print hex)
         #[arg(short, long)]
         decode: bool,
+
+        /// Raw frame output format (ignored with --decode)
+        #[arg(long, value_enum, default_value = "hex")]
+        format: OutputFormat,
     },
 
     /// Live capture from RTL-SDR dongle (requires native-sdr feature)
@@ -59,6 +100,38 @@ enum Commands {
         /// Parse and decode frames (not just print hex)
         #[arg(short, long)]
         decode: bool,
+
+        /// Raw frame output format (ignored with --decode)
+        #[arg(long, value_enum, default_value = "hex")]
+        format: OutputFormat,
+    },
+
+    /// Live capture from a networked `rtl_tcp` server (no librtlsdr needed
+    /// on this machine)
+    Net {
+        /// rtl_tcp server hostname or IP
+        #[arg(long, default_value = "localhost")]
+        host: String,
+
+        /// rtl_tcp server port
+        #[arg(long, default_value = "1234")]
+        port: u16,
+
+        /// Gain in tenths of dB (e.g. 400 = 40.0 dB). Omit for AGC.
+        #[arg(long)]
+        gain: Option<i32>,
+
+        /// Frequency correction in PPM
+        #[arg(long, default_value = "0")]
+        ppm: i32,
+
+        /// Parse and decode frames (not just print hex)
+        #[arg(short, long)]
+        decode: bool,
+
+        /// Raw frame output format (ignored with --decode)
+        #[arg(long, value_enum, default_value = "hex")]
+        format: OutputFormat,
     },
 }
 
@@ -69,21 +142,32 @@ fn main() {
         Commands::Demod {
             file,
             sample_rate,
+            iq_format,
             decode: do_decode,
-        } => cmd_demod(file, sample_rate, do_decode),
+            format,
+        } => cmd_demod(file, sample_rate, iq_format, do_decode, format),
         #[cfg(feature = "native-sdr")]
         Commands::Live {
             device,
             gain,
             ppm,
             decode: do_decode,
-        } => cmd_live(device, gain, ppm, do_decode),
+            format,
+        } => cmd_live(device, gain, ppm, do_decode, format),
+        Commands::Net {
+            host,
+            port,
+            gain,
+            ppm,
+            decode: do_decode,
+            format,
+        } => cmd_net(host, port, gain, ppm, do_decode, format),
     }
 }
 
-fn cmd_demod(file: PathBuf, sample_rate: u32, do_decode: bool) {
+fn cmd_demod(file: PathBuf, sample_rate: u32, iq_format: IqFormat, do_decode: bool, format: OutputFormat) {
     let path_str = file.display().to_string();
-    let reader = capture::IQReader::new(&path_str, sample_rate);
+    let reader = capture::IQReader::new(&path_str, sample_rate, iq_format.into());
 
     let duration = reader.duration_seconds().unwrap_or(0.0);
     let n_samples = reader.n_samples().unwrap_or(0);
@@ -111,8 +195,14 @@ fn cmd_demod(file: PathBuf, sample_rate: u32, do_decode: bool) {
         let mut decoded_count = 0u64;
 
         for raw in &frames {
-            let parsed =
-                frame::parse_frame(&raw.hex_str, raw.timestamp, None, false, &mut icao_cache);
+            let parsed = frame::parse_frame(
+                &raw.hex_str,
+                raw.timestamp,
+                None,
+                false,
+                &mut icao_cache,
+                &crc::GLOBAL_CORRECTOR,
+            );
             if let Some(f) = parsed {
                 if let Some(msg) = decode::decode(&f) {
                     decoded_count += 1;
@@ -122,6 +212,8 @@ fn cmd_demod(file: PathBuf, sample_rate: u32, do_decode: bool) {
             }
         }
         eprintln!("{decoded_count} decoded messages");
+    } else if format == OutputFormat::Beast {
+        write_beast_frames(&frames);
     } else {
         for raw in &frames {
             println!(
@@ -132,10 +224,21 @@ fn cmd_demod(file: PathBuf, sample_rate: u32, do_decode: bool) {
     }
 }
 
-#[cfg(feature = "native-sdr")]
-fn cmd_live(device: u32, gain: Option<i32>, ppm: i32, do_decode: bool) {
-    use adsb_core::demod::NoiseFloorTracker;
+/// Write a batch of raw frames to stdout as Beast binary messages.
+fn write_beast_frames(frames: &[adsb_core::demod::RawFrame]) {
+    use std::io::Write;
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    for raw in frames {
+        if let Some(bytes) = hex_decode(&raw.hex_str) {
+            let encoded = beast::encode_beast_bytes(&bytes, raw.timestamp, Some(raw.signal_level));
+            let _ = out.write_all(&encoded);
+        }
+    }
+}
 
+#[cfg(feature = "native-sdr")]
+fn cmd_live(device: u32, gain: Option<i32>, ppm: i32, do_decode: bool, format: OutputFormat) {
     eprintln!("Opening RTL-SDR device {device} (1090 MHz, 2 MHz sample rate)");
     if let Some(g) = gain {
         eprintln!("  Gain: {:.1} dB", g as f64 / 10.0);
@@ -155,15 +258,47 @@ fn cmd_live(device: u32, gain: Option<i32>, ppm: i32, do_decode: bool) {
     };
 
     eprintln!("Streaming... (Ctrl+C to stop)");
+    run_live_stream(&mut source, do_decode, format);
+}
+
+fn cmd_net(host: String, port: u16, gain: Option<i32>, ppm: i32, do_decode: bool, format: OutputFormat) {
+    eprintln!("Connecting to rtl_tcp at {host}:{port} (1090 MHz, 2 MHz sample rate)");
+    if let Some(g) = gain {
+        eprintln!("  Gain: {:.1} dB", g as f64 / 10.0);
+    } else {
+        eprintln!("  Gain: AGC");
+    }
+
+    let mut source = match capture::RtlTcpCapture::connect(&host, port, 2_000_000, gain, ppm) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            eprintln!("Hint: Is rtl_tcp running and reachable at {host}:{port}?");
+            std::process::exit(1);
+        }
+    };
+
+    eprintln!("Streaming... (Ctrl+C to stop)");
+    run_live_stream(&mut source, do_decode, format);
+}
+
+/// Shared `demodulate_stream` driving loop for the live capture commands:
+/// feeds the source through the demodulator and prints raw, decoded, or
+/// Beast-binary-encoded frames as they're found.
+fn run_live_stream<R: std::io::Read>(source: &mut R, do_decode: bool, format: OutputFormat) {
+    use adsb_core::demod::NoiseFloorTracker;
+    use std::io::Write;
 
     let mut noise_tracker = NoiseFloorTracker::new();
     let mut icao_cache = IcaoCache::new(60.0);
     let mut frame_count = 0u64;
     let mut decoded_count = 0u64;
+    let stdout = std::io::stdout();
 
     let result = capture::demodulate_stream(
-        &mut source,
+        source,
         2_000_000,
+        adsb_core::demod::SampleFormat::U8,
         &mut noise_tracker,
         &mut |raw| {
             frame_count += 1;
@@ -174,6 +309,7 @@ fn cmd_live(device: u32, gain: Option<i32>, ppm: i32, do_decode: bool) {
                     Some(raw.signal_level),
                     false,
                     &mut icao_cache,
+                    &crc::GLOBAL_CORRECTOR,
                 );
                 if let Some(f) = parsed {
                     if let Some(msg) = decode::decode(&f) {
@@ -182,6 +318,12 @@ fn cmd_live(device: u32, gain: Option<i32>, ppm: i32, do_decode: bool) {
                         println!("  {:?}", msg);
                     }
                 }
+            } else if format == OutputFormat::Beast {
+                if let Some(bytes) = hex_decode(&raw.hex_str) {
+                    let encoded =
+                        beast::encode_beast_bytes(&bytes, raw.timestamp, Some(raw.signal_level));
+                    let _ = stdout.lock().write_all(&encoded);
+                }
             } else {
                 println!(
                     "{:.6} {} signal={:.0}",
@@ -0,0 +1,91 @@
+//! A small Bloom filter for ICAO presence checks, so `Database` can answer
+//! "have I seen this aircraft/position before?" without a SQL round trip on
+//! the hot path. A `false` answer is definite; a `true` answer may be a
+//! false positive, so callers still fall back to the authoritative SQL
+//! check in that case.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: usize,
+    num_hashes: usize,
+}
+
+impl BloomFilter {
+    /// Size a filter for `expected_items` entries at `false_positive_rate`
+    /// (e.g. 0.01 for 1%), using the standard optimal-bits/optimal-hashes
+    /// formulas.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let n = expected_items.max(1) as f64;
+        let num_bits = (-(n * false_positive_rate.ln()) / (std::f64::consts::LN_2 * std::f64::consts::LN_2))
+            .ceil()
+            .max(64.0) as usize;
+        let num_hashes = ((num_bits as f64 / n) * std::f64::consts::LN_2).round().clamp(1.0, 16.0) as usize;
+
+        BloomFilter {
+            bits: vec![0u64; num_bits.div_ceil(64)],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    /// Two independent hashes of `item`, combined via double hashing
+    /// (Kirsch/Mitzenmacher) to simulate `num_hashes` hash functions without
+    /// computing more than two.
+    fn hash_pair(item: &str) -> (u64, u64) {
+        let mut h1 = DefaultHasher::new();
+        item.hash(&mut h1);
+        let mut h2 = DefaultHasher::new();
+        item.hash(&mut h2);
+        1u8.hash(&mut h2);
+        (h1.finish(), h2.finish())
+    }
+
+    pub fn insert(&mut self, item: &str) {
+        let (h1, h2) = Self::hash_pair(item);
+        for i in 0..self.num_hashes {
+            let idx = (h1.wrapping_add((i as u64).wrapping_mul(h2)) % self.num_bits as u64) as usize;
+            self.bits[idx / 64] |= 1 << (idx % 64);
+        }
+    }
+
+    /// `false` is a definite "not present"; `true` may be a false positive.
+    pub fn contains(&self, item: &str) -> bool {
+        let (h1, h2) = Self::hash_pair(item);
+        (0..self.num_hashes).all(|i| {
+            let idx = (h1.wrapping_add((i as u64).wrapping_mul(h2)) % self.num_bits as u64) as usize;
+            self.bits[idx / 64] & (1 << (idx % 64)) != 0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains_after_insert() {
+        let mut filter = BloomFilter::new(100, 0.01);
+        filter.insert("ABC123");
+        assert!(filter.contains("ABC123"));
+    }
+
+    #[test]
+    fn test_definitely_absent_item_not_contained() {
+        let filter = BloomFilter::new(100, 0.01);
+        assert!(!filter.contains("NEVERSEEN"));
+    }
+
+    #[test]
+    fn test_low_false_positive_rate_on_disjoint_sets() {
+        let mut filter = BloomFilter::new(1000, 0.01);
+        for i in 0..1000 {
+            filter.insert(&format!("icao-{i}"));
+        }
+        let false_positives = (1000..11000).filter(|i| filter.contains(&format!("icao-{i}"))).count();
+        // Allow some slack over the nominal 1% target given hash skew.
+        assert!(false_positives < 300, "{false_positives} false positives out of 10000");
+    }
+}
@@ -4,12 +4,20 @@
 //! Every position and capture records which receiver heard it.
 
 use rusqlite::{params, Connection, Result as SqlResult};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use adsb_core::filter::haversine_nm;
 use adsb_core::tracker::TrackEvent;
-use adsb_core::types::{icao_to_string, Icao};
+use adsb_core::types::{icao_from_hex, icao_to_string, AltitudeSource, Icao};
+
+use crate::bloom::BloomFilter;
+use crate::query::{Cmp, Order, Query, Relation};
 
 const SCHEMA: &str = r#"
 CREATE TABLE IF NOT EXISTS receivers (
@@ -26,9 +34,14 @@ CREATE TABLE IF NOT EXISTS aircraft (
     icao TEXT PRIMARY KEY,
     registration TEXT,
     country TEXT,
+    type TEXT,
+    operator TEXT,
     is_military INTEGER DEFAULT 0,
     first_seen REAL NOT NULL,
-    last_seen REAL NOT NULL
+    last_seen REAL NOT NULL,
+    seen REAL NOT NULL,
+    seen_pos REAL,
+    active INTEGER NOT NULL DEFAULT 1
 );
 
 CREATE TABLE IF NOT EXISTS sightings (
@@ -80,6 +93,43 @@ CREATE TABLE IF NOT EXISTS events (
     timestamp REAL NOT NULL
 );
 
+CREATE TABLE IF NOT EXISTS logbook (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    icao TEXT NOT NULL REFERENCES aircraft(icao),
+    event_type TEXT NOT NULL,
+    lat REAL NOT NULL,
+    lon REAL NOT NULL,
+    altitude_ft INTEGER,
+    duration_sec REAL NOT NULL,
+    timestamp REAL NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS coverage (
+    receiver_id INTEGER NOT NULL REFERENCES receivers(id),
+    bearing_bucket INTEGER NOT NULL,
+    max_distance_km REAL NOT NULL,
+    max_altitude_ft INTEGER,
+    sample_count INTEGER NOT NULL,
+    updated_at REAL NOT NULL,
+    PRIMARY KEY (receiver_id, bearing_bucket)
+);
+
+CREATE TABLE IF NOT EXISTS registry (
+    icao TEXT PRIMARY KEY,
+    registration TEXT,
+    type TEXT,
+    operator TEXT
+);
+
+CREATE TABLE IF NOT EXISTS selected_state (
+    icao TEXT PRIMARY KEY REFERENCES aircraft(icao),
+    selected_altitude_ft INTEGER,
+    baro_setting_hpa REAL,
+    track_deg REAL,
+    mach REAL,
+    updated_at REAL NOT NULL
+);
+
 CREATE INDEX IF NOT EXISTS idx_positions_icao ON positions(icao);
 CREATE INDEX IF NOT EXISTS idx_positions_timestamp ON positions(timestamp);
 CREATE INDEX IF NOT EXISTS idx_positions_receiver ON positions(receiver_id);
@@ -88,8 +138,178 @@ CREATE INDEX IF NOT EXISTS idx_sightings_icao_capture ON sightings(icao, capture
 CREATE INDEX IF NOT EXISTS idx_events_icao ON events(icao);
 CREATE INDEX IF NOT EXISTS idx_events_type ON events(event_type);
 CREATE INDEX IF NOT EXISTS idx_aircraft_last_seen ON aircraft(last_seen);
+CREATE INDEX IF NOT EXISTS idx_logbook_icao ON logbook(icao);
 "#;
 
+/// Emitted by `Database::detect_takeoffs_landings`.
+pub const LOGBOOK_TAKEOFF: &str = "takeoff";
+pub const LOGBOOK_LANDING: &str = "landing";
+
+/// Emitted by `Database::upsert_aircraft` and `Database::expire_stale`,
+/// mirroring the Appeared/Disappeared state machine used in heliwatch.
+pub const EVENT_APPEARED: &str = "appeared";
+pub const EVENT_DISAPPEARED: &str = "disappeared";
+
+/// Emitted by `Database::upsert_sighting` when a standard emergency squawk
+/// is seen.
+pub const EVENT_EMERGENCY_SQUAWK: &str = "emergency_squawk";
+
+/// Standard 4-digit emergency transponder codes and the condition each one
+/// names, checked by `Database::upsert_sighting`.
+const EMERGENCY_SQUAWKS: &[(&str, &str)] = &[
+    ("7500", "Squawk 7500 - hijack"),
+    ("7600", "Squawk 7600 - radio failure"),
+    ("7700", "Squawk 7700 - general emergency"),
+];
+
+fn emergency_squawk_description(code: &str) -> Option<&'static str> {
+    EMERGENCY_SQUAWKS
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, desc)| *desc)
+}
+
+/// Ground speed above which a fix is considered airborne (OGN-style
+/// ground/air classifier — see `detect_takeoffs_landings`).
+const AIRBORNE_SPEED_KTS: f64 = 30.0;
+/// Consecutive fixes more than this far apart are treated as a stale track
+/// rather than a real ground/air transition.
+const MAX_TRANSITION_GAP_SEC: f64 = 60.0;
+
+/// Ground/air classifier for a `(lat, lon, altitude_ft, speed_kts,
+/// timestamp)` position fix, used by `Database::detect_takeoffs_landings`.
+/// A fix with no speed reported can't be classified and is treated as
+/// on-ground (conservative: no speed usually means no movement data).
+fn is_airborne(fix: &(f64, f64, Option<i32>, Option<f64>, f64)) -> bool {
+    fix.3.is_some_and(|kts| kts > AIRBORNE_SPEED_KTS)
+}
+
+/// Nautical miles to kilometers, for `Database::compute_coverage`'s
+/// `max_distance_km` (the rest of the crate works in nm via
+/// `adsb_core::filter::haversine_nm`).
+const NM_TO_KM: f64 = 1.852;
+
+/// Ground speed ceiling at sea level for `Database::add_position`'s
+/// plausibility check, in knots. No ADS-B-equipped aircraft flies this
+/// fast; an implied speed above this means a garbled CPR decode, not a
+/// real position.
+const BASE_SPEED_CEILING_KTS: f64 = 1400.0;
+/// The speed ceiling widens with altitude (high-altitude supersonic
+/// traffic, and CPR error magnitude growing with range) by this many
+/// knots per 1,000 ft.
+const ALTITUDE_SPEED_CEILING_KTS_PER_1000FT: f64 = 10.0;
+
+/// Maximum plausible ground speed for a fix at `altitude_ft`, used by
+/// `Database::add_position` and `Database::clean_positions` to reject
+/// positions that imply an aircraft teleporting.
+fn max_plausible_speed_kts(altitude_ft: Option<i32>) -> f64 {
+    let altitude_ft = altitude_ft.unwrap_or(0).max(0) as f64;
+    BASE_SPEED_CEILING_KTS + (altitude_ft / 1000.0) * ALTITUDE_SPEED_CEILING_KTS_PER_1000FT
+}
+
+/// False-positive rate for the ICAO-presence `BloomFilter`s rebuilt on
+/// `Database::open`. Lower trades memory for fewer hot-path lookups falling
+/// through to the (still-authoritative) SQL check.
+const BLOOM_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// Initial great-circle bearing from (lat1, lon1) to (lat2, lon2), in degrees
+/// clockwise from true north. Used by `Database::compute_coverage` to bin
+/// positions by direction from the receiver.
+fn initial_bearing_deg(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let d_lon = (lon2 - lon1).to_radians();
+    let y = d_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+    (y.atan2(x).to_degrees() + 360.0) % 360.0
+}
+
+/// Pass one aircraft's ascending-timestamp positions through a trailing
+/// jitter window of `window` points: each point is checked against the
+/// coordinate-wise median of the last `window` points (itself included)
+/// and rejected — replaced with the last accepted position — if it lies
+/// outside valid lat/lon ranges or more than `max_deviation_km` from that
+/// median. Mirrors the jitter-buffer technique used in trail rendering.
+fn smooth_trail(rows: &[PositionRow], window: usize, max_deviation_km: f64) -> Vec<PositionRow> {
+    if window < 2 {
+        return rows.to_vec();
+    }
+
+    let mut out = Vec::with_capacity(rows.len());
+    let mut last_good = rows.first().map(|r| (r.lat, r.lon));
+
+    for (i, row) in rows.iter().enumerate() {
+        let lo = i.saturating_sub(window - 1);
+        let win = &rows[lo..=i];
+        let mut lats: Vec<f64> = win.iter().map(|r| r.lat).collect();
+        let mut lons: Vec<f64> = win.iter().map(|r| r.lon).collect();
+        let median_lat = median(&mut lats);
+        let median_lon = median(&mut lons);
+
+        let in_range = (-90.0..=90.0).contains(&row.lat) && (-180.0..=180.0).contains(&row.lon);
+        let deviation_km = haversine_nm(row.lat, row.lon, median_lat, median_lon) * NM_TO_KM;
+
+        if in_range && deviation_km <= max_deviation_km {
+            last_good = Some((row.lat, row.lon));
+            out.push(row.clone());
+        } else if let Some((lat, lon)) = last_good {
+            let mut carried = row.clone();
+            carried.lat = lat;
+            carried.lon = lon;
+            out.push(carried);
+        } else {
+            out.push(row.clone());
+        }
+    }
+    out
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// One row of an offline ICAO registry, as read by `Database::import_registry`.
+#[derive(Debug, Deserialize)]
+struct RegistryEntry {
+    icao: String,
+    #[serde(default)]
+    registration: Option<String>,
+    #[serde(rename = "type", default)]
+    aircraft_type: Option<String>,
+    #[serde(default)]
+    operator: Option<String>,
+}
+
+/// Parse one line of a simple `icao,registration,type,operator` CSV export
+/// (no quoting or embedded commas, matching common bulk registry dumps).
+/// Blank fields are treated as absent; the header row and blank lines are
+/// skipped.
+fn parse_registry_csv_line(line: &str) -> Option<RegistryEntry> {
+    let fields: Vec<&str> = line.split(',').collect();
+    let icao = fields.first()?.trim();
+    if icao.is_empty() || icao.eq_ignore_ascii_case("icao") {
+        return None;
+    }
+    let field = |i: usize| {
+        fields
+            .get(i)
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    Some(RegistryEntry {
+        icao: icao.to_uppercase(),
+        registration: field(1),
+        aircraft_type: field(2),
+        operator: field(3),
+    })
+}
+
 fn now() -> f64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -97,11 +317,116 @@ fn now() -> f64 {
         .as_secs_f64()
 }
 
+/// One fix read by `Database::export_track_geojson`.
+struct TrackFix {
+    lat: f64,
+    lon: f64,
+    altitude_ft: Option<i32>,
+    speed_kts: Option<f64>,
+    heading_deg: Option<f64>,
+    timestamp: f64,
+}
+
+// ---------------------------------------------------------------------------
+// Spatial/altitude scoping
+// ---------------------------------------------------------------------------
+
+/// Optional lat/lon viewport bounds and altitude band, shared by the
+/// position/query/heatmap endpoints so map clients can scope requests to
+/// what's currently visible instead of pulling every stored position.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpatialFilter {
+    pub lat_min: Option<f64>,
+    pub lat_max: Option<f64>,
+    pub lon_min: Option<f64>,
+    pub lon_max: Option<f64>,
+    pub floor_ft: Option<i32>,
+    pub ceiling_ft: Option<i32>,
+}
+
+impl SpatialFilter {
+    /// True if `(lat, lon)` falls inside the viewport and `altitude_ft`
+    /// (when known) falls inside the altitude band. Positions with unknown
+    /// altitude are never excluded by a floor/ceiling.
+    pub fn matches(&self, lat: f64, lon: f64, altitude_ft: Option<i32>) -> bool {
+        if self.lat_min.is_some_and(|v| lat < v) {
+            return false;
+        }
+        if self.lat_max.is_some_and(|v| lat > v) {
+            return false;
+        }
+        if self.lon_min.is_some_and(|v| lon < v) {
+            return false;
+        }
+        if self.lon_max.is_some_and(|v| lon > v) {
+            return false;
+        }
+        if let Some(alt) = altitude_ft {
+            if self.floor_ft.is_some_and(|v| alt < v) {
+                return false;
+            }
+            if self.ceiling_ft.is_some_and(|v| alt > v) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Append SQL conditions and bound values for this filter onto the
+    /// dynamic-WHERE accumulators used throughout this module.
+    fn push_conditions(
+        &self,
+        conditions: &mut Vec<String>,
+        bind_values: &mut Vec<Box<dyn rusqlite::types::ToSql>>,
+        lat_col: &str,
+        lon_col: &str,
+        alt_col: &str,
+    ) {
+        if let Some(v) = self.lat_min {
+            conditions.push(format!("{lat_col} >= ?{}", bind_values.len() + 1));
+            bind_values.push(Box::new(v));
+        }
+        if let Some(v) = self.lat_max {
+            conditions.push(format!("{lat_col} <= ?{}", bind_values.len() + 1));
+            bind_values.push(Box::new(v));
+        }
+        if let Some(v) = self.lon_min {
+            conditions.push(format!("{lon_col} >= ?{}", bind_values.len() + 1));
+            bind_values.push(Box::new(v));
+        }
+        if let Some(v) = self.lon_max {
+            conditions.push(format!("{lon_col} <= ?{}", bind_values.len() + 1));
+            bind_values.push(Box::new(v));
+        }
+        if let Some(v) = self.floor_ft {
+            conditions.push(format!("({alt_col} IS NULL OR {alt_col} >= ?{})", bind_values.len() + 1));
+            bind_values.push(Box::new(v));
+        }
+        if let Some(v) = self.ceiling_ft {
+            conditions.push(format!("({alt_col} IS NULL OR {alt_col} <= ?{})", bind_values.len() + 1));
+            bind_values.push(Box::new(v));
+        }
+    }
+}
+
 /// SQLite database for ADS-B aircraft tracking data.
 pub struct Database {
     conn: Connection,
     autocommit: bool,
     pending: u32,
+    /// Per-ICAO (lat, lon, timestamp) of the last position `add_position`
+    /// accepted, consulted by its speed-plausibility check so the hot path
+    /// stays O(1) instead of re-querying `positions`.
+    last_position: HashMap<String, (f64, f64, f64)>,
+    /// Every ICAO ever upserted into `aircraft`, rebuilt from the table on
+    /// `open`. Consulted by `upsert_aircraft`/`contains_icao` so a
+    /// definite "not present" can skip a SQL lookup.
+    known_icaos: BloomFilter,
+    /// Every ICAO that has ever had a row in `positions`, rebuilt from the
+    /// table on `open`. Lets `prune_phantom_aircraft` skip its expensive
+    /// "never had a position" check for aircraft the filter already rules
+    /// out.
+    icaos_with_positions: BloomFilter,
 }
 
 impl Database {
@@ -120,10 +445,33 @@ impl Database {
         conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")?;
         conn.execute_batch(SCHEMA)?;
 
+        let aircraft_count: i64 = conn.query_row("SELECT COUNT(*) FROM aircraft", [], |r| r.get(0))?;
+        let mut known_icaos = BloomFilter::new(aircraft_count as usize, BLOOM_FALSE_POSITIVE_RATE);
+        {
+            let mut stmt = conn.prepare("SELECT icao FROM aircraft")?;
+            let rows = stmt.query_map([], |r| r.get::<_, String>(0))?;
+            for icao in rows.filter_map(|r| r.ok()) {
+                known_icaos.insert(&icao);
+            }
+        }
+
+        let positioned_count: i64 = conn.query_row("SELECT COUNT(DISTINCT icao) FROM positions", [], |r| r.get(0))?;
+        let mut icaos_with_positions = BloomFilter::new(positioned_count as usize, BLOOM_FALSE_POSITIVE_RATE);
+        {
+            let mut stmt = conn.prepare("SELECT DISTINCT icao FROM positions")?;
+            let rows = stmt.query_map([], |r| r.get::<_, String>(0))?;
+            for icao in rows.filter_map(|r| r.ok()) {
+                icaos_with_positions.insert(&icao);
+            }
+        }
+
         Ok(Database {
             conn,
             autocommit: true,
             pending: 0,
+            last_position: HashMap::new(),
+            known_icaos,
+            icaos_with_positions,
         })
     }
 
@@ -185,6 +533,7 @@ impl Database {
                     callsign,
                     squawk,
                     altitude_ft,
+                    altitude_source: _,
                     timestamp,
                 } => {
                     self.upsert_sighting(
@@ -201,11 +550,14 @@ impl Database {
                     lat,
                     lon,
                     altitude_ft,
+                    altitude_source: _,
                     speed_kts,
                     heading_deg,
                     vertical_rate_fpm,
+                    vertical_rate_source: _,
                     receiver_id,
                     timestamp,
+                    on_ground: _,
                 } => {
                     self.add_position(
                         icao,
@@ -219,6 +571,23 @@ impl Database {
                         *timestamp,
                     );
                 }
+                TrackEvent::SelectedStateUpdate {
+                    icao,
+                    selected_altitude_ft,
+                    baro_setting_hpa,
+                    track_deg,
+                    mach,
+                    timestamp,
+                } => {
+                    self.update_selected_state(
+                        icao,
+                        *selected_altitude_ft,
+                        *baro_setting_hpa,
+                        *track_deg,
+                        *mach,
+                        *timestamp,
+                    );
+                }
             }
         }
     }
@@ -254,7 +623,22 @@ impl Database {
     // Aircraft
     // -----------------------------------------------------------------------
 
-    /// Insert or update aircraft record.
+    /// True if this ICAO has definitely been stored by a previous
+    /// `upsert_aircraft` call. Backed by a Bloom filter rebuilt from
+    /// `aircraft` on `open`: `false` is a definite "never seen", but `true`
+    /// can be a false positive, so callers needing certainty still fall
+    /// back to a real lookup.
+    pub fn contains_icao(&self, icao: &Icao) -> bool {
+        self.known_icaos.contains(&icao_to_string(icao))
+    }
+
+    /// Insert or update aircraft record. Logs an `appeared` event the first
+    /// time an ICAO is seen, or when it reappears after `expire_stale` had
+    /// marked it inactive — giving downstream consumers a clean session
+    /// boundary instead of having to infer gaps from raw timestamps.
+    /// Consults `lookup_registry` to fill in type/registration/operator
+    /// from a previously imported registry, without overwriting values the
+    /// caller or a prior live sighting already supplied.
     pub fn upsert_aircraft(
         &mut self,
         icao: &Icao,
@@ -264,19 +648,162 @@ impl Database {
         timestamp: f64,
     ) {
         let icao_str = icao_to_string(icao);
+
+        // A definite "not present" means this is a fresh insert, so the
+        // was-it-active lookup (whose only purpose is deciding whether to
+        // log an `appeared` event) can be skipped outright.
+        let was_active: Option<i64> = if self.known_icaos.contains(&icao_str) {
+            self.conn
+                .query_row(
+                    "SELECT active FROM aircraft WHERE icao = ?1",
+                    params![icao_str],
+                    |r| r.get(0),
+                )
+                .ok()
+        } else {
+            None
+        };
+
+        let registry = self.lookup_registry(&icao_str);
+        let (reg_registration, reg_type, reg_operator) = match &registry {
+            Some(r) => (r.registration.as_deref(), r.aircraft_type.as_deref(), r.operator.as_deref()),
+            None => (None, None, None),
+        };
+
         let _ = self.conn.execute(
-            "INSERT INTO aircraft (icao, country, registration, is_military, first_seen, last_seen)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+            "INSERT INTO aircraft (icao, country, registration, type, operator, is_military, first_seen, last_seen, seen, active)
+             VALUES (?1, ?2, COALESCE(?3, ?6), ?7, ?8, ?4, ?5, ?5, ?5, 1)
              ON CONFLICT(icao) DO UPDATE SET
                  country = COALESCE(excluded.country, country),
-                 registration = COALESCE(excluded.registration, registration),
+                 registration = COALESCE(?3, registration, ?6),
+                 type = COALESCE(type, ?7),
+                 operator = COALESCE(operator, ?8),
                  is_military = MAX(is_military, excluded.is_military),
-                 last_seen = MAX(last_seen, excluded.last_seen)",
-            params![icao_str, country, registration, is_military as i32, timestamp],
+                 last_seen = MAX(last_seen, excluded.last_seen),
+                 seen = MAX(seen, excluded.seen),
+                 active = 1",
+            params![
+                icao_str,
+                country,
+                registration,
+                is_military as i32,
+                timestamp,
+                reg_registration,
+                reg_type,
+                reg_operator
+            ],
+        );
+        self.maybe_commit();
+        self.known_icaos.insert(&icao_str);
+
+        if was_active != Some(1) {
+            self.add_event(icao, EVENT_APPEARED, "Aircraft appeared", None, None, None, timestamp);
+        }
+    }
+
+    /// Upsert BDS4,0/5,0/6,0 selected/derived state. `None` fields leave the
+    /// existing stored value alone, since each BDS register only ever
+    /// refreshes a subset of these columns (e.g. BDS6,0 only sets `mach`).
+    pub fn update_selected_state(
+        &mut self,
+        icao: &Icao,
+        selected_altitude_ft: Option<i32>,
+        baro_setting_hpa: Option<f64>,
+        track_deg: Option<f64>,
+        mach: Option<f64>,
+        timestamp: f64,
+    ) {
+        let icao_str = icao_to_string(icao);
+        let _ = self.conn.execute(
+            "INSERT INTO selected_state (icao, selected_altitude_ft, baro_setting_hpa, track_deg, mach, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(icao) DO UPDATE SET
+                 selected_altitude_ft = COALESCE(?2, selected_altitude_ft),
+                 baro_setting_hpa = COALESCE(?3, baro_setting_hpa),
+                 track_deg = COALESCE(?4, track_deg),
+                 mach = COALESCE(?5, mach),
+                 updated_at = ?6",
+            params![icao_str, selected_altitude_ft, baro_setting_hpa, track_deg, mach, timestamp],
         );
         self.maybe_commit();
     }
 
+    // -----------------------------------------------------------------------
+    // Registry
+    // -----------------------------------------------------------------------
+
+    /// Look up an ICAO in the offline registry imported by `import_registry`.
+    /// Consulted by `upsert_aircraft` to backfill type/registration/operator
+    /// for newly or previously seen aircraft.
+    pub fn lookup_registry(&self, icao_hex: &str) -> Option<RegistryRow> {
+        self.conn
+            .query_row(
+                "SELECT registration, type, operator FROM registry WHERE icao = ?1",
+                params![icao_hex],
+                |r| {
+                    Ok(RegistryRow {
+                        registration: r.get(0)?,
+                        aircraft_type: r.get(1)?,
+                        operator: r.get(2)?,
+                    })
+                },
+            )
+            .ok()
+    }
+
+    /// Stream a bulk ICAO registry (24-bit hex ICAO keyed) from a JSON array
+    /// or a simple CSV export into the `registry` table, as in the advisory
+    /// circular `create_aircraft_info_db` flow. Existing rows are updated
+    /// without clobbering fields the new row leaves blank. Rows are written
+    /// with autocommit off and `flush`ed every 50,000 rows, with progress
+    /// printed at each flush, so the import is not one single transaction.
+    /// Returns the number of rows read.
+    pub fn import_registry(&mut self, path: &Path) -> std::io::Result<usize> {
+        let entries: Box<dyn Iterator<Item = RegistryEntry>> =
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                let file = File::open(path)?;
+                let values: Vec<RegistryEntry> =
+                    serde_json::from_reader(BufReader::new(file)).map_err(std::io::Error::other)?;
+                Box::new(values.into_iter())
+            } else {
+                let file = File::open(path)?;
+                let reader = BufReader::new(file);
+                Box::new(
+                    reader
+                        .lines()
+                        .map_while(|l| l.ok())
+                        .filter_map(|line| parse_registry_csv_line(&line)),
+                )
+            };
+
+        let was_autocommit = self.autocommit;
+        self.set_autocommit(false);
+
+        let mut imported = 0usize;
+        for entry in entries {
+            let _ = self.conn.execute(
+                "INSERT INTO registry (icao, registration, type, operator)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(icao) DO UPDATE SET
+                     registration = COALESCE(excluded.registration, registration),
+                     type = COALESCE(excluded.type, type),
+                     operator = COALESCE(excluded.operator, operator)",
+                params![entry.icao, entry.registration, entry.aircraft_type, entry.operator],
+            );
+            imported += 1;
+            self.maybe_commit();
+
+            if imported.is_multiple_of(50_000) {
+                self.flush();
+                eprintln!("import_registry: {imported} rows imported");
+            }
+        }
+
+        self.flush();
+        self.set_autocommit(was_autocommit);
+        Ok(imported)
+    }
+
     pub fn get_aircraft(&self, icao_hex: &str) -> Option<AircraftRow> {
         self.conn
             .query_row(
@@ -303,10 +830,53 @@ impl Database {
             .unwrap_or(0)
     }
 
+    /// Find active aircraft whose `last_seen` is older than `max_age_sec`,
+    /// log a `disappeared` event for each, and clear their active flag —
+    /// mirroring the Appeared/Disappeared state machine used in heliwatch.
+    /// Returns the number of aircraft expired.
+    pub fn expire_stale(&mut self, max_age_sec: f64) -> usize {
+        let cutoff = now() - max_age_sec;
+        let mut stmt = self
+            .conn
+            .prepare("SELECT icao FROM aircraft WHERE active = 1 AND last_seen < ?1")
+            .unwrap();
+        let stale: Vec<String> = stmt
+            .query_map(params![cutoff], |r| r.get(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(stmt);
+
+        let expired_at = now();
+        for icao_hex in &stale {
+            if let Some(icao) = icao_from_hex(icao_hex) {
+                self.add_event(
+                    &icao,
+                    EVENT_DISAPPEARED,
+                    "Aircraft disappeared",
+                    None,
+                    None,
+                    None,
+                    expired_at,
+                );
+            }
+            let _ = self
+                .conn
+                .execute("UPDATE aircraft SET active = 0 WHERE icao = ?1", params![icao_hex]);
+        }
+        self.maybe_commit();
+        stale.len()
+    }
+
     // -----------------------------------------------------------------------
     // Positions
     // -----------------------------------------------------------------------
 
+    /// Insert a position, rejecting it if the implied ground speed from the
+    /// aircraft's last accepted position (see `max_plausible_speed_kts`)
+    /// is impossible — a garbled CPR decode rather than a real fix. The
+    /// last-accepted position is cached per ICAO so this check stays O(1)
+    /// on the hot path. Returns whether the position was accepted.
     pub fn add_position(
         &mut self,
         icao: &Icao,
@@ -318,14 +888,78 @@ impl Database {
         vertical_rate_fpm: Option<i32>,
         receiver_id: Option<i64>,
         timestamp: f64,
-    ) {
+    ) -> bool {
         let icao_str = icao_to_string(icao);
+
+        if let Some(&(last_lat, last_lon, last_ts)) = self.last_position.get(&icao_str) {
+            let elapsed_hr = (timestamp - last_ts) / 3600.0;
+            if elapsed_hr > 0.0 {
+                let implied_kts = haversine_nm(last_lat, last_lon, lat, lon) / elapsed_hr;
+                if implied_kts > max_plausible_speed_kts(altitude_ft) {
+                    return false;
+                }
+            }
+        }
+        self.last_position.insert(icao_str.clone(), (lat, lon, timestamp));
+
         let _ = self.conn.execute(
             "INSERT INTO positions (icao, receiver_id, lat, lon, altitude_ft, speed_kts, heading_deg, vertical_rate_fpm, timestamp)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![icao_str, receiver_id, lat, lon, altitude_ft, speed_kts, heading_deg, vertical_rate_fpm, timestamp],
         );
+        let _ = self.conn.execute(
+            "UPDATE aircraft SET seen_pos = MAX(COALESCE(seen_pos, 0), ?1) WHERE icao = ?2",
+            params![timestamp, icao_str],
+        );
+        self.maybe_commit();
+        self.icaos_with_positions.insert(&icao_str);
+        true
+    }
+
+    /// Retroactively remove implausible position jumps from the last
+    /// `hours` of stored positions, using the same ground-speed ceiling as
+    /// `add_position` (see `max_plausible_speed_kts`) chained per ICAO in
+    /// timestamp order. Returns the number of rows deleted.
+    pub fn clean_positions(&mut self, hours: f64) -> usize {
+        let cutoff = now() - (hours * 3600.0);
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, icao, lat, lon, altitude_ft, timestamp FROM positions
+                 WHERE timestamp >= ?1 ORDER BY icao, timestamp ASC",
+            )
+            .unwrap();
+
+        let rows: Vec<(i64, String, f64, f64, Option<i32>, f64)> = stmt
+            .query_map(params![cutoff], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?, r.get(5)?))
+            })
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(stmt);
+
+        let mut last: HashMap<String, (f64, f64, f64)> = HashMap::new();
+        let mut to_delete = Vec::new();
+        for (id, icao, lat, lon, altitude_ft, timestamp) in &rows {
+            if let Some(&(last_lat, last_lon, last_ts)) = last.get(icao) {
+                let elapsed_hr = (timestamp - last_ts) / 3600.0;
+                if elapsed_hr > 0.0 {
+                    let implied_kts = haversine_nm(last_lat, last_lon, *lat, *lon) / elapsed_hr;
+                    if implied_kts > max_plausible_speed_kts(*altitude_ft) {
+                        to_delete.push(*id);
+                        continue;
+                    }
+                }
+            }
+            last.insert(icao.clone(), (*lat, *lon, *timestamp));
+        }
+
+        for id in &to_delete {
+            let _ = self.conn.execute("DELETE FROM positions WHERE id = ?1", params![id]);
+        }
         self.maybe_commit();
+        to_delete.len()
     }
 
     pub fn get_positions(&self, icao_hex: &str, limit: i64) -> Vec<PositionRow> {
@@ -421,6 +1055,215 @@ impl Database {
             .unwrap_or(0)
     }
 
+    // -----------------------------------------------------------------------
+    // Logbook
+    // -----------------------------------------------------------------------
+
+    /// Scan `icao_hex`'s stored positions in chronological order and record
+    /// takeoff/landing transitions into the `logbook` table.
+    ///
+    /// Borrows the OGN-python takeoff/landing heuristic: a fix is airborne
+    /// when its ground speed is above `AIRBORNE_SPEED_KTS`, on ground
+    /// otherwise. A ground->air transition logs a takeoff, air->ground logs
+    /// a landing, each stamped with the new fix's position/time. Fixes more
+    /// than `MAX_TRANSITION_GAP_SEC` apart are treated as a stale track and
+    /// never produce a transition, to avoid false logbook entries from
+    /// reception gaps. Returns the number of logbook entries added.
+    pub fn detect_takeoffs_landings(&mut self, icao_hex: &str) -> usize {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT lat, lon, altitude_ft, speed_kts, timestamp
+                 FROM positions WHERE icao = ?1 ORDER BY timestamp ASC",
+            )
+            .unwrap();
+        let fixes: Vec<(f64, f64, Option<i32>, Option<f64>, f64)> = stmt
+            .query_map(params![icao_hex], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?))
+            })
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(stmt);
+
+        let icao = match icao_from_hex(icao_hex) {
+            Some(icao) => icao,
+            None => return 0,
+        };
+
+        let mut added = 0;
+        for pair in fixes.windows(2) {
+            let (prev, curr) = (&pair[0], &pair[1]);
+            let gap = curr.4 - prev.4;
+            if gap <= 0.0 || gap > MAX_TRANSITION_GAP_SEC {
+                continue;
+            }
+
+            let event_type = match (is_airborne(prev), is_airborne(curr)) {
+                (false, true) => LOGBOOK_TAKEOFF,
+                (true, false) => LOGBOOK_LANDING,
+                _ => continue,
+            };
+
+            self.add_logbook_entry(&icao, event_type, curr.0, curr.1, curr.2, gap, curr.4);
+            added += 1;
+        }
+        added
+    }
+
+    fn add_logbook_entry(
+        &mut self,
+        icao: &Icao,
+        event_type: &str,
+        lat: f64,
+        lon: f64,
+        altitude_ft: Option<i32>,
+        duration_sec: f64,
+        timestamp: f64,
+    ) {
+        let icao_str = icao_to_string(icao);
+        let _ = self.conn.execute(
+            "INSERT INTO logbook (icao, event_type, lat, lon, altitude_ft, duration_sec, timestamp)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![icao_str, event_type, lat, lon, altitude_ft, duration_sec, timestamp],
+        );
+        self.maybe_commit();
+    }
+
+    /// Get logbook entries, most recent first, optionally filtered to one
+    /// aircraft.
+    pub fn get_logbook(&self, icao_hex: Option<&str>, limit: i64) -> Vec<LogbookRow> {
+        let sql = if icao_hex.is_some() {
+            "SELECT id, icao, event_type, lat, lon, altitude_ft, duration_sec, timestamp
+             FROM logbook WHERE icao = ?1 ORDER BY timestamp DESC LIMIT ?2"
+        } else {
+            "SELECT id, icao, event_type, lat, lon, altitude_ft, duration_sec, timestamp
+             FROM logbook ORDER BY timestamp DESC LIMIT ?2"
+        };
+
+        let mut stmt = self.conn.prepare(sql).unwrap();
+        let ic = icao_hex.unwrap_or("");
+
+        stmt.query_map(params![ic, limit], |r| {
+            Ok(LogbookRow {
+                id: r.get(0)?,
+                icao: r.get(1)?,
+                event_type: r.get(2)?,
+                lat: r.get(3)?,
+                lon: r.get(4)?,
+                altitude_ft: r.get(5)?,
+                duration_sec: r.get(6)?,
+                timestamp: r.get(7)?,
+            })
+        })
+        .unwrap()
+        .filter_map(|r| r.ok())
+        .collect()
+    }
+
+    pub fn count_logbook(&self) -> i64 {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM logbook", [], |r| r.get(0))
+            .unwrap_or(0)
+    }
+
+    // -----------------------------------------------------------------------
+    // Coverage
+    // -----------------------------------------------------------------------
+
+    /// Recompute `receiver_id`'s reception range by bearing, an "ognrange"-
+    /// style polar coverage polygon: every position it's heard is binned by
+    /// great-circle bearing from the receiver into `sectors` equal-width
+    /// buckets, keeping the farthest (haversine) distance seen in each.
+    /// Skips receivers with no recorded lat/lon, since there's no reference
+    /// point to bear from. Replaces any previously computed coverage for
+    /// this receiver. Returns the number of buckets with at least one
+    /// sample.
+    pub fn compute_coverage(&mut self, receiver_id: i64, sectors: usize) -> usize {
+        if sectors == 0 {
+            return 0;
+        }
+
+        let receiver: Option<(Option<f64>, Option<f64>)> = self
+            .conn
+            .query_row(
+                "SELECT lat, lon FROM receivers WHERE id = ?1",
+                params![receiver_id],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .ok();
+        let (rx_lat, rx_lon) = match receiver {
+            Some((Some(lat), Some(lon))) => (lat, lon),
+            _ => return 0,
+        };
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT lat, lon, altitude_ft FROM positions WHERE receiver_id = ?1")
+            .unwrap();
+        let fixes: Vec<(f64, f64, Option<i32>)> = stmt
+            .query_map(params![receiver_id], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(stmt);
+
+        // (max_distance_km, altitude at that max, sample_count)
+        let mut buckets: HashMap<usize, (f64, Option<i32>, i64)> = HashMap::new();
+        let sector_width = 360.0 / sectors as f64;
+        for (lat, lon, altitude_ft) in fixes {
+            let distance_km = haversine_nm(rx_lat, rx_lon, lat, lon) * NM_TO_KM;
+            let bearing = initial_bearing_deg(rx_lat, rx_lon, lat, lon);
+            let bucket = ((bearing / sector_width) as usize).min(sectors - 1);
+
+            let entry = buckets.entry(bucket).or_insert((0.0, None, 0));
+            entry.2 += 1;
+            if distance_km > entry.0 {
+                entry.0 = distance_km;
+                entry.1 = altitude_ft;
+            }
+        }
+
+        let _ = self
+            .conn
+            .execute("DELETE FROM coverage WHERE receiver_id = ?1", params![receiver_id]);
+        let updated_at = now();
+        for (bucket, (max_distance_km, max_altitude_ft, sample_count)) in &buckets {
+            let _ = self.conn.execute(
+                "INSERT INTO coverage (receiver_id, bearing_bucket, max_distance_km, max_altitude_ft, sample_count, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![receiver_id, *bucket as i64, max_distance_km, max_altitude_ft, sample_count, updated_at],
+            );
+        }
+        self.maybe_commit();
+        buckets.len()
+    }
+
+    /// Get computed coverage buckets for a receiver, ordered by bearing.
+    pub fn get_coverage(&self, receiver_id: i64) -> Vec<CoverageRow> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT receiver_id, bearing_bucket, max_distance_km, max_altitude_ft, sample_count, updated_at
+                 FROM coverage WHERE receiver_id = ?1 ORDER BY bearing_bucket ASC",
+            )
+            .unwrap();
+
+        stmt.query_map(params![receiver_id], |r| {
+            Ok(CoverageRow {
+                receiver_id: r.get(0)?,
+                bearing_bucket: r.get(1)?,
+                max_distance_km: r.get(2)?,
+                max_altitude_ft: r.get(3)?,
+                sample_count: r.get(4)?,
+                updated_at: r.get(5)?,
+            })
+        })
+        .unwrap()
+        .filter_map(|r| r.ok())
+        .collect()
+    }
+
     // -----------------------------------------------------------------------
     // Sightings
     // -----------------------------------------------------------------------
@@ -437,17 +1280,17 @@ impl Database {
         let icao_str = icao_to_string(icao);
 
         // Check for existing sighting
-        let existing: Option<(i64, Option<i32>, Option<i32>)> = self
+        let existing: Option<(i64, Option<i32>, Option<i32>, f64)> = self
             .conn
             .query_row(
-                "SELECT id, min_altitude_ft, max_altitude_ft FROM sightings
+                "SELECT id, min_altitude_ft, max_altitude_ft, first_seen FROM sightings
                  WHERE icao = ?1 AND capture_id IS ?2",
                 params![icao_str, capture_id],
-                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
             )
             .ok();
 
-        if let Some((id, min_alt, max_alt)) = existing {
+        let sighting_first_seen = if let Some((id, min_alt, max_alt, first_seen)) = existing {
             let new_min = match (min_alt, altitude_ft) {
                 (Some(m), Some(a)) => Some(m.min(a)),
                 (None, Some(a)) => Some(a),
@@ -471,6 +1314,7 @@ impl Database {
                  WHERE id = ?6",
                 params![callsign, squawk, new_min, new_max, timestamp, id],
             );
+            first_seen
         } else {
             let _ = self.conn.execute(
                 "INSERT INTO sightings
@@ -478,11 +1322,59 @@ impl Database {
                  VALUES (?1, ?2, ?3, ?4, ?5, ?5, 1, ?6, ?6)",
                 params![icao_str, capture_id, callsign, squawk, altitude_ft, timestamp],
             );
+            timestamp
+        };
+
+        if let Some(code) = squawk {
+            self.check_emergency_squawk(icao, code, sighting_first_seen, timestamp);
         }
         self.maybe_commit();
     }
 
-    // -----------------------------------------------------------------------
+    /// Detect a standard emergency squawk (7500 hijack, 7600 radio failure,
+    /// 7700 general emergency) and log an `emergency_squawk` event for it,
+    /// stamped with the aircraft's most recent position if one is on file.
+    /// Guarded against re-logging the same code for the same sighting by
+    /// checking whether the latest matching event already postdates the
+    /// sighting's first_seen.
+    fn check_emergency_squawk(&mut self, icao: &Icao, code: &str, sighting_first_seen: f64, timestamp: f64) {
+        let description = match emergency_squawk_description(code) {
+            Some(d) => d,
+            None => return,
+        };
+        let icao_str = icao_to_string(icao);
+
+        let already_logged: Option<f64> = self
+            .conn
+            .query_row(
+                "SELECT timestamp FROM events
+                 WHERE icao = ?1 AND event_type = ?2 AND description = ?3
+                 ORDER BY timestamp DESC LIMIT 1",
+                params![icao_str, EVENT_EMERGENCY_SQUAWK, description],
+                |r| r.get(0),
+            )
+            .ok();
+        if already_logged.is_some_and(|t| t >= sighting_first_seen) {
+            return;
+        }
+
+        let position: Option<(f64, f64, Option<i32>)> = self
+            .conn
+            .query_row(
+                "SELECT lat, lon, altitude_ft FROM positions WHERE icao = ?1 ORDER BY timestamp DESC LIMIT 1",
+                params![icao_str],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .ok();
+        let (lat, lon, altitude_ft) = match position {
+            Some((lat, lon, altitude_ft)) => (Some(lat), Some(lon), altitude_ft),
+            None => (None, None, None),
+        };
+
+        self.add_event(icao, EVENT_EMERGENCY_SQUAWK, description, lat, lon, altitude_ft, timestamp);
+    }
+
+    // -----------------------------------------------------------------------
     // Maintenance
     // -----------------------------------------------------------------------
 
@@ -527,37 +1419,62 @@ impl Database {
     }
 
     /// Delete phantom aircraft (no positions, old). Returns total rows deleted.
+    /// Delete aircraft (and their sightings/events) that have gone untouched
+    /// since `cutoff` and have never had a position. `icaos_with_positions`
+    /// rules most stale aircraft in or out without touching `positions` at
+    /// all: the filter can never wrongly rule one *out* (no false
+    /// negatives), so only its "maybe has a position" candidates need the
+    /// expensive `NOT IN (SELECT DISTINCT icao FROM positions)` check,
+    /// instead of running it over the whole stale set as before.
     pub fn prune_phantom_aircraft(&mut self, min_age_hours: f64) -> usize {
         let cutoff = now() - (min_age_hours * 3600.0);
+
+        let mut stmt = self.conn.prepare("SELECT icao FROM aircraft WHERE last_seen < ?1").unwrap();
+        let stale: Vec<String> = stmt
+            .query_map(params![cutoff], |r| r.get(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(stmt);
+
+        let (mut phantom, maybe_positioned): (Vec<String>, Vec<String>) =
+            stale.into_iter().partition(|icao| !self.icaos_with_positions.contains(icao));
+
+        if !maybe_positioned.is_empty() {
+            let placeholders = (1..=maybe_positioned.len()).map(|i| format!("?{i}")).collect::<Vec<_>>().join(",");
+            let sql = format!(
+                "SELECT icao FROM aircraft WHERE icao IN ({placeholders})
+                 AND icao NOT IN (SELECT DISTINCT icao FROM positions)"
+            );
+            let mut stmt = self.conn.prepare(&sql).unwrap();
+            let refs: Vec<&dyn rusqlite::types::ToSql> =
+                maybe_positioned.iter().map(|s| s as &dyn rusqlite::types::ToSql).collect();
+            let confirmed: Vec<String> = stmt
+                .query_map(refs.as_slice(), |r| r.get(0))
+                .unwrap()
+                .filter_map(|r| r.ok())
+                .collect();
+            phantom.extend(confirmed);
+        }
+
+        if phantom.is_empty() {
+            return 0;
+        }
+
+        let placeholders = (1..=phantom.len()).map(|i| format!("?{i}")).collect::<Vec<_>>().join(",");
+        let refs: Vec<&dyn rusqlite::types::ToSql> = phantom.iter().map(|s| s as &dyn rusqlite::types::ToSql).collect();
+
         let c1 = self
             .conn
-            .execute(
-                "DELETE FROM sightings WHERE icao IN (
-                    SELECT icao FROM aircraft WHERE icao NOT IN
-                    (SELECT DISTINCT icao FROM positions) AND last_seen < ?1
-                )",
-                params![cutoff],
-            )
+            .execute(&format!("DELETE FROM sightings WHERE icao IN ({placeholders})"), refs.as_slice())
             .unwrap_or(0);
         let c2 = self
             .conn
-            .execute(
-                "DELETE FROM events WHERE icao IN (
-                    SELECT icao FROM aircraft WHERE icao NOT IN
-                    (SELECT DISTINCT icao FROM positions) AND last_seen < ?1
-                )",
-                params![cutoff],
-            )
+            .execute(&format!("DELETE FROM events WHERE icao IN ({placeholders})"), refs.as_slice())
             .unwrap_or(0);
         let c3 = self
             .conn
-            .execute(
-                "DELETE FROM aircraft WHERE icao IN (
-                    SELECT icao FROM aircraft WHERE icao NOT IN
-                    (SELECT DISTINCT icao FROM positions) AND last_seen < ?1
-                )",
-                params![cutoff],
-            )
+            .execute(&format!("DELETE FROM aircraft WHERE icao IN ({placeholders})"), refs.as_slice())
             .unwrap_or(0);
         let _ = self.conn.execute_batch("COMMIT; BEGIN;");
         c1 + c2 + c3
@@ -603,7 +1520,7 @@ pub struct AircraftRow {
     pub last_seen: f64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PositionRow {
     pub icao: String,
     pub lat: f64,
@@ -649,6 +1566,35 @@ pub struct EventRow {
     pub timestamp: f64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct LogbookRow {
+    pub id: i64,
+    pub icao: String,
+    pub event_type: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub altitude_ft: Option<i32>,
+    pub duration_sec: f64,
+    pub timestamp: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoverageRow {
+    pub receiver_id: i64,
+    pub bearing_bucket: i64,
+    pub max_distance_km: f64,
+    pub max_altitude_ft: Option<i32>,
+    pub sample_count: i64,
+    pub updated_at: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RegistryRow {
+    pub registration: Option<String>,
+    pub aircraft_type: Option<String>,
+    pub operator: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ReceiverRow {
     pub id: i64,
@@ -659,6 +1605,45 @@ pub struct ReceiverRow {
     pub created_at: f64,
 }
 
+/// Data-coverage range for one icao or receiver, analogous to a station
+/// inventory table's `hourly_start`/`hourly_end` and
+/// `daily_start`/`daily_end` columns: the span over which hour-bucketed
+/// (resp. day-bucketed) data exists, plus how many distinct buckets in
+/// that span actually have a sample (so gaps show up as `*_buckets` being
+/// smaller than the span would suggest).
+#[derive(Debug, Serialize)]
+pub struct InventoryRow {
+    pub earliest: f64,
+    pub latest: f64,
+    pub hourly_start: f64,
+    pub hourly_end: f64,
+    pub hourly_buckets: i64,
+    pub daily_start: f64,
+    pub daily_end: f64,
+    pub daily_buckets: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AircraftInventoryRow {
+    pub icao: String,
+    #[serde(flatten)]
+    pub inventory: InventoryRow,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReceiverInventoryRow {
+    pub receiver_id: i64,
+    pub receiver_name: Option<String>,
+    #[serde(flatten)]
+    pub inventory: InventoryRow,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoverageInventory {
+    pub by_aircraft: Vec<AircraftInventoryRow>,
+    pub by_receiver: Vec<ReceiverInventoryRow>,
+}
+
 // ---------------------------------------------------------------------------
 // Web query methods
 // ---------------------------------------------------------------------------
@@ -689,18 +1674,33 @@ impl Database {
         .collect()
     }
 
-    /// Get recent positions within a time window.
-    pub fn get_recent_positions(&self, minutes: f64, limit: i64) -> Vec<PositionRow> {
+    /// Get recent positions within a time window, optionally scoped to a
+    /// map viewport and altitude band via `filter`.
+    pub fn get_recent_positions(
+        &self,
+        minutes: f64,
+        filter: SpatialFilter,
+        limit: i64,
+    ) -> Vec<PositionRow> {
         let cutoff = now() - (minutes * 60.0);
-        let mut stmt = self
-            .conn
-            .prepare(
-                "SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg, vertical_rate_fpm, timestamp
-                 FROM positions WHERE timestamp >= ?1 ORDER BY timestamp DESC LIMIT ?2",
-            )
-            .unwrap();
 
-        stmt.query_map(params![cutoff, limit], |r| {
+        let mut conditions = vec!["timestamp >= ?1".to_string()];
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(cutoff)];
+        filter.push_conditions(&mut conditions, &mut bind_values, "lat", "lon", "altitude_ft");
+
+        let where_clause = conditions.join(" AND ");
+        let sql = format!(
+            "SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg, vertical_rate_fpm, timestamp
+             FROM positions WHERE {where_clause}
+             ORDER BY timestamp DESC LIMIT ?{}",
+            bind_values.len() + 1
+        );
+        bind_values.push(Box::new(limit));
+
+        let mut stmt = self.conn.prepare(&sql).unwrap();
+        let refs: Vec<&dyn rusqlite::types::ToSql> = bind_values.iter().map(|b| b.as_ref()).collect();
+
+        stmt.query_map(refs.as_slice(), |r| {
             Ok(PositionRow {
                 icao: r.get(0)?,
                 lat: r.get(1)?,
@@ -800,19 +1800,58 @@ impl Database {
         .collect()
     }
 
-    /// Get heatmap data points.
-    pub fn get_heatmap_positions(&self, minutes: f64, limit: i64) -> Vec<(f64, f64, Option<i32>)> {
+    /// Like `get_trails`, but passes each aircraft's trail through a jitter
+    /// buffer to reject isolated CPR-decode outliers (a point that jumps
+    /// hundreds of km then snaps back) before returning it. See
+    /// `smooth_trail` for the windowing rule.
+    pub fn get_trails_smoothed(
+        &self,
+        minutes: f64,
+        limit_per_aircraft: i64,
+        window: usize,
+        max_deviation_km: f64,
+    ) -> Vec<PositionRow> {
+        let raw = self.get_trails(minutes, limit_per_aircraft);
+
+        let mut out = Vec::with_capacity(raw.len());
+        let mut start = 0;
+        while start < raw.len() {
+            let mut end = start + 1;
+            while end < raw.len() && raw[end].icao == raw[start].icao {
+                end += 1;
+            }
+            out.extend(smooth_trail(&raw[start..end], window, max_deviation_km));
+            start = end;
+        }
+        out
+    }
+
+    /// Get heatmap data points, optionally scoped to a viewport/altitude band.
+    pub fn get_heatmap_positions(
+        &self,
+        minutes: f64,
+        filter: SpatialFilter,
+        limit: i64,
+    ) -> Vec<(f64, f64, Option<i32>)> {
         let cutoff = now() - (minutes * 60.0);
-        let mut stmt = self
-            .conn
-            .prepare(
-                "SELECT lat, lon, altitude_ft FROM positions
-                 WHERE timestamp >= ?1
-                 ORDER BY RANDOM() LIMIT ?2",
-            )
-            .unwrap();
 
-        stmt.query_map(params![cutoff, limit], |r| {
+        let mut conditions = vec!["timestamp >= ?1".to_string()];
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(cutoff)];
+        filter.push_conditions(&mut conditions, &mut bind_values, "lat", "lon", "altitude_ft");
+
+        let where_clause = conditions.join(" AND ");
+        let sql = format!(
+            "SELECT lat, lon, altitude_ft FROM positions
+             WHERE {where_clause}
+             ORDER BY RANDOM() LIMIT ?{}",
+            bind_values.len() + 1
+        );
+        bind_values.push(Box::new(limit));
+
+        let mut stmt = self.conn.prepare(&sql).unwrap();
+        let refs: Vec<&dyn rusqlite::types::ToSql> = bind_values.iter().map(|b| b.as_ref()).collect();
+
+        stmt.query_map(refs.as_slice(), |r| {
             Ok((r.get(0)?, r.get(1)?, r.get(2)?))
         })
         .unwrap()
@@ -820,50 +1859,95 @@ impl Database {
         .collect()
     }
 
+    /// Grid-binned heatmap aggregate: quantizes each position to a
+    /// `cell_deg` lat/lon cell (optionally also banding by `alt_band_ft`
+    /// so two aircraft at very different altitudes over the same cell
+    /// don't get averaged together), then `GROUP BY`s the cell instead of
+    /// `get_heatmap_positions`'s `ORDER BY RANDOM()` subsample. Scales to a
+    /// bounded number of cells regardless of table size, and the result is
+    /// actually proportional to traffic density. Returns
+    /// `(cell_center_lat, cell_center_lon, count, mean_altitude_ft)`.
+    pub fn get_heatmap_binned(
+        &self,
+        minutes: f64,
+        cell_deg: f64,
+        alt_band_ft: Option<i32>,
+    ) -> Vec<(f64, f64, i64, Option<f64>)> {
+        let cutoff = now() - (minutes * 60.0);
+
+        let mut group_by = "CAST(lat / ?1 AS INTEGER) - (lat / ?1 < CAST(lat / ?1 AS INTEGER)), \
+                            CAST(lon / ?1 AS INTEGER) - (lon / ?1 < CAST(lon / ?1 AS INTEGER))"
+            .to_string();
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(cell_deg), Box::new(cutoff)];
+        if let Some(band) = alt_band_ft {
+            group_by.push_str(&format!(", CAST(COALESCE(altitude_ft, 0) / ?{} AS INTEGER)", bind_values.len() + 1));
+            bind_values.push(Box::new(band));
+        }
+
+        let sql = format!(
+            "SELECT (CAST(lat / ?1 AS INTEGER) - (lat / ?1 < CAST(lat / ?1 AS INTEGER)) + 0.5) * ?1 AS cell_lat,
+                    (CAST(lon / ?1 AS INTEGER) - (lon / ?1 < CAST(lon / ?1 AS INTEGER)) + 0.5) * ?1 AS cell_lon,
+                    COUNT(*) AS n,
+                    AVG(altitude_ft) AS mean_alt
+             FROM positions
+             WHERE timestamp >= ?2
+             GROUP BY {group_by}"
+        );
+
+        let mut stmt = self.conn.prepare(&sql).unwrap();
+        let refs: Vec<&dyn rusqlite::types::ToSql> = bind_values.iter().map(|b| b.as_ref()).collect();
+
+        stmt.query_map(refs.as_slice(), |r| {
+            Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?))
+        })
+        .unwrap()
+        .filter_map(|r| r.ok())
+        .collect()
+    }
+
     /// Query positions with filters.
+    ///
+    /// Built on `Query`/`execute_query` rather than its own `conditions`/
+    /// `bind_values` bookkeeping, so the same min/max altitude and military
+    /// predicates this method exposes can be composed with others a caller
+    /// builds directly through `Query` (see `execute_query`).
     pub fn query_positions(
         &self,
         min_alt: Option<i32>,
         max_alt: Option<i32>,
         icao: Option<&str>,
         military: bool,
+        filter: SpatialFilter,
         limit: i64,
+        offset: i64,
     ) -> Vec<PositionRow> {
-        let mut conditions = vec!["1=1".to_string()];
-        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        let mut query = Query::new(
+            Relation::Positions,
+            &["icao", "lat", "lon", "altitude_ft", "speed_kts", "heading_deg", "vertical_rate_fpm", "timestamp"],
+        )
+        .join(Relation::Aircraft);
 
         if let Some(min) = min_alt {
-            conditions.push(format!("altitude_ft >= ?{}", bind_values.len() + 1));
-            bind_values.push(Box::new(min));
+            query = query.filter("altitude_ft", Cmp::Gte, Box::new(min));
         }
         if let Some(max) = max_alt {
-            conditions.push(format!("altitude_ft <= ?{}", bind_values.len() + 1));
-            bind_values.push(Box::new(max));
+            query = query.filter("altitude_ft", Cmp::Lte, Box::new(max));
         }
         if let Some(ic) = icao {
-            conditions.push(format!("p.icao = ?{}", bind_values.len() + 1));
-            bind_values.push(Box::new(ic.to_string()));
+            query = query.filter("icao", Cmp::Eq, Box::new(ic.to_string()));
         }
         if military {
-            conditions.push("a.is_military = 1".to_string());
+            query = query.filter_on(Relation::Aircraft, "is_military", Cmp::Eq, Box::new(1));
         }
 
-        let where_clause = conditions.join(" AND ");
-        let sql = format!(
-            "SELECT p.icao, p.lat, p.lon, p.altitude_ft, p.speed_kts, p.heading_deg, p.vertical_rate_fpm, p.timestamp
-             FROM positions p
-             LEFT JOIN aircraft a ON p.icao = a.icao
-             WHERE {where_clause}
-             ORDER BY p.timestamp DESC LIMIT ?{}",
-            bind_values.len() + 1
-        );
-
-        bind_values.push(Box::new(limit));
+        let mut conditions = Vec::new();
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+        filter.push_conditions(&mut conditions, &mut bind_values, "p.lat", "p.lon", "p.altitude_ft");
+        query = query.raw_conditions(conditions, bind_values);
 
-        let mut stmt = self.conn.prepare(&sql).unwrap();
-        let refs: Vec<&dyn rusqlite::types::ToSql> = bind_values.iter().map(|b| b.as_ref()).collect();
+        query = query.order_by("timestamp", Order::Desc).limit(limit).offset(offset);
 
-        stmt.query_map(refs.as_slice(), |r| {
+        self.execute_query(query, |r| {
             Ok(PositionRow {
                 icao: r.get(0)?,
                 lat: r.get(1)?,
@@ -875,9 +1959,26 @@ impl Database {
                 timestamp: r.get(7)?,
             })
         })
-        .unwrap()
-        .filter_map(|r| r.ok())
-        .collect()
+    }
+
+    /// Compile `query` and map each result row with `row_fn`, the same
+    /// per-column `r.get(N)` convention used by every hand-written query in
+    /// this module. Lets a caller compose predicates across relations that
+    /// no fixed `Database` method anticipates (e.g. "events for military
+    /// aircraft seen above FL350 in the last hour") without writing its own
+    /// SQL.
+    pub fn execute_query<T, F>(&self, query: Query, row_fn: F) -> Vec<T>
+    where
+        F: FnMut(&rusqlite::Row) -> SqlResult<T>,
+    {
+        let (sql, bind_values) = query.compile();
+        let mut stmt = self.conn.prepare(&sql).unwrap();
+        let refs: Vec<&dyn rusqlite::types::ToSql> = bind_values.iter().map(|b| b.as_ref()).collect();
+
+        stmt.query_map(refs.as_slice(), row_fn)
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect()
     }
 
     /// Get all positions for replay.
@@ -996,6 +2097,243 @@ impl Database {
         .collect()
     }
 
+    /// Export one aircraft's track between `start` and `end` (unix
+    /// timestamps) as a GeoJSON `Feature`. The geometry is a `LineString`
+    /// unless two consecutive fixes are more than `max_gap_sec` apart, in
+    /// which case the track is split into a `MultiLineString` at each such
+    /// dropout so consumers don't interpolate a false straight line across
+    /// it. Altitude/speed/heading/timestamp per fix are carried in
+    /// `properties.points`, parallel to the geometry's coordinates.
+    pub fn export_track_geojson(&self, icao_hex: &str, start: f64, end: f64, max_gap_sec: f64) -> Value {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT lat, lon, altitude_ft, speed_kts, heading_deg, timestamp
+                 FROM positions WHERE icao = ?1 AND timestamp >= ?2 AND timestamp <= ?3
+                 ORDER BY timestamp ASC",
+            )
+            .unwrap();
+
+        let fixes: Vec<TrackFix> = stmt
+            .query_map(params![icao_hex, start, end], |r| {
+                Ok(TrackFix {
+                    lat: r.get(0)?,
+                    lon: r.get(1)?,
+                    altitude_ft: r.get(2)?,
+                    speed_kts: r.get(3)?,
+                    heading_deg: r.get(4)?,
+                    timestamp: r.get(5)?,
+                })
+            })
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+
+        let mut lines: Vec<Vec<[f64; 2]>> = Vec::new();
+        let mut segment: Vec<[f64; 2]> = Vec::new();
+        let mut last_ts: Option<f64> = None;
+
+        for fix in &fixes {
+            if last_ts.is_some_and(|t| fix.timestamp - t > max_gap_sec) {
+                lines.push(std::mem::take(&mut segment));
+            }
+            segment.push([fix.lon, fix.lat]);
+            last_ts = Some(fix.timestamp);
+        }
+        if !segment.is_empty() {
+            lines.push(segment);
+        }
+
+        let geometry = if lines.len() <= 1 {
+            json!({
+                "type": "LineString",
+                "coordinates": lines.into_iter().next().unwrap_or_default(),
+            })
+        } else {
+            json!({
+                "type": "MultiLineString",
+                "coordinates": lines,
+            })
+        };
+
+        let points: Vec<Value> = fixes
+            .iter()
+            .map(|fix| {
+                json!({
+                    "altitude_ft": fix.altitude_ft,
+                    "speed_kts": fix.speed_kts,
+                    "heading_deg": fix.heading_deg,
+                    "timestamp": fix.timestamp,
+                })
+            })
+            .collect();
+
+        json!({
+            "type": "Feature",
+            "geometry": geometry,
+            "properties": {
+                "icao": icao_hex,
+                "points": points,
+            },
+        })
+    }
+
+    /// Stream positions as CSV rows to `writer`, scoped to one aircraft
+    /// and/or a `start`/`end` time window (unix timestamps). Either may be
+    /// `None` to leave that dimension unbounded. Returns the number of rows
+    /// written.
+    pub fn export_positions_csv<W: Write>(
+        &self,
+        icao: Option<&str>,
+        start: Option<f64>,
+        end: Option<f64>,
+        writer: &mut W,
+    ) -> std::io::Result<usize> {
+        let mut conditions = Vec::new();
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        if let Some(ic) = icao {
+            conditions.push(format!("icao = ?{}", bind_values.len() + 1));
+            bind_values.push(Box::new(ic.to_string()));
+        }
+        if let Some(s) = start {
+            conditions.push(format!("timestamp >= ?{}", bind_values.len() + 1));
+            bind_values.push(Box::new(s));
+        }
+        if let Some(e) = end {
+            conditions.push(format!("timestamp <= ?{}", bind_values.len() + 1));
+            bind_values.push(Box::new(e));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            "1=1".to_string()
+        } else {
+            conditions.join(" AND ")
+        };
+
+        let sql = format!(
+            "SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg, vertical_rate_fpm, timestamp
+             FROM positions WHERE {where_clause}
+             ORDER BY timestamp ASC"
+        );
+
+        let mut stmt = self.conn.prepare(&sql).unwrap();
+        let refs: Vec<&dyn rusqlite::types::ToSql> = bind_values.iter().map(|b| b.as_ref()).collect();
+        let rows: Vec<PositionRow> = stmt
+            .query_map(refs.as_slice(), |r| {
+                Ok(PositionRow {
+                    icao: r.get(0)?,
+                    lat: r.get(1)?,
+                    lon: r.get(2)?,
+                    altitude_ft: r.get(3)?,
+                    speed_kts: r.get(4)?,
+                    heading_deg: r.get(5)?,
+                    vertical_rate_fpm: r.get(6)?,
+                    timestamp: r.get(7)?,
+                })
+            })
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+
+        writeln!(writer, "icao,lat,lon,altitude_ft,speed_kts,heading_deg,vertical_rate_fpm,timestamp")?;
+        for row in &rows {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{}",
+                row.icao,
+                row.lat,
+                row.lon,
+                row.altitude_ft.map(|v| v.to_string()).unwrap_or_default(),
+                row.speed_kts.map(|v| v.to_string()).unwrap_or_default(),
+                row.heading_deg.map(|v| v.to_string()).unwrap_or_default(),
+                row.vertical_rate_fpm.map(|v| v.to_string()).unwrap_or_default(),
+                row.timestamp,
+            )?;
+        }
+
+        Ok(rows.len())
+    }
+
+    /// Export recent trails (see `get_trails`) as a GeoJSON
+    /// `FeatureCollection`: one `LineString` feature per aircraft, ordered
+    /// by timestamp with altitude as the third ordinate, and `icao`/
+    /// `callsign`/`is_military` properties per feature. The multi-aircraft
+    /// companion to `export_track_geojson`'s single-aircraft `Feature`.
+    pub fn export_geojson(&self, minutes: f64, limit_per_aircraft: i64) -> Value {
+        let trails = self.get_trails(minutes, limit_per_aircraft);
+
+        let mut features = Vec::new();
+        let mut start = 0;
+        while start < trails.len() {
+            let mut end = start + 1;
+            while end < trails.len() && trails[end].icao == trails[start].icao {
+                end += 1;
+            }
+
+            let icao = &trails[start].icao;
+            let coordinates: Vec<Value> = trails[start..end]
+                .iter()
+                .map(|p| json!([p.lon, p.lat, p.altitude_ft]))
+                .collect();
+
+            let is_military = self.get_aircraft(icao).map(|a| a.is_military).unwrap_or(false);
+            let callsign: Option<String> = self
+                .conn
+                .query_row(
+                    "SELECT callsign FROM sightings WHERE icao = ?1 AND callsign IS NOT NULL
+                     ORDER BY last_seen DESC LIMIT 1",
+                    params![icao],
+                    |r| r.get(0),
+                )
+                .ok();
+
+            features.push(json!({
+                "type": "Feature",
+                "geometry": {
+                    "type": "LineString",
+                    "coordinates": coordinates,
+                },
+                "properties": {
+                    "icao": icao,
+                    "callsign": callsign,
+                    "is_military": is_military,
+                },
+            }));
+            start = end;
+        }
+
+        json!({
+            "type": "FeatureCollection",
+            "features": features,
+        })
+    }
+
+    /// Stream recent trails (see `get_trails`) as flat CSV rows to `writer`.
+    /// Returns the number of rows written. The multi-aircraft, time-window
+    /// companion to `export_positions_csv`'s icao/start/end scoping.
+    pub fn export_csv<W: Write>(&self, minutes: f64, limit_per_aircraft: i64, writer: &mut W) -> std::io::Result<usize> {
+        let trails = self.get_trails(minutes, limit_per_aircraft);
+
+        writeln!(writer, "icao,lat,lon,altitude_ft,speed_kts,heading_deg,vertical_rate_fpm,timestamp")?;
+        for row in &trails {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{}",
+                row.icao,
+                row.lat,
+                row.lon,
+                row.altitude_ft.map(|v| v.to_string()).unwrap_or_default(),
+                row.speed_kts.map(|v| v.to_string()).unwrap_or_default(),
+                row.heading_deg.map(|v| v.to_string()).unwrap_or_default(),
+                row.vertical_rate_fpm.map(|v| v.to_string()).unwrap_or_default(),
+                row.timestamp,
+            )?;
+        }
+
+        Ok(trails.len())
+    }
+
     /// Get all receivers.
     pub fn get_receivers(&self) -> Vec<ReceiverRow> {
         let mut stmt = self
@@ -1064,6 +2402,31 @@ mod tests {
         assert_eq!(ac.last_seen, 5.0);
     }
 
+    #[test]
+    fn test_update_selected_state() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 1.0);
+
+        db.update_selected_state(&icao, Some(35000), None, None, None, 1.0);
+        db.update_selected_state(&icao, None, Some(1013.0), Some(270.0), None, 2.0);
+
+        let (alt, baro, track): (Option<i32>, Option<f64>, Option<f64>) = db
+            .conn
+            .query_row(
+                "SELECT selected_altitude_ft, baro_setting_hpa, track_deg FROM selected_state WHERE icao = '4840D6'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        // The second update left `selected_altitude_ft` as `None`, which
+        // should leave the first update's value in place rather than
+        // clobbering it.
+        assert_eq!(alt, Some(35000));
+        assert_eq!(baro, Some(1013.0));
+        assert_eq!(track, Some(270.0));
+    }
+
     #[test]
     fn test_add_position() {
         let mut db = test_db();
@@ -1084,14 +2447,14 @@ mod tests {
         let icao = icao_from_hex("40621D").unwrap();
         db.upsert_aircraft(&icao, Some("UK"), None, false, 1.0);
         db.add_position(&icao, 52.25, 3.92, Some(38000), None, None, None, None, 1.0);
-        db.add_position(&icao, 52.26, 3.93, Some(38100), None, None, None, None, 2.0);
-        db.add_position(&icao, 52.27, 3.94, Some(38200), None, None, None, None, 3.0);
+        db.add_position(&icao, 52.26, 3.93, Some(38100), None, None, None, None, 601.0);
+        db.add_position(&icao, 52.27, 3.94, Some(38200), None, None, None, None, 1201.0);
 
         assert_eq!(db.count_positions(), 3);
         let positions = db.get_positions("40621D", 2);
         assert_eq!(positions.len(), 2);
         // Should be most recent first
-        assert_eq!(positions[0].timestamp, 3.0);
+        assert_eq!(positions[0].timestamp, 1201.0);
     }
 
     #[test]
@@ -1124,7 +2487,8 @@ mod tests {
         db.upsert_aircraft(&icao, Some("United States"), None, true, 1.0);
         db.add_event(&icao, "military", "US military aircraft", None, None, None, 1.0);
 
-        assert_eq!(db.count_events(), 1);
+        // One "appeared" event from upsert_aircraft, plus the one added here.
+        assert_eq!(db.count_events(), 2);
     }
 
     #[test]
@@ -1181,7 +2545,8 @@ mod tests {
         let stats = db.stats();
         assert_eq!(stats.aircraft, 1);
         assert_eq!(stats.positions, 1);
-        assert_eq!(stats.events, 0);
+        // upsert_aircraft logs an "appeared" event for the new ICAO.
+        assert_eq!(stats.events, 1);
     }
 
     #[test]
@@ -1215,11 +2580,14 @@ mod tests {
                 lat: 52.25,
                 lon: 3.92,
                 altitude_ft: Some(38000),
+                altitude_source: Some(AltitudeSource::Barometric),
                 speed_kts: Some(450.0),
                 heading_deg: Some(90.0),
                 vertical_rate_fpm: None,
+                vertical_rate_source: None,
                 receiver_id: None,
                 timestamp: 1.0,
+                on_ground: false,
             },
             TrackEvent::SightingUpdate {
                 icao,
@@ -1227,6 +2595,7 @@ mod tests {
                 callsign: Some("KLM1023".into()),
                 squawk: None,
                 altitude_ft: Some(38000),
+                altitude_source: Some(AltitudeSource::Barometric),
                 timestamp: 1.0,
             },
         ];
@@ -1262,4 +2631,548 @@ mod tests {
         assert!(db.get_aircraft("4840D6").is_some());
         assert!(db.get_aircraft("AAAAAA").is_none());
     }
+
+    #[test]
+    fn test_contains_icao() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        let unseen = icao_from_hex("AAAAAA").unwrap();
+
+        assert!(!db.contains_icao(&icao));
+        db.upsert_aircraft(&icao, None, None, false, 0.0);
+        assert!(db.contains_icao(&icao));
+        assert!(!db.contains_icao(&unseen));
+    }
+
+    #[test]
+    fn test_detect_takeoff_and_landing() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 0.0);
+
+        // On ground, then a clean ground->air transition.
+        db.add_position(&icao, 52.0, 3.0, Some(0), Some(5.0), None, None, None, 0.0);
+        db.add_position(&icao, 52.01, 3.01, Some(200), Some(80.0), None, None, None, 20.0);
+        // Cruising airborne.
+        db.add_position(&icao, 52.2, 3.2, Some(10000), Some(420.0), None, None, None, 300.0);
+        // Air->ground transition back down.
+        db.add_position(&icao, 52.3, 3.3, Some(50), Some(60.0), None, None, None, 600.0);
+        db.add_position(&icao, 52.3, 3.3, Some(0), Some(2.0), None, None, None, 620.0);
+
+        let added = db.detect_takeoffs_landings("4840D6");
+        assert_eq!(added, 2);
+        assert_eq!(db.count_logbook(), 2);
+
+        let entries = db.get_logbook(Some("4840D6"), 10);
+        assert_eq!(entries.len(), 2);
+        // Most recent first: landing, then takeoff.
+        assert_eq!(entries[0].event_type, LOGBOOK_LANDING);
+        assert_eq!(entries[0].timestamp, 620.0);
+        assert_eq!(entries[1].event_type, LOGBOOK_TAKEOFF);
+        assert_eq!(entries[1].timestamp, 20.0);
+    }
+
+    #[test]
+    fn test_detect_takeoffs_landings_ignores_stale_gap() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 0.0);
+
+        // Ground fix, then an airborne fix over an hour later - too stale
+        // a gap to trust as a real takeoff.
+        db.add_position(&icao, 52.0, 3.0, Some(0), Some(5.0), None, None, None, 0.0);
+        db.add_position(&icao, 52.5, 3.5, Some(10000), Some(420.0), None, None, None, 3600.0);
+
+        let added = db.detect_takeoffs_landings("4840D6");
+        assert_eq!(added, 0);
+        assert_eq!(db.count_logbook(), 0);
+    }
+
+    #[test]
+    fn test_compute_coverage_buckets_by_bearing() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 0.0);
+        let rx = db.add_receiver("test-rx", Some(52.0), Some(3.0), None, "Test");
+
+        // Two positions due north of the receiver at different ranges - same
+        // bucket, the farther one should win.
+        db.add_position(&icao, 52.3, 3.0, Some(5000), None, None, None, Some(rx), 0.0);
+        db.add_position(&icao, 52.6, 3.0, Some(6000), None, None, None, Some(rx), 900.0);
+        // A position due east - a different bucket.
+        db.add_position(&icao, 52.0, 4.0, Some(3000), None, None, None, Some(rx), 1800.0);
+
+        let buckets = db.compute_coverage(rx, 72);
+        assert_eq!(buckets, 2);
+
+        let coverage = db.get_coverage(rx);
+        assert_eq!(coverage.len(), 2);
+
+        let north = coverage.iter().find(|c| c.bearing_bucket == 0).unwrap();
+        assert_eq!(north.sample_count, 2);
+        assert_eq!(north.max_altitude_ft, Some(6000));
+        assert!(north.max_distance_km > 30.0);
+
+        let east = coverage.iter().find(|c| c.bearing_bucket != 0).unwrap();
+        assert_eq!(east.sample_count, 1);
+        assert_eq!(east.max_altitude_ft, Some(3000));
+    }
+
+    #[test]
+    fn test_compute_coverage_skips_receiver_without_position() {
+        let mut db = test_db();
+        let rx = db.add_receiver("no-location", None, None, None, "Test");
+
+        let buckets = db.compute_coverage(rx, 72);
+        assert_eq!(buckets, 0);
+        assert!(db.get_coverage(rx).is_empty());
+    }
+
+    #[test]
+    fn test_upsert_aircraft_logs_appeared_event() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 1.0);
+        assert_eq!(db.count_events(), 1);
+
+        // A second upsert for the same, still-active aircraft shouldn't log
+        // another appeared event.
+        db.upsert_aircraft(&icao, None, None, false, 2.0);
+        assert_eq!(db.count_events(), 1);
+    }
+
+    #[test]
+    fn test_expire_stale_marks_inactive_and_logs_disappeared() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 1.0);
+
+        let expired = db.expire_stale(0.0);
+        assert_eq!(expired, 1);
+        assert_eq!(db.count_events(), 2); // appeared + disappeared
+
+        let active: i64 = db
+            .conn
+            .query_row("SELECT active FROM aircraft WHERE icao = '4840D6'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(active, 0);
+
+        // A stale aircraft isn't expired again on the next sweep.
+        assert_eq!(db.expire_stale(0.0), 0);
+
+        // Re-upserting the same ICAO after it expired logs a fresh appeared event.
+        db.upsert_aircraft(&icao, None, None, false, 10.0);
+        assert_eq!(db.count_events(), 3);
+    }
+
+    #[test]
+    fn test_add_position_updates_seen_pos() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 1.0);
+        db.add_position(&icao, 52.0, 3.0, None, None, None, None, None, 5.0);
+
+        let seen_pos: f64 = db
+            .conn
+            .query_row("SELECT seen_pos FROM aircraft WHERE icao = '4840D6'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(seen_pos, 5.0);
+    }
+
+    #[test]
+    fn test_emergency_squawk_logs_event_with_last_position() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 1.0);
+        db.add_position(&icao, 52.0, 3.0, Some(5000), None, None, None, None, 1.0);
+
+        db.upsert_sighting(&icao, None, Some("KLM1023"), Some("7700"), Some(5000), 2.0);
+
+        // appeared + emergency_squawk
+        assert_eq!(db.count_events(), 2);
+        let events = db.get_events(None, Some("4840D6"), 10);
+        let emergency = events
+            .iter()
+            .find(|e| e.event_type == EVENT_EMERGENCY_SQUAWK)
+            .unwrap();
+        assert_eq!(emergency.lat, Some(52.0));
+        assert_eq!(emergency.lon, Some(3.0));
+        assert!(emergency.description.contains("7700"));
+    }
+
+    #[test]
+    fn test_emergency_squawk_not_relogged_for_same_sighting() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 1.0);
+
+        db.upsert_sighting(&icao, None, None, Some("7700"), None, 1.0);
+        db.upsert_sighting(&icao, None, None, Some("7700"), None, 2.0);
+        db.upsert_sighting(&icao, None, None, Some("7700"), None, 3.0);
+
+        let events = db.get_events(None, Some("4840D6"), 10);
+        let emergency_count = events
+            .iter()
+            .filter(|e| e.event_type == EVENT_EMERGENCY_SQUAWK)
+            .count();
+        assert_eq!(emergency_count, 1);
+    }
+
+    #[test]
+    fn test_non_emergency_squawk_logs_no_event() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 1.0);
+
+        db.upsert_sighting(&icao, None, None, Some("1234"), None, 1.0);
+
+        let events = db.get_events(None, Some("4840D6"), 10);
+        assert!(!events.iter().any(|e| e.event_type == EVENT_EMERGENCY_SQUAWK));
+    }
+
+    #[test]
+    fn test_import_registry_csv() {
+        let mut db = test_db();
+        let path = std::env::temp_dir().join("adsb_test_registry_import.csv");
+        std::fs::write(&path, "icao,registration,type,operator\n4840D6,PH-ABC,B738,KLM\nA1B2C3,,,\n").unwrap();
+
+        let count = db.import_registry(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(count, 2);
+        let row = db.lookup_registry("4840D6").unwrap();
+        assert_eq!(row.registration.as_deref(), Some("PH-ABC"));
+        assert_eq!(row.aircraft_type.as_deref(), Some("B738"));
+        assert_eq!(row.operator.as_deref(), Some("KLM"));
+        assert!(db.lookup_registry("A1B2C3").is_some());
+        assert!(db.lookup_registry("000000").is_none());
+    }
+
+    #[test]
+    fn test_import_registry_json() {
+        let mut db = test_db();
+        let path = std::env::temp_dir().join("adsb_test_registry_import.json");
+        std::fs::write(
+            &path,
+            r#"[{"icao":"4840D6","registration":"PH-ABC","type":"B738","operator":"KLM"}]"#,
+        )
+        .unwrap();
+
+        let count = db.import_registry(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(count, 1);
+        let row = db.lookup_registry("4840D6").unwrap();
+        assert_eq!(row.aircraft_type.as_deref(), Some("B738"));
+    }
+
+    #[test]
+    fn test_import_registry_update_does_not_clobber() {
+        let mut db = test_db();
+        let path = std::env::temp_dir().join("adsb_test_registry_import_update.csv");
+
+        std::fs::write(&path, "icao,registration,type,operator\n4840D6,PH-ABC,B738,KLM\n").unwrap();
+        db.import_registry(&path).unwrap();
+
+        std::fs::write(&path, "icao,registration,type,operator\n4840D6,,,Transavia\n").unwrap();
+        db.import_registry(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let row = db.lookup_registry("4840D6").unwrap();
+        assert_eq!(row.registration.as_deref(), Some("PH-ABC"));
+        assert_eq!(row.aircraft_type.as_deref(), Some("B738"));
+        assert_eq!(row.operator.as_deref(), Some("Transavia"));
+    }
+
+    #[test]
+    fn test_upsert_aircraft_backfills_from_registry() {
+        let mut db = test_db();
+        let path = std::env::temp_dir().join("adsb_test_registry_backfill.csv");
+        std::fs::write(&path, "icao,registration,type,operator\n4840D6,PH-ABC,B738,KLM\n").unwrap();
+        db.import_registry(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 1.0);
+        let aircraft = db.get_aircraft("4840D6").unwrap();
+        assert_eq!(aircraft.registration.as_deref(), Some("PH-ABC"));
+
+        // A live-supplied registration on a later sighting must not be
+        // clobbered by the registry value.
+        db.upsert_aircraft(&icao, None, Some("N12345"), false, 2.0);
+        let aircraft = db.get_aircraft("4840D6").unwrap();
+        assert_eq!(aircraft.registration.as_deref(), Some("N12345"));
+    }
+
+    #[test]
+    fn test_export_track_geojson_linestring() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 0.0);
+        db.add_position(&icao, 52.0, 4.0, Some(1000), None, None, None, None, 0.0);
+        db.add_position(&icao, 52.1, 4.1, Some(2000), None, None, None, None, 600.0);
+
+        let feature = db.export_track_geojson("4840D6", 0.0, 700.0, 1000.0);
+        assert_eq!(feature["type"], "Feature");
+        assert_eq!(feature["geometry"]["type"], "LineString");
+        assert_eq!(feature["geometry"]["coordinates"][0][0], 4.0);
+        assert_eq!(feature["geometry"]["coordinates"][0][1], 52.0);
+        assert_eq!(feature["properties"]["points"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_export_track_geojson_splits_on_gap() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 0.0);
+        db.add_position(&icao, 52.0, 4.0, Some(1000), None, None, None, None, 0.0);
+        db.add_position(&icao, 52.001, 4.001, Some(2000), None, None, None, None, 60.0);
+        db.add_position(&icao, 53.0, 5.0, Some(3000), None, None, None, None, 600.0);
+
+        let feature = db.export_track_geojson("4840D6", 0.0, 1000.0, 120.0);
+        assert_eq!(feature["geometry"]["type"], "MultiLineString");
+        let lines = feature["geometry"]["coordinates"].as_array().unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].as_array().unwrap().len(), 2);
+        assert_eq!(lines[1].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_export_positions_csv() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 1.0);
+        db.add_position(&icao, 52.0, 4.0, Some(1000), Some(250.0), Some(90.0), None, None, 1.0);
+
+        let mut out = Vec::new();
+        let count = db.export_positions_csv(Some("4840D6"), None, None, &mut out).unwrap();
+        assert_eq!(count, 1);
+
+        let text = String::from_utf8(out).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(lines.next().unwrap(), "icao,lat,lon,altitude_ft,speed_kts,heading_deg,vertical_rate_fpm,timestamp");
+        assert_eq!(lines.next().unwrap(), "4840D6,52,4,1000,250,90,,1");
+    }
+
+    #[test]
+    fn test_export_geojson_feature_collection() {
+        let mut db = test_db();
+        let t = now();
+        let mil = icao_from_hex("4840D6").unwrap();
+        let civ = icao_from_hex("4840D7").unwrap();
+        db.upsert_aircraft(&mil, None, None, true, t);
+        db.upsert_aircraft(&civ, None, None, false, t);
+        db.add_position(&mil, 52.0, 4.0, Some(36000), None, None, None, None, t);
+        db.add_position(&mil, 52.001, 4.001, Some(36000), None, None, None, None, t + 1.0);
+        db.add_position(&civ, 50.0, 3.0, Some(10000), None, None, None, None, t);
+
+        let collection = db.export_geojson(60.0, 100);
+        assert_eq!(collection["type"], "FeatureCollection");
+        let features = collection["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+
+        let mil_feature = features.iter().find(|f| f["properties"]["icao"] == "4840D6").unwrap();
+        assert_eq!(mil_feature["geometry"]["type"], "LineString");
+        assert_eq!(mil_feature["geometry"]["coordinates"].as_array().unwrap().len(), 2);
+        assert_eq!(mil_feature["properties"]["is_military"], true);
+
+        let civ_feature = features.iter().find(|f| f["properties"]["icao"] == "4840D7").unwrap();
+        assert_eq!(civ_feature["properties"]["is_military"], false);
+    }
+
+    #[test]
+    fn test_export_csv_multiple_aircraft() {
+        let mut db = test_db();
+        let t = now();
+        let a = icao_from_hex("4840D6").unwrap();
+        let b = icao_from_hex("4840D7").unwrap();
+        db.upsert_aircraft(&a, None, None, false, t);
+        db.upsert_aircraft(&b, None, None, false, t);
+        db.add_position(&a, 52.0, 4.0, Some(1000), Some(250.0), Some(90.0), None, None, t);
+        db.add_position(&b, 51.0, 3.0, Some(2000), None, None, None, None, t);
+
+        let mut out = Vec::new();
+        let count = db.export_csv(60.0, 100, &mut out).unwrap();
+        assert_eq!(count, 2);
+
+        let text = String::from_utf8(out).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(lines.next().unwrap(), "icao,lat,lon,altitude_ft,speed_kts,heading_deg,vertical_rate_fpm,timestamp");
+        assert_eq!(lines.count(), 2);
+    }
+
+    #[test]
+    fn test_get_trails_smoothed_rejects_spike() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        let t = now();
+        db.upsert_aircraft(&icao, None, None, false, t);
+        db.add_position(&icao, 52.0, 4.0, None, None, None, None, None, t);
+        db.add_position(&icao, 52.0, 4.0, None, None, None, None, None, t + 600.0);
+        // CPR-decode spike: jumps ~65km away then snaps back. Ten minutes
+        // between fixes keeps the implied speed plausible (add_position
+        // accepts it), but it's still far enough from its neighbours for
+        // get_trails_smoothed's spatial check to flag it.
+        db.add_position(&icao, 52.5, 4.5, None, None, None, None, None, t + 1200.0);
+        db.add_position(&icao, 52.0, 4.0, None, None, None, None, None, t + 1800.0);
+        db.add_position(&icao, 52.0, 4.0, None, None, None, None, None, t + 2400.0);
+
+        let smoothed = db.get_trails_smoothed(60.0, 100, 3, 50.0);
+        assert_eq!(smoothed.len(), 5);
+        // The spike should be replaced with the last good position, not 52.5/4.5.
+        assert!((smoothed[2].lat - 52.0).abs() < 0.001);
+        assert!((smoothed[2].lon - 4.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_get_trails_smoothed_keeps_consistent_track() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        let t = now();
+        db.upsert_aircraft(&icao, None, None, false, t);
+        for i in 0..5 {
+            db.add_position(&icao, 52.0 + i as f64 * 0.001, 4.0 + i as f64 * 0.001, None, None, None, None, None, t + i as f64);
+        }
+
+        let raw = db.get_trails(60.0, 100);
+        let smoothed = db.get_trails_smoothed(60.0, 100, 3, 50.0);
+        assert_eq!(raw.len(), smoothed.len());
+        for (r, s) in raw.iter().zip(smoothed.iter()) {
+            assert!((r.lat - s.lat).abs() < 1e-9);
+            assert!((r.lon - s.lon).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_add_position_rejects_implausible_jump() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 0.0);
+
+        assert!(db.add_position(&icao, 52.0, 4.0, Some(30000), None, None, None, None, 0.0));
+        // A second later, ~500km away: implies an impossible ground speed.
+        assert!(!db.add_position(&icao, 57.0, 4.0, Some(30000), None, None, None, None, 1.0));
+
+        assert_eq!(db.count_positions(), 1);
+        let positions = db.get_positions("4840D6", 10);
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].lat, 52.0);
+    }
+
+    #[test]
+    fn test_clean_positions_removes_implausible_jumps() {
+        let mut db = test_db();
+        let icao = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&icao, None, None, false, 0.0);
+
+        // Bypass add_position's own gate with direct inserts, simulating
+        // rows that predate this check (e.g. a bulk import) and that
+        // clean_positions must catch retroactively.
+        for (lat, lon, ts) in [
+            (52.0, 4.0, now() - 3000.0),
+            (57.0, 4.0, now() - 2999.0),
+            (52.01, 4.01, now() - 2998.0),
+        ] {
+            db.conn
+                .execute(
+                    "INSERT INTO positions (icao, lat, lon, altitude_ft, timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![icao_to_string(&icao), lat, lon, 30000, ts],
+                )
+                .unwrap();
+        }
+        assert_eq!(db.count_positions(), 3);
+
+        let removed = db.clean_positions(2.0);
+        assert_eq!(removed, 1);
+        assert_eq!(db.count_positions(), 2);
+
+        let positions = db.get_positions("4840D6", 10);
+        assert!(positions.iter().all(|p| p.lat < 53.0));
+    }
+
+    #[test]
+    fn test_query_positions_military_above_altitude() {
+        let mut db = test_db();
+        let mil = icao_from_hex("4840D6").unwrap();
+        let civ = icao_from_hex("4840D7").unwrap();
+        db.upsert_aircraft(&mil, None, None, true, 0.0);
+        db.upsert_aircraft(&civ, None, None, false, 0.0);
+        db.add_position(&mil, 52.0, 4.0, Some(36000), None, None, None, None, 0.0);
+        db.add_position(&civ, 52.0, 4.0, Some(36000), None, None, None, None, 0.0);
+
+        let rows = db.query_positions(Some(35000), None, None, true, SpatialFilter::default(), 10, 0);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].icao, "4840D6");
+    }
+
+    #[test]
+    fn test_execute_query_composes_filters_across_relations() {
+        let mut db = test_db();
+        let mil = icao_from_hex("4840D6").unwrap();
+        let civ = icao_from_hex("4840D7").unwrap();
+        db.upsert_aircraft(&mil, None, None, true, 0.0);
+        db.upsert_aircraft(&civ, None, None, true, 0.0);
+        db.add_position(&mil, 52.0, 4.0, Some(36000), None, None, None, None, 0.0);
+        db.add_position(&civ, 52.0, 4.0, Some(20000), None, None, None, None, 0.0);
+
+        // "positions for military aircraft above FL350" - a filter
+        // combination query_positions doesn't expose directly.
+        let query = Query::new(Relation::Positions, &["icao", "altitude_ft"])
+            .join(Relation::Aircraft)
+            .filter_on(Relation::Aircraft, "is_military", Cmp::Eq, Box::new(1))
+            .filter("altitude_ft", Cmp::Gt, Box::new(35000))
+            .order_by("timestamp", Order::Desc)
+            .limit(10);
+
+        let rows: Vec<(String, Option<i32>)> = db.execute_query(query, |r| Ok((r.get(0)?, r.get(1)?)));
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, "4840D6");
+        assert_eq!(rows[0].1, Some(36000));
+    }
+
+    #[test]
+    fn test_get_heatmap_binned_groups_nearby_positions() {
+        let mut db = test_db();
+        let t = now();
+        let a = icao_from_hex("4840D6").unwrap();
+        let b = icao_from_hex("4840D7").unwrap();
+        db.upsert_aircraft(&a, None, None, false, t);
+        db.upsert_aircraft(&b, None, None, false, t);
+        // Two fixes in the same 1-degree cell...
+        db.add_position(&a, 52.1, 4.1, Some(10000), None, None, None, None, t);
+        db.add_position(&b, 52.2, 4.2, Some(20000), None, None, None, None, t);
+        // ...and one far away in a different cell (long enough after the
+        // first fix from the same aircraft to pass add_position's own
+        // speed-plausibility gate).
+        db.add_position(&a, -10.0, -10.0, Some(5000), None, None, None, None, t + 100_000.0);
+
+        let bins = db.get_heatmap_binned(120.0, 1.0, None);
+        assert_eq!(bins.len(), 2);
+
+        let dense = bins.iter().find(|b| b.2 == 2).unwrap();
+        assert!((dense.0 - 52.5).abs() < 1.0);
+        assert!((dense.1 - 4.5).abs() < 1.0);
+        assert_eq!(dense.3, Some(15000.0));
+
+        let sparse = bins.iter().find(|b| b.2 == 1).unwrap();
+        assert_eq!(sparse.3, Some(5000.0));
+    }
+
+    #[test]
+    fn test_get_heatmap_binned_altitude_band_splits_same_cell() {
+        let mut db = test_db();
+        let t = now();
+        let a = icao_from_hex("4840D6").unwrap();
+        db.upsert_aircraft(&a, None, None, false, t);
+        db.add_position(&a, 52.1, 4.1, Some(1000), None, None, None, None, t);
+        db.add_position(&a, 52.1, 4.1, Some(35000), None, None, None, None, t + 1.0);
+
+        let unbanded = db.get_heatmap_binned(60.0, 1.0, None);
+        assert_eq!(unbanded.len(), 1);
+        assert_eq!(unbanded[0].2, 2);
+
+        let banded = db.get_heatmap_binned(60.0, 1.0, Some(10000));
+        assert_eq!(banded.len(), 2);
+        assert!(banded.iter().all(|b| b.2 == 1));
+    }
 }
@@ -12,9 +12,15 @@
 
 #![cfg(feature = "timescaledb")]
 
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json::{json, Value};
 use sqlx::postgres::PgPoolOptions;
 use sqlx::{PgPool, Row};
 
+use adsb_core::filter::haversine_nm;
+
 use crate::db::*;
 
 /// TimescaleDB schema — creates tables, hypertables, and policies.
@@ -205,6 +211,42 @@ impl TimescaleDb {
 
         Ok(TimescaleDb { pool })
     }
+
+    /// Like `get_recent_positions`, but forces a specific resolution tier
+    /// instead of auto-selecting one from `minutes` (see
+    /// `resolved_positions_cte`).
+    pub async fn get_recent_positions_at_resolution(
+        &self,
+        minutes: f64,
+        filter: SpatialFilter,
+        limit: i64,
+        resolution: Duration,
+    ) -> Vec<PositionRow> {
+        query_recent_positions(&self.pool, minutes, &filter, limit, Some(resolution)).await
+    }
+
+    /// Like `get_trails`, but forces a specific resolution tier instead of
+    /// auto-selecting one from `minutes`.
+    pub async fn get_trails_at_resolution(
+        &self,
+        minutes: f64,
+        limit_per_aircraft: i64,
+        resolution: Duration,
+    ) -> Vec<PositionRow> {
+        query_trails(&self.pool, minutes, limit_per_aircraft, Some(resolution)).await
+    }
+
+    /// Like `export_positions`, but forces a specific resolution tier
+    /// instead of auto-selecting one from `hours`.
+    pub async fn export_positions_at_resolution(
+        &self,
+        hours: Option<f64>,
+        icao: Option<&str>,
+        limit: i64,
+        resolution: Duration,
+    ) -> Vec<PositionRow> {
+        query_export_positions(&self.pool, hours, icao, limit, Some(resolution)).await
+    }
 }
 
 /// Helper: convert epoch seconds to PostgreSQL TIMESTAMPTZ.
@@ -220,6 +262,432 @@ fn pg_to_epoch(dt: chrono::DateTime<chrono::Utc>) -> f64 {
     dt.timestamp() as f64 + dt.timestamp_subsec_nanos() as f64 / 1_000_000_000.0
 }
 
+// ---------------------------------------------------------------------------
+// Takeoff/landing detection
+// ---------------------------------------------------------------------------
+//
+// Slides a 3-point window over each aircraft's positions (ordered by time)
+// looking for a ground/airborne transition. There's no per-airport
+// elevation data wired into this crate, so "ground" is approximated as sea
+// level (`altitude_ft` itself) rather than true AGL -- good enough near
+// low-elevation fields, optimistic at high-elevation ones.
+
+/// AGL threshold (approximated against sea level, see above) below which a
+/// position counts as "on the ground".
+const GROUND_THRESHOLD_FT: i32 = 150;
+
+/// Minimum ground speed for a climb-through-threshold to count as a takeoff
+/// rather than e.g. a low pass.
+const TAKEOFF_MIN_SPEED_KTS: f64 = 55.0;
+
+/// Minimum climb/descent rate for the transition point to count as an
+/// actual takeoff/landing rather than level flight that happens to cross
+/// the threshold (e.g. terrain-following at a ridge).
+const MIN_VERTICAL_RATE_FPM: i32 = 100;
+
+/// Reject a candidate window whose total duration exceeds this, so a
+/// reception gap spanning minutes doesn't get mistaken for a transition.
+const MAX_EVENT_WINDOW_SECS: f64 = 100.0;
+
+/// Reject a candidate window whose great-circle span exceeds this, for the
+/// same reason.
+const MAX_EVENT_SPAN_KM: f64 = 5.0;
+
+/// A detected takeoff or landing, anchored at the window's transition point
+/// (`positions[1]`).
+struct TakeoffLandingEvent {
+    icao: String,
+    event_type: &'static str,
+    lat: f64,
+    lon: f64,
+    altitude_ft: Option<i32>,
+    timestamp: f64,
+}
+
+/// Classify a 3-point `(p0, p1, p2)` window as a takeoff, a landing, or
+/// neither. All three points must belong to the same aircraft -- callers
+/// are expected to only form windows within a single icao's position run.
+fn classify_takeoff_landing(
+    p0: &PositionRow,
+    p1: &PositionRow,
+    p2: &PositionRow,
+) -> Option<TakeoffLandingEvent> {
+    let duration = p2.timestamp - p0.timestamp;
+    if duration <= 0.0 || duration > MAX_EVENT_WINDOW_SECS {
+        return None;
+    }
+
+    let span_km = haversine_nm(p0.lat, p0.lon, p2.lat, p2.lon) * 1.852;
+    if span_km > MAX_EVENT_SPAN_KM {
+        return None;
+    }
+
+    let (alt0, alt1, alt2) = (p0.altitude_ft?, p1.altitude_ft?, p2.altitude_ft?);
+
+    let event_type = if alt0 < GROUND_THRESHOLD_FT
+        && alt1 >= GROUND_THRESHOLD_FT
+        && alt2 >= GROUND_THRESHOLD_FT
+        && p1.speed_kts.is_some_and(|s| s >= TAKEOFF_MIN_SPEED_KTS)
+        && p1.vertical_rate_fpm.is_some_and(|vr| vr > MIN_VERTICAL_RATE_FPM)
+    {
+        "takeoff"
+    } else if alt0 >= GROUND_THRESHOLD_FT
+        && alt1 < GROUND_THRESHOLD_FT
+        && alt2 < GROUND_THRESHOLD_FT
+        && p1.vertical_rate_fpm.is_some_and(|vr| vr < -MIN_VERTICAL_RATE_FPM)
+    {
+        "landing"
+    } else {
+        return None;
+    };
+
+    Some(TakeoffLandingEvent {
+        icao: p1.icao.clone(),
+        event_type,
+        lat: p1.lat,
+        lon: p1.lon,
+        altitude_ft: p1.altitude_ft,
+        timestamp: p1.timestamp,
+    })
+}
+
+/// Append `SpatialFilter` bounds onto a dynamic `$N`-placeholder WHERE clause,
+/// advancing `idx` for each bound value pushed. Mirrors
+/// `SpatialFilter::push_conditions` in db.rs but uses Postgres placeholder
+/// syntax instead of rusqlite's.
+fn push_spatial_conditions(
+    filter: &SpatialFilter,
+    conditions: &mut Vec<String>,
+    idx: &mut i32,
+    lat_col: &str,
+    lon_col: &str,
+    alt_col: &str,
+) {
+    if filter.lat_min.is_some() {
+        conditions.push(format!("{lat_col} >= ${idx}"));
+        *idx += 1;
+    }
+    if filter.lat_max.is_some() {
+        conditions.push(format!("{lat_col} <= ${idx}"));
+        *idx += 1;
+    }
+    if filter.lon_min.is_some() {
+        conditions.push(format!("{lon_col} >= ${idx}"));
+        *idx += 1;
+    }
+    if filter.lon_max.is_some() {
+        conditions.push(format!("{lon_col} <= ${idx}"));
+        *idx += 1;
+    }
+    if filter.floor_ft.is_some() {
+        conditions.push(format!("({alt_col} IS NULL OR {alt_col} >= ${idx})"));
+        *idx += 1;
+    }
+    if filter.ceiling_ft.is_some() {
+        conditions.push(format!("({alt_col} IS NULL OR {alt_col} <= ${idx})"));
+        *idx += 1;
+    }
+}
+
+/// Bind a `SpatialFilter`'s present values onto a query, in the same order
+/// `push_spatial_conditions` pushed their placeholders.
+fn bind_spatial_values<'q>(
+    mut query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+    filter: &SpatialFilter,
+) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+    if let Some(v) = filter.lat_min {
+        query = query.bind(v);
+    }
+    if let Some(v) = filter.lat_max {
+        query = query.bind(v);
+    }
+    if let Some(v) = filter.lon_min {
+        query = query.bind(v);
+    }
+    if let Some(v) = filter.lon_max {
+        query = query.bind(v);
+    }
+    if let Some(v) = filter.floor_ft {
+        query = query.bind(v);
+    }
+    if let Some(v) = filter.ceiling_ft {
+        query = query.bind(v);
+    }
+    query
+}
+
+// ---------------------------------------------------------------------------
+// Resolution-aware position queries
+// ---------------------------------------------------------------------------
+//
+// `get_recent_positions`, `get_trails` and `export_positions` all read over
+// a time span that can range from minutes to days. Reading the raw
+// `positions` hypertable for a multi-day span returns millions of rows, so
+// these pick the coarsest of the continuous aggregates (see `CAGG_30S`,
+// `CAGG_5M`) that still satisfies the request.
+
+/// A source table for position queries, from finest to coarsest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resolution {
+    Raw,
+    ThirtySeconds,
+    FiveMinutes,
+}
+
+impl Resolution {
+    /// Auto-select the coarsest resolution that's still acceptable for a
+    /// span of `span_secs` seconds: raw data under ~1 hour, 30-second
+    /// buckets up to ~6 hours, 5-minute buckets beyond that.
+    fn for_span(span_secs: f64) -> Self {
+        const ONE_HOUR: f64 = 3600.0;
+        const SIX_HOURS: f64 = 6.0 * 3600.0;
+        if span_secs <= ONE_HOUR {
+            Resolution::Raw
+        } else if span_secs <= SIX_HOURS {
+            Resolution::ThirtySeconds
+        } else {
+            Resolution::FiveMinutes
+        }
+    }
+
+    /// Map a caller-supplied override onto the nearest tier: <=1s is raw,
+    /// <=30s is the 30-second aggregate, anything coarser is the 5-minute
+    /// one.
+    fn from_override(d: Duration) -> Self {
+        if d <= Duration::from_secs(1) {
+            Resolution::Raw
+        } else if d <= Duration::from_secs(30) {
+            Resolution::ThirtySeconds
+        } else {
+            Resolution::FiveMinutes
+        }
+    }
+}
+
+/// Query the materialized watermark for a continuous aggregate: the most
+/// recent bucket it has already computed. Rows newer than this haven't
+/// been materialized yet and must be aggregated live from `positions`.
+async fn aggregate_watermark(pool: &PgPool, table: &str) -> chrono::DateTime<chrono::Utc> {
+    let sql = format!("SELECT MAX(bucket) FROM {table}");
+    sqlx::query_scalar::<_, Option<chrono::DateTime<chrono::Utc>>>(&sql)
+        .fetch_one(pool)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| chrono::Utc::now() - chrono::Duration::days(365))
+}
+
+/// Build a `WITH src AS (icao, lat, lon, altitude_ft, speed_kts,
+/// heading_deg, vertical_rate_fpm, time)` CTE over the requested span,
+/// automatically picking the coarsest source that keeps result cardinality
+/// bounded (see `Resolution::for_span`). `resolution` overrides the
+/// automatic choice so callers can force a specific bucket width.
+///
+/// For the two aggregate tiers, the materialized rows (older than the
+/// aggregate's watermark) are UNIONed with a live `time_bucket`
+/// aggregation of raw rows newer than it, so not-yet-materialized data
+/// still shows up. The watermark is queried once up front and spliced in
+/// as a constant bound rather than a parameter, so the planner can exclude
+/// old chunks before it even looks at the query's own placeholders.
+async fn resolved_positions_cte(
+    pool: &PgPool,
+    span_secs: f64,
+    resolution: Option<Duration>,
+) -> String {
+    let tier = resolution
+        .map(Resolution::from_override)
+        .unwrap_or_else(|| Resolution::for_span(span_secs));
+
+    let (table, bucket_width) = match tier {
+        Resolution::Raw => {
+            return "WITH src AS (
+                SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg, vertical_rate_fpm, time
+                FROM positions
+            )"
+            .to_string();
+        }
+        Resolution::ThirtySeconds => ("positions_30s", "30 seconds"),
+        Resolution::FiveMinutes => ("positions_5m", "5 minutes"),
+    };
+
+    let watermark = aggregate_watermark(pool, table).await.to_rfc3339();
+    format!(
+        "WITH src AS (
+            SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg, vertical_rate_fpm, bucket AS time
+            FROM {table}
+            WHERE bucket <= '{watermark}'::TIMESTAMPTZ
+            UNION ALL
+            SELECT icao, AVG(lat) AS lat, AVG(lon) AS lon, AVG(altitude_ft)::INTEGER AS altitude_ft,
+                   AVG(speed_kts) AS speed_kts, AVG(heading_deg) AS heading_deg,
+                   AVG(vertical_rate_fpm)::INTEGER AS vertical_rate_fpm,
+                   time_bucket('{bucket_width}', time) AS time
+            FROM positions
+            WHERE time > '{watermark}'::TIMESTAMPTZ
+            GROUP BY icao, time_bucket('{bucket_width}', time)
+        )"
+    )
+}
+
+/// Core of `get_recent_positions` / `get_recent_positions_at_resolution`:
+/// resolve the source CTE for `minutes` and apply the spatial filter and
+/// limit on top of it.
+async fn query_recent_positions(
+    pool: &PgPool,
+    minutes: f64,
+    filter: &SpatialFilter,
+    limit: i64,
+    resolution: Option<Duration>,
+) -> Vec<PositionRow> {
+    let cte = resolved_positions_cte(pool, minutes * 60.0, resolution).await;
+    let interval = format!("{} minutes", minutes as i64);
+
+    let mut conditions = vec!["time >= NOW() - $1::INTERVAL".to_string()];
+    let mut idx = 2;
+    push_spatial_conditions(filter, &mut conditions, &mut idx, "lat", "lon", "altitude_ft");
+
+    let where_clause = conditions.join(" AND ");
+    let sql = format!(
+        "{cte}
+         SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg,
+                vertical_rate_fpm, EXTRACT(EPOCH FROM time) as timestamp
+         FROM src
+         WHERE {where_clause}
+         ORDER BY time DESC LIMIT ${idx}"
+    );
+
+    let mut query = sqlx::query(&sql).bind(interval);
+    query = bind_spatial_values(query, filter);
+    query = query.bind(limit);
+
+    query
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|r| PositionRow {
+            icao: r.get("icao"),
+            lat: r.get("lat"),
+            lon: r.get("lon"),
+            altitude_ft: r.get("altitude_ft"),
+            speed_kts: r.get("speed_kts"),
+            heading_deg: r.get("heading_deg"),
+            vertical_rate_fpm: r.get("vertical_rate_fpm"),
+            timestamp: r.get("timestamp"),
+        })
+        .collect()
+}
+
+/// Core of `get_trails` / `get_trails_at_resolution`: resolve the source
+/// CTE for `minutes`, then apply the same per-icao `ROW_NUMBER` windowing
+/// the raw-table version used.
+async fn query_trails(
+    pool: &PgPool,
+    minutes: f64,
+    limit_per_aircraft: i64,
+    resolution: Option<Duration>,
+) -> Vec<PositionRow> {
+    let cte = resolved_positions_cte(pool, minutes * 60.0, resolution).await;
+    let interval = format!("{} minutes", minutes as i64);
+
+    let sql = format!(
+        "{cte}
+         SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg,
+                vertical_rate_fpm, EXTRACT(EPOCH FROM time) as timestamp
+         FROM (
+             SELECT *, ROW_NUMBER() OVER (PARTITION BY icao ORDER BY time DESC) as rn
+             FROM src WHERE time >= NOW() - $1::INTERVAL
+         ) sub WHERE rn <= $2
+         ORDER BY icao, time ASC"
+    );
+
+    sqlx::query(&sql)
+        .bind(interval)
+        .bind(limit_per_aircraft)
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|r| PositionRow {
+            icao: r.get("icao"),
+            lat: r.get("lat"),
+            lon: r.get("lon"),
+            altitude_ft: r.get("altitude_ft"),
+            speed_kts: r.get("speed_kts"),
+            heading_deg: r.get("heading_deg"),
+            vertical_rate_fpm: r.get("vertical_rate_fpm"),
+            timestamp: r.get("timestamp"),
+        })
+        .collect()
+}
+
+/// Core of `export_positions` / `export_positions_at_resolution`. An
+/// unbounded export (`hours: None`) has no span to size a resolution from,
+/// so it's treated as the widest possible span and gets the coarsest tier
+/// unless `resolution` overrides it.
+async fn query_export_positions(
+    pool: &PgPool,
+    hours: Option<f64>,
+    icao: Option<&str>,
+    limit: i64,
+    resolution: Option<Duration>,
+) -> Vec<PositionRow> {
+    let span_secs = hours.map(|h| h * 3600.0).unwrap_or(f64::INFINITY);
+    let cte = resolved_positions_cte(pool, span_secs, resolution).await;
+
+    let mut conditions = Vec::new();
+    let mut idx = 1;
+
+    if hours.is_some() {
+        conditions.push(format!("time >= NOW() - ${idx}::INTERVAL"));
+        idx += 1;
+    }
+    if icao.is_some() {
+        conditions.push(format!("icao = ${idx}"));
+        idx += 1;
+    }
+
+    let where_clause = if conditions.is_empty() {
+        "TRUE".to_string()
+    } else {
+        conditions.join(" AND ")
+    };
+
+    let sql = format!(
+        "{cte}
+         SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg,
+                vertical_rate_fpm, EXTRACT(EPOCH FROM time) as timestamp
+         FROM src WHERE {where_clause}
+         ORDER BY time ASC LIMIT ${idx}"
+    );
+
+    let mut query = sqlx::query(&sql);
+    if let Some(h) = hours {
+        let interval = format!("{} hours", h as i64);
+        query = query.bind(interval);
+    }
+    if let Some(ic) = icao {
+        query = query.bind(ic);
+    }
+    query = query.bind(limit);
+
+    query
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|r| PositionRow {
+            icao: r.get("icao"),
+            lat: r.get("lat"),
+            lon: r.get("lon"),
+            altitude_ft: r.get("altitude_ft"),
+            speed_kts: r.get("speed_kts"),
+            heading_deg: r.get("heading_deg"),
+            vertical_rate_fpm: r.get("vertical_rate_fpm"),
+            timestamp: r.get("timestamp"),
+        })
+        .collect()
+}
+
 #[async_trait::async_trait]
 impl AdsbDatabase for TimescaleDb {
     async fn stats(&self) -> DbStats {
@@ -298,22 +766,13 @@ impl AdsbDatabase for TimescaleDb {
         .unwrap_or_default()
     }
 
-    async fn get_recent_positions(&self, minutes: f64, limit: i64) -> Vec<PositionRow> {
-        let interval = format!("{} minutes", minutes as i64);
-        sqlx::query_as!(
-            PositionRow,
-            r#"SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg,
-                      vertical_rate_fpm,
-                      EXTRACT(EPOCH FROM time) as "timestamp!: f64"
-               FROM positions
-               WHERE time >= NOW() - $1::INTERVAL
-               ORDER BY time DESC LIMIT $2"#,
-            interval,
-            limit
-        )
-        .fetch_all(&self.pool)
-        .await
-        .unwrap_or_default()
+    async fn get_recent_positions(
+        &self,
+        minutes: f64,
+        filter: crate::db::SpatialFilter,
+        limit: i64,
+    ) -> Vec<PositionRow> {
+        query_recent_positions(&self.pool, minutes, &filter, limit, None).await
     }
 
     async fn get_events(
@@ -389,41 +848,33 @@ impl AdsbDatabase for TimescaleDb {
     }
 
     async fn get_trails(&self, minutes: f64, limit_per_aircraft: i64) -> Vec<PositionRow> {
-        let interval = format!("{} minutes", minutes as i64);
-        sqlx::query_as!(
-            PositionRow,
-            r#"SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg,
-                      vertical_rate_fpm,
-                      EXTRACT(EPOCH FROM time) as "timestamp!: f64"
-               FROM (
-                   SELECT *, ROW_NUMBER() OVER (PARTITION BY icao ORDER BY time DESC) as rn
-                   FROM positions WHERE time >= NOW() - $1::INTERVAL
-               ) sub WHERE rn <= $2
-               ORDER BY icao, time ASC"#,
-            interval,
-            limit_per_aircraft
-        )
-        .fetch_all(&self.pool)
-        .await
-        .unwrap_or_default()
+        query_trails(&self.pool, minutes, limit_per_aircraft, None).await
     }
 
     async fn get_heatmap_positions(
         &self,
         minutes: f64,
+        filter: SpatialFilter,
         limit: i64,
     ) -> Vec<(f64, f64, Option<i32>)> {
         let interval = format!("{} minutes", minutes as i64);
-        let rows = sqlx::query(
+
+        let mut conditions = vec!["time >= NOW() - $1::INTERVAL".to_string()];
+        let mut idx = 2;
+        push_spatial_conditions(&filter, &mut conditions, &mut idx, "lat", "lon", "altitude_ft");
+
+        let where_clause = conditions.join(" AND ");
+        let sql = format!(
             "SELECT lat, lon, altitude_ft FROM positions
-             WHERE time >= NOW() - $1::INTERVAL
-             ORDER BY RANDOM() LIMIT $2",
-        )
-        .bind(&interval)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await
-        .unwrap_or_default();
+             WHERE {where_clause}
+             ORDER BY RANDOM() LIMIT ${idx}"
+        );
+
+        let mut query = sqlx::query(&sql).bind(&interval);
+        query = bind_spatial_values(query, &filter);
+        query = query.bind(limit);
+
+        let rows = query.fetch_all(&self.pool).await.unwrap_or_default();
 
         rows.iter()
             .map(|r| {
@@ -442,7 +893,9 @@ impl AdsbDatabase for TimescaleDb {
         max_alt: Option<i32>,
         icao: Option<&str>,
         military: bool,
+        filter: SpatialFilter,
         limit: i64,
+        offset: i64,
     ) -> Vec<PositionRow> {
         // Build dynamic WHERE clause
         let mut conditions = vec!["TRUE".to_string()];
@@ -463,6 +916,7 @@ impl AdsbDatabase for TimescaleDb {
         if military {
             conditions.push("a.is_military = TRUE".to_string());
         }
+        push_spatial_conditions(&filter, &mut conditions, &mut idx, "p.lat", "p.lon", "p.altitude_ft");
 
         let where_clause = conditions.join(" AND ");
         let sql = format!(
@@ -471,7 +925,8 @@ impl AdsbDatabase for TimescaleDb {
              FROM positions p
              LEFT JOIN aircraft a ON p.icao = a.icao
              WHERE {where_clause}
-             ORDER BY p.time DESC LIMIT ${idx}"
+             ORDER BY p.time DESC LIMIT ${idx} OFFSET ${}",
+            idx + 1
         );
 
         let mut query = sqlx::query(&sql);
@@ -484,7 +939,9 @@ impl AdsbDatabase for TimescaleDb {
         if let Some(v) = icao {
             query = query.bind(v);
         }
+        query = bind_spatial_values(query, &filter);
         query = query.bind(limit);
+        query = query.bind(offset);
 
         query
             .fetch_all(&self.pool)
@@ -540,6 +997,95 @@ impl AdsbDatabase for TimescaleDb {
             .collect()
     }
 
+    /// Data-coverage inventory, per icao and per receiver: the earliest/
+    /// latest sample, plus the hour- and day-bucketed span and how many
+    /// distinct buckets in that span actually have data (see
+    /// `InventoryRow`).
+    ///
+    /// The per-aircraft scan reads `positions_5m` rather than raw
+    /// `positions`, since it's already downsampled and a 90-day scan over
+    /// it is cheap. `positions_30s`/`positions_5m` don't carry
+    /// `receiver_id` (they're grouped by `(bucket, icao)` only, see
+    /// `CAGG_30S`/`CAGG_5M`), so the per-receiver scan has no aggregate to
+    /// fall back to and reads raw `positions` directly.
+    async fn coverage_inventory(&self) -> CoverageInventory {
+        let aircraft_rows = sqlx::query(
+            "SELECT icao,
+                    EXTRACT(EPOCH FROM MIN(bucket)) as earliest,
+                    EXTRACT(EPOCH FROM MAX(bucket)) as latest,
+                    EXTRACT(EPOCH FROM MIN(time_bucket('1 hour', bucket))) as hourly_start,
+                    EXTRACT(EPOCH FROM MAX(time_bucket('1 hour', bucket))) as hourly_end,
+                    COUNT(DISTINCT time_bucket('1 hour', bucket)) as hourly_buckets,
+                    EXTRACT(EPOCH FROM MIN(time_bucket('1 day', bucket))) as daily_start,
+                    EXTRACT(EPOCH FROM MAX(time_bucket('1 day', bucket))) as daily_end,
+                    COUNT(DISTINCT time_bucket('1 day', bucket)) as daily_buckets
+             FROM positions_5m
+             GROUP BY icao
+             ORDER BY icao",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        let by_aircraft = aircraft_rows
+            .iter()
+            .map(|r| AircraftInventoryRow {
+                icao: r.get("icao"),
+                inventory: InventoryRow {
+                    earliest: r.get("earliest"),
+                    latest: r.get("latest"),
+                    hourly_start: r.get("hourly_start"),
+                    hourly_end: r.get("hourly_end"),
+                    hourly_buckets: r.get("hourly_buckets"),
+                    daily_start: r.get("daily_start"),
+                    daily_end: r.get("daily_end"),
+                    daily_buckets: r.get("daily_buckets"),
+                },
+            })
+            .collect();
+
+        let receiver_rows = sqlx::query(
+            "SELECT p.receiver_id,
+                    r.name as receiver_name,
+                    EXTRACT(EPOCH FROM MIN(p.time)) as earliest,
+                    EXTRACT(EPOCH FROM MAX(p.time)) as latest,
+                    EXTRACT(EPOCH FROM MIN(time_bucket('1 hour', p.time))) as hourly_start,
+                    EXTRACT(EPOCH FROM MAX(time_bucket('1 hour', p.time))) as hourly_end,
+                    COUNT(DISTINCT time_bucket('1 hour', p.time)) as hourly_buckets,
+                    EXTRACT(EPOCH FROM MIN(time_bucket('1 day', p.time))) as daily_start,
+                    EXTRACT(EPOCH FROM MAX(time_bucket('1 day', p.time))) as daily_end,
+                    COUNT(DISTINCT time_bucket('1 day', p.time)) as daily_buckets
+             FROM positions p
+             LEFT JOIN receivers r ON p.receiver_id = r.id
+             WHERE p.receiver_id IS NOT NULL
+             GROUP BY p.receiver_id, r.name
+             ORDER BY p.receiver_id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        let by_receiver = receiver_rows
+            .iter()
+            .map(|r| ReceiverInventoryRow {
+                receiver_id: r.get("receiver_id"),
+                receiver_name: r.get("receiver_name"),
+                inventory: InventoryRow {
+                    earliest: r.get("earliest"),
+                    latest: r.get("latest"),
+                    hourly_start: r.get("hourly_start"),
+                    hourly_end: r.get("hourly_end"),
+                    hourly_buckets: r.get("hourly_buckets"),
+                    daily_start: r.get("daily_start"),
+                    daily_end: r.get("daily_end"),
+                    daily_buckets: r.get("daily_buckets"),
+                },
+            })
+            .collect();
+
+        CoverageInventory { by_aircraft, by_receiver }
+    }
+
     async fn get_aircraft_history(&self, hours: f64) -> Vec<HistoryRow> {
         let interval = format!("{} hours", hours as i64);
         let rows = sqlx::query(
@@ -579,56 +1125,314 @@ impl AdsbDatabase for TimescaleDb {
         icao: Option<&str>,
         limit: i64,
     ) -> Vec<PositionRow> {
-        let mut conditions = Vec::new();
-        let mut idx = 1;
+        query_export_positions(&self.pool, hours, icao, limit, None).await
+    }
 
-        if hours.is_some() {
-            conditions.push(format!("time >= NOW() - ${}::INTERVAL", idx));
-            idx += 1;
+    /// Build a GeoJSON `FeatureCollection` of aircraft trails, one `LineString`
+    /// feature per icao, from the last `minutes` of positions. Reuses
+    /// `get_trails` for the underlying windowed query so the two stay in
+    /// sync, then groups the rows by icao and attaches each aircraft's most
+    /// recent callsign plus its altitude range and sample count as feature
+    /// properties. Lets callers drop the output straight into Leaflet/Mapbox
+    /// without a separate conversion step.
+    async fn export_trails_geojson(&self, minutes: f64, limit_per_aircraft: i64) -> String {
+        let positions = self.get_trails(minutes, limit_per_aircraft).await;
+
+        let callsigns: HashMap<String, String> = sqlx::query(
+            "SELECT DISTINCT ON (icao) icao, callsign
+             FROM sightings
+             WHERE callsign IS NOT NULL
+             ORDER BY icao, last_seen DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|r| (r.get::<String, _>("icao"), r.get::<String, _>("callsign")))
+        .collect();
+
+        let mut order: Vec<String> = Vec::new();
+        let mut grouped: HashMap<String, Vec<&PositionRow>> = HashMap::new();
+        for p in &positions {
+            grouped.entry(p.icao.clone()).or_insert_with(|| {
+                order.push(p.icao.clone());
+                Vec::new()
+            });
+            grouped.get_mut(&p.icao).unwrap().push(p);
         }
-        if icao.is_some() {
-            conditions.push(format!("icao = ${}", idx));
-            idx += 1;
+
+        let features: Vec<Value> = order
+            .iter()
+            .map(|icao| {
+                let rows = &grouped[icao];
+                let coordinates: Vec<[f64; 2]> = rows.iter().map(|p| [p.lon, p.lat]).collect();
+                let altitudes: Vec<i32> = rows.iter().filter_map(|p| p.altitude_ft).collect();
+
+                json!({
+                    "type": "Feature",
+                    "geometry": {
+                        "type": "LineString",
+                        "coordinates": coordinates,
+                    },
+                    "properties": {
+                        "icao": icao,
+                        "callsign": callsigns.get(icao),
+                        "min_altitude_ft": altitudes.iter().min(),
+                        "max_altitude_ft": altitudes.iter().max(),
+                        "sample_count": rows.len(),
+                    },
+                })
+            })
+            .collect();
+
+        let collection = json!({
+            "type": "FeatureCollection",
+            "features": features,
+        });
+
+        serde_json::to_string(&collection).unwrap_or_else(|_| r#"{"type":"FeatureCollection","features":[]}"#.to_string())
+    }
+
+    /// Scan positions from the last `hours` and write a `takeoff`/`landing`
+    /// row into `events` for each detected transition (see
+    /// `classify_takeoff_landing`). Returns the number of events written.
+    async fn detect_takeoffs_landings(&self, hours: f64) -> u64 {
+        let interval = format!("{} hours", hours as i64);
+        let positions: Vec<PositionRow> = sqlx::query_as!(
+            PositionRow,
+            r#"SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg,
+                      vertical_rate_fpm,
+                      EXTRACT(EPOCH FROM time) as "timestamp!: f64"
+               FROM positions WHERE time >= NOW() - $1::INTERVAL
+               ORDER BY icao, time ASC"#,
+            interval
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        let mut written = 0u64;
+        for window in positions.windows(3) {
+            let [p0, p1, p2] = window else { continue };
+            if p0.icao != p1.icao || p1.icao != p2.icao {
+                continue;
+            }
+            let Some(event) = classify_takeoff_landing(p0, p1, p2) else {
+                continue;
+            };
+
+            let result = sqlx::query(
+                "INSERT INTO events (time, icao, event_type, description, lat, lon, altitude_ft)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            )
+            .bind(epoch_to_pg(event.timestamp))
+            .bind(&event.icao)
+            .bind(event.event_type)
+            .bind(format!("Detected {} from position history", event.event_type))
+            .bind(event.lat)
+            .bind(event.lon)
+            .bind(event.altitude_ft)
+            .execute(&self.pool)
+            .await;
+
+            if result.is_ok() {
+                written += 1;
+            }
         }
 
-        let where_clause = if conditions.is_empty() {
-            "TRUE".to_string()
-        } else {
-            conditions.join(" AND ")
-        };
+        written
+    }
 
-        let sql = format!(
-            "SELECT icao, lat, lon, altitude_ft, speed_kts, heading_deg,
-                    vertical_rate_fpm, EXTRACT(EPOCH FROM time) as timestamp
-             FROM positions WHERE {where_clause}
-             ORDER BY time ASC LIMIT ${idx}"
-        );
+    /// Find reception gaps longer than `min_gap_secs` within the last
+    /// `hours`, for both individual aircraft and individual receivers, and
+    /// record each as a `gap` event. Unlike `detect_takeoffs_landings`,
+    /// this doesn't need a Rust-side sliding window: `time - LAG(time)
+    /// OVER (PARTITION BY ... ORDER BY time)` computes the inter-sample
+    /// delta entirely in SQL, so only the rows that actually exceed the
+    /// threshold ever leave Postgres.
+    ///
+    /// The receiver scan only sees rows with `receiver_id` set — a gap on
+    /// an unlabeled receiver can't be attributed to one — and is stored
+    /// with `icao = "receiver:<id>"` since `events.icao` is a plain
+    /// NOT NULL text column, not a foreign key.
+    async fn detect_gaps(&self, hours: f64, min_gap_secs: f64) -> u64 {
+        let interval = format!("{} hours", hours as i64);
+        let mut written = 0u64;
 
-        let mut query = sqlx::query(&sql);
-        if let Some(h) = hours {
-            let interval = format!("{} hours", h as i64);
-            query = query.bind(interval);
+        let aircraft_gaps = sqlx::query(
+            "SELECT icao,
+                    EXTRACT(EPOCH FROM prev_time) as gap_start,
+                    EXTRACT(EPOCH FROM time) as gap_end,
+                    prev_lat, prev_lon, prev_altitude_ft
+             FROM (
+                 SELECT icao, lat, lon, altitude_ft, time,
+                        LAG(time) OVER (PARTITION BY icao ORDER BY time) as prev_time,
+                        LAG(lat) OVER (PARTITION BY icao ORDER BY time) as prev_lat,
+                        LAG(lon) OVER (PARTITION BY icao ORDER BY time) as prev_lon,
+                        LAG(altitude_ft) OVER (PARTITION BY icao ORDER BY time) as prev_altitude_ft
+                 FROM positions
+                 WHERE time >= NOW() - $1::INTERVAL
+             ) w
+             WHERE prev_time IS NOT NULL AND EXTRACT(EPOCH FROM (time - prev_time)) > $2",
+        )
+        .bind(&interval)
+        .bind(min_gap_secs)
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        for row in &aircraft_gaps {
+            let icao: String = row.get("icao");
+            let gap_start: f64 = row.get("gap_start");
+            let gap_end: f64 = row.get("gap_end");
+            let description = format!(
+                "Reception gap of {:.0}s for {icao}: last heard at t={gap_start:.0}, next heard at t={gap_end:.0}",
+                gap_end - gap_start
+            );
+
+            let result = sqlx::query(
+                "INSERT INTO events (time, icao, event_type, description, lat, lon, altitude_ft)
+                 VALUES ($1, $2, 'gap', $3, $4, $5, $6)",
+            )
+            .bind(epoch_to_pg(gap_start))
+            .bind(&icao)
+            .bind(description)
+            .bind(row.get::<Option<f64>, _>("prev_lat"))
+            .bind(row.get::<Option<f64>, _>("prev_lon"))
+            .bind(row.get::<Option<i32>, _>("prev_altitude_ft"))
+            .execute(&self.pool)
+            .await;
+
+            if result.is_ok() {
+                written += 1;
+            }
         }
-        if let Some(ic) = icao {
-            query = query.bind(ic);
+
+        let receiver_gaps = sqlx::query(
+            "SELECT receiver_id,
+                    EXTRACT(EPOCH FROM prev_time) as gap_start,
+                    EXTRACT(EPOCH FROM time) as gap_end,
+                    prev_lat, prev_lon, prev_altitude_ft
+             FROM (
+                 SELECT receiver_id, lat, lon, altitude_ft, time,
+                        LAG(time) OVER (PARTITION BY receiver_id ORDER BY time) as prev_time,
+                        LAG(lat) OVER (PARTITION BY receiver_id ORDER BY time) as prev_lat,
+                        LAG(lon) OVER (PARTITION BY receiver_id ORDER BY time) as prev_lon,
+                        LAG(altitude_ft) OVER (PARTITION BY receiver_id ORDER BY time) as prev_altitude_ft
+                 FROM positions
+                 WHERE time >= NOW() - $1::INTERVAL AND receiver_id IS NOT NULL
+             ) w
+             WHERE prev_time IS NOT NULL AND EXTRACT(EPOCH FROM (time - prev_time)) > $2",
+        )
+        .bind(&interval)
+        .bind(min_gap_secs)
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        for row in &receiver_gaps {
+            let receiver_id: i64 = row.get("receiver_id");
+            let gap_start: f64 = row.get("gap_start");
+            let gap_end: f64 = row.get("gap_end");
+            let description = format!(
+                "Reception gap of {:.0}s for receiver {receiver_id}: last heard at t={gap_start:.0}, next heard at t={gap_end:.0}",
+                gap_end - gap_start
+            );
+
+            let result = sqlx::query(
+                "INSERT INTO events (time, icao, event_type, description, lat, lon, altitude_ft)
+                 VALUES ($1, $2, 'gap', $3, $4, $5, $6)",
+            )
+            .bind(epoch_to_pg(gap_start))
+            .bind(format!("receiver:{receiver_id}"))
+            .bind(description)
+            .bind(row.get::<Option<f64>, _>("prev_lat"))
+            .bind(row.get::<Option<f64>, _>("prev_lon"))
+            .bind(row.get::<Option<i32>, _>("prev_altitude_ft"))
+            .execute(&self.pool)
+            .await;
+
+            if result.is_ok() {
+                written += 1;
+            }
         }
-        query = query.bind(limit);
 
-        query
-            .fetch_all(&self.pool)
-            .await
-            .unwrap_or_default()
-            .iter()
-            .map(|r| PositionRow {
-                icao: r.get("icao"),
-                lat: r.get("lat"),
-                lon: r.get("lon"),
-                altitude_ft: r.get("altitude_ft"),
-                speed_kts: r.get("speed_kts"),
-                heading_deg: r.get("heading_deg"),
-                vertical_rate_fpm: r.get("vertical_rate_fpm"),
-                timestamp: r.get("timestamp"),
-            })
-            .collect()
+        written
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(icao: &str, t: f64, lat: f64, lon: f64, alt: i32, speed: f64, vr: i32) -> PositionRow {
+        PositionRow {
+            icao: icao.to_string(),
+            lat,
+            lon,
+            altitude_ft: Some(alt),
+            speed_kts: Some(speed),
+            heading_deg: None,
+            vertical_rate_fpm: Some(vr),
+            timestamp: t,
+        }
+    }
+
+    #[test]
+    fn test_classify_takeoff() {
+        let p0 = position("ABC123", 0.0, 35.0, -82.0, 50, 20.0, 0);
+        let p1 = position("ABC123", 20.0, 35.001, -82.001, 300, 90.0, 1200);
+        let p2 = position("ABC123", 40.0, 35.002, -82.002, 800, 120.0, 1500);
+        let event = classify_takeoff_landing(&p0, &p1, &p2).unwrap();
+        assert_eq!(event.event_type, "takeoff");
+        assert_eq!(event.lat, p1.lat);
+        assert_eq!(event.timestamp, p1.timestamp);
+    }
+
+    #[test]
+    fn test_classify_landing() {
+        let p0 = position("ABC123", 0.0, 35.0, -82.0, 800, 130.0, -1000);
+        let p1 = position("ABC123", 20.0, 35.001, -82.001, 100, 70.0, -600);
+        let p2 = position("ABC123", 40.0, 35.002, -82.002, 40, 20.0, -300);
+        let event = classify_takeoff_landing(&p0, &p1, &p2).unwrap();
+        assert_eq!(event.event_type, "landing");
+    }
+
+    #[test]
+    fn test_classify_rejects_level_flight_crossing_threshold() {
+        // Crosses the ground threshold but with no meaningful climb/descent
+        // rate and no takeoff speed -- e.g. noisy altitude near the cutoff.
+        let p0 = position("ABC123", 0.0, 35.0, -82.0, 100, 20.0, 0);
+        let p1 = position("ABC123", 20.0, 35.0, -82.0, 200, 25.0, 10);
+        let p2 = position("ABC123", 40.0, 35.0, -82.0, 250, 25.0, 10);
+        assert!(classify_takeoff_landing(&p0, &p1, &p2).is_none());
+    }
+
+    #[test]
+    fn test_classify_rejects_long_duration_window() {
+        let p0 = position("ABC123", 0.0, 35.0, -82.0, 50, 20.0, 0);
+        let p1 = position("ABC123", 60.0, 35.001, -82.001, 300, 90.0, 1200);
+        let p2 = position("ABC123", 200.0, 35.002, -82.002, 800, 120.0, 1500);
+        assert!(classify_takeoff_landing(&p0, &p1, &p2).is_none());
+    }
+
+    #[test]
+    fn test_classify_rejects_large_spatial_span() {
+        // Same timing/altitude profile as test_classify_takeoff, but the
+        // endpoints are ~57km apart -- almost certainly two different
+        // reception gaps stitched together, not one continuous climb.
+        let p0 = position("ABC123", 0.0, 35.0, -82.0, 50, 20.0, 0);
+        let p1 = position("ABC123", 20.0, 35.2, -82.2, 300, 90.0, 1200);
+        let p2 = position("ABC123", 40.0, 35.4, -82.4, 800, 120.0, 1500);
+        assert!(classify_takeoff_landing(&p0, &p1, &p2).is_none());
+    }
+
+    #[test]
+    fn test_classify_missing_altitude_returns_none() {
+        let p0 = position("ABC123", 0.0, 35.0, -82.0, 50, 20.0, 0);
+        let mut p1 = position("ABC123", 20.0, 35.001, -82.001, 300, 90.0, 1200);
+        p1.altitude_ft = None;
+        let p2 = position("ABC123", 40.0, 35.002, -82.002, 800, 120.0, 1500);
+        assert!(classify_takeoff_landing(&p0, &p1, &p2).is_none());
     }
 }
@@ -0,0 +1,415 @@
+//! Arrow Flight SQL server — exposes the `aircraft`, `positions`, `events`,
+//! `captures` and `receivers` tables over Arrow Flight SQL so external
+//! analytics tools (DataFusion, Python/pandas via ADBC, etc.) can run
+//! ad-hoc SQL and pull results back as Arrow record batches instead of
+//! going through the bespoke per-method API or speaking PostgreSQL wire
+//! protocol directly.
+//!
+//! Requires the `flightsql` feature flag. Talks to the same `PgPool` the
+//! `timescaledb` backend uses, so it's only useful alongside that backend.
+
+#![cfg(feature = "flightsql")]
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array, RecordBatch, StringArray};
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use arrow_flight::sql::server::FlightSqlService;
+use arrow_flight::sql::{CommandGetDbSchemas, CommandGetTables, CommandStatementQuery, SqlInfo};
+use arrow_flight::{FlightDescriptor, FlightInfo, Ticket};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use futures::{Stream, StreamExt};
+use sqlx::{Column, PgPool, Row, TypeInfo};
+use tonic::{Request, Response, Status, Streaming};
+
+/// The tables this server exposes, in the order `GetTables` enumerates
+/// them. Schemas are fixed rather than introspected from `information_schema`
+/// because the set of tables `AdsbDatabase` backs is itself fixed (see the
+/// `CREATE TABLE` statements in `db_pg.rs`).
+const TABLE_NAMES: &[&str] = &["aircraft", "positions", "events", "captures", "receivers"];
+
+fn aircraft_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("icao", DataType::Utf8, false),
+        Field::new("registration", DataType::Utf8, true),
+        Field::new("country", DataType::Utf8, true),
+        Field::new("is_military", DataType::Boolean, true),
+        Field::new("first_seen", DataType::Float64, false),
+        Field::new("last_seen", DataType::Float64, false),
+    ])
+}
+
+fn positions_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("icao", DataType::Utf8, false),
+        Field::new("lat", DataType::Float64, false),
+        Field::new("lon", DataType::Float64, false),
+        Field::new("altitude_ft", DataType::Int32, true),
+        Field::new("speed_kts", DataType::Float64, true),
+        Field::new("heading_deg", DataType::Float64, true),
+        Field::new("vertical_rate_fpm", DataType::Int32, true),
+        Field::new("timestamp", DataType::Float64, false),
+    ])
+}
+
+fn events_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("icao", DataType::Utf8, false),
+        Field::new("event_type", DataType::Utf8, false),
+        Field::new("description", DataType::Utf8, true),
+        Field::new("lat", DataType::Float64, true),
+        Field::new("lon", DataType::Float64, true),
+        Field::new("altitude_ft", DataType::Int32, true),
+        Field::new("timestamp", DataType::Float64, false),
+    ])
+}
+
+fn captures_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("receiver_id", DataType::Int64, true),
+        Field::new("source", DataType::Utf8, true),
+        Field::new("start_time", DataType::Float64, true),
+        Field::new("end_time", DataType::Float64, true),
+        Field::new("total_frames", DataType::Int64, true),
+        Field::new("valid_frames", DataType::Int64, true),
+        Field::new("aircraft_count", DataType::Int64, true),
+    ])
+}
+
+fn receivers_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("lat", DataType::Float64, true),
+        Field::new("lon", DataType::Float64, true),
+        Field::new("description", DataType::Utf8, true),
+    ])
+}
+
+fn schema_for_table(name: &str) -> Option<Schema> {
+    match name {
+        "aircraft" => Some(aircraft_schema()),
+        "positions" => Some(positions_schema()),
+        "events" => Some(events_schema()),
+        "captures" => Some(captures_schema()),
+        "receivers" => Some(receivers_schema()),
+        _ => None,
+    }
+}
+
+/// Convert a slice of dynamically-typed Postgres rows into a single Arrow
+/// `RecordBatch`, inferring each column's Arrow type from the first row's
+/// Postgres type name. Used for every `CommandStatementQuery` result,
+/// including queries over the five known tables; `schema_for_table` only
+/// supplies the schemas that `GetTables` reports for them.
+fn rows_to_record_batch(rows: &[sqlx::postgres::PgRow]) -> Result<RecordBatch, Status> {
+    let Some(first) = rows.first() else {
+        return Ok(RecordBatch::new_empty(Arc::new(Schema::empty())));
+    };
+
+    let mut fields = Vec::new();
+    let mut arrays: Vec<ArrayRef> = Vec::new();
+
+    for (idx, col) in first.columns().iter().enumerate() {
+        let pg_type = col.type_info().name();
+        match pg_type {
+            "INT4" => {
+                let values: Vec<Option<i32>> = rows.iter().map(|r| r.try_get(idx).ok()).collect();
+                fields.push(Field::new(col.name(), DataType::Int32, true));
+                arrays.push(Arc::new(Int32Array::from(values)));
+            }
+            "INT8" | "BIGINT" => {
+                let values: Vec<Option<i64>> = rows.iter().map(|r| r.try_get(idx).ok()).collect();
+                fields.push(Field::new(col.name(), DataType::Int64, true));
+                arrays.push(Arc::new(Int64Array::from(values)));
+            }
+            "FLOAT4" | "FLOAT8" | "DOUBLE PRECISION" | "NUMERIC" => {
+                let values: Vec<Option<f64>> = rows.iter().map(|r| r.try_get(idx).ok()).collect();
+                fields.push(Field::new(col.name(), DataType::Float64, true));
+                arrays.push(Arc::new(Float64Array::from(values)));
+            }
+            "BOOL" | "BOOLEAN" => {
+                let values: Vec<Option<bool>> = rows.iter().map(|r| r.try_get(idx).ok()).collect();
+                fields.push(Field::new(col.name(), DataType::Boolean, true));
+                arrays.push(Arc::new(BooleanArray::from(values)));
+            }
+            // TEXT, VARCHAR, TIMESTAMPTZ and anything else we don't special-case
+            // are rendered as their string representation.
+            _ => {
+                let values: Vec<Option<String>> = rows
+                    .iter()
+                    .map(|r| r.try_get::<String, _>(idx).ok())
+                    .collect();
+                fields.push(Field::new(col.name(), DataType::Utf8, true));
+                arrays.push(Arc::new(StringArray::from(values)));
+            }
+        }
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+        .map_err(|e| Status::internal(format!("failed to build Arrow batch: {e}")))
+}
+
+/// Stream a single `RecordBatch` back to the client as Flight data.
+fn encode_batch(
+    schema: SchemaRef,
+    batch: RecordBatch,
+) -> Pin<Box<dyn Stream<Item = Result<arrow_flight::FlightData, Status>> + Send + 'static>> {
+    let stream = futures::stream::once(async move { Ok(batch) });
+    Box::pin(
+        FlightDataEncoderBuilder::new()
+            .with_schema(schema)
+            .build(stream)
+            .map(|r| r.map_err(|e| Status::internal(e.to_string()))),
+    )
+}
+
+/// Arrow Flight SQL front end for the ADS-B store: runs `CommandStatementQuery`
+/// against `pool` directly and answers catalog/metadata requests
+/// (`GetTables`, `GetDbSchemas`, `GetSqlInfo`) from the fixed table list
+/// above rather than querying `information_schema`.
+pub struct FlightSqlServiceImpl {
+    pool: PgPool,
+}
+
+impl FlightSqlServiceImpl {
+    pub fn new(pool: PgPool) -> Self {
+        FlightSqlServiceImpl { pool }
+    }
+}
+
+#[tonic::async_trait]
+impl FlightSqlService for FlightSqlServiceImpl {
+    type FlightService = FlightSqlServiceImpl;
+
+    async fn do_handshake(
+        &self,
+        _request: Request<Streaming<arrow_flight::HandshakeRequest>>,
+    ) -> Result<
+        Response<Pin<Box<dyn Stream<Item = Result<arrow_flight::HandshakeResponse, Status>> + Send>>>,
+        Status,
+    > {
+        // No auth: any client can connect. Fine for a read-only analytics
+        // endpoint on a trusted network; put this behind a reverse proxy
+        // with TLS + auth if exposed more broadly.
+        let output = futures::stream::empty();
+        Ok(Response::new(Box::pin(output)))
+    }
+
+    async fn get_flight_info_statement(
+        &self,
+        query: CommandStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let descriptor = request.into_inner();
+        let handle = arrow_flight::sql::TicketStatementQuery {
+            statement_handle: query.query.into_bytes().into(),
+        };
+        let ticket = Ticket::new(handle.as_any().encode_to_vec());
+        let info = FlightInfo::new()
+            .try_with_schema(&Schema::empty())
+            .map_err(|e| Status::internal(e.to_string()))?
+            .with_endpoint(arrow_flight::FlightEndpoint::new().with_ticket(ticket))
+            .with_descriptor(descriptor);
+        Ok(Response::new(info))
+    }
+
+    async fn do_get_statement(
+        &self,
+        ticket: arrow_flight::sql::TicketStatementQuery,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let sql = String::from_utf8(ticket.statement_handle.to_vec())
+            .map_err(|e| Status::invalid_argument(format!("bad statement handle: {e}")))?;
+
+        // Run inside a read-only transaction (rolled back on drop) so the
+        // server stays read-only, as `GetSqlInfo` reports.
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Status::internal(format!("query failed: {e}")))?;
+        sqlx::query("SET TRANSACTION READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Status::internal(format!("query failed: {e}")))?;
+        let rows = sqlx::query(&sql)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| Status::internal(format!("query failed: {e}")))?;
+
+        let batch = rows_to_record_batch(&rows)?;
+        let schema = batch.schema();
+        Ok(Response::new(encode_batch(schema, batch)))
+    }
+
+    async fn get_flight_info_tables(
+        &self,
+        _query: CommandGetTables,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let descriptor = request.into_inner();
+        let schema = tables_result_schema();
+        let ticket = Ticket::new(b"__tables__".to_vec());
+        let info = FlightInfo::new()
+            .try_with_schema(&schema)
+            .map_err(|e| Status::internal(e.to_string()))?
+            .with_endpoint(arrow_flight::FlightEndpoint::new().with_ticket(ticket))
+            .with_descriptor(descriptor);
+        Ok(Response::new(info))
+    }
+
+    async fn do_get_tables(
+        &self,
+        _query: CommandGetTables,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let schema = Arc::new(tables_result_schema());
+
+        let names: Vec<&str> = TABLE_NAMES.to_vec();
+        let table_type: Vec<&str> = names.iter().map(|_| "TABLE").collect();
+        let table_schema: Vec<Vec<u8>> = TABLE_NAMES
+            .iter()
+            .map(|t| schema_for_table(t).unwrap().to_bytes(&Default::default()).to_vec())
+            .collect();
+
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec![None::<&str>; names.len()])),
+                Arc::new(StringArray::from(vec![Some("public"); names.len()])),
+                Arc::new(StringArray::from(names.clone())),
+                Arc::new(StringArray::from(table_type)),
+                Arc::new(arrow_array::BinaryArray::from_iter_values(table_schema)),
+            ],
+        )
+        .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(encode_batch(schema, batch)))
+    }
+
+    async fn get_flight_info_db_schemas(
+        &self,
+        _query: CommandGetDbSchemas,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let descriptor = request.into_inner();
+        let schema = db_schemas_result_schema();
+        let ticket = Ticket::new(b"__db_schemas__".to_vec());
+        let info = FlightInfo::new()
+            .try_with_schema(&schema)
+            .map_err(|e| Status::internal(e.to_string()))?
+            .with_endpoint(arrow_flight::FlightEndpoint::new().with_ticket(ticket))
+            .with_descriptor(descriptor);
+        Ok(Response::new(info))
+    }
+
+    async fn do_get_db_schemas(
+        &self,
+        _query: CommandGetDbSchemas,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        // A single schema, "public", matching TIMESCALE_SCHEMA in db_pg.rs.
+        let schema = Arc::new(db_schemas_result_schema());
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec![None::<&str>])),
+                Arc::new(StringArray::from(vec!["public"])),
+            ],
+        )
+        .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(encode_batch(schema, batch)))
+    }
+
+    async fn get_flight_info_sql_info(
+        &self,
+        _query: arrow_flight::sql::CommandGetSqlInfo,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let descriptor = request.into_inner();
+        let schema = sql_info_result_schema();
+        let ticket = Ticket::new(b"__sql_info__".to_vec());
+        let info = FlightInfo::new()
+            .try_with_schema(&schema)
+            .map_err(|e| Status::internal(e.to_string()))?
+            .with_endpoint(arrow_flight::FlightEndpoint::new().with_ticket(ticket))
+            .with_descriptor(descriptor);
+        Ok(Response::new(info))
+    }
+
+    async fn do_get_sql_info(
+        &self,
+        query: arrow_flight::sql::CommandGetSqlInfo,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let schema = Arc::new(sql_info_result_schema());
+
+        let info_values: Vec<(u32, &str)> = vec![
+            (SqlInfo::FlightSqlServerName as u32, "adsb-decode flightsql"),
+            (SqlInfo::FlightSqlServerReadOnly as u32, "true"),
+            (SqlInfo::FlightSqlServerSql as u32, "true"),
+        ];
+        let requested = query.info;
+        let rows: Vec<&(u32, &str)> = info_values
+            .iter()
+            .filter(|(code, _)| requested.is_empty() || requested.contains(code))
+            .collect();
+
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(arrow_array::UInt32Array::from_iter_values(rows.iter().map(|(c, _)| *c))),
+                Arc::new(StringArray::from(rows.iter().map(|(_, v)| *v).collect::<Vec<_>>())),
+            ],
+        )
+        .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(encode_batch(schema, batch)))
+    }
+}
+
+/// Output schema for `GetTables`, per the Flight SQL spec: catalog, schema,
+/// table name, table type, and the table's own schema serialized as IPC
+/// bytes.
+fn tables_result_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("catalog_name", DataType::Utf8, true),
+        Field::new("db_schema_name", DataType::Utf8, true),
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("table_type", DataType::Utf8, false),
+        Field::new("table_schema", DataType::Binary, false),
+    ])
+}
+
+/// Output schema for `GetDbSchemas`: catalog, schema name.
+fn db_schemas_result_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("catalog_name", DataType::Utf8, true),
+        Field::new("db_schema_name", DataType::Utf8, false),
+    ])
+}
+
+/// Output schema for `GetSqlInfo`: info code, string value. Only the
+/// string-valued subset of `SqlInfo` is supported; that's everything this
+/// server reports today.
+fn sql_info_result_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("info_name", DataType::UInt32, false),
+        Field::new("value", DataType::Utf8, false),
+    ])
+}
+
+/// Serve Flight SQL on `addr` until the process exits, querying `pool` for
+/// every request.
+pub async fn serve(addr: std::net::SocketAddr, pool: PgPool) -> Result<(), tonic::transport::Error> {
+    let service = FlightSqlServiceImpl::new(pool);
+    tonic::transport::Server::builder()
+        .add_service(FlightServiceServer::new(service))
+        .serve(addr)
+        .await
+}
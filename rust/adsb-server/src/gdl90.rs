@@ -0,0 +1,308 @@
+//! GDL90 UDP broadcast for EFB apps (ForeFlight, SkyDemon, Avare).
+//!
+//! Encodes the live `Tracker` snapshot as GDL90 Heartbeat (message ID 0, once
+//! per `interval`) and Traffic Report (message ID 20, one per active
+//! aircraft) messages, framed per the GDL90 Data Interface Specification:
+//! `0x7E` flag bytes, `0x7E`/`0x7D` byte-stuffed, with a little-endian
+//! CRC-16 appended before stuffing.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use adsb_core::tracker::{AircraftState, Tracker};
+
+const FLAG: u8 = 0x7E;
+const CONTROL_ESCAPE: u8 = 0x7D;
+const ESCAPE_XOR: u8 = 0x20;
+
+const MSG_ID_HEARTBEAT: u8 = 0;
+const MSG_ID_TRAFFIC_REPORT: u8 = 20;
+
+/// Degrees per semicircle LSB (24-bit signed lat/lon encoding): 180/2^23.
+const SEMICIRCLE_DEG: f64 = 180.0 / 8_388_608.0;
+
+/// Broadcast GDL90 Heartbeat + Traffic Report messages for `tracker`'s
+/// active aircraft to `target` every `interval`, forever.
+///
+/// Meant to be `tokio::spawn`ed alongside the live tracker, the same way
+/// `web::serve_beast_tcp` re-broadcasts decoded frames — a UDP send error
+/// (no listener, network down) is logged once and otherwise ignored, since
+/// EFB clients come and go without the broadcaster needing to know.
+pub async fn broadcast_loop(tracker: Arc<RwLock<Tracker>>, target: SocketAddr, interval: Duration) {
+    let socket = match tokio::net::UdpSocket::bind("0.0.0.0:0").await {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("  [gdl90] cannot open UDP socket: {e}");
+            return;
+        }
+    };
+
+    let mut ticker = tokio::time::interval(interval);
+    let mut send_error_logged = false;
+    loop {
+        ticker.tick().await;
+
+        if let Err(e) = socket.send_to(&encode_heartbeat(), target).await {
+            if !send_error_logged {
+                eprintln!("  [gdl90] send to {target} failed: {e}");
+                send_error_logged = true;
+            }
+            continue;
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        let active: Vec<_> = {
+            let tracker = tracker.read().unwrap();
+            tracker
+                .get_active(now)
+                .into_iter()
+                .map(encode_traffic_report)
+                .collect()
+        };
+        for msg in active {
+            let _ = socket.send_to(&msg, target).await;
+        }
+    }
+}
+
+/// Build a Heartbeat message (ID 0): GPS-valid and UAT-initialized status
+/// bits, the current UTC seconds-since-midnight timestamp, and a zeroed
+/// message-count field (this crate doesn't track UAT uplink volume).
+fn encode_heartbeat() -> Vec<u8> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs_today = now.as_secs() % 86_400;
+
+    let status1 = 0b1000_0001u8; // GPS pos valid | UAT initialized
+    let status2 = 0b1000_0000u8 | ((secs_today >> 16) & 0x01) as u8; // UTC OK | ts bit 16
+    let ts_lo = (secs_today & 0xFFFF) as u16;
+
+    let payload = vec![
+        MSG_ID_HEARTBEAT,
+        status1,
+        status2,
+        ts_lo as u8,
+        (ts_lo >> 8) as u8,
+        0, // message counts: not tracked
+        0,
+    ];
+    frame_message(&payload)
+}
+
+/// Build a Traffic Report message (ID 20) from one tracked aircraft's
+/// current state. Fields this crate has no data for (NIC/NACp, emitter
+/// category) use the spec's "no information" defaults rather than guessing.
+fn encode_traffic_report(ac: &AircraftState) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(28);
+    payload.push(MSG_ID_TRAFFIC_REPORT);
+    payload.push(0x00); // traffic alert status = none, address type = ADS-B ICAO
+
+    payload.extend_from_slice(&ac.icao);
+
+    let (lat, lon) = (ac.lat.unwrap_or(0.0), ac.lon.unwrap_or(0.0));
+    payload.extend_from_slice(&encode_semicircle(lat));
+    payload.extend_from_slice(&encode_semicircle(lon));
+
+    let altitude = encode_altitude(ac.altitude_ft);
+    let misc = encode_misc(ac.on_ground);
+    payload.push((altitude >> 4) as u8);
+    payload.push((((altitude & 0x0F) as u8) << 4) | misc);
+
+    payload.push(0x88); // NIC=8, NACp=8: no real source, spec-reasonable default
+
+    let h_velocity = encode_horizontal_velocity(ac.speed_kts);
+    let v_velocity = encode_vertical_velocity(ac.vertical_rate_fpm);
+    payload.push((h_velocity >> 4) as u8);
+    payload.push((((h_velocity & 0x0F) as u8) << 4) | ((v_velocity >> 8) as u8 & 0x0F));
+    payload.push((v_velocity & 0xFF) as u8);
+
+    payload.push(encode_track(ac.heading_deg));
+    payload.push(0x00); // emitter category: no information
+
+    payload.extend_from_slice(&encode_callsign(ac.callsign.as_deref()));
+    payload.push(0x00); // emergency/priority code, spare
+
+    frame_message(&payload)
+}
+
+/// Encode a latitude or longitude as a 24-bit signed semicircle integer,
+/// MSB first.
+fn encode_semicircle(deg: f64) -> [u8; 3] {
+    let raw = (deg / SEMICIRCLE_DEG).round() as i32;
+    let clamped = raw.clamp(-8_388_608, 8_388_607) & 0x00FF_FFFF;
+    [(clamped >> 16) as u8, (clamped >> 8) as u8, clamped as u8]
+}
+
+/// Encode altitude as a 12-bit value in 25 ft increments offset by 1000 ft,
+/// or `0xFFF` ("no data") when unknown, per the GDL90 spec.
+fn encode_altitude(altitude_ft: Option<i32>) -> u16 {
+    match altitude_ft {
+        Some(ft) => ((ft + 1000) / 25).clamp(0, 0xFFE) as u16,
+        None => 0xFFF,
+    }
+}
+
+/// Misc indicator nibble: track-type = true track angle, airborne/ground
+/// from `on_ground`.
+fn encode_misc(on_ground: bool) -> u8 {
+    let track_type = 0b01; // true track angle
+    let airborne = if on_ground { 0 } else { 1 };
+    track_type | (airborne << 2)
+}
+
+/// Encode ground speed as a 12-bit knots value, or `0xFFF` when unknown.
+fn encode_horizontal_velocity(speed_kts: Option<f64>) -> u16 {
+    match speed_kts {
+        Some(kts) => (kts.round() as i64).clamp(0, 0xFFE) as u16,
+        None => 0xFFF,
+    }
+}
+
+/// Encode vertical rate as a signed 12-bit value in 64 fpm units, or the
+/// spec's `0x800` "no data" sentinel when unknown.
+fn encode_vertical_velocity(vertical_rate_fpm: Option<i32>) -> u16 {
+    match vertical_rate_fpm {
+        Some(fpm) => {
+            let units = (fpm / 64).clamp(-511, 511);
+            (units & 0x0FFF) as u16
+        }
+        None => 0x0800,
+    }
+}
+
+/// Encode track/heading as an 8-bit value, 360/256 degrees per LSB.
+fn encode_track(heading_deg: Option<f64>) -> u8 {
+    let deg = heading_deg.unwrap_or(0.0).rem_euclid(360.0);
+    ((deg / 360.0 * 256.0).round() as i64 & 0xFF) as u8
+}
+
+/// Encode an 8-character, space-padded ASCII callsign field.
+fn encode_callsign(callsign: Option<&str>) -> [u8; 8] {
+    let mut out = [b' '; 8];
+    if let Some(cs) = callsign {
+        for (slot, byte) in out.iter_mut().zip(cs.trim().as_bytes()) {
+            *slot = byte.to_ascii_uppercase();
+        }
+    }
+    out
+}
+
+/// Append a little-endian CRC-16, byte-stuff any `0x7E`/`0x7D` in the
+/// message+CRC, and wrap the result in `0x7E` flag bytes.
+fn frame_message(payload: &[u8]) -> Vec<u8> {
+    let crc = crc16(payload);
+    let mut unstuffed = Vec::with_capacity(payload.len() + 2);
+    unstuffed.extend_from_slice(payload);
+    unstuffed.push((crc & 0xFF) as u8);
+    unstuffed.push((crc >> 8) as u8);
+
+    let mut out = Vec::with_capacity(unstuffed.len() + 4);
+    out.push(FLAG);
+    for byte in unstuffed {
+        if byte == FLAG || byte == CONTROL_ESCAPE {
+            out.push(CONTROL_ESCAPE);
+            out.push(byte ^ ESCAPE_XOR);
+        } else {
+            out.push(byte);
+        }
+    }
+    out.push(FLAG);
+    out
+}
+
+/// CRC-16 per the GDL90 spec: polynomial `0x1021`, MSB-first, zero initial
+/// value — the same construction as CRC-CCITT but computed byte-at-a-time
+/// without a precomputed table, since this runs once per message rather
+/// than per byte of a large payload.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adsb_core::types::Icao;
+
+    fn sample_aircraft(icao: Icao) -> AircraftState {
+        let mut ac = AircraftState::new(icao, 1700000000.0);
+        ac.lat = Some(39.0);
+        ac.lon = Some(-104.0);
+        ac.altitude_ft = Some(5000);
+        ac.speed_kts = Some(120.0);
+        ac.heading_deg = Some(90.0);
+        ac.vertical_rate_fpm = Some(640);
+        ac.callsign = Some("UAL123".to_string());
+        ac
+    }
+
+    #[test]
+    fn test_frame_message_wraps_in_flags() {
+        let framed = frame_message(&[MSG_ID_HEARTBEAT, 0x81, 0x00, 0, 0, 0, 0]);
+        assert_eq!(framed[0], FLAG);
+        assert_eq!(*framed.last().unwrap(), FLAG);
+    }
+
+    #[test]
+    fn test_frame_message_stuffs_flag_bytes() {
+        let framed = frame_message(&[FLAG, FLAG]);
+        // Each literal 0x7E in the body becomes a 2-byte 0x7D,0x5E escape.
+        let body = &framed[1..framed.len() - 1];
+        assert_eq!(body.iter().filter(|&&b| b == FLAG).count(), 0);
+        assert!(body.windows(2).any(|w| w == [CONTROL_ESCAPE, FLAG ^ ESCAPE_XOR]));
+    }
+
+    #[test]
+    fn test_crc16_matches_xmodem_check_value() {
+        // GDL90's CRC (poly 0x1021, init 0, non-reflected) is the same
+        // construction as CRC-16/XMODEM, whose standard check value for the
+        // ASCII string "123456789" is 0x31C3.
+        let crc = crc16(b"123456789");
+        assert_eq!(crc, 0x31C3);
+    }
+
+    #[test]
+    fn test_encode_semicircle_roundtrips() {
+        let encoded = encode_semicircle(45.0);
+        let raw = (encoded[0] as i32) << 16 | (encoded[1] as i32) << 8 | encoded[2] as i32;
+        let sign_extended = (raw << 8) >> 8; // sign-extend from 24 to 32 bits
+        let decoded = sign_extended as f64 * SEMICIRCLE_DEG;
+        assert!((decoded - 45.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_encode_altitude_offset() {
+        assert_eq!(encode_altitude(Some(-1000)), 0);
+        assert_eq!(encode_altitude(Some(0)), 40);
+        assert_eq!(encode_altitude(None), 0xFFF);
+    }
+
+    #[test]
+    fn test_encode_callsign_pads_and_uppercases() {
+        assert_eq!(&encode_callsign(Some("ual123")), b"UAL123  ");
+        assert_eq!(&encode_callsign(None), b"        ");
+    }
+
+    #[test]
+    fn test_encode_traffic_report_length() {
+        let ac = sample_aircraft([0xAD, 0xF7, 0xC8]);
+        let framed = encode_traffic_report(&ac);
+        // 1 flag + 28 payload + 2 CRC + 1 flag, assuming no escapes needed.
+        assert_eq!(framed.len(), 32);
+    }
+}
@@ -8,15 +8,21 @@ use clap::{Parser, Subcommand};
 use comfy_table::{Cell, Table};
 
 use adsb_core::cpr;
+use adsb_core::crc;
 use adsb_core::decode;
 use adsb_core::frame::{self, IcaoCache};
 use adsb_core::icao;
 use adsb_core::tracker::Tracker;
 use adsb_core::types::*;
 
+mod bloom;
 mod db;
 #[cfg(feature = "timescaledb")]
 mod db_pg;
+#[cfg(feature = "flightsql")]
+mod flightsql;
+mod gdl90;
+mod query;
 mod web;
 
 #[derive(Parser)]
@@ -66,6 +72,46 @@ enum Commands {
         /// CORS allowed origin (e.g. "https://example.com"). Omit for same-origin only.
         #[arg(long)]
         cors_origin: Option<String>,
+
+        /// Re-broadcast decoded frames in Beast binary format on this TCP port
+        #[arg(long)]
+        beast_port: Option<u16>,
+
+        /// Connect to a Beast feed (e.g. dump1090/readsb) at host:port instead of
+        /// a local RTL-SDR dongle
+        #[arg(long)]
+        beast: Option<String>,
+
+        /// Directory of SRTM `.hgt` elevation tiles, for altitude_agl_ft enrichment
+        #[arg(long)]
+        dem_dir: Option<String>,
+
+        /// GeoJSON FeatureCollection of region boundary polygons, for the `region` field
+        #[arg(long)]
+        regions_path: Option<String>,
+
+        /// Directory to periodically write a readsb-compatible aircraft.json to,
+        /// for tar1090/SkyAware-style frontends
+        #[arg(long)]
+        json_dir: Option<String>,
+
+        /// Receiver reference latitude, for single-frame local CPR decoding.
+        /// Auto-derived from the first global CPR fix if omitted.
+        #[arg(long)]
+        ref_lat: Option<f64>,
+
+        /// Receiver reference longitude (see --ref-lat)
+        #[arg(long)]
+        ref_lon: Option<f64>,
+
+        /// Broadcast GDL90 traffic reports to this UDP host:port (e.g. a
+        /// tablet running ForeFlight/SkyDemon/Avare on the same Wi-Fi)
+        #[arg(long)]
+        gdl90_target: Option<String>,
+
+        /// Interval between GDL90 broadcasts, in milliseconds
+        #[arg(long, default_value = "1000")]
+        gdl90_interval_ms: u64,
     },
 
     /// Show database statistics
@@ -134,10 +180,44 @@ enum Commands {
         /// CORS allowed origin (e.g. "https://example.com"). Omit for same-origin only.
         #[arg(long)]
         cors_origin: Option<String>,
+
+        /// Directory of SRTM `.hgt` elevation tiles, for altitude_agl_ft enrichment
+        #[arg(long)]
+        dem_dir: Option<String>,
+
+        /// GeoJSON FeatureCollection of region boundary polygons, for the `region` field
+        #[arg(long)]
+        regions_path: Option<String>,
+    },
+
+    /// Serve the positions/events/aircraft tables over Arrow Flight SQL
+    #[cfg(feature = "flightsql")]
+    FlightSql {
+        /// PostgreSQL connection string (the same database the
+        /// `timescaledb` backend writes to)
+        #[arg(long)]
+        postgres_url: String,
+
+        /// Port to listen on
+        #[arg(short, long, default_value = "8081")]
+        port: u16,
+
+        /// Host to bind to
+        #[arg(long, default_value = "0.0.0.0")]
+        host: String,
     },
 
     /// Interactive setup wizard — configure receiver, database, and server
-    Setup,
+    Setup {
+        /// Explicit config file path, checked before the usual search cascade
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Load the config even if it's over the size cap (guards against
+        /// accidentally pointing --config at something that isn't one)
+        #[arg(long)]
+        allow_large_config: bool,
+    },
 }
 
 /// Accumulated aircraft state from decoded messages.
@@ -220,6 +300,17 @@ impl AircraftState {
                     }
                 }
             }
+            DecodedMsg::SurfacePosition(m) => {
+                // Surface CPR needs a nearby reference to disambiguate (see
+                // `cpr::global_decode_surface`), which this quick summary
+                // view doesn't have — just surface the movement/track data.
+                if let Some(mv) = m.movement_kts {
+                    self.speed_kts = Some(mv);
+                }
+                if let Some(trk) = m.ground_track_deg {
+                    self.heading_deg = Some(trk);
+                }
+            }
             DecodedMsg::Velocity(m) => {
                 self.speed_kts = m.speed_kts;
                 self.heading_deg = m.heading_deg;
@@ -231,6 +322,15 @@ impl AircraftState {
             DecodedMsg::Squawk(m) => {
                 self.squawk = Some(m.squawk.clone());
             }
+            DecodedMsg::CommB(CommBMsg::Bds20(m)) => {
+                self.callsign = Some(m.callsign.trim().to_string());
+            }
+            DecodedMsg::CommB(_) => {}
+            DecodedMsg::AircraftStatus(AircraftStatusMsg::Emergency(m)) => {
+                self.squawk = Some(m.squawk.clone());
+            }
+            DecodedMsg::AircraftStatus(AircraftStatusMsg::AcasRa(_)) => {}
+            DecodedMsg::TargetState(_) => {}
         }
     }
 }
@@ -249,24 +349,91 @@ async fn main() {
             min_interval,
             port,
             cors_origin,
+            beast_port,
+            beast,
+            dem_dir,
+            regions_path,
+            json_dir,
+            ref_lat,
+            ref_lon,
+            gdl90_target,
+            gdl90_interval_ms,
         } => {
-            if !live && file.is_none() {
-                eprintln!("Error: provide a FILE or use --live for RTL-SDR capture");
+            if !live && file.is_none() && beast.is_none() {
+                eprintln!("Error: provide a FILE, use --live for RTL-SDR capture, or --beast host:port");
                 std::process::exit(1);
             }
             if native_demod && !live {
                 eprintln!("Error: --native-demod requires --live");
                 std::process::exit(1);
             }
-            if live {
+            if beast.is_some() && (live || file.is_some()) {
+                eprintln!("Error: --beast cannot be combined with --live or a FILE");
+                std::process::exit(1);
+            }
+            if ref_lat.is_some() != ref_lon.is_some() {
+                eprintln!("Error: --ref-lat and --ref-lon must be given together");
+                std::process::exit(1);
+            }
+            let gdl90_target = gdl90_target.map(|t| {
+                t.parse::<std::net::SocketAddr>().unwrap_or_else(|e| {
+                    eprintln!("Error: invalid --gdl90-target {t}: {e}");
+                    std::process::exit(1);
+                })
+            });
+            if let Some(addr) = beast {
+                cmd_track_beast(
+                    &addr,
+                    &db_path,
+                    min_interval,
+                    port,
+                    cors_origin.as_deref(),
+                    beast_port,
+                    dem_dir,
+                    regions_path,
+                    json_dir,
+                    ref_lat,
+                    ref_lon,
+                    gdl90_target,
+                    gdl90_interval_ms,
+                )
+                .await;
+            } else if live {
                 if native_demod {
-                    cmd_track_live_native(&db_path, min_interval, port, cors_origin.as_deref())
-                        .await;
+                    cmd_track_live_native(
+                        &db_path,
+                        min_interval,
+                        port,
+                        cors_origin.as_deref(),
+                        beast_port,
+                        dem_dir,
+                        regions_path,
+                        json_dir,
+                        ref_lat,
+                        ref_lon,
+                        gdl90_target,
+                        gdl90_interval_ms,
+                    )
+                    .await;
                 } else {
-                    cmd_track_live(&db_path, min_interval, port, cors_origin.as_deref()).await;
+                    cmd_track_live(
+                        &db_path,
+                        min_interval,
+                        port,
+                        cors_origin.as_deref(),
+                        beast_port,
+                        dem_dir,
+                        regions_path,
+                        json_dir,
+                        ref_lat,
+                        ref_lon,
+                        gdl90_target,
+                        gdl90_interval_ms,
+                    )
+                    .await;
                 }
             } else {
-                cmd_track(file.unwrap(), &db_path, min_interval);
+                cmd_track(file.unwrap(), &db_path, min_interval, ref_lat, ref_lon);
             }
         }
         Commands::Stats { db_path } => cmd_stats(&db_path),
@@ -288,12 +455,51 @@ async fn main() {
             port,
             host,
             cors_origin,
+            dem_dir,
+            regions_path,
         } => {
             let db: std::sync::Arc<dyn db::AdsbDatabase> =
                 std::sync::Arc::new(db::SqliteDb::new(db_path));
-            web::serve(db, host, port, cors_origin.as_deref()).await;
+            web::serve(
+                db,
+                host,
+                port,
+                cors_origin.as_deref(),
+                dem_dir,
+                regions_path,
+            )
+            .await;
+        }
+        #[cfg(feature = "flightsql")]
+        Commands::FlightSql {
+            postgres_url,
+            port,
+            host,
+        } => {
+            let pool = match sqlx::postgres::PgPoolOptions::new()
+                .max_connections(10)
+                .connect(&postgres_url)
+                .await
+            {
+                Ok(pool) => pool,
+                Err(e) => {
+                    eprintln!("Failed to connect to {postgres_url}: {e}");
+                    std::process::exit(1);
+                }
+            };
+            let addr = format!("{host}:{port}").parse().unwrap_or_else(|e| {
+                eprintln!("Invalid host/port {host}:{port}: {e}");
+                std::process::exit(1);
+            });
+            if let Err(e) = flightsql::serve(addr, pool).await {
+                eprintln!("Flight SQL server error: {e}");
+                std::process::exit(1);
+            }
         }
-        Commands::Setup => cmd_setup(),
+        Commands::Setup {
+            config,
+            allow_large_config,
+        } => cmd_setup(config, allow_large_config),
     }
 }
 
@@ -333,7 +539,7 @@ fn cmd_decode(file: PathBuf, raw: bool) {
         };
         timestamp = ts + 0.001; // Auto-increment for files without timestamps
 
-        let frame = match frame::parse_frame(hex_part, ts, None, true, &mut icao_cache) {
+        let frame = match frame::parse_frame(hex_part, ts, None, true, &mut icao_cache, &crc::GLOBAL_CORRECTOR) {
             Some(f) => f,
             None => continue,
         };
@@ -359,7 +565,7 @@ fn cmd_decode(file: PathBuf, raw: bool) {
     }
 }
 
-fn cmd_track(file: PathBuf, db_path: &str, min_interval: f64) {
+fn cmd_track(file: PathBuf, db_path: &str, min_interval: f64, ref_lat: Option<f64>, ref_lon: Option<f64>) {
     let mut database = db::Database::open(db_path).unwrap_or_else(|e| {
         eprintln!("Error opening database {db_path}: {e}");
         std::process::exit(1);
@@ -368,7 +574,7 @@ fn cmd_track(file: PathBuf, db_path: &str, min_interval: f64) {
     let source = file.display().to_string();
     let capture_id = database.start_capture(&source, None);
 
-    let mut tracker = Tracker::new(None, Some(capture_id), None, None, min_interval);
+    let mut tracker = Tracker::new(None, Some(capture_id), ref_lat, ref_lon, min_interval);
     let mut icao_cache = IcaoCache::new(60.0);
 
     let reader: Box<dyn BufRead> = if file.to_str() == Some("-") {
@@ -404,9 +610,9 @@ fn cmd_track(file: PathBuf, db_path: &str, min_interval: f64) {
         };
         timestamp = ts + 0.1;
 
-        let frame = match frame::parse_frame(hex_part, ts, None, true, &mut icao_cache) {
+        let frame = match frame::parse_frame(hex_part, ts, None, true, &mut icao_cache, &crc::GLOBAL_CORRECTOR) {
             Some(f) => f,
-            None => match frame::parse_frame(hex_part, ts, None, false, &mut icao_cache) {
+            None => match frame::parse_frame(hex_part, ts, None, false, &mut icao_cache, &crc::GLOBAL_CORRECTOR) {
                 Some(f) => f,
                 None => continue,
             },
@@ -434,12 +640,16 @@ fn cmd_track(file: PathBuf, db_path: &str, min_interval: f64) {
         tracker.total_frames, tracker.valid_frames
     );
     println!(
-        "  Positions: {} decoded, {} stored, {} downsampled",
+        "  Positions: {} decoded, {} stored, {} downsampled, {} rejected (jitter)",
         tracker.position_decodes,
         tracker.position_decodes - tracker.positions_skipped,
-        tracker.positions_skipped
+        tracker.positions_skipped,
+        tracker.positions_rejected
     );
     println!("  Aircraft: {}", tracker.aircraft.len());
+    if ref_lat.is_some() && ref_lon.is_some() {
+        println!("  Max range: {:.1} nm", tracker.range_stats().max_range_nm);
+    }
     println!();
     println!("Database: {db_path}");
     println!(
@@ -449,6 +659,7 @@ fn cmd_track(file: PathBuf, db_path: &str, min_interval: f64) {
 
     // Print aircraft table
     let now = timestamp;
+    tracker.expire_fields(now);
     let active = tracker.get_active(now + 3600.0); // Show all (generous timeout)
 
     if !active.is_empty() {
@@ -456,7 +667,7 @@ fn cmd_track(file: PathBuf, db_path: &str, min_interval: f64) {
         let mut table = Table::new();
         table.set_header(vec![
             "ICAO", "Callsign", "Squawk", "Alt (ft)", "Speed", "Hdg", "Lat", "Lon", "Country",
-            "Msgs",
+            "RSSI", "Msgs",
         ]);
 
         for ac in &active {
@@ -478,6 +689,11 @@ fn cmd_track(file: PathBuf, db_path: &str, min_interval: f64) {
                 Cell::new(ac.lat.map(|l| format!("{l:.4}")).unwrap_or("-".into())),
                 Cell::new(ac.lon.map(|l| format!("{l:.4}")).unwrap_or("-".into())),
                 Cell::new(ac.country.unwrap_or("-")),
+                Cell::new(
+                    ac.rssi_dbfs
+                        .map(|r| format!("{r:.1}"))
+                        .unwrap_or("-".into()),
+                ),
                 Cell::new(ac.message_count),
             ]);
         }
@@ -486,15 +702,39 @@ fn cmd_track(file: PathBuf, db_path: &str, min_interval: f64) {
     }
 }
 
+/// Write a readsb-compatible `aircraft.json` snapshot of `tracker` into
+/// `json_dir`, for tar1090/SkyAware-style frontends that poll a directory.
+/// A no-op when `json_dir` is `None`; write failures are logged and ignored
+/// since this is a best-effort side channel, not the system of record.
+fn flush_aircraft_json(json_dir: &Option<String>, tracker: &Tracker, now_ts: f64) {
+    let Some(dir) = json_dir else { return };
+    let doc = web::routes::readsb_aircraft_json(tracker, now_ts);
+    let path = std::path::Path::new(dir).join("aircraft.json");
+    if let Err(e) = std::fs::write(&path, doc.to_string()) {
+        eprintln!("Warning: failed to write aircraft.json to {}: {e}", path.display());
+    }
+}
+
 async fn cmd_track_live(
     db_path: &str,
     min_interval: f64,
     port: Option<u16>,
     cors_origin: Option<&str>,
+    beast_port: Option<u16>,
+    dem_dir: Option<String>,
+    regions_path: Option<String>,
+    json_dir: Option<String>,
+    ref_lat: Option<f64>,
+    ref_lon: Option<f64>,
+    gdl90_target: Option<std::net::SocketAddr>,
+    gdl90_interval_ms: u64,
 ) {
     use std::process::{Command, Stdio};
     use std::sync::{Arc, Mutex, RwLock};
 
+    let (beast_tx, _) = tokio::sync::broadcast::channel(web::BEAST_CHANNEL_CAPACITY);
+    let (track_tx, _) = tokio::sync::broadcast::channel(web::TRACK_EVENT_CHANNEL_CAPACITY);
+
     let mut database = db::Database::open(db_path).unwrap_or_else(|e| {
         eprintln!("Error opening database {db_path}: {e}");
         std::process::exit(1);
@@ -509,8 +749,8 @@ async fn cmd_track_live(
     let tracker = Arc::new(RwLock::new(Tracker::new(
         None,
         Some(capture_id),
-        None,
-        None,
+        ref_lat,
+        ref_lon,
         min_interval,
     )));
     let mut icao_cache = IcaoCache::new(60.0);
@@ -519,11 +759,28 @@ async fn cmd_track_live(
     // SqliteDb opens fresh connections per request, so it sees writes from our Database
     if let Some(p) = port {
         let web_db: Arc<dyn db::AdsbDatabase> = Arc::new(db::SqliteDb::new(db_path.to_string()));
+        let dem = dem_dir.map(|dir| {
+            std::sync::Mutex::new(adsb_core::dem::DemSource::new(
+                dir,
+                web::DEM_TILE_CACHE_CAPACITY,
+            ))
+        });
+        let regions = web::load_regions(regions_path);
+        let (event_tx, event_rx) = tokio::sync::mpsc::channel(web::PERSISTENCE_QUEUE_CAPACITY);
+        tokio::spawn(web::ingest::run_persistence_worker(event_rx, web_db.clone()));
         let state = Arc::new(web::AppState {
             db: web_db,
             tracker: Some(tracker.clone()),
             geofences: RwLock::new(Vec::new()),
             geofence_next_id: RwLock::new(1),
+            auth_token: None,
+            credentials: RwLock::new(Vec::new()),
+            beast_tx: beast_tx.clone(),
+            track_tx: track_tx.clone(),
+            event_tx,
+            persistence_dropped: std::sync::atomic::AtomicU64::new(0),
+            dem,
+            regions,
         });
         let app = web::build_router(state, cors_origin);
         let addr = format!("0.0.0.0:{p}");
@@ -543,6 +800,27 @@ async fn cmd_track_live(
         });
     }
 
+    // Re-broadcast decoded frames in Beast binary format if --beast-port given
+    if let Some(bp) = beast_port {
+        let beast_tx = beast_tx.clone();
+        tokio::spawn(async move {
+            web::serve_beast_tcp(beast_tx, "0.0.0.0".to_string(), bp).await;
+        });
+    }
+
+    // Broadcast GDL90 traffic reports to an EFB app if --gdl90-target given
+    if let Some(target) = gdl90_target {
+        let tracker = tracker.clone();
+        tokio::spawn(async move {
+            gdl90::broadcast_loop(
+                tracker,
+                target,
+                std::time::Duration::from_millis(gdl90_interval_ms),
+            )
+            .await;
+        });
+    }
+
     // Background data retention task (every 60 minutes)
     let retention_db = database.clone();
     tokio::spawn(async move {
@@ -606,11 +884,13 @@ async fn cmd_track_live(
             .unwrap()
             .as_secs_f64();
 
-        let frame = match frame::parse_frame(hex_clean, now_ts, None, true, &mut icao_cache) {
+        let frame = match frame::parse_frame(hex_clean, now_ts, None, true, &mut icao_cache, &crc::GLOBAL_CORRECTOR) {
             Some(f) => f,
             None => continue,
         };
 
+        let _ = beast_tx.send(adsb_core::beast::encode_beast_frame(&frame));
+
         let events = {
             let mut t = tracker.write().unwrap();
             let (_msg, events) = t.update(&frame);
@@ -627,7 +907,9 @@ async fn cmd_track_live(
                 "  {} frames, {} valid, {} active aircraft, {} positions",
                 t.total_frames, t.valid_frames, active.len(), t.position_decodes
             );
+            t.expire_fields(now_ts);
             t.prune_stale(now_ts);
+            flush_aircraft_json(&json_dir, &t, now_ts);
             last_print = std::time::Instant::now();
         }
 
@@ -656,15 +938,216 @@ async fn cmd_track_live(
     );
 }
 
+/// Track aircraft from a network Beast feed (e.g. dump1090/readsb on port
+/// 30005) instead of a local RTL-SDR dongle.
+async fn cmd_track_beast(
+    addr: &str,
+    db_path: &str,
+    min_interval: f64,
+    port: Option<u16>,
+    cors_origin: Option<&str>,
+    beast_port: Option<u16>,
+    dem_dir: Option<String>,
+    regions_path: Option<String>,
+    json_dir: Option<String>,
+    ref_lat: Option<f64>,
+    ref_lon: Option<f64>,
+    gdl90_target: Option<std::net::SocketAddr>,
+    gdl90_interval_ms: u64,
+) {
+    use adsb_core::reader::FrameReader;
+    use std::net::TcpStream;
+    use std::sync::{Arc, Mutex, RwLock};
+
+    let (beast_tx, _) = tokio::sync::broadcast::channel(web::BEAST_CHANNEL_CAPACITY);
+    let (track_tx, _) = tokio::sync::broadcast::channel(web::TRACK_EVENT_CHANNEL_CAPACITY);
+
+    let mut database = db::Database::open(db_path).unwrap_or_else(|e| {
+        eprintln!("Error opening database {db_path}: {e}");
+        std::process::exit(1);
+    });
+
+    let source = format!("beast:{addr}");
+    let capture_id = database.start_capture(&source, None);
+    database.set_autocommit(false);
+
+    let database = Arc::new(Mutex::new(database));
+
+    let tracker = Arc::new(RwLock::new(Tracker::new(
+        None,
+        Some(capture_id),
+        ref_lat,
+        ref_lon,
+        min_interval,
+    )));
+
+    // Start web server if --port given
+    if let Some(p) = port {
+        let web_db: Arc<dyn db::AdsbDatabase> = Arc::new(db::SqliteDb::new(db_path.to_string()));
+        let dem = dem_dir.map(|dir| {
+            std::sync::Mutex::new(adsb_core::dem::DemSource::new(
+                dir,
+                web::DEM_TILE_CACHE_CAPACITY,
+            ))
+        });
+        let regions = web::load_regions(regions_path);
+        let (event_tx, event_rx) = tokio::sync::mpsc::channel(web::PERSISTENCE_QUEUE_CAPACITY);
+        tokio::spawn(web::ingest::run_persistence_worker(event_rx, web_db.clone()));
+        let state = Arc::new(web::AppState {
+            db: web_db,
+            tracker: Some(tracker.clone()),
+            geofences: RwLock::new(Vec::new()),
+            geofence_next_id: RwLock::new(1),
+            auth_token: None,
+            credentials: RwLock::new(Vec::new()),
+            beast_tx: beast_tx.clone(),
+            track_tx: track_tx.clone(),
+            event_tx,
+            persistence_dropped: std::sync::atomic::AtomicU64::new(0),
+            dem,
+            regions,
+        });
+        let app = web::build_router(state, cors_origin);
+        let addr = format!("0.0.0.0:{p}");
+        eprintln!("Dashboard → http://127.0.0.1:{p}");
+        let listener = match tokio::net::TcpListener::bind(&addr).await {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("Error: cannot bind to {addr}: {e}");
+                if e.kind() == std::io::ErrorKind::AddrInUse {
+                    eprintln!("Hint: port {p} is already in use. Try a different --port.");
+                }
+                std::process::exit(1);
+            }
+        };
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+    }
+
+    // Re-broadcast decoded frames in Beast binary format if --beast-port given
+    if let Some(bp) = beast_port {
+        let beast_tx = beast_tx.clone();
+        tokio::spawn(async move {
+            web::serve_beast_tcp(beast_tx, "0.0.0.0".to_string(), bp).await;
+        });
+    }
+
+    // Broadcast GDL90 traffic reports to an EFB app if --gdl90-target given
+    if let Some(target) = gdl90_target {
+        let tracker = tracker.clone();
+        tokio::spawn(async move {
+            gdl90::broadcast_loop(
+                tracker,
+                target,
+                std::time::Duration::from_millis(gdl90_interval_ms),
+            )
+            .await;
+        });
+    }
+
+    // Background data retention task (every 60 minutes)
+    let retention_db = database.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+        interval.tick().await; // skip immediate first tick
+        loop {
+            interval.tick().await;
+            let mut db = retention_db.lock().unwrap();
+            let pruned_pos = db.prune_positions(72);
+            let downsampled = db.downsample_positions(24, 30);
+            let phantoms = db.prune_phantom_aircraft(24.0);
+            let pruned_evt = db.prune_events(168);
+            db.flush();
+            eprintln!(
+                "  [retention] pruned {pruned_pos} positions, downsampled {downsampled}, \
+                 removed {phantoms} phantom aircraft, pruned {pruned_evt} events"
+            );
+        }
+    });
+
+    eprintln!("Connecting to Beast feed at {addr}...");
+    let mut stream = TcpStream::connect(addr).unwrap_or_else(|e| {
+        eprintln!("Error connecting to {addr}: {e}");
+        std::process::exit(1);
+    });
+    eprintln!("Live tracking started — Ctrl+C to stop\n");
+
+    let mut last_print = std::time::Instant::now();
+    let mut last_flush = std::time::Instant::now();
+
+    let mut reader = FrameReader::new(&mut stream);
+    while let Some(frame) = reader.next() {
+        let _ = beast_tx.send(adsb_core::beast::encode_beast_frame(&frame));
+
+        let events = {
+            let mut t = tracker.write().unwrap();
+            let (_msg, events) = t.update(&frame);
+            events
+        };
+
+        database.lock().unwrap().apply_events(&events);
+
+        if last_print.elapsed().as_secs_f64() > 10.0 {
+            let now_ts = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs_f64();
+            let mut t = tracker.write().unwrap();
+            let active = t.get_active(now_ts);
+            eprintln!(
+                "  {} frames, {} valid, {} active aircraft, {} positions",
+                t.total_frames, t.valid_frames, active.len(), t.position_decodes
+            );
+            t.expire_fields(now_ts);
+            t.prune_stale(now_ts);
+            flush_aircraft_json(&json_dir, &t, now_ts);
+            last_print = std::time::Instant::now();
+        }
+
+        if last_flush.elapsed().as_secs_f64() > 5.0 {
+            database.lock().unwrap().flush();
+            last_flush = std::time::Instant::now();
+        }
+    }
+
+    // Cleanup — runs on Ctrl+C or when the feed closes the connection
+    let mut db = database.lock().unwrap();
+    db.flush();
+    let t = tracker.read().unwrap();
+    db.end_capture(
+        capture_id,
+        t.total_frames,
+        t.valid_frames,
+        t.aircraft.len() as u64,
+    );
+    db.flush();
+    eprintln!(
+        "\nStopped. {} frames, {} valid, {} aircraft",
+        t.total_frames, t.valid_frames, t.aircraft.len()
+    );
+}
+
 async fn cmd_track_live_native(
     db_path: &str,
     min_interval: f64,
     port: Option<u16>,
     cors_origin: Option<&str>,
+    beast_port: Option<u16>,
+    dem_dir: Option<String>,
+    regions_path: Option<String>,
+    json_dir: Option<String>,
+    ref_lat: Option<f64>,
+    ref_lon: Option<f64>,
+    gdl90_target: Option<std::net::SocketAddr>,
+    gdl90_interval_ms: u64,
 ) {
     use std::process::{Command, Stdio};
     use std::sync::{Arc, Mutex, RwLock};
 
+    let (beast_tx, _) = tokio::sync::broadcast::channel(web::BEAST_CHANNEL_CAPACITY);
+    let (track_tx, _) = tokio::sync::broadcast::channel(web::TRACK_EVENT_CHANNEL_CAPACITY);
+
     let mut database = db::Database::open(db_path).unwrap_or_else(|e| {
         eprintln!("Error opening database {db_path}: {e}");
         std::process::exit(1);
@@ -679,8 +1162,8 @@ async fn cmd_track_live_native(
     let tracker = Arc::new(RwLock::new(Tracker::new(
         None,
         Some(capture_id),
-        None,
-        None,
+        ref_lat,
+        ref_lon,
         min_interval,
     )));
     let mut icao_cache = IcaoCache::new(60.0);
@@ -688,11 +1171,28 @@ async fn cmd_track_live_native(
     // Start web server if --port given
     if let Some(p) = port {
         let web_db: Arc<dyn db::AdsbDatabase> = Arc::new(db::SqliteDb::new(db_path.to_string()));
+        let dem = dem_dir.map(|dir| {
+            std::sync::Mutex::new(adsb_core::dem::DemSource::new(
+                dir,
+                web::DEM_TILE_CACHE_CAPACITY,
+            ))
+        });
+        let regions = web::load_regions(regions_path);
+        let (event_tx, event_rx) = tokio::sync::mpsc::channel(web::PERSISTENCE_QUEUE_CAPACITY);
+        tokio::spawn(web::ingest::run_persistence_worker(event_rx, web_db.clone()));
         let state = Arc::new(web::AppState {
             db: web_db,
             tracker: Some(tracker.clone()),
             geofences: RwLock::new(Vec::new()),
             geofence_next_id: RwLock::new(1),
+            auth_token: None,
+            credentials: RwLock::new(Vec::new()),
+            beast_tx: beast_tx.clone(),
+            track_tx: track_tx.clone(),
+            event_tx,
+            persistence_dropped: std::sync::atomic::AtomicU64::new(0),
+            dem,
+            regions,
         });
         let app = web::build_router(state, cors_origin);
         let addr = format!("0.0.0.0:{p}");
@@ -712,6 +1212,27 @@ async fn cmd_track_live_native(
         });
     }
 
+    // Re-broadcast decoded frames in Beast binary format if --beast-port given
+    if let Some(bp) = beast_port {
+        let beast_tx = beast_tx.clone();
+        tokio::spawn(async move {
+            web::serve_beast_tcp(beast_tx, "0.0.0.0".to_string(), bp).await;
+        });
+    }
+
+    // Broadcast GDL90 traffic reports to an EFB app if --gdl90-target given
+    if let Some(target) = gdl90_target {
+        let tracker = tracker.clone();
+        tokio::spawn(async move {
+            gdl90::broadcast_loop(
+                tracker,
+                target,
+                std::time::Duration::from_millis(gdl90_interval_ms),
+            )
+            .await;
+        });
+    }
+
     // Background data retention task (every 60 minutes)
     let retention_db = database.clone();
     tokio::spawn(async move {
@@ -759,6 +1280,7 @@ async fn cmd_track_live_native(
     let result = adsb_feeder::capture::demodulate_stream(
         &mut stdout,
         sample_rate,
+        adsb_core::demod::SampleFormat::U8,
         &mut noise_tracker,
         &mut |raw_frame| {
             let now_ts = std::time::SystemTime::now()
@@ -772,11 +1294,14 @@ async fn cmd_track_live_native(
                 Some(raw_frame.signal_level as f64),
                 true,
                 &mut icao_cache,
+                &crc::GLOBAL_CORRECTOR,
             ) {
                 Some(f) => f,
                 None => return,
             };
 
+            let _ = beast_tx.send(adsb_core::beast::encode_beast_frame(&frame));
+
             let events = {
                 let mut t = tracker_ref.write().unwrap();
                 let (_msg, events) = t.update(&frame);
@@ -792,7 +1317,9 @@ async fn cmd_track_live_native(
                     "  {} frames, {} valid, {} active aircraft, {} positions [native]",
                     t.total_frames, t.valid_frames, active.len(), t.position_decodes
                 );
+                t.expire_fields(now_ts);
                 t.prune_stale(now_ts);
+                flush_aircraft_json(&json_dir, &t, now_ts);
                 last_print = std::time::Instant::now();
             }
 
@@ -1013,7 +1540,7 @@ fn cmd_export(
     }
 }
 
-fn cmd_setup() {
+fn cmd_setup(config_path: Option<PathBuf>, allow_large_config: bool) {
     use adsb_core::config;
 
     println!();
@@ -1021,7 +1548,17 @@ fn cmd_setup() {
     println!("========================");
     println!();
 
-    let existing = config::load_config();
+    let (existing, loaded_from, errors) = config::load_config(config_path.as_deref(), allow_large_config);
+    match &loaded_from {
+        Some(path) => println!("Loaded existing config from {}", path.display()),
+        None => println!("No existing config found; starting from defaults"),
+    }
+    if !errors.is_empty() {
+        println!("Warning: existing config has problems to fix:");
+        for e in &errors {
+            println!("  - {e}");
+        }
+    }
     let mut config = existing.clone();
 
     // Receiver name
@@ -1085,7 +1622,7 @@ fn cmd_setup() {
     }
 
     // Save
-    match config::save_config(&config) {
+    match config::save_config(&config, config_path.as_deref()) {
         Ok(path) => {
             println!();
             println!("Configuration saved to {}", path.display());
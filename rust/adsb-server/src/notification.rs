@@ -1,61 +1,182 @@
 //! Webhook notification dispatch for filter events.
 //!
-//! Fire-and-forget HTTP POST of filter events as JSON.
+//! Delivery goes through a bounded queue and a background worker rather than
+//! posting inline, so a slow or temporarily-down endpoint can't pile up
+//! outstanding requests or silently lose events: the worker coalesces
+//! whatever arrives within a short flush window into one JSON array POST and
+//! retries failures with capped exponential backoff.
+
+use std::time::Duration;
+
+use tokio::sync::mpsc;
 
 use adsb_core::filter::FilterEvent;
 use adsb_core::types::icao_to_string;
 
+/// Default bounded queue capacity used by `WebhookDispatcher::new`.
+const DEFAULT_QUEUE_CAPACITY: usize = 256;
+/// Default flush-coalescing window used by `new`: events that arrive within
+/// this long of the first one in a batch are sent together as one POST.
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+/// Default max delivery attempts (first try plus retries) before a batch is
+/// dropped.
+const DEFAULT_MAX_RETRIES: u32 = 5;
+/// Base exponential-backoff delay between retries: 1s, 2s, 4s, 8s, ...
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+/// Backoff is capped here regardless of attempt count.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
 /// Dispatches filter events to a webhook URL via HTTP POST.
+///
+/// Cloning shares the same background worker and queue (the clone is just
+/// another handle to the channel sender), matching how other state handles
+/// in this crate are shared across request handlers.
 #[derive(Clone)]
 pub struct WebhookDispatcher {
-    url: String,
-    client: reqwest::Client,
+    tx: mpsc::Sender<FilterEvent>,
 }
 
 impl WebhookDispatcher {
+    /// New dispatcher with the default queue capacity, flush window, and
+    /// retry limit (see the `DEFAULT_*` constants above).
     pub fn new(url: &str) -> Self {
-        WebhookDispatcher {
-            url: url.to_string(),
-            client: reqwest::Client::new(),
-        }
+        Self::with_config(
+            url,
+            DEFAULT_QUEUE_CAPACITY,
+            DEFAULT_FLUSH_INTERVAL,
+            DEFAULT_MAX_RETRIES,
+        )
+    }
+
+    /// New dispatcher with an explicit queue capacity, flush-coalescing
+    /// window, and max retry count, spawning the background worker that
+    /// drains the queue.
+    pub fn with_config(
+        url: &str,
+        queue_capacity: usize,
+        flush_interval: Duration,
+        max_retries: u32,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel(queue_capacity.max(1));
+        let url = url.to_string();
+        let client = reqwest::Client::new();
+        tokio::spawn(Self::run_worker(rx, client, url, flush_interval, max_retries));
+        WebhookDispatcher { tx }
     }
 
-    /// Fire-and-forget POST of a filter event as JSON.
+    /// Queue a filter event for delivery. If the queue is full (the endpoint
+    /// is down and retries are backed up) the event is dropped and logged
+    /// rather than blocking or slowing the filter pipeline.
     pub fn notify(&self, event: &FilterEvent) {
-        let payload = serde_json::json!({
-            "icao": icao_to_string(&event.icao),
-            "event_type": event.event_type,
-            "description": event.description,
-            "lat": event.lat,
-            "lon": event.lon,
-            "altitude_ft": event.altitude_ft,
-            "timestamp": event.timestamp,
-        });
-
-        let client = self.client.clone();
-        let url = self.url.clone();
-
-        tokio::spawn(async move {
-            if let Err(e) = client.post(&url).json(&payload).send().await {
-                eprintln!("  [webhook] POST failed: {e}");
+        if let Err(e) = self.tx.try_send(event.clone()) {
+            eprintln!("  [webhook] queue full, dropping event: {e}");
+        }
+    }
+
+    /// Drain the queue, coalescing whatever arrives within `flush_interval`
+    /// of each batch's first event into a single POST, and hand each batch
+    /// to `deliver_with_retry`.
+    async fn run_worker(
+        mut rx: mpsc::Receiver<FilterEvent>,
+        client: reqwest::Client,
+        url: String,
+        flush_interval: Duration,
+        max_retries: u32,
+    ) {
+        while let Some(first) = rx.recv().await {
+            let mut batch = vec![first];
+            let deadline = tokio::time::Instant::now() + flush_interval;
+
+            loop {
+                let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                match tokio::time::timeout(remaining, rx.recv()).await {
+                    Ok(Some(event)) => batch.push(event),
+                    Ok(None) => break, // sender dropped, flush what we have
+                    Err(_) => break,   // flush window elapsed
+                }
             }
-        });
+
+            Self::deliver_with_retry(&client, &url, &batch, max_retries).await;
+        }
+    }
+
+    /// POST `batch` as a JSON array, retrying on failure (or non-2xx status)
+    /// with exponential backoff plus jitter, up to `max_retries` attempts
+    /// total before giving up and dropping the batch.
+    async fn deliver_with_retry(
+        client: &reqwest::Client,
+        url: &str,
+        batch: &[FilterEvent],
+        max_retries: u32,
+    ) {
+        let payload: Vec<_> = batch.iter().map(event_payload).collect();
+        let mut attempt = 0u32;
+
+        loop {
+            attempt += 1;
+            match client.post(url).json(&payload).send().await {
+                Ok(resp) if resp.status().is_success() => return,
+                Ok(resp) => eprintln!(
+                    "  [webhook] POST returned {} (attempt {attempt}/{max_retries})",
+                    resp.status()
+                ),
+                Err(e) => {
+                    eprintln!("  [webhook] POST failed: {e} (attempt {attempt}/{max_retries})")
+                }
+            }
+
+            if attempt >= max_retries {
+                eprintln!(
+                    "  [webhook] giving up after {attempt} attempts, dropping {} event(s)",
+                    batch.len()
+                );
+                return;
+            }
+
+            let backoff = BASE_BACKOFF
+                .saturating_mul(1u32 << (attempt - 1).min(10))
+                .min(MAX_BACKOFF);
+            tokio::time::sleep(backoff + jitter(backoff / 4)).await;
+        }
+    }
+}
+
+fn event_payload(event: &FilterEvent) -> serde_json::Value {
+    serde_json::json!({
+        "icao": icao_to_string(&event.icao),
+        "event_type": event.event_type,
+        "description": event.description,
+        "lat": event.lat,
+        "lon": event.lon,
+        "altitude_ft": event.altitude_ft,
+        "timestamp": event.timestamp,
+    })
+}
+
+/// A pseudo-random duration in `0..=max`, derived from the system clock.
+/// Good enough to de-synchronize retrying clients without pulling in a
+/// dependency just for one jitter call site.
+fn jitter(max: Duration) -> Duration {
+    let max_nanos = max.as_nanos();
+    if max_nanos == 0 {
+        return Duration::ZERO;
     }
+    let now_nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    Duration::from_nanos((u128::from(now_nanos) % (max_nanos + 1)) as u64)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_webhook_dispatcher_creation() {
-        let wh = WebhookDispatcher::new("https://example.com/hook");
-        assert_eq!(wh.url, "https://example.com/hook");
-    }
-
-    #[test]
-    fn test_filter_event_serialization() {
-        let event = FilterEvent {
+    fn sample_event() -> FilterEvent {
+        FilterEvent {
             icao: [0xAD, 0xF7, 0xC8],
             event_type: "military_detected",
             description: "Military aircraft detected: REACH42".to_string(),
@@ -63,20 +184,52 @@ mod tests {
             lon: Some(-82.5),
             altitude_ft: Some(25000),
             timestamp: 1700000000.0,
-        };
-
-        let payload = serde_json::json!({
-            "icao": icao_to_string(&event.icao),
-            "event_type": event.event_type,
-            "description": event.description,
-            "lat": event.lat,
-            "lon": event.lon,
-            "altitude_ft": event.altitude_ft,
-            "timestamp": event.timestamp,
-        });
+            overhead: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_event_serialization() {
+        let payload = event_payload(&sample_event());
 
         assert_eq!(payload["icao"], "ADF7C8");
         assert_eq!(payload["event_type"], "military_detected");
         assert!(payload["lat"].as_f64().is_some());
     }
+
+    #[test]
+    fn test_jitter_bounded() {
+        for _ in 0..20 {
+            let j = jitter(Duration::from_millis(100));
+            assert!(j <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_jitter_zero_max_is_zero() {
+        assert_eq!(jitter(Duration::ZERO), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_notify_queues_without_blocking() {
+        let wh = WebhookDispatcher::with_config(
+            "http://127.0.0.1:0/hook", // unroutable: delivery will fail
+            4,
+            Duration::from_millis(10),
+            1, // give up after the first attempt so the worker doesn't loop
+        );
+        wh.notify(&sample_event());
+        // notify() returns immediately regardless of delivery outcome.
+    }
+
+    #[tokio::test]
+    async fn test_notify_drops_when_queue_full() {
+        let (tx, _rx) = mpsc::channel(1);
+        let wh = WebhookDispatcher { tx };
+        // Fill the one slot, then the background task that would normally
+        // drain it isn't running, so the second notify must be dropped
+        // rather than blocking this test.
+        wh.notify(&sample_event());
+        wh.notify(&sample_event());
+    }
 }
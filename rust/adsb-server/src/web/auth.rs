@@ -0,0 +1,337 @@
+//! Per-feeder API credentials for the ingest API.
+//!
+//! `check_auth` used to validate a single shared bearer token for every
+//! feeder, so a leaked token could impersonate any receiver. This module
+//! replaces that with scoped, revocable credentials: each one is bound to a
+//! single feeder name and a set of scopes, so a leaked token can only act as
+//! that feeder and only for the endpoints its scopes cover.
+//!
+//! `AppState::auth_token` is kept as a fallback single-token mode for
+//! deployments that haven't minted any credentials yet, and it also guards
+//! the admin endpoints below (minting/revoking is always an admin action,
+//! never scoped to a feeder).
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::web::AppState;
+
+/// What a credential is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// POST /api/v1/frames and /api/v1/heartbeat, for the bound feeder only.
+    Ingest,
+    /// GET /api/v1/receivers.
+    Receivers,
+}
+
+/// A minted API credential, scoped to a single feeder.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Credential {
+    /// SHA-256 hex digest of the raw token. The raw token itself is never
+    /// stored — it's returned once, at mint time, and can't be recovered.
+    #[serde(skip)]
+    token_hash: String,
+    pub feeder_name: String,
+    pub scopes: Vec<Scope>,
+    pub enabled: bool,
+}
+
+/// SHA-256 hex digest of `token`, used to store and look up credentials
+/// without keeping raw bearer tokens in memory.
+fn hash_token(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// A fresh bearer token: 256 bits of CSPRNG output, hex-encoded.
+fn generate_token() -> String {
+    use rand::RngCore;
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+}
+
+fn unauthorized() -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({"error": "invalid or missing bearer token"})),
+    )
+}
+
+fn forbidden(msg: &str) -> (StatusCode, Json<Value>) {
+    (StatusCode::FORBIDDEN, Json(json!({"error": msg})))
+}
+
+/// Validate the bearer token in `headers` against the credential store (or
+/// the legacy single-token fallback when no credentials have been minted),
+/// and check that it's allowed `required`.
+///
+/// - No credentials minted and no `auth_token` configured: auth is off,
+///   accept everything (the server's existing no-auth default).
+/// - No credentials minted but `auth_token` is set: legacy mode — any
+///   request bearing that token is accepted regardless of scope or feeder.
+/// - Credentials minted: the bearer token must hash to an enabled
+///   credential. A missing or unrecognized token is 401. A recognized
+///   token whose `feeder_name` doesn't match `receiver`, or whose scopes
+///   don't include `required`, is 403.
+pub fn check_auth(
+    state: &AppState,
+    headers: &HeaderMap,
+    required: Scope,
+    receiver: Option<&str>,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let credentials = state.credentials.read().unwrap();
+
+    if credentials.is_empty() {
+        return match &state.auth_token {
+            Some(expected) if bearer_token(headers) == Some(expected.as_str()) => Ok(()),
+            Some(_) => Err(unauthorized()),
+            None => Ok(()),
+        };
+    }
+
+    let token = bearer_token(headers).ok_or_else(unauthorized)?;
+    let hash = hash_token(token);
+    let cred = credentials
+        .iter()
+        .find(|c| c.enabled && c.token_hash == hash)
+        .ok_or_else(unauthorized)?;
+
+    if let Some(receiver) = receiver {
+        if cred.feeder_name != receiver {
+            return Err(forbidden("token is not authorized for this feeder"));
+        }
+    }
+    if !cred.scopes.contains(&required) {
+        return Err(forbidden("token does not have the required scope"));
+    }
+    Ok(())
+}
+
+/// Validate the admin bearer token (`AppState::auth_token`) guarding the
+/// mint/revoke endpoints below. Unlike `check_auth`, this never consults the
+/// credential store — minting credentials is always an admin-only action,
+/// not something a credential can grant itself.
+fn check_admin_auth(state: &AppState, headers: &HeaderMap) -> Result<(), (StatusCode, Json<Value>)> {
+    match &state.auth_token {
+        Some(expected) if bearer_token(headers) == Some(expected.as_str()) => Ok(()),
+        Some(_) => Err(unauthorized()),
+        None => Ok(()), // No admin token configured — matches the server's no-auth default.
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MintCredentialRequest {
+    feeder_name: String,
+    scopes: Vec<Scope>,
+}
+
+/// POST /api/v1/admin/credentials — mint a new scoped credential for a
+/// feeder. Returns the raw bearer token; it is never shown again.
+pub async fn api_mint_credential(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(body): Json<MintCredentialRequest>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(resp) = check_admin_auth(&state, &headers) {
+        return resp;
+    }
+
+    let token = generate_token();
+    state.credentials.write().unwrap().push(Credential {
+        token_hash: hash_token(&token),
+        feeder_name: body.feeder_name,
+        scopes: body.scopes,
+        enabled: true,
+    });
+
+    (StatusCode::OK, Json(json!({"token": token})))
+}
+
+/// DELETE /api/v1/admin/credentials/:feeder_name — revoke every credential
+/// minted for a feeder. Revoked rows are disabled, not removed, so mint
+/// history stays visible for auditing.
+pub async fn api_revoke_credential(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(feeder_name): Path<String>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(resp) = check_admin_auth(&state, &headers) {
+        return resp;
+    }
+
+    let mut revoked = 0u32;
+    for cred in state.credentials.write().unwrap().iter_mut() {
+        if cred.feeder_name == feeder_name && cred.enabled {
+            cred.enabled = false;
+            revoked += 1;
+        }
+    }
+
+    (StatusCode::OK, Json(json!({"revoked": revoked})))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::RwLock;
+
+    fn state_with(auth_token: Option<&str>, credentials: Vec<Credential>) -> (AppState, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db").to_str().unwrap().to_string();
+        let state = AppState {
+            db: Arc::new(crate::db::SqliteDb::new(db_path)),
+            tracker: None,
+            geofences: RwLock::new(Vec::new()),
+            geofence_next_id: RwLock::new(1),
+            auth_token: auth_token.map(str::to_string),
+            credentials: RwLock::new(credentials),
+            beast_tx: tokio::sync::broadcast::channel(16).0,
+            track_tx: tokio::sync::broadcast::channel(16).0,
+            event_tx: tokio::sync::mpsc::channel(16).0,
+            persistence_dropped: std::sync::atomic::AtomicU64::new(0),
+        };
+        (state, dir)
+    }
+
+    #[test]
+    fn test_no_auth_configured_accepts_all() {
+        let (state, _dir) = state_with(None, Vec::new());
+        let headers = HeaderMap::new();
+        assert!(check_auth(&state, &headers, Scope::Ingest, Some("rx1")).is_ok());
+    }
+
+    #[test]
+    fn test_legacy_token_ignores_feeder_and_scope() {
+        let (state, _dir) = state_with(Some("shared-secret"), Vec::new());
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer shared-secret".parse().unwrap());
+        assert!(check_auth(&state, &headers, Scope::Receivers, Some("any-feeder")).is_ok());
+    }
+
+    #[test]
+    fn test_missing_token_is_unauthorized() {
+        let credential = Credential {
+            token_hash: hash_token("tok-1"),
+            feeder_name: "rx1".to_string(),
+            scopes: vec![Scope::Ingest],
+            enabled: true,
+        };
+        let (state, _dir) = state_with(None, vec![credential]);
+        let headers = HeaderMap::new();
+        let err = check_auth(&state, &headers, Scope::Ingest, Some("rx1")).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_wrong_feeder_is_forbidden() {
+        let credential = Credential {
+            token_hash: hash_token("tok-1"),
+            feeder_name: "rx1".to_string(),
+            scopes: vec![Scope::Ingest],
+            enabled: true,
+        };
+        let (state, _dir) = state_with(None, vec![credential]);
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer tok-1".parse().unwrap());
+        let err = check_auth(&state, &headers, Scope::Ingest, Some("rx2")).unwrap_err();
+        assert_eq!(err.0, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_missing_scope_is_forbidden() {
+        let credential = Credential {
+            token_hash: hash_token("tok-1"),
+            feeder_name: "rx1".to_string(),
+            scopes: vec![Scope::Ingest],
+            enabled: true,
+        };
+        let (state, _dir) = state_with(None, vec![credential]);
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer tok-1".parse().unwrap());
+        let err = check_auth(&state, &headers, Scope::Receivers, Some("rx1")).unwrap_err();
+        assert_eq!(err.0, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_matching_credential_is_accepted() {
+        let credential = Credential {
+            token_hash: hash_token("tok-1"),
+            feeder_name: "rx1".to_string(),
+            scopes: vec![Scope::Ingest, Scope::Receivers],
+            enabled: true,
+        };
+        let (state, _dir) = state_with(None, vec![credential]);
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer tok-1".parse().unwrap());
+        assert!(check_auth(&state, &headers, Scope::Ingest, Some("rx1")).is_ok());
+        assert!(check_auth(&state, &headers, Scope::Receivers, None).is_ok());
+    }
+
+    #[test]
+    fn test_disabled_credential_is_unauthorized() {
+        let credential = Credential {
+            token_hash: hash_token("tok-1"),
+            feeder_name: "rx1".to_string(),
+            scopes: vec![Scope::Ingest],
+            enabled: false,
+        };
+        let (state, _dir) = state_with(None, vec![credential]);
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer tok-1".parse().unwrap());
+        let err = check_auth(&state, &headers, Scope::Ingest, Some("rx1")).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_mint_then_revoke_disables_credential() {
+        let (state, _dir) = state_with(Some("admin-secret"), Vec::new());
+        let state = Arc::new(state);
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer admin-secret".parse().unwrap());
+
+        let (status, Json(body)) = api_mint_credential(
+            State(state.clone()),
+            headers.clone(),
+            Json(MintCredentialRequest {
+                feeder_name: "rx1".to_string(),
+                scopes: vec![Scope::Ingest],
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let token = body["token"].as_str().unwrap().to_string();
+
+        let mut auth_headers = HeaderMap::new();
+        auth_headers.insert("authorization", format!("Bearer {token}").parse().unwrap());
+        assert!(check_auth(&state, &auth_headers, Scope::Ingest, Some("rx1")).is_ok());
+
+        let (status, Json(body)) =
+            api_revoke_credential(State(state.clone()), headers, Path("rx1".to_string())).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["revoked"], 1);
+
+        let err = check_auth(&state, &auth_headers, Scope::Ingest, Some("rx1")).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -8,17 +8,21 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::{HeaderMap, StatusCode};
-use axum::response::IntoResponse;
 use axum::Json;
 use serde::Deserialize;
 use serde_json::{json, Value};
 
-use adsb_core::frame::{self, IcaoCache};
+use adsb_core::beast::encode_beast_frame;
+use adsb_core::frame::{self, IcaoCache, ModeFrame};
+use adsb_core::mlat;
+use adsb_core::reader::{FrameReader, ReaderFlags};
 use adsb_core::tracker::{TrackEvent, Tracker};
 use adsb_core::types::icao_to_string;
 
+use crate::db::AdsbDatabase;
+use crate::web::auth::{self, Scope};
 use crate::web::AppState;
 
 // ---------------------------------------------------------------------------
@@ -33,6 +37,24 @@ static FEEDER_TRACKERS: LazyLock<RwLock<HashMap<String, FeederState>>> =
 static RECEIVER_STATUS: LazyLock<RwLock<HashMap<String, ReceiverStatus>>> =
     LazyLock::new(|| RwLock::new(HashMap::new()));
 
+/// Pending multilateration observations, keyed by decoded message bytes
+/// (post error-correction, so independent receivers' copies of the same
+/// transmission normalize to the same key) with each observing receiver's
+/// most recent reception time. See `record_mlat_observation`.
+static MLAT_BUFFER: LazyLock<RwLock<HashMap<Vec<u8>, HashMap<String, f64>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Window within which reports of the same payload from different
+/// receivers are treated as one physical transmission for MLAT.
+const MLAT_WINDOW_SEC: f64 = 0.05;
+/// Receivers (with known position) that must have reported the same
+/// payload within the window before a solve is attempted.
+const MLAT_MIN_RECEIVERS: usize = 4;
+/// Synthetic `receiver_id` stamped on MLAT-derived positions. Never a real
+/// `receivers.id` row — just a marker so the UI (and the database) can tell
+/// an MLAT fix apart from an ADS-B position.
+const MLAT_RECEIVER_ID: i64 = -1;
+
 struct FeederState {
     tracker: Tracker,
     icao_cache: IcaoCache,
@@ -71,6 +93,13 @@ pub struct FrameData {
     signal_level: Option<f64>,
 }
 
+#[derive(Deserialize)]
+pub struct BeastIngestQuery {
+    receiver: String,
+    lat: Option<f64>,
+    lon: Option<f64>,
+}
+
 #[derive(Deserialize)]
 pub struct HeartbeatRequest {
     receiver: String,
@@ -81,42 +110,125 @@ pub struct HeartbeatRequest {
     uptime_sec: Option<f64>,
 }
 
-// ---------------------------------------------------------------------------
-// Auth helper
-// ---------------------------------------------------------------------------
-
-fn now() -> f64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs_f64()
+/// Feed one decoded frame into `feeder`'s tracker, re-broadcast it to
+/// `/api/beast` subscribers, and fold the resulting `TrackEvent`s into the
+/// running counters/collections both ingest handlers return. Shared by
+/// `api_ingest_frames` (hex/JSON) and `api_ingest_frames_beast` (BEAST
+/// binary/AVR) so every ingest path drives the same downstream logic.
+fn apply_frame(
+    state: &AppState,
+    feeder: &mut FeederState,
+    f: &ModeFrame,
+    decoded: &mut u64,
+    positions: &mut u64,
+    events_out: &mut Vec<Value>,
+    all_track_events: &mut Vec<TrackEvent>,
+) {
+    let _ = state.beast_tx.send(encode_beast_frame(f));
+    let (msg, track_events) = feeder.tracker.update(f);
+    if msg.is_some() {
+        *decoded += 1;
+    }
+    for te in &track_events {
+        match te {
+            TrackEvent::PositionUpdate { .. } => *positions += 1,
+            TrackEvent::NewAircraft {
+                icao, timestamp, ..
+            } => {
+                events_out.push(json!({
+                    "type": "new_aircraft",
+                    "icao": icao_to_string(icao),
+                    "timestamp": timestamp,
+                }));
+            }
+            _ => {}
+        }
+    }
+    all_track_events.extend(track_events);
 }
 
-/// Validate bearer token if auth is configured. Returns Err response on failure.
-fn check_auth(
-    state: &AppState,
-    headers: &HeaderMap,
-) -> Result<(), (StatusCode, Json<Value>)> {
-    let expected = match &state.auth_token {
-        Some(t) => t,
-        None => return Ok(()), // No auth configured — accept all
-    };
+/// Fold one accepted frame into the MLAT ring buffer (see `MLAT_BUFFER`)
+/// and, once the same payload has been seen by at least
+/// `MLAT_MIN_RECEIVERS` receivers with a known position, resolve a
+/// multilateration fix and push it onto `all_track_events` as an extra
+/// `TrackEvent::PositionUpdate` — on top of whatever `apply_frame` already
+/// derived from the frame itself.
+///
+/// Every call first sweeps the whole buffer for payloads with no
+/// observation within `MLAT_WINDOW_SEC`, so a payload that never reaches
+/// `MLAT_MIN_RECEIVERS` receivers still ages out instead of sitting in the
+/// map for the life of the process.
+///
+/// Reliable TDOA needs reception timestamps that agree to a few hundred
+/// nanoseconds across receivers, which in practice means a 12 MHz BEAST
+/// hardware timestamp; hex/JSON ingest only has a wall-clock approximation
+/// (see `api_ingest_frames`), so fixes solved from it should be treated as
+/// low-confidence until feeders migrate to `/api/v1/frames/beast`.
+fn record_mlat_observation(receiver: &str, f: &ModeFrame, all_track_events: &mut Vec<TrackEvent>) {
+    let mut buffer = MLAT_BUFFER.write().unwrap();
+
+    // Sweep every payload in the buffer, not just this frame's, so one that
+    // never reaches MLAT_MIN_RECEIVERS still gets aged out instead of sitting
+    // in the map for the life of the process -- the old per-key retain only
+    // ever ran for the payload currently being observed.
+    buffer.retain(|_, observations| {
+        observations.retain(|_, ts| (f.timestamp - *ts).abs() <= MLAT_WINDOW_SEC);
+        !observations.is_empty()
+    });
+
+    let observations = buffer.entry(f.raw.clone()).or_default();
+    observations.insert(receiver.to_string(), f.timestamp);
+
+    if observations.len() < MLAT_MIN_RECEIVERS {
+        return;
+    }
+
+    let status = RECEIVER_STATUS.read().unwrap();
+    let mlat_observations: Vec<mlat::Observation> = observations
+        .iter()
+        .filter_map(|(name, &timestamp)| {
+            let r = status.get(name)?;
+            Some(mlat::Observation {
+                lat: r.lat?,
+                lon: r.lon?,
+                altitude_ft: 0.0,
+                timestamp,
+            })
+        })
+        .collect();
+    drop(status);
 
-    let auth_header = headers
-        .get("authorization")
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("");
+    if mlat_observations.len() < MLAT_MIN_RECEIVERS {
+        return;
+    }
 
-    if let Some(token) = auth_header.strip_prefix("Bearer ") {
-        if token == expected {
-            return Ok(());
-        }
+    if let Some(fix) = mlat::solve(&mlat_observations) {
+        all_track_events.push(TrackEvent::PositionUpdate {
+            icao: f.icao,
+            lat: fix.lat,
+            lon: fix.lon,
+            altitude_ft: Some(fix.altitude_ft.round() as i32),
+            altitude_source: None,
+            speed_kts: None,
+            heading_deg: None,
+            vertical_rate_fpm: None,
+            vertical_rate_source: None,
+            receiver_id: Some(MLAT_RECEIVER_ID),
+            timestamp: f.timestamp,
+            on_ground: false,
+        });
     }
 
-    Err((
-        StatusCode::UNAUTHORIZED,
-        Json(json!({"error": "invalid or missing bearer token"})),
-    ))
+    // Whether it resolved or not, this payload has had its shot — drop it
+    // so a straggling receiver report doesn't keep re-running the solve.
+    buffer.remove(&f.raw);
+}
+
+fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs_f64()
 }
 
 // ---------------------------------------------------------------------------
@@ -129,7 +241,7 @@ pub async fn api_ingest_frames(
     headers: HeaderMap,
     Json(body): Json<IngestRequest>,
 ) -> (StatusCode, Json<Value>) {
-    if let Err(resp) = check_auth(&state, &headers) {
+    if let Err(resp) = auth::check_auth(&state, &headers, Scope::Ingest, Some(&body.receiver)) {
         return resp;
     }
 
@@ -160,30 +272,21 @@ pub async fn api_ingest_frames(
                 frame_data.signal_level,
                 true,
                 &mut feeder.icao_cache,
+                &adsb_core::crc::GLOBAL_CORRECTOR,
             );
 
             if let Some(f) = parsed {
                 accepted += 1;
-                let (msg, track_events) = feeder.tracker.update(&f);
-                if msg.is_some() {
-                    decoded += 1;
-                }
-                for te in &track_events {
-                    match te {
-                        TrackEvent::PositionUpdate { .. } => positions += 1,
-                        TrackEvent::NewAircraft {
-                            icao, timestamp, ..
-                        } => {
-                            events_out.push(json!({
-                                "type": "new_aircraft",
-                                "icao": icao_to_string(icao),
-                                "timestamp": timestamp,
-                            }));
-                        }
-                        _ => {}
-                    }
-                }
-                all_track_events.extend(track_events);
+                apply_frame(
+                    &state,
+                    feeder,
+                    &f,
+                    &mut decoded,
+                    &mut positions,
+                    &mut events_out,
+                    &mut all_track_events,
+                );
+                record_mlat_observation(&body.receiver, &f, &mut all_track_events);
             }
         }
 
@@ -191,83 +294,16 @@ pub async fn api_ingest_frames(
         (accepted, decoded, positions, events_out, all_track_events, active_count)
     }; // lock dropped here
 
-    // Async section: persist to database (no locks held)
-    for te in &all_track_events {
-        match te {
-            TrackEvent::NewAircraft {
-                icao,
-                country,
-                registration,
-                is_military,
-                timestamp,
-            } => {
-                let icao_str = icao_to_string(icao);
-                state
-                    .db
-                    .upsert_aircraft(
-                        &icao_str,
-                        *country,
-                        registration.as_deref(),
-                        *is_military,
-                        *timestamp,
-                    )
-                    .await;
-            }
-            TrackEvent::AircraftUpdate { icao, timestamp } => {
-                let icao_str = icao_to_string(icao);
-                state
-                    .db
-                    .upsert_aircraft(&icao_str, None, None, false, *timestamp)
-                    .await;
-            }
-            TrackEvent::SightingUpdate {
-                icao,
-                capture_id,
-                callsign,
-                squawk,
-                altitude_ft,
-                timestamp,
-            } => {
-                let icao_str = icao_to_string(icao);
-                state
-                    .db
-                    .upsert_sighting(
-                        &icao_str,
-                        *capture_id,
-                        callsign.as_deref(),
-                        squawk.as_deref(),
-                        *altitude_ft,
-                        *timestamp,
-                    )
-                    .await;
-            }
-            TrackEvent::PositionUpdate {
-                icao,
-                lat,
-                lon,
-                altitude_ft,
-                speed_kts,
-                heading_deg,
-                vertical_rate_fpm,
-                receiver_id,
-                timestamp,
-            } => {
-                let icao_str = icao_to_string(icao);
-                state
-                    .db
-                    .add_position(
-                        &icao_str,
-                        *lat,
-                        *lon,
-                        *altitude_ft,
-                        *speed_kts,
-                        *heading_deg,
-                        *vertical_rate_fpm,
-                        *receiver_id,
-                        *timestamp,
-                    )
-                    .await;
-            }
+    // Async section (no locks held): broadcast to `/api/v1/stream`
+    // subscribers and hand off to the persistence worker. Neither of these
+    // touches the database, so the handler returns without waiting on it —
+    // see `run_persistence_worker`.
+    for te in all_track_events {
+        let _ = state.track_tx.send(te.clone());
+        if state.event_tx.try_send(te).is_err() {
+            state
+                .persistence_dropped
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         }
     }
 
@@ -304,13 +340,119 @@ pub async fn api_ingest_frames(
     )
 }
 
+/// POST /api/v1/frames/beast — batch ingest of raw BEAST binary or AVR ASCII
+/// frames, for feeders (dump1090, readsb) that speak their native wire
+/// format instead of transcoding into `IngestRequest` JSON. The body is an
+/// `application/octet-stream` byte stream decoded with
+/// `adsb_core::reader::FrameReader`, which auto-detects BEAST vs AVR framing
+/// per record and decodes the embedded 12 MHz MLAT timestamp and signal
+/// level into `ModeFrame::timestamp`/`signal_level` the same way
+/// `frame::parse_frame` does for JSON frames. From there every frame is fed
+/// through the same `FeederState` tracker path as `api_ingest_frames`, so
+/// re-broadcast, the live stream, and persistence are all shared.
+///
+/// The receiver name (and optional position, used only the first time a
+/// feeder is seen) travel as query parameters since the body carries no
+/// JSON envelope to hold them.
+pub async fn api_ingest_frames_beast(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<BeastIngestQuery>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> (StatusCode, Json<Value>) {
+    if let Err(resp) = auth::check_auth(&state, &headers, Scope::Ingest, Some(&query.receiver)) {
+        return resp;
+    }
+
+    let (accepted, decoded, positions, events_out, all_track_events, active_count) = {
+        let mut feeders = FEEDER_TRACKERS.write().unwrap();
+        let feeder = feeders
+            .entry(query.receiver.clone())
+            .or_insert_with(|| FeederState {
+                tracker: Tracker::new(None, None, query.lat, query.lon, 2.0),
+                icao_cache: IcaoCache::new(60.0),
+            });
+
+        let mut decoded = 0u64;
+        let mut positions = 0u64;
+        let mut events_out: Vec<Value> = Vec::new();
+        let mut all_track_events: Vec<TrackEvent> = Vec::new();
+
+        // FrameReader owns its IcaoCache for the life of the stream; swap the
+        // feeder's cache in and back out so confirmation counts carry over to
+        // the next request from this feeder, just like the hex/JSON path
+        // threading `&mut feeder.icao_cache` straight into `parse_frame`.
+        let mut cursor = body.as_ref();
+        let icao_cache = std::mem::replace(&mut feeder.icao_cache, IcaoCache::new(60.0));
+        let mut reader = FrameReader::with_cache(&mut cursor, ReaderFlags::default(), icao_cache);
+        let frames: Vec<ModeFrame> = (&mut reader).collect();
+        feeder.icao_cache = reader.into_icao_cache();
+
+        let accepted = frames.len() as u64;
+        for f in &frames {
+            apply_frame(
+                &state,
+                feeder,
+                f,
+                &mut decoded,
+                &mut positions,
+                &mut events_out,
+                &mut all_track_events,
+            );
+        }
+
+        let active_count = feeder.tracker.aircraft.len();
+        (accepted, decoded, positions, events_out, all_track_events, active_count)
+    }; // lock dropped here
+
+    for te in all_track_events {
+        let _ = state.track_tx.send(te.clone());
+        if state.event_tx.try_send(te).is_err() {
+            state
+                .persistence_dropped
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    {
+        let mut status = RECEIVER_STATUS.write().unwrap();
+        let entry = status
+            .entry(query.receiver.clone())
+            .or_insert_with(|| ReceiverStatus {
+                name: query.receiver.clone(),
+                lat: query.lat,
+                lon: query.lon,
+                last_heartbeat: now(),
+                frames_captured: 0,
+                frames_sent: 0,
+                uptime_sec: 0.0,
+                active_aircraft: 0,
+                online: true,
+            });
+        entry.last_heartbeat = now();
+        entry.lat = query.lat.or(entry.lat);
+        entry.lon = query.lon.or(entry.lon);
+        entry.active_aircraft = active_count;
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "accepted": accepted,
+            "decoded": decoded,
+            "positions": positions,
+            "events": events_out,
+        })),
+    )
+}
+
 /// POST /api/v1/heartbeat — receiver status update.
 pub async fn api_heartbeat(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Json(body): Json<HeartbeatRequest>,
 ) -> (StatusCode, Json<Value>) {
-    if let Err(resp) = check_auth(&state, &headers) {
+    if let Err(resp) = auth::check_auth(&state, &headers, Scope::Ingest, Some(&body.receiver)) {
         return resp;
     }
 
@@ -340,8 +482,17 @@ pub async fn api_heartbeat(
     (StatusCode::OK, Json(json!({"ok": true})))
 }
 
-/// GET /api/v1/receivers — list all receivers with status.
-pub async fn api_receivers(State(_state): State<Arc<AppState>>) -> impl IntoResponse {
+/// GET /api/v1/receivers — list all receivers with status, plus persistence
+/// queue health so operators can see when the database is falling behind.
+/// Requires the `Receivers` scope (see `auth::check_auth`).
+pub async fn api_receivers(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> (StatusCode, Json<Value>) {
+    if let Err(resp) = auth::check_auth(&state, &headers, Scope::Receivers, None) {
+        return resp;
+    }
+
     let status = RECEIVER_STATUS.read().unwrap();
     let current = now();
 
@@ -363,7 +514,242 @@ pub async fn api_receivers(State(_state): State<Arc<AppState>>) -> impl IntoResp
         })
         .collect();
 
-    Json(json!(receivers))
+    (
+        StatusCode::OK,
+        Json(json!({
+            "receivers": receivers,
+            "persistence_queue_depth": state.persistence_queue_depth(),
+            "persistence_dropped_events": state
+                .persistence_dropped
+                .load(std::sync::atomic::Ordering::Relaxed),
+        })),
+    )
+}
+
+/// Drain `rx` and persist each `TrackEvent` to `db`, one DB call per event
+/// type (see `TrackEvent`'s variants). Runs for the life of the process as a
+/// background task spawned alongside the web server — see
+/// `crate::web::serve` and `main.rs`'s equivalent command-line entry points.
+///
+/// Events arrive here after `api_ingest_frames` has already broadcast them
+/// to `/api/v1/stream` and returned, so a slow database doesn't add latency
+/// to ingest requests; it only grows this queue (bounded — see
+/// `AppState::persistence_dropped`).
+pub async fn run_persistence_worker(mut rx: tokio::sync::mpsc::Receiver<TrackEvent>, db: Arc<dyn AdsbDatabase>) {
+    while let Some(te) = rx.recv().await {
+        persist_track_event(&db, &te).await;
+    }
+}
+
+/// Apply a single `TrackEvent` to `db` via the matching upsert/insert call.
+async fn persist_track_event(db: &Arc<dyn AdsbDatabase>, te: &TrackEvent) {
+    match te {
+        TrackEvent::NewAircraft {
+            icao,
+            country,
+            registration,
+            is_military,
+            timestamp,
+        } => {
+            let icao_str = icao_to_string(icao);
+            db.upsert_aircraft(&icao_str, *country, registration.as_deref(), *is_military, *timestamp)
+                .await;
+        }
+        TrackEvent::AircraftUpdate { icao, timestamp } => {
+            let icao_str = icao_to_string(icao);
+            db.upsert_aircraft(&icao_str, None, None, false, *timestamp).await;
+        }
+        TrackEvent::SightingUpdate {
+            icao,
+            capture_id,
+            callsign,
+            squawk,
+            altitude_ft,
+            altitude_source: _,
+            timestamp,
+        } => {
+            let icao_str = icao_to_string(icao);
+            db.upsert_sighting(&icao_str, *capture_id, callsign.as_deref(), squawk.as_deref(), *altitude_ft, *timestamp)
+                .await;
+        }
+        TrackEvent::PositionUpdate {
+            icao,
+            lat,
+            lon,
+            altitude_ft,
+            altitude_source: _,
+            speed_kts,
+            heading_deg,
+            vertical_rate_fpm,
+            vertical_rate_source: _,
+            receiver_id,
+            timestamp,
+            on_ground: _,
+        } => {
+            let icao_str = icao_to_string(icao);
+            db.add_position(&icao_str, *lat, *lon, *altitude_ft, *speed_kts, *heading_deg, *vertical_rate_fpm, *receiver_id, *timestamp)
+                .await;
+        }
+        TrackEvent::SelectedStateUpdate {
+            icao,
+            selected_altitude_ft,
+            baro_setting_hpa,
+            track_deg,
+            mach,
+            timestamp,
+        } => {
+            let icao_str = icao_to_string(icao);
+            db.update_selected_state(&icao_str, *selected_altitude_ft, *baro_setting_hpa, *track_deg, *mach, *timestamp)
+                .await;
+        }
+    }
+}
+
+/// GET /api/v1/stream — live Server-Sent Events feed of track events, so the
+/// dashboard can update without polling `/api/v1/frames` responses. Each
+/// event is sent as a named SSE event (`new_aircraft`, `position`,
+/// `sighting`); event kinds with no UI consumer are dropped. A subscriber
+/// that falls behind the bounded broadcast channel just misses events
+/// (`RecvError::Lagged`) rather than blocking ingest.
+pub async fn api_stream(
+    State(state): State<Arc<AppState>>,
+) -> axum::response::sse::Sse<
+    impl futures::Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>>,
+> {
+    use axum::response::sse::{KeepAlive, Sse};
+    use tokio_stream::wrappers::BroadcastStream;
+    use tokio_stream::StreamExt;
+
+    let rx = state.track_tx.subscribe();
+    let stream = BroadcastStream::new(rx)
+        .filter_map(|item| item.ok())
+        .filter_map(|te| sse_event_for(&te).map(Ok));
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Map a `TrackEvent` to its SSE event name and JSON payload, or `None` for
+/// event kinds the live stream doesn't forward yet.
+fn sse_event_for(te: &TrackEvent) -> Option<axum::response::sse::Event> {
+    let (name, payload) = track_event_json(te)?;
+    Some(axum::response::sse::Event::default().event(name).data(payload.to_string()))
+}
+
+/// Map a `TrackEvent` to its wire event name and JSON payload, shared by
+/// `api_stream` (SSE) and `api_ws` (WebSocket) — the same live feed, two
+/// transports. `None` for event kinds neither stream forwards yet.
+fn track_event_json(te: &TrackEvent) -> Option<(&'static str, Value)> {
+    let pair = match te {
+        TrackEvent::NewAircraft {
+            icao,
+            country,
+            registration,
+            is_military,
+            timestamp,
+        } => (
+            "new_aircraft",
+            json!({
+                "icao": icao_to_string(icao),
+                "country": country,
+                "registration": registration,
+                "is_military": is_military,
+                "timestamp": timestamp,
+            }),
+        ),
+        TrackEvent::PositionUpdate {
+            icao,
+            lat,
+            lon,
+            altitude_ft,
+            speed_kts,
+            heading_deg,
+            vertical_rate_fpm,
+            on_ground,
+            timestamp,
+            ..
+        } => (
+            "position",
+            json!({
+                "icao": icao_to_string(icao),
+                "lat": lat,
+                "lon": lon,
+                "altitude_ft": altitude_ft,
+                "speed_kts": speed_kts,
+                "heading_deg": heading_deg,
+                "vertical_rate_fpm": vertical_rate_fpm,
+                "on_ground": on_ground,
+                "timestamp": timestamp,
+            }),
+        ),
+        TrackEvent::SightingUpdate {
+            icao,
+            callsign,
+            squawk,
+            altitude_ft,
+            timestamp,
+            ..
+        } => (
+            "sighting",
+            json!({
+                "icao": icao_to_string(icao),
+                "callsign": callsign,
+                "squawk": squawk,
+                "altitude_ft": altitude_ft,
+                "timestamp": timestamp,
+            }),
+        ),
+        TrackEvent::AircraftUpdate { .. } | TrackEvent::SelectedStateUpdate { .. } => return None,
+    };
+
+    Some(pair)
+}
+
+/// GET /api/ws — upgrades to a WebSocket that sends a full aircraft
+/// snapshot on connect (the same document as `/data/aircraft.json`), then
+/// streams incremental `TrackEvent`s as JSON text frames
+/// (`{"type": "position", "data": {...}}`) for as long as the client stays
+/// connected. The WebSocket sibling of `/api/v1/stream`'s SSE feed, for
+/// clients that want a single persistent socket instead of an EventSource.
+pub async fn api_ws(
+    State(state): State<Arc<AppState>>,
+    ws: axum::extract::ws::WebSocketUpgrade,
+) -> impl axum::response::IntoResponse {
+    ws.on_upgrade(move |socket| handle_ws(socket, state))
+}
+
+async fn handle_ws(mut socket: axum::extract::ws::WebSocket, state: Arc<AppState>) {
+    use axum::extract::ws::Message;
+
+    if let Some(tracker) = &state.tracker {
+        let snapshot = {
+            let tracker = tracker.read().unwrap();
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs_f64();
+            crate::web::routes::readsb_aircraft_json(&tracker, now)
+        };
+        if socket.send(Message::Text(snapshot.to_string())).await.is_err() {
+            return;
+        }
+    }
+
+    let mut rx = state.track_tx.subscribe();
+    loop {
+        match rx.recv().await {
+            Ok(te) => {
+                let Some((name, payload)) = track_event_json(&te) else {
+                    continue;
+                };
+                let text = json!({ "type": name, "data": payload }).to_string();
+                if socket.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -389,6 +775,11 @@ mod tests {
             geofences: RwLock::new(Vec::new()),
             geofence_next_id: RwLock::new(1),
             auth_token: None,
+            credentials: RwLock::new(Vec::new()),
+            beast_tx: tokio::sync::broadcast::channel(16).0,
+            track_tx: tokio::sync::broadcast::channel(16).0,
+            event_tx: tokio::sync::mpsc::channel(16).0,
+            persistence_dropped: std::sync::atomic::AtomicU64::new(0),
         });
         (state, dir)
     }
@@ -402,6 +793,11 @@ mod tests {
             geofences: RwLock::new(Vec::new()),
             geofence_next_id: RwLock::new(1),
             auth_token: Some(token.to_string()),
+            credentials: RwLock::new(Vec::new()),
+            beast_tx: tokio::sync::broadcast::channel(16).0,
+            track_tx: tokio::sync::broadcast::channel(16).0,
+            event_tx: tokio::sync::mpsc::channel(16).0,
+            persistence_dropped: std::sync::atomic::AtomicU64::new(0),
         });
         (state, dir)
     }
@@ -473,6 +869,158 @@ mod tests {
         assert!(json["accepted"].as_u64().unwrap() >= 1);
     }
 
+    #[tokio::test]
+    async fn test_api_ingest_frames_beast_avr() {
+        let (state, _dir) = test_state();
+        let app = crate::web::build_router(state, None);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/frames/beast?receiver=test")
+                    .header("content-type", "application/octet-stream")
+                    .body(Body::from(
+                        b"*8D4840D6202CC371C32CE0576098;".as_slice(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["accepted"].as_u64().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_api_ingest_frames_beast_binary() {
+        let (state, _dir) = test_state();
+        let app = crate::web::build_router(state, None);
+
+        let raw = adsb_core::types::hex_decode("8D4840D6202CC371C32CE0576098").unwrap();
+        let mut payload = vec![adsb_core::beast::ESCAPE, adsb_core::beast::TYPE_MODE_S_LONG];
+        payload.extend_from_slice(&[0, 0, 0, 0, 0, 1]); // 12 MHz MLAT counter
+        payload.push(128); // signal level
+        payload.extend_from_slice(&raw);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/frames/beast?receiver=test")
+                    .header("content-type", "application/octet-stream")
+                    .body(Body::from(payload))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["accepted"].as_u64().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_api_ingest_frames_publishes_to_track_stream() {
+        let (state, _dir) = test_state();
+        let mut track_rx = state.track_tx.subscribe();
+        let app = crate::web::build_router(state, None);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/frames")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"receiver":"test","frames":[{"hex":"8D4840D6202CC371C32CE0576098"}]}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        // A NewAircraft event (at least) should have been broadcast.
+        assert!(track_rx.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_persistence_worker_drains_until_sender_dropped() {
+        let (state, _dir) = test_state();
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let worker = tokio::spawn(run_persistence_worker(rx, state.db.clone()));
+
+        tx.send(TrackEvent::NewAircraft {
+            icao: [0xAD, 0xF7, 0xC8],
+            country: Some("US"),
+            registration: None,
+            is_military: false,
+            timestamp: 1700000000.0,
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        // The worker exits once the channel is drained and the sender is gone.
+        tokio::time::timeout(std::time::Duration::from_secs(5), worker)
+            .await
+            .expect("worker should finish promptly")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sse_event_for_new_aircraft() {
+        let te = TrackEvent::NewAircraft {
+            icao: [0xAD, 0xF7, 0xC8],
+            country: Some("US"),
+            registration: None,
+            is_military: false,
+            timestamp: 1700000000.0,
+        };
+        let event = sse_event_for(&te).expect("new aircraft event should be forwarded");
+        assert!(format!("{event:?}").contains("new_aircraft"));
+    }
+
+    #[test]
+    fn test_sse_event_for_drops_aircraft_update() {
+        let te = TrackEvent::AircraftUpdate {
+            icao: [0xAD, 0xF7, 0xC8],
+            timestamp: 1700000000.0,
+        };
+        assert!(sse_event_for(&te).is_none());
+    }
+
+    #[test]
+    fn test_track_event_json_new_aircraft() {
+        let te = TrackEvent::NewAircraft {
+            icao: [0xAD, 0xF7, 0xC8],
+            country: Some("US"),
+            registration: None,
+            is_military: false,
+            timestamp: 1700000000.0,
+        };
+        let (name, payload) = track_event_json(&te).expect("new aircraft event should be forwarded");
+        assert_eq!(name, "new_aircraft");
+        assert_eq!(payload["icao"], icao_to_string(&[0xAD, 0xF7, 0xC8]));
+    }
+
+    #[test]
+    fn test_track_event_json_drops_aircraft_update() {
+        let te = TrackEvent::AircraftUpdate {
+            icao: [0xAD, 0xF7, 0xC8],
+            timestamp: 1700000000.0,
+        };
+        assert!(track_event_json(&te).is_none());
+    }
+
     #[tokio::test]
     async fn test_auth_reject_without_token() {
         let (state, _dir) = test_state_with_auth("secret-token-123");
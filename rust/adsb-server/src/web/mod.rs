@@ -9,10 +9,11 @@ use axum::Router;
 use tower_http::cors::{AllowOrigin, CorsLayer};
 use tower_http::set_header::SetResponseHeaderLayer;
 
-use adsb_core::tracker::Tracker;
+use adsb_core::tracker::{TrackEvent, Tracker};
 
 use crate::db::AdsbDatabase;
 
+pub mod auth;
 pub mod ingest;
 pub mod pages;
 pub mod routes;
@@ -26,6 +27,60 @@ pub struct AppState {
     pub tracker: Option<Arc<RwLock<Tracker>>>,
     pub geofences: RwLock<Vec<GeofenceEntry>>,
     pub geofence_next_id: RwLock<u64>,
+    /// Shared bearer token accepted by every feeder when no per-feeder
+    /// `credentials` have been minted, and always required for the
+    /// `/api/v1/admin/*` endpoints. `None` disables auth entirely.
+    pub auth_token: Option<String>,
+    /// Scoped, per-feeder API credentials minted via
+    /// `auth::api_mint_credential`. Empty until an operator mints one, in
+    /// which case `auth_token` stops being accepted for ingest/receivers
+    /// requests (see `auth::check_auth`).
+    pub credentials: RwLock<Vec<auth::Credential>>,
+    /// Re-broadcasts every decoded frame in Beast binary format to
+    /// `/api/beast` subscribers (see `adsb_core::beast`).
+    pub beast_tx: tokio::sync::broadcast::Sender<Vec<u8>>,
+    /// Pushes every `TrackEvent` collected during ingest to `/api/v1/stream`
+    /// subscribers, so dashboards update live instead of polling.
+    pub track_tx: tokio::sync::broadcast::Sender<TrackEvent>,
+    /// Hands `TrackEvent`s off to `ingest::run_persistence_worker` so ingest
+    /// requests return as soon as events are queued instead of waiting on
+    /// the database. See `ingest::api_ingest_frames`.
+    pub event_tx: tokio::sync::mpsc::Sender<TrackEvent>,
+    /// Events shed because `event_tx`'s queue was full — persistence has
+    /// fallen behind ingest. Surfaced by `ingest::api_receivers`.
+    pub persistence_dropped: std::sync::atomic::AtomicU64,
+    /// Terrain elevation lookups for `altitude_agl_ft` enrichment. `None`
+    /// when no `--dem-dir` was configured.
+    pub dem: Option<std::sync::Mutex<adsb_core::dem::DemSource>>,
+    /// Region boundary polygons for reverse-geocoding positions. Loaded
+    /// once at startup, like `geofences` but read-only. `None` when no
+    /// `--regions-path` was configured.
+    pub regions: Option<adsb_core::region::RegionSet>,
+}
+
+impl AppState {
+    /// Height above ground in feet for a position, or `None` if no DEM is
+    /// configured, the terrain cell has no coverage, or `altitude_ft` is
+    /// unknown.
+    pub fn altitude_agl_ft(&self, lat: f64, lon: f64, altitude_ft: Option<i32>) -> Option<i32> {
+        let altitude_ft = altitude_ft?;
+        let elevation_m = self.dem.as_ref()?.lock().unwrap().elevation_m(lat, lon)?;
+        let elevation_ft = elevation_m * 3.28084;
+        Some(altitude_ft - elevation_ft.round() as i32)
+    }
+
+    /// The named region containing `(lat, lon)`, or `None` if no region
+    /// set is configured or the position falls outside every polygon.
+    pub fn region_for(&self, lat: f64, lon: f64) -> Option<&str> {
+        self.regions.as_ref()?.classify(lat, lon)
+    }
+
+    /// Events currently queued for `event_tx` but not yet drained by
+    /// `ingest::run_persistence_worker` — a rising number means persistence
+    /// is falling behind ingest.
+    pub fn persistence_queue_depth(&self) -> usize {
+        self.event_tx.max_capacity() - self.event_tx.capacity()
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -38,6 +93,22 @@ pub struct GeofenceEntry {
     pub description: Option<String>,
 }
 
+/// Backlog size for the Beast re-broadcast channel. Slow subscribers that
+/// fall this far behind start missing frames rather than blocking ingest.
+pub const BEAST_CHANNEL_CAPACITY: usize = 4096;
+
+/// Backlog size for the track-event SSE channel. Slow subscribers that fall
+/// this far behind start missing events rather than blocking ingest.
+pub const TRACK_EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Backlog size for the write-behind persistence queue. Ingest sheds events
+/// (counted in `AppState::persistence_dropped`) rather than growing this
+/// past bound when the database falls behind.
+pub const PERSISTENCE_QUEUE_CAPACITY: usize = 8192;
+
+/// Number of DEM tiles (~2.8MB each for SRTM-3) kept resident at once.
+pub const DEM_TILE_CACHE_CAPACITY: usize = 16;
+
 // ---------------------------------------------------------------------------
 // Router
 // ---------------------------------------------------------------------------
@@ -45,6 +116,25 @@ pub struct GeofenceEntry {
 pub fn build_router(state: Arc<AppState>, cors_origin: Option<&str>) -> Router {
     use http::HeaderValue;
 
+    // Feeders batch hundreds of hex frames per POST; decompress gzip/zstd
+    // request bodies here (before the `Json<IngestRequest>` extractor runs)
+    // rather than in each handler. Unsupported `Content-Encoding` values get
+    // a 415 from the layer itself.
+    let ingest_routes = Router::new()
+        .route(
+            "/api/v1/frames",
+            axum::routing::post(ingest::api_ingest_frames),
+        )
+        .route(
+            "/api/v1/heartbeat",
+            axum::routing::post(ingest::api_heartbeat),
+        )
+        .route(
+            "/api/v1/frames/beast",
+            axum::routing::post(ingest::api_ingest_frames_beast),
+        )
+        .layer(tower_http::decompression::RequestDecompressionLayer::new());
+
     let mut app = Router::new()
         // Page routes
         .route("/", axum::routing::get(pages::page_map))
@@ -61,6 +151,15 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<&str>) -> Router {
             "/api/aircraft/:icao",
             axum::routing::get(routes::api_aircraft_detail),
         )
+        .route(
+            "/api/aircraft/:icao/track.gpx",
+            axum::routing::get(routes::api_aircraft_track_gpx),
+        )
+        .route("/api/beast", axum::routing::get(routes::api_beast))
+        .route(
+            "/data/aircraft.json",
+            axum::routing::get(routes::api_aircraft_json),
+        )
         .route("/api/positions", axum::routing::get(routes::api_positions))
         .route("/api/trails", axum::routing::get(routes::api_trails))
         .route("/api/events", axum::routing::get(routes::api_events))
@@ -82,17 +181,20 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<&str>) -> Router {
         )
         // Ingest API (multi-receiver)
         .route(
-            "/api/v1/frames",
-            axum::routing::post(ingest::api_ingest_frames),
+            "/api/v1/receivers",
+            axum::routing::get(ingest::api_receivers),
         )
+        .route("/api/v1/stream", axum::routing::get(ingest::api_stream))
+        .route("/api/ws", axum::routing::get(ingest::api_ws))
         .route(
-            "/api/v1/heartbeat",
-            axum::routing::post(ingest::api_heartbeat),
+            "/api/v1/admin/credentials",
+            axum::routing::post(auth::api_mint_credential),
         )
         .route(
-            "/api/v1/receivers",
-            axum::routing::get(ingest::api_receivers),
+            "/api/v1/admin/credentials/:feeder_name",
+            axum::routing::delete(auth::api_revoke_credential),
         )
+        .merge(ingest_routes)
         .with_state(state);
 
     // CORS — only add when explicitly configured
@@ -124,18 +226,49 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<&str>) -> Router {
     app
 }
 
+/// Load a region set from `--regions-path`, logging and continuing without
+/// region tagging if the file is missing or malformed.
+pub fn load_regions(regions_path: Option<String>) -> Option<adsb_core::region::RegionSet> {
+    let path = regions_path?;
+    match adsb_core::region::RegionSet::load(&path) {
+        Ok(regions) => Some(regions),
+        Err(e) => {
+            eprintln!("Warning: failed to load regions from {path}: {e}");
+            None
+        }
+    }
+}
+
 /// Start the web server.
 pub async fn serve(
     db: Arc<dyn AdsbDatabase>,
     host: String,
     port: u16,
     cors_origin: Option<&str>,
+    dem_dir: Option<String>,
+    regions_path: Option<String>,
 ) {
+    let (beast_tx, _) = tokio::sync::broadcast::channel(BEAST_CHANNEL_CAPACITY);
+    let (track_tx, _) = tokio::sync::broadcast::channel(TRACK_EVENT_CHANNEL_CAPACITY);
+    let (event_tx, event_rx) = tokio::sync::mpsc::channel(PERSISTENCE_QUEUE_CAPACITY);
+    let dem = dem_dir.map(|dir| {
+        std::sync::Mutex::new(adsb_core::dem::DemSource::new(dir, DEM_TILE_CACHE_CAPACITY))
+    });
+    let regions = load_regions(regions_path);
+    tokio::spawn(ingest::run_persistence_worker(event_rx, db.clone()));
     let state = Arc::new(AppState {
         db,
         tracker: None,
         geofences: RwLock::new(Vec::new()),
         geofence_next_id: RwLock::new(1),
+        auth_token: None,
+        credentials: RwLock::new(Vec::new()),
+        beast_tx,
+        track_tx,
+        event_tx,
+        persistence_dropped: std::sync::atomic::AtomicU64::new(0),
+        dem,
+        regions,
     });
 
     let app = build_router(state, cors_origin);
@@ -155,3 +288,41 @@ pub async fn serve(
     };
     axum::serve(listener, app).await.unwrap();
 }
+
+/// Serve raw Beast binary frames over a plain TCP listener, bypassing HTTP
+/// entirely — the format MLAT clients, dump1090-style viewers, and flight-sim
+/// feeders (e.g. FSX/X-Plane feeders) expect on the classic "Beast port".
+pub async fn serve_beast_tcp(beast_tx: tokio::sync::broadcast::Sender<Vec<u8>>, host: String, port: u16) {
+    use tokio::io::AsyncWriteExt;
+
+    let addr = format!("{host}:{port}");
+    let listener = match tokio::net::TcpListener::bind(&addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("Error: cannot bind Beast TCP listener to {addr}: {e}");
+            return;
+        }
+    };
+    eprintln!("Beast binary stream listening on {addr}");
+
+    loop {
+        let (mut socket, _) = match listener.accept().await {
+            Ok(pair) => pair,
+            Err(_) => continue,
+        };
+        let mut rx = beast_tx.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(frame) => {
+                        if socket.write_all(&frame).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
@@ -7,8 +7,8 @@
 use std::sync::Arc;
 
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -17,6 +17,9 @@ use adsb_core::types::icao_to_string;
 
 use crate::web::AppState;
 
+/// Maximum gap between consecutive points before starting a new `<trkseg>`.
+const GPX_SEGMENT_GAP_SECS: f64 = 120.0;
+
 // ---------------------------------------------------------------------------
 // Query param types
 // ---------------------------------------------------------------------------
@@ -24,17 +27,26 @@ use crate::web::AppState;
 #[derive(Deserialize)]
 pub struct AircraftParams {
     military: Option<bool>,
+    format: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct PositionParams {
     minutes: Option<f64>,
+    lat_min: Option<f64>,
+    lat_max: Option<f64>,
+    lon_min: Option<f64>,
+    lon_max: Option<f64>,
+    floor: Option<i32>,
+    ceiling: Option<i32>,
+    format: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct TrailParams {
     minutes: Option<f64>,
     limit: Option<i64>,
+    format: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -51,11 +63,24 @@ pub struct QueryParams {
     icao: Option<String>,
     military: Option<bool>,
     limit: Option<i64>,
+    lat_min: Option<f64>,
+    lat_max: Option<f64>,
+    lon_min: Option<f64>,
+    lon_max: Option<f64>,
+    floor: Option<i32>,
+    ceiling: Option<i32>,
+    region: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct HeatmapParams {
     minutes: Option<f64>,
+    lat_min: Option<f64>,
+    lat_max: Option<f64>,
+    lon_min: Option<f64>,
+    lon_max: Option<f64>,
+    floor: Option<i32>,
+    ceiling: Option<i32>,
 }
 
 #[derive(Deserialize)]
@@ -84,15 +109,102 @@ fn clamp_i64(val: i64, min: i64, max: i64) -> i64 {
     val.max(min).min(max)
 }
 
+/// Whether the client asked for GeoJSON via `?format=geojson` or an
+/// `Accept: application/geo+json` header.
+fn wants_geojson(headers: &HeaderMap, format: Option<&str>) -> bool {
+    if format == Some("geojson") {
+        return true;
+    }
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("application/geo+json"))
+}
+
+/// Respond as plain JSON, or as a `FeatureCollection` with
+/// `Content-Type: application/geo+json` when `geojson` is set.
+fn json_or_geojson(value: Value, geojson: bool) -> Response {
+    if geojson {
+        (
+            [(header::CONTENT_TYPE, "application/geo+json")],
+            Json(value),
+        )
+            .into_response()
+    } else {
+        Json(value).into_response()
+    }
+}
+
+/// Convert position objects (each with `icao`/`lat`/`lon`/`callsign`/
+/// `altitude_ft`/`speed_kts`/`heading_deg`/`timestamp`) into a GeoJSON
+/// `FeatureCollection` of `Point` features, `[lon, lat]` per the spec.
+fn positions_to_geojson(positions: &[Value]) -> Value {
+    let features: Vec<Value> = positions
+        .iter()
+        .map(|p| {
+            json!({
+                "type": "Feature",
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [p["lon"], p["lat"]],
+                },
+                "properties": {
+                    "icao": p["icao"],
+                    "callsign": p["callsign"],
+                    "altitude_ft": p["altitude_ft"],
+                    "speed_kts": p["speed_kts"],
+                    "heading_deg": p["heading_deg"],
+                    "timestamp": p["timestamp"],
+                },
+            })
+        })
+        .collect();
+    json!({
+        "type": "FeatureCollection",
+        "features": features,
+    })
+}
+
+/// Convert aircraft objects into a GeoJSON `FeatureCollection`, carrying
+/// every existing field through as `properties`. Aircraft with no known
+/// position (e.g. DB-backed metadata rows with no `lat`/`lon`) get a
+/// `null` geometry rather than being dropped.
+fn aircraft_to_geojson(aircraft: &[Value]) -> Value {
+    let features: Vec<Value> = aircraft
+        .iter()
+        .map(|a| {
+            let geometry = match (a.get("lat").and_then(Value::as_f64), a.get("lon").and_then(Value::as_f64)) {
+                (Some(lat), Some(lon)) => json!({
+                    "type": "Point",
+                    "coordinates": [lon, lat],
+                }),
+                _ => Value::Null,
+            };
+            json!({
+                "type": "Feature",
+                "geometry": geometry,
+                "properties": a,
+            })
+        })
+        .collect();
+    json!({
+        "type": "FeatureCollection",
+        "features": features,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Aircraft endpoints
 // ---------------------------------------------------------------------------
 
-/// GET /api/aircraft — list all aircraft.
+/// GET /api/aircraft — list all aircraft. Emits GeoJSON with
+/// `?format=geojson` or an `Accept: application/geo+json` header.
 pub async fn api_aircraft(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Query(params): Query<AircraftParams>,
 ) -> impl IntoResponse {
+    let geojson = wants_geojson(&headers, params.format.as_deref());
     // Dual-path: live tracker or DB
     if let Some(tracker) = &state.tracker {
         let tracker = tracker.read().unwrap();
@@ -111,6 +223,10 @@ pub async fn api_aircraft(
                 }
             })
             .map(|ac| {
+                let position = ac.lat.zip(ac.lon);
+                let altitude_agl_ft =
+                    position.and_then(|(lat, lon)| state.altitude_agl_ft(lat, lon, ac.altitude_ft));
+                let region = position.and_then(|(lat, lon)| state.region_for(lat, lon));
                 json!({
                     "icao": icao_to_string(&ac.icao),
                     "callsign": ac.callsign,
@@ -118,18 +234,26 @@ pub async fn api_aircraft(
                     "lat": ac.lat,
                     "lon": ac.lon,
                     "altitude_ft": ac.altitude_ft,
+                    "altitude_agl_ft": altitude_agl_ft,
+                    "region": region,
                     "speed_kts": ac.speed_kts,
                     "heading_deg": ac.heading_deg,
                     "vertical_rate_fpm": ac.vertical_rate_fpm,
                     "country": ac.country,
                     "is_military": ac.is_military,
+                    "rssi_dbfs": ac.rssi_dbfs,
                     "messages": ac.message_count,
                     "first_seen": ac.first_seen,
                     "last_seen": ac.last_seen,
                 })
             })
             .collect();
-        return Json(json!(aircraft));
+        let body = if geojson {
+            aircraft_to_geojson(&aircraft)
+        } else {
+            json!(aircraft)
+        };
+        return json_or_geojson(body, geojson);
     }
 
     let mut aircraft = state.db.get_all_aircraft().await;
@@ -137,7 +261,13 @@ pub async fn api_aircraft(
         aircraft.retain(|a| a.is_military);
     }
 
-    Json(serde_json::to_value(&aircraft).unwrap_or(json!([])))
+    let aircraft = serde_json::to_value(&aircraft).unwrap_or(json!([]));
+    let body = if geojson {
+        aircraft_to_geojson(aircraft.as_array().map(Vec::as_slice).unwrap_or(&[]))
+    } else {
+        aircraft
+    };
+    json_or_geojson(body, geojson)
 }
 
 /// GET /api/aircraft/:icao — single aircraft detail + positions + events.
@@ -169,16 +299,227 @@ pub async fn api_aircraft_detail(
     .into_response()
 }
 
+/// GET /api/aircraft/:icao/track.gpx — flight path as a GPX 1.1 document.
+pub async fn api_aircraft_track_gpx(
+    State(state): State<Arc<AppState>>,
+    Path(icao): Path<String>,
+) -> impl IntoResponse {
+    let icao_upper = icao.to_ascii_uppercase();
+
+    let aircraft = state.db.get_aircraft(&icao_upper).await;
+    if aircraft.is_none() {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Aircraft not found"})),
+        )
+            .into_response();
+    }
+
+    let mut positions = state.db.get_positions(&icao_upper, 5000).await;
+    positions.sort_by(|a, b| a.timestamp.partial_cmp(&b.timestamp).unwrap());
+
+    let track_name = aircraft
+        .as_ref()
+        .and_then(|a| a.registration.clone())
+        .unwrap_or_else(|| icao_upper.clone());
+
+    let gpx = render_gpx(&track_name, &positions);
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/gpx+xml".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{icao_upper}.gpx\""),
+            ),
+        ],
+        gpx,
+    )
+        .into_response()
+}
+
+/// Build a GPX 1.1 document from ordered positions, splitting into a new
+/// `<trkseg>` whenever consecutive points are more than
+/// `GPX_SEGMENT_GAP_SECS` apart.
+fn render_gpx(track_name: &str, positions: &[crate::db::PositionRow]) -> String {
+    let mut gpx = String::with_capacity(positions.len() * 128 + 256);
+    gpx.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    gpx.push_str("<gpx version=\"1.1\" creator=\"adsb-decode\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n");
+    gpx.push_str("  <trk>\n");
+    gpx.push_str(&format!("    <name>{}</name>\n", xml_escape(track_name)));
+
+    let mut in_segment = false;
+    let mut last_ts: Option<f64> = None;
+
+    for pos in positions {
+        let gap = last_ts.map(|t| pos.timestamp - t).unwrap_or(0.0);
+        if in_segment && gap > GPX_SEGMENT_GAP_SECS {
+            gpx.push_str("    </trkseg>\n");
+            in_segment = false;
+        }
+        if !in_segment {
+            gpx.push_str("    <trkseg>\n");
+            in_segment = true;
+        }
+
+        gpx.push_str(&format!(
+            "      <trkpt lat=\"{:.6}\" lon=\"{:.6}\">\n",
+            pos.lat, pos.lon
+        ));
+        if let Some(alt_ft) = pos.altitude_ft {
+            gpx.push_str(&format!("        <ele>{:.1}</ele>\n", alt_ft as f64 * 0.3048));
+        }
+        gpx.push_str(&format!("        <time>{}</time>\n", unix_to_rfc3339(pos.timestamp)));
+        gpx.push_str("      </trkpt>\n");
+
+        last_ts = Some(pos.timestamp);
+    }
+
+    if in_segment {
+        gpx.push_str("    </trkseg>\n");
+    }
+
+    gpx.push_str("  </trk>\n");
+    gpx.push_str("</gpx>\n");
+    gpx
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Format a unix timestamp as an RFC3339 UTC stamp (no external date crate).
+fn unix_to_rfc3339(ts: f64) -> String {
+    let secs = ts.floor() as i64;
+    let (year, month, day, hour, min, sec) = civil_from_unix(secs);
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{min:02}:{sec:02}Z")
+}
+
+/// Convert unix seconds to (year, month, day, hour, min, sec) using the
+/// Howard Hinnant civil_from_days algorithm (proleptic Gregorian, UTC).
+fn civil_from_unix(secs: i64) -> (i64, u32, u32, u32, u32, u32) {
+    let days = secs.div_euclid(86400);
+    let time_of_day = secs.rem_euclid(86400);
+    let hour = (time_of_day / 3600) as u32;
+    let min = ((time_of_day % 3600) / 60) as u32;
+    let sec = (time_of_day % 60) as u32;
+
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = if month <= 2 { y + 1 } else { y };
+
+    (year, month, day, hour, min, sec)
+}
+
+// ---------------------------------------------------------------------------
+// Beast binary re-broadcast
+// ---------------------------------------------------------------------------
+
+/// GET /api/beast — live re-broadcast of decoded frames in Beast binary
+/// format, for dump1090-style viewers, MLAT clients, and flight-sim feeders.
+/// Streams until the client disconnects; frames emitted before the
+/// subscription started are not replayed.
+pub async fn api_beast(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    use tokio_stream::wrappers::BroadcastStream;
+    use tokio_stream::StreamExt;
+
+    let rx = state.beast_tx.subscribe();
+    let stream = BroadcastStream::new(rx)
+        .filter_map(|frame| frame.ok().map(|bytes| Ok::<_, std::io::Error>(axum::body::Bytes::from(bytes))));
+
+    (
+        [(header::CONTENT_TYPE, "application/vnd.adsb.beast")],
+        axum::body::Body::from_stream(stream),
+    )
+}
+
+/// Build a readsb/dump1090-compatible `aircraft.json` document from the live
+/// tracker's current snapshot, so existing frontends (tar1090, SkyAware)
+/// that expect this de-facto format can point at this server.
+///
+/// Shared by the `/data/aircraft.json` route and the `--json-dir` flush in
+/// the live track modes.
+pub fn readsb_aircraft_json(tracker: &adsb_core::tracker::Tracker, now: f64) -> Value {
+    let aircraft: Vec<Value> = tracker
+        .get_active(now)
+        .iter()
+        .map(|ac| {
+            let seen_pos = ac
+                .position_history
+                .last()
+                .map(|&(ts, ..)| (now - ts).max(0.0));
+            json!({
+                "hex": icao_to_string(&ac.icao).to_lowercase(),
+                "flight": ac.callsign,
+                "alt_baro": ac.altitude_ft,
+                "gs": ac.speed_kts,
+                "track": ac.heading_deg,
+                "baro_rate": ac.vertical_rate_fpm,
+                "squawk": ac.squawk,
+                "lat": ac.lat,
+                "lon": ac.lon,
+                "seen": (now - ac.last_seen).max(0.0),
+                "seen_pos": seen_pos,
+                "rssi": ac.rssi_dbfs,
+                "messages": ac.message_count,
+            })
+        })
+        .collect();
+
+    json!({
+        "now": now,
+        "messages": tracker.total_frames,
+        "aircraft": aircraft,
+    })
+}
+
+/// GET /data/aircraft.json — readsb-compatible aircraft snapshot for
+/// tar1090/SkyAware-style frontends. 404s when no live tracker is attached
+/// (DB-backed serving has no comparable "currently tracked" snapshot).
+pub async fn api_aircraft_json(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let Some(tracker) = &state.tracker else {
+        return (StatusCode::NOT_FOUND, "no live tracker attached").into_response();
+    };
+    let tracker = tracker.read().unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs_f64();
+    Json(readsb_aircraft_json(&tracker, now)).into_response()
+}
+
 // ---------------------------------------------------------------------------
 // Position endpoints
 // ---------------------------------------------------------------------------
 
-/// GET /api/positions — recent positions for map polling.
+/// GET /api/positions — recent positions for map polling. Emits GeoJSON
+/// with `?format=geojson` or an `Accept: application/geo+json` header.
 pub async fn api_positions(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Query(params): Query<PositionParams>,
 ) -> impl IntoResponse {
+    let geojson = wants_geojson(&headers, params.format.as_deref());
     let minutes = clamp(params.minutes.unwrap_or(5.0), 1.0, 525600.0);
+    let filter = crate::db::SpatialFilter {
+        lat_min: params.lat_min,
+        lat_max: params.lat_max,
+        lon_min: params.lon_min,
+        lon_max: params.lon_max,
+        floor_ft: params.floor,
+        ceiling_ft: params.ceiling,
+    };
 
     // Dual-path: live tracker for sub-second latency
     if let Some(tracker) = &state.tracker {
@@ -191,13 +532,20 @@ pub async fn api_positions(
         let active = tracker.get_active(now);
         let positions: Vec<Value> = active
             .iter()
-            .filter(|ac| ac.has_position() && ac.last_seen >= cutoff)
+            .filter(|ac| {
+                ac.has_position()
+                    && ac.last_seen >= cutoff
+                    && filter.matches(ac.lat.unwrap(), ac.lon.unwrap(), ac.altitude_ft)
+            })
             .map(|ac| {
+                let (lat, lon) = (ac.lat.unwrap(), ac.lon.unwrap());
                 json!({
                     "icao": icao_to_string(&ac.icao),
                     "lat": ac.lat,
                     "lon": ac.lon,
                     "altitude_ft": ac.altitude_ft,
+                    "altitude_agl_ft": state.altitude_agl_ft(lat, lon, ac.altitude_ft),
+                    "region": state.region_for(lat, lon),
                     "speed_kts": ac.speed_kts,
                     "heading_deg": ac.heading_deg,
                     "vertical_rate_fpm": ac.vertical_rate_fpm,
@@ -206,39 +554,101 @@ pub async fn api_positions(
                 })
             })
             .collect();
-        return Json(json!(positions));
+        let body = if geojson {
+            positions_to_geojson(&positions)
+        } else {
+            json!(positions)
+        };
+        return json_or_geojson(body, geojson);
     }
 
-    let positions = state.db.get_recent_positions(minutes, 50000).await;
-    Json(serde_json::to_value(&positions).unwrap_or(json!([])))
+    let positions = state.db.get_recent_positions(minutes, filter, 50000).await;
+    let positions: Vec<Value> = positions
+        .iter()
+        .map(|p| {
+            let mut v = serde_json::to_value(p).unwrap_or(json!({}));
+            v["altitude_agl_ft"] = json!(state.altitude_agl_ft(p.lat, p.lon, p.altitude_ft));
+            v["region"] = json!(state.region_for(p.lat, p.lon));
+            v
+        })
+        .collect();
+    let body = if geojson {
+        positions_to_geojson(&positions)
+    } else {
+        json!(positions)
+    };
+    json_or_geojson(body, geojson)
 }
 
-/// GET /api/trails — position trails per aircraft.
+/// GET /api/trails — position trails per aircraft. Emits GeoJSON with
+/// `?format=geojson` or an `Accept: application/geo+json` header.
 pub async fn api_trails(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Query(params): Query<TrailParams>,
 ) -> impl IntoResponse {
+    let geojson = wants_geojson(&headers, params.format.as_deref());
     let minutes = clamp(params.minutes.unwrap_or(60.0), 1.0, 1440.0);
     let limit = clamp_i64(params.limit.unwrap_or(500), 1, 5000);
 
     let positions = state.db.get_trails(minutes, limit).await;
 
-    // Group by ICAO
-    let mut trails: std::collections::HashMap<String, Vec<Value>> =
+    // Group by ICAO, preserving arrival order for each aircraft's trail
+    let mut order: Vec<String> = Vec::new();
+    let mut trails: std::collections::HashMap<String, Vec<&crate::db::PositionRow>> =
         std::collections::HashMap::new();
     for pos in &positions {
-        trails
-            .entry(pos.icao.clone())
-            .or_default()
-            .push(json!({
-                "lat": pos.lat,
-                "lon": pos.lon,
-                "altitude_ft": pos.altitude_ft,
-                "timestamp": pos.timestamp,
-            }));
+        if !trails.contains_key(&pos.icao) {
+            order.push(pos.icao.clone());
+        }
+        trails.entry(pos.icao.clone()).or_default().push(pos);
     }
 
-    Json(json!(trails))
+    if geojson {
+        let features: Vec<Value> = order
+            .iter()
+            .map(|icao| {
+                let points = &trails[icao];
+                let coordinates: Vec<Value> =
+                    points.iter().map(|p| json!([p.lon, p.lat])).collect();
+                json!({
+                    "type": "Feature",
+                    "geometry": {
+                        "type": "LineString",
+                        "coordinates": coordinates,
+                    },
+                    "properties": {
+                        "icao": icao,
+                        "callsign": Value::Null,
+                    },
+                })
+            })
+            .collect();
+        let body = json!({
+            "type": "FeatureCollection",
+            "features": features,
+        });
+        return json_or_geojson(body, true);
+    }
+
+    let trails: std::collections::HashMap<String, Vec<Value>> = order
+        .into_iter()
+        .map(|icao| {
+            let points: Vec<Value> = trails[&icao]
+                .iter()
+                .map(|p| {
+                    json!({
+                        "lat": p.lat,
+                        "lon": p.lon,
+                        "altitude_ft": p.altitude_ft,
+                        "timestamp": p.timestamp,
+                    })
+                })
+                .collect();
+            (icao, points)
+        })
+        .collect();
+    json_or_geojson(json!(trails), false)
 }
 
 /// GET /api/positions/all — all positions for replay.
@@ -280,24 +690,76 @@ pub async fn api_stats(State(state): State<Arc<AppState>>) -> impl IntoResponse
 // Query + Heatmap
 // ---------------------------------------------------------------------------
 
+/// Max positions to scan across pages when `region` narrows the result
+/// further than `query_positions`'s SQL-level filters can express — bounds
+/// worst-case DB cost when a region matches only a sliver (or none) of a
+/// much larger table.
+const REGION_QUERY_MAX_SCANNED: i64 = 200_000;
+
 /// GET /api/query — filtered position query.
+///
+/// `region` can't be pushed into `query_positions`'s SQL (it's an arbitrary
+/// polygon classification, not a column the DB can filter on), so when it's
+/// given this pages through `query_positions` with a growing `offset`,
+/// region-filtering each page, until `limit` matches are collected or the
+/// source is exhausted (or `REGION_QUERY_MAX_SCANNED` rows have been
+/// scanned) — filtering a single limit-bound page after the fact would
+/// silently return fewer than `limit` rows whenever the most recent page
+/// happens to fall outside the region.
 pub async fn api_query(
     State(state): State<Arc<AppState>>,
     Query(params): Query<QueryParams>,
 ) -> impl IntoResponse {
     let limit = clamp_i64(params.limit.unwrap_or(1000), 1, 50000);
+    let filter = crate::db::SpatialFilter {
+        lat_min: params.lat_min,
+        lat_max: params.lat_max,
+        lon_min: params.lon_min,
+        lon_max: params.lon_max,
+        floor_ft: params.floor,
+        ceiling_ft: params.ceiling,
+    };
 
-    let positions = state
-        .db
-        .query_positions(
-            params.min_alt,
-            params.max_alt,
-            params.icao.as_deref(),
-            params.military.unwrap_or(false),
-            limit,
-        )
-        .await;
-    Json(serde_json::to_value(&positions).unwrap_or(json!([])))
+    let mut positions: Vec<Value> = Vec::new();
+    let mut offset: i64 = 0;
+    loop {
+        let page = state
+            .db
+            .query_positions(
+                params.min_alt,
+                params.max_alt,
+                params.icao.as_deref(),
+                params.military.unwrap_or(false),
+                filter,
+                limit,
+                offset,
+            )
+            .await;
+        let page_len = page.len() as i64;
+        offset += page_len;
+
+        for p in &page {
+            let region = state.region_for(p.lat, p.lon);
+            if let Some(wanted) = &params.region {
+                if region != Some(wanted.as_str()) {
+                    continue;
+                }
+            }
+            let mut v = serde_json::to_value(p).unwrap_or(json!({}));
+            v["region"] = json!(region);
+            positions.push(v);
+            if positions.len() as i64 >= limit {
+                break;
+            }
+        }
+
+        let satisfied = positions.len() as i64 >= limit;
+        let exhausted = page_len < limit;
+        if params.region.is_none() || satisfied || exhausted || offset >= REGION_QUERY_MAX_SCANNED {
+            break;
+        }
+    }
+    Json(json!(positions))
 }
 
 /// GET /api/heatmap — position density data.
@@ -306,8 +768,16 @@ pub async fn api_heatmap(
     Query(params): Query<HeatmapParams>,
 ) -> impl IntoResponse {
     let minutes = clamp(params.minutes.unwrap_or(1440.0), 1.0, 10080.0);
+    let filter = crate::db::SpatialFilter {
+        lat_min: params.lat_min,
+        lat_max: params.lat_max,
+        lon_min: params.lon_min,
+        lon_max: params.lon_max,
+        floor_ft: params.floor,
+        ceiling_ft: params.ceiling,
+    };
 
-    let points = state.db.get_heatmap_positions(minutes, 50000).await;
+    let points = state.db.get_heatmap_positions(minutes, filter, 50000).await;
     let data: Vec<Value> = points
         .iter()
         .map(|(lat, lon, alt)| json!({"lat": lat, "lon": lon, "altitude_ft": alt}))
@@ -428,6 +898,12 @@ mod tests {
             tracker: None,
             geofences: RwLock::new(Vec::new()),
             geofence_next_id: RwLock::new(1),
+            auth_token: None,
+            credentials: RwLock::new(Vec::new()),
+            beast_tx: tokio::sync::broadcast::channel(16).0,
+            track_tx: tokio::sync::broadcast::channel(16).0,
+            event_tx: tokio::sync::mpsc::channel(16).0,
+            persistence_dropped: std::sync::atomic::AtomicU64::new(0),
         });
         (state, dir)
     }
@@ -547,6 +1023,12 @@ mod tests {
             tracker: None,
             geofences: RwLock::new(Vec::new()),
             geofence_next_id: RwLock::new(1),
+            auth_token: None,
+            credentials: RwLock::new(Vec::new()),
+            beast_tx: tokio::sync::broadcast::channel(16).0,
+            track_tx: tokio::sync::broadcast::channel(16).0,
+            event_tx: tokio::sync::mpsc::channel(16).0,
+            persistence_dropped: std::sync::atomic::AtomicU64::new(0),
         });
 
         // Create geofence
@@ -617,6 +1099,12 @@ mod tests {
             tracker: None,
             geofences: RwLock::new(Vec::new()),
             geofence_next_id: RwLock::new(1),
+            auth_token: None,
+            credentials: RwLock::new(Vec::new()),
+            beast_tx: tokio::sync::broadcast::channel(16).0,
+            track_tx: tokio::sync::broadcast::channel(16).0,
+            event_tx: tokio::sync::mpsc::channel(16).0,
+            persistence_dropped: std::sync::atomic::AtomicU64::new(0),
         }));
 
         let response = app
@@ -674,6 +1162,52 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_api_aircraft_track_gpx() {
+        let (state, _dir) = test_state();
+        let app = crate::web::build_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/aircraft/4840D6/track.gpx")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/gpx+xml"
+        );
+        let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains("<gpx"));
+        assert!(text.contains("<trkpt"));
+    }
+
+    #[tokio::test]
+    async fn test_api_aircraft_track_gpx_not_found() {
+        let (state, _dir) = test_state();
+        let app = crate::web::build_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/aircraft/FFFFFF/track.gpx")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_api_query() {
         let (state, _dir) = test_state();
@@ -691,4 +1225,99 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_api_positions_viewport_excludes_outside_bbox() {
+        let (state, _dir) = test_state();
+        let app = crate::web::build_router(state);
+
+        // Test fixture position is at (52.25, 3.92) — box over the US excludes it.
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/positions?lat_min=30&lat_max=40&lon_min=-90&lon_max=-80")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_api_query_altitude_band_excludes_below_floor() {
+        let (state, _dir) = test_state();
+        let app = crate::web::build_router(state);
+
+        // Test fixture position is at 38000ft — floor above it excludes it.
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/query?floor=40000")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_api_heatmap_viewport_bounds() {
+        let (state, _dir) = test_state();
+        let app = crate::web::build_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/heatmap?lat_min=0&lat_max=90&lon_min=0&lon_max=10")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), 1024 * 1024)
+            .await
+            .unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_api_beast_content_type() {
+        let (state, _dir) = test_state();
+        let app = crate::web::build_router(state);
+
+        // Don't read the body — it's a live stream with nothing queued yet,
+        // so it would hang waiting for a frame that never arrives.
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/beast")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/vnd.adsb.beast"
+        );
+    }
 }